@@ -38,6 +38,14 @@ impl PixelatedAlbumArt {
             pixels: vec![vec![AnsiColor { r: 0, g: 0, b: 0 }; width as usize]; height as usize],
         }
     }
+
+    /// Bytes taken up by the pixels
+    pub fn memory_bytes(&self) -> usize {
+        self.pixels
+            .iter()
+            .map(|row| row.capacity() * std::mem::size_of::<AnsiColor>())
+            .sum()
+    }
 }
 
 /// Cached art entry with metadata
@@ -152,6 +160,16 @@ impl AlbumArtManager {
         Ok(art)
     }
 
+    /// Number of pictures held in memory and the bytes their pixels take up
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let bytes = self
+            .memory_cache
+            .values()
+            .map(|cached| cached.art.memory_bytes())
+            .sum();
+        (self.memory_cache.len(), bytes)
+    }
+
     /// Add to memory cache with LRU eviction
     fn add_to_memory_cache(&mut self, key: String, cached: CachedArt) {
         // Evict oldest entries if cache is full
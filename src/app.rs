@@ -1,38 +1,65 @@
 use super::user_config::UserConfig;
-use crate::network::IoEvent;
+use crate::event::Key;
+use crate::network::{DispatchedEvent, IoEvent, PlaybackOffset, RepeatState};
+use crate::now_playing;
 use crate::focus_manager::{FocusManager, ComponentId, FocusState};
 use crate::album_art::{AlbumArtManager, PixelatedAlbumArt};
+use crate::debug_stats::{FrameStats, NetworkStats};
+use crate::episode_positions::EpisodePositions;
+use crate::duplicates::{Duplicate, PlaylistDuplicates};
+use crate::fuzzy;
+use crate::logging::LOG_STREAM_TARGET;
+use crate::hooks::{self, PlaybackEvent, PlaybackState};
+use crate::open_in::{self, OpenInMenu};
+use crate::search_history::{HistoryRecall, ReverseSearch, SearchHistory};
+use crate::jump_list::{Jump, JumpList};
+use crate::location::{self, PendingSelection};
+use crate::workspace::Workspaces;
+use crate::share::{self, ShareItem, ShareMenu};
+use crate::template;
+use crate::ui::util::millis_to_minutes;
+use crate::playlist_sort::{self, SortEntry};
 use rspotify::model::PlayableItem;
 use anyhow::anyhow;
 use rspotify::{
   model::{
     album::{FullAlbum, SavedAlbum, SimplifiedAlbum},
     artist::FullArtist,
-    audio::AudioAnalysis,
+    audio::{AudioAnalysis, AudioFeatures},
+    category::Category,
     context::CurrentPlaybackContext,
     device::DevicePayload,
     page::{CursorBasedPage, Page},
     playing::PlayHistory,
-    playlist::{PlaylistTracksRef, SimplifiedPlaylist},
-    show::{FullShow, Show, SimplifiedEpisode, SimplifiedShow},
+    playlist::{FeaturedPlaylists, SimplifiedPlaylist},
+    show::{FullShow, SimplifiedEpisode, SimplifiedShow},
     track::{FullTrack, SavedTrack, SimplifiedTrack},
     user::PrivateUser,
     // PlaylistItem,  // Using network::PlayingItem instead
   },
-  model::enums::Country,
+  model::enums::{Country, SearchType, SubscriptionLevel},
 };
 use std::str::FromStr;
-use std::sync::mpsc::Sender;
+use std::sync::{
+  mpsc::{Receiver, Sender},
+  Arc,
+};
 use std::{
+  cell::Cell,
   cmp::{max, min},
-  collections::HashSet,
-  time::{Instant, SystemTime},
+  collections::{HashMap, HashSet, VecDeque},
+  ops::RangeInclusive,
+  time::{Duration, Instant, SystemTime},
 };
 use ratatui::layout::Rect;
 
 use arboard::Clipboard;
+use serde::de::DeserializeOwned;
+
+/// Placeholders the `now_playing_template` behavior option can use
+pub const NOW_PLAYING_FIELDS: [&str; 4] = ["track", "artist", "album", "url"];
 
-pub const LIBRARY_OPTIONS: [&str; 7] = [
+pub const LIBRARY_OPTIONS: [&str; 12] = [
   "Recently Played",
   "Liked Songs",
   "Albums",
@@ -40,12 +67,80 @@ pub const LIBRARY_OPTIONS: [&str; 7] = [
   "Podcasts",
   "Top Tracks",
   "Top Artists",
+  "Made For You",
+  "New Releases",
+  "Featured Playlists",
+  "Categories",
+  "Profile",
 ];
 
+/// The library's options, in `LIBRARY_OPTIONS` order so each one's index is its place there
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LibraryOption {
+  RecentlyPlayed,
+  LikedSongs,
+  Albums,
+  Artists,
+  Podcasts,
+  TopTracks,
+  TopArtists,
+  MadeForYou,
+  NewReleases,
+  FeaturedPlaylists,
+  Categories,
+  Profile,
+}
+
+impl LibraryOption {
+  const ALL: [LibraryOption; LIBRARY_OPTIONS.len()] = [
+    LibraryOption::RecentlyPlayed,
+    LibraryOption::LikedSongs,
+    LibraryOption::Albums,
+    LibraryOption::Artists,
+    LibraryOption::Podcasts,
+    LibraryOption::TopTracks,
+    LibraryOption::TopArtists,
+    LibraryOption::MadeForYou,
+    LibraryOption::NewReleases,
+    LibraryOption::FeaturedPlaylists,
+    LibraryOption::Categories,
+    LibraryOption::Profile,
+  ];
+
+  pub fn from_index(index: usize) -> Option<LibraryOption> {
+    LibraryOption::ALL.get(index).copied()
+  }
+}
+
+const TOAST_DURATION_MS: u128 = 3_000;
+
+// Errors stay up longer than toasts, to give time to read them and reach for the log
+const ERROR_BANNER_DURATION_MS: u128 = 8_000;
+
+// How long the playbar shows the volume instead of the track's progress after it changes
+const VOLUME_GAUGE_MS: u128 = 2_000;
+
+// Spotify takes up to 5 seeds for recommendations
+const AUTOPLAY_SEED_TRACKS: usize = 5;
+
+// Minutes the sleep_timer key cycles through before turning the timer off
+const SLEEP_TIMER_PRESETS: [u64; 4] = [15, 30, 45, 60];
+
+// How many errors `:bugreport` includes
+const RECENT_ERROR_LIMIT: usize = 20;
+
+// How many messages the log stream keeps
+const LOG_MESSAGE_LIMIT: usize = 100;
+
+// How often the recently played view refetches the history while it is open
+const RECENTLY_PLAYED_POLL_INTERVAL_MS: u128 = 30_000;
+
 const DEFAULT_ROUTE: Route = Route {
   id: RouteId::Home,
   active_block: ActiveBlock::Empty,
   hovered_block: ActiveBlock::Library,
+  loading: false,
+  updated_at: None,
 };
 
 #[derive(Clone)]
@@ -91,6 +186,8 @@ pub struct Library {
   pub saved_shows: ScrollableResultPages<Page<SimplifiedShow>>,
   pub saved_artists: ScrollableResultPages<CursorBasedPage<FullArtist>>,
   pub show_episodes: ScrollableResultPages<Page<SimplifiedEpisode>>,
+  // Discover Weekly, Release Radar, the Daily Mixes and the like
+  pub made_for_you_playlists: Vec<SimplifiedPlaylist>,
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -113,9 +210,63 @@ pub enum ArtistBlock {
 
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
 pub enum DialogContext {
+  // The Ok/Cancel dialog, asking about `App::dialog`
   #[default]
-  PlaylistWindow,
-  PlaylistSearch,
+  Confirm,
+  AddToPlaylist,
+  OpenIn,
+  Share,
+}
+
+/// What the Ok/Cancel dialog asks about, carrying everything needed to act on the answer so it
+/// doesn't depend on the selection staying put
+#[derive(Clone, PartialEq, Debug)]
+pub enum Dialog {
+  // Deletes the playlist when the user owns it
+  UnfollowPlaylist {
+    id: String,
+    name: String,
+  },
+  UnfollowArtist {
+    id: String,
+    name: String,
+  },
+  RemoveSavedAlbum {
+    id: String,
+    name: String,
+  },
+  RemoveFromPlaylist {
+    playlist_id: String,
+    track_uris: Vec<String>,
+    // The track's name, or how many tracks are marked
+    subject: String,
+  },
+  RemoveDuplicates {
+    playlist_id: String,
+    duplicates: Vec<Duplicate>,
+    subject: String,
+  },
+  // Not destructive, Cancel still plays the episode but from the start
+  ResumeEpisode {
+    uri: String,
+    position_ms: u32,
+    name: String,
+  },
+}
+
+impl Dialog {
+  /// What the dialog is about, shown in bold between the question's two halves
+  pub fn subject(&self) -> &str {
+    match self {
+      Dialog::UnfollowPlaylist { name, .. }
+      | Dialog::UnfollowArtist { name, .. }
+      | Dialog::RemoveSavedAlbum { name, .. }
+      | Dialog::ResumeEpisode { name, .. } => name,
+      Dialog::RemoveFromPlaylist { subject, .. } | Dialog::RemoveDuplicates { subject, .. } => {
+        subject
+      }
+    }
+  }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -132,6 +283,12 @@ pub enum ActiveBlock {
   Library,
   MyPlaylists,
   Podcasts,
+  MadeForYou,
+  NewReleases,
+  FeaturedPlaylists,
+  Categories,
+  CategoryPlaylists,
+  Profile,
   EpisodeTable,
   RecentlyPlayed,
   SearchResultBlock,
@@ -140,6 +297,8 @@ pub enum ActiveBlock {
   Artists,
   BasicView,
   LogStream,
+  Duplicates,
+  ColorStory,
   Dialog(DialogContext),
 }
 
@@ -159,16 +318,44 @@ pub enum RouteId {
   Artists,
   Podcasts,
   PodcastEpisodes,
+  MadeForYou,
+  NewReleases,
+  FeaturedPlaylists,
+  Categories,
+  CategoryPlaylists,
+  Profile,
   Recommendations,
   LogStream,
+  Duplicates,
+  ColorStory,
   Dialog,
 }
 
+impl RouteId {
+  /// Dialogs and the views shown over the others rather than in the main area
+  pub fn is_overlay(&self) -> bool {
+    matches!(
+      self,
+      RouteId::Dialog
+        | RouteId::LogStream
+        | RouteId::Error
+        | RouteId::SelectedDevice
+        | RouteId::Analysis
+        | RouteId::BasicView
+        | RouteId::ColorStory
+    )
+  }
+}
+
 #[derive(Debug)]
 pub struct Route {
   pub id: RouteId,
   pub active_block: ActiveBlock,
   pub hovered_block: ActiveBlock,
+  // Set while the data for the route is being fetched, so it shows a skeleton instead of
+  // whatever the previous route left behind
+  pub loading: bool,
+  pub updated_at: Option<chrono::DateTime<chrono::Local>>,
 }
 
 // Is it possible to compose enums?
@@ -180,6 +367,55 @@ pub enum TrackTableContext {
   PlaylistSearch,
   SavedTracks,
   RecommendedTracks,
+  TopTracks,
+  MadeForYou,
+  FeaturedPlaylists,
+  CategoryPlaylists,
+}
+
+impl TrackTableContext {
+  /// Short name of where the tracks came from, shown as a badge in the track table title
+  pub fn badge(&self) -> &'static str {
+    match self {
+      TrackTableContext::MyPlaylists => "Playlist",
+      TrackTableContext::AlbumSearch => "Album",
+      TrackTableContext::PlaylistSearch => "Search",
+      TrackTableContext::SavedTracks => "Liked",
+      TrackTableContext::RecommendedTracks => "Radio",
+      TrackTableContext::TopTracks => "Top Tracks",
+      TrackTableContext::MadeForYou => "Made For You",
+      TrackTableContext::FeaturedPlaylists => "Featured",
+      TrackTableContext::CategoryPlaylists => "Browse",
+    }
+  }
+}
+
+/// Period the Top Tracks and Top Artists views cover, switched with 1/2/3
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TopTimeRange {
+  FourWeeks,
+  #[default]
+  SixMonths,
+  AllTime,
+}
+
+impl TopTimeRange {
+  pub fn from_key(key: Key) -> Option<TopTimeRange> {
+    match key {
+      Key::Char('1') => Some(TopTimeRange::FourWeeks),
+      Key::Char('2') => Some(TopTimeRange::SixMonths),
+      Key::Char('3') => Some(TopTimeRange::AllTime),
+      _ => None,
+    }
+  }
+
+  pub fn label(&self) -> &'static str {
+    match self {
+      TopTimeRange::FourWeeks => "last 4 weeks",
+      TopTimeRange::SixMonths => "last 6 months",
+      TopTimeRange::AllTime => "all time",
+    }
+  }
 }
 
 // Is it possible to compose enums?
@@ -214,6 +450,114 @@ pub struct SearchResult {
   pub selected_shows_index: Option<usize>,
   pub hovered_block: SearchResultBlock,
   pub selected_block: SearchResultBlock,
+  // The query the results are for and the page size, to fetch more pages of them
+  pub query: String,
+  pub page_limit: u32,
+  // The pages of each kind of result fetched so far, the shown one being the current page
+  pub track_pages: ScrollableResultPages<Page<FullTrack>>,
+  pub artist_pages: ScrollableResultPages<Page<FullArtist>>,
+  pub album_pages: ScrollableResultPages<Page<SimplifiedAlbum>>,
+  pub playlist_pages: ScrollableResultPages<Page<SimplifiedPlaylist>>,
+  pub show_pages: ScrollableResultPages<Page<SimplifiedShow>>,
+}
+
+impl Default for SearchResult {
+  fn default() -> Self {
+    SearchResult {
+      hovered_block: SearchResultBlock::SongSearch,
+      selected_block: SearchResultBlock::Empty,
+      albums: None,
+      artists: None,
+      playlists: None,
+      shows: None,
+      selected_album_index: None,
+      selected_artists_index: None,
+      selected_playlists_index: None,
+      selected_tracks_index: None,
+      selected_shows_index: None,
+      tracks: None,
+      query: String::new(),
+      page_limit: 0,
+      track_pages: ScrollableResultPages::new(),
+      artist_pages: ScrollableResultPages::new(),
+      album_pages: ScrollableResultPages::new(),
+      playlist_pages: ScrollableResultPages::new(),
+      show_pages: ScrollableResultPages::new(),
+    }
+  }
+}
+
+impl SearchResult {
+  /// Forget the results of the previous query
+  pub fn clear(&mut self, query: String, page_limit: u32) {
+    self.query = query;
+    self.page_limit = page_limit;
+    self.tracks = None;
+    self.artists = None;
+    self.albums = None;
+    self.playlists = None;
+    self.shows = None;
+    self.track_pages = ScrollableResultPages::new();
+    self.artist_pages = ScrollableResultPages::new();
+    self.album_pages = ScrollableResultPages::new();
+    self.playlist_pages = ScrollableResultPages::new();
+    self.show_pages = ScrollableResultPages::new();
+  }
+
+  /// Show a newly fetched page of results, keeping it to page back to
+  pub fn add_page(&mut self, result: rspotify::model::SearchResult) {
+    use rspotify::model::SearchResult as Found;
+
+    match result {
+      Found::Tracks(page) => {
+        self.track_pages.add_pages(page.clone());
+        self.tracks = Some(page);
+      }
+      Found::Artists(page) => {
+        self.artist_pages.add_pages(page.clone());
+        self.artists = Some(page);
+      }
+      Found::Albums(page) => {
+        self.album_pages.add_pages(page.clone());
+        self.albums = Some(page);
+      }
+      Found::Playlists(page) => {
+        self.playlist_pages.add_pages(page.clone());
+        self.playlists = Some(page);
+      }
+      Found::Shows(page) => {
+        self.show_pages.add_pages(page.clone());
+        self.shows = Some(page);
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Show the next or previous of `pages` when it was fetched before, with the selection back at
+/// the top. Returns the offset to fetch the next page from when it wasn't, unless `pages` ends
+/// with the last one.
+fn step_search_page<T: Clone + DeserializeOwned>(
+  pages: &mut ScrollableResultPages<Page<T>>,
+  shown: &mut Option<Page<T>>,
+  selected_index: &mut Option<usize>,
+  forward: bool,
+) -> Option<u32> {
+  let index = if forward {
+    pages.index + 1
+  } else {
+    pages.index.checked_sub(1)?
+  };
+  if let Some(page) = pages.get_results(Some(index)).cloned() {
+    pages.index = index;
+    *shown = Some(page);
+    *selected_index = selected_index.map(|_| 0);
+    return None;
+  }
+  let last = pages.get_results(None)?;
+  last.next.as_ref()?;
+  *selected_index = selected_index.map(|_| 0);
+  Some(last.offset + last.limit)
 }
 
 #[derive(Default)]
@@ -221,6 +565,70 @@ pub struct TrackTable {
   pub tracks: Vec<FullTrack>,
   pub selected_index: usize,
   pub context: Option<TrackTableContext>,
+  // Where visual mode started; the marked range runs from here to `selected_index`
+  pub visual_anchor: Option<usize>,
+  // When each track was added, by track ID, for playlists and liked songs
+  pub added_at: HashMap<String, chrono::DateTime<chrono::Utc>>,
+  // Each row's index in its playlist, which runs ahead of the row's own once episodes or
+  // unavailable items were left out. Empty when they are the same.
+  pub context_positions: Vec<u32>,
+}
+
+impl TrackTable {
+  /// The rows marked in visual mode, if it is on
+  pub fn visual_range(&self) -> Option<RangeInclusive<usize>> {
+    let last = self.tracks.len().checked_sub(1)?;
+    self.visual_anchor.map(|anchor| {
+      let anchor = anchor.min(last);
+      let cursor = self.selected_index.min(last);
+      anchor.min(cursor)..=anchor.max(cursor)
+    })
+  }
+
+  /// Where a row is in the album or playlist it was loaded from, to start playback at
+  pub fn context_position(&self, row: usize) -> u32 {
+    self
+      .context_positions
+      .get(row)
+      .copied()
+      .unwrap_or(row as u32)
+  }
+
+  /// Drop every row of the given track IDs, as removing them from a playlist does, moving the
+  /// rows after them up in the playlist too
+  pub fn remove_tracks(&mut self, removed_ids: &[String]) {
+    let is_removed = |track: &FullTrack| {
+      track
+        .id
+        .as_ref()
+        .is_some_and(|id| removed_ids.contains(&id.to_string()))
+    };
+
+    let mut removed_before = 0;
+    let mut positions = vec![];
+    for (row, track) in self.tracks.iter().enumerate() {
+      if is_removed(track) {
+        removed_before += 1;
+      } else if !self.context_positions.is_empty() {
+        positions.push(self.context_position(row) - removed_before);
+      }
+    }
+    self.context_positions = positions;
+    self.tracks.retain(|track| !is_removed(track));
+    self.selected_index = self.selected_index.min(self.tracks.len().saturating_sub(1));
+  }
+
+  /// The tracks an action applies to: the marked range in visual mode, otherwise the track under
+  /// the cursor
+  pub fn selected_tracks(&self) -> &[FullTrack] {
+    match self.visual_range() {
+      Some(range) => &self.tracks[range],
+      None => {
+        let end = (self.selected_index + 1).min(self.tracks.len());
+        &self.tracks[self.selected_index.min(end)..end]
+      }
+    }
+  }
 }
 
 #[derive(Clone)]
@@ -250,10 +658,24 @@ pub struct SelectedFullAlbum {
 pub enum IdleAnimation {
   SpinningRecord,
   CoinFlip,
+  // Album art on one half, the track in large type on the other
+  Split,
+}
+
+impl IdleAnimation {
+  pub fn from_config_str(value: &str) -> Option<IdleAnimation> {
+    match value {
+      "spinning_record" => Some(IdleAnimation::SpinningRecord),
+      "coin_flip" => Some(IdleAnimation::CoinFlip),
+      "split" => Some(IdleAnimation::Split),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Clone)]
 pub struct Artist {
+  pub artist_id: String,
   pub artist_name: String,
   pub albums: Page<SimplifiedAlbum>,
   pub related_artists: Vec<FullArtist>,
@@ -265,12 +687,204 @@ pub struct Artist {
   pub artist_selected_block: ArtistBlock,
 }
 
+/// What happens when the sleep timer runs out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SleepAction {
+  Pause,
+  // Leaves playback as it is on the device
+  Quit,
+}
+
+#[derive(Clone, Debug)]
+pub struct SleepTimer {
+  pub duration: Duration,
+  pub started_at: Instant,
+  pub action: SleepAction,
+}
+
+impl SleepTimer {
+  pub fn remaining(&self) -> Duration {
+    self.duration.saturating_sub(self.started_at.elapsed())
+  }
+}
+
+/// A stretch of the playing track to repeat, as Spotify has no looping of its own. Without an
+/// end it is only marked so far.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbLoop {
+  pub track_id: String,
+  pub start_ms: u32,
+  pub end_ms: Option<u32>,
+}
+
+impl AbLoop {
+  /// Whether playback at `position_ms` of `track_id` has run past the loop's end
+  pub fn is_past_end(&self, track_id: &str, position_ms: u128) -> bool {
+    self.track_id == track_id
+      && self
+        .end_ms
+        .is_some_and(|end_ms| position_ms >= u128::from(end_ms))
+  }
+}
+
+/// How much a log stream message matters, least first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+  Debug,
+  Info,
+  Warn,
+  Error,
+}
+
+impl LogLevel {
+  pub fn label(self) -> &'static str {
+    match self {
+      LogLevel::Debug => "DEBUG",
+      LogLevel::Info => "INFO",
+      LogLevel::Warn => "WARN",
+      LogLevel::Error => "ERROR",
+    }
+  }
+
+  /// The next level the log stream's filter cycles to, back round to everything after errors
+  pub fn next(self) -> Self {
+    match self {
+      LogLevel::Debug => LogLevel::Info,
+      LogLevel::Info => LogLevel::Warn,
+      LogLevel::Warn => LogLevel::Error,
+      LogLevel::Error => LogLevel::Debug,
+    }
+  }
+}
+
+/// A line of the log stream, already stamped with its time and level
+#[derive(Clone, Debug)]
+pub struct LogMessage {
+  pub level: LogLevel,
+  pub text: String,
+}
+
+impl LogMessage {
+  /// Whether the log stream shows this message with the given filter and search
+  pub fn is_shown(&self, min_level: LogLevel, search: &str) -> bool {
+    self.level >= min_level
+      && (search.is_empty() || self.text.to_lowercase().contains(&search.to_lowercase()))
+  }
+}
+
+/// The vim style `:` command line while it is open
+#[derive(Clone, Default)]
+pub struct CommandLine {
+  pub input: String,
+  // Input before the word being completed, the candidates for that word and the one shown
+  pub completion_base: String,
+  pub completions: Vec<String>,
+  pub completion_index: Option<usize>,
+}
+
+/// What the text typed into the input box is for
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum InputPurpose {
+  #[default]
+  Search,
+  NewPlaylistName,
+  // The id of the playlist being renamed
+  RenamePlaylist(String),
+}
+
+impl InputPurpose {
+  pub fn title(&self) -> &'static str {
+    match self {
+      InputPurpose::Search => "Search",
+      InputPurpose::NewPlaylistName => "New playlist name",
+      InputPurpose::RenamePlaylist(_) => "Rename playlist",
+    }
+  }
+}
+
+/// The add-to-playlist dialog: the tracks being added and the filter typed so far
+#[derive(Clone, Default)]
+pub struct PlaylistPicker {
+  pub track_uris: Vec<String>,
+  pub filter: String,
+  pub selected_index: usize,
+}
+
 pub struct App {
   pub instant_since_last_current_playback_poll: Instant,
   pub instant_since_last_playback_toggle: Instant,
   pub instant_since_last_device_poll: Instant,
+  pub instant_since_last_recently_played_poll: Instant,
+  pub instant_since_last_theme_check: Instant,
+  pub episode_positions: EpisodePositions,
+  // Numeric prefix typed before a movement key, e.g. the 5 in `5j`
+  pub pending_count: Option<usize>,
+  // How many rows the key being handled moves, from the count typed before it
+  pub motion_count: usize,
+  // Short notice shown over the bottom of the screen, with the time it was raised
+  pub toast: Option<(String, Instant)>,
+  // The latest error's summary, shown along the bottom without taking the user anywhere
+  pub error_banner: Option<(String, Instant)>,
+  pub command_line: Option<CommandLine>,
+  pub sleep_timer: Option<SleepTimer>,
+  // The volume from before muting, while muted
+  pub muted_volume: Option<u8>,
+  pub ab_loop: Option<AbLoop>,
+  // The name of spotui's own playback device while it is running
+  pub streaming_device: Option<String>,
+  // A device spotui started, to switch to once Spotify lists it
+  pub awaited_device: Option<String>,
+  // Playback as the hooks last saw it
+  hooked_playback: Option<PlaybackState>,
+  // The text and cover URL last put in the now playing files
+  written_now_playing: Option<(String, Option<String>)>,
+  pub volume_changed_at: Option<Instant>,
+  pub playlist_picker: Option<PlaylistPicker>,
+  pub open_in_menu: Option<OpenInMenu>,
+  pub share_menu: Option<ShareMenu>,
+  // Quick filter typed into the Playlists block, narrowing the sidebar while it is open
+  pub playlist_filter: Option<String>,
+  // IDs of the sidebar playlists in the order Spotify returned them, for the `recent` sort
+  pub playlist_spotify_order: Vec<String>,
+  pub playlist_duplicates: Option<PlaylistDuplicates>,
+  pub input_purpose: InputPurpose,
+  pub search_history: SearchHistory,
+  // Set while Up/Down show an older search in the search box
+  pub search_history_recall: Option<HistoryRecall>,
+  pub reverse_search: Option<ReverseSearch>,
+  // When the search box was last edited, until the search it is waiting to run goes out
+  pub pending_search: Option<Instant>,
+  pub top_time_range: TopTimeRange,
+  // Whether `artists` holds the top artists rather than the followed ones
+  pub showing_top_artists: bool,
+  pub should_quit: bool,
+  // Set by Ctrl-Z, `:suspend` or SIGTSTP for the UI loop to hand the terminal back and stop
+  pub suspend_requested: bool,
+  // Set by SIGCONT, so the terminal is set up again after being stopped from outside
+  pub resume_requested: bool,
+  // Selections from the last session or the jump list, made once what they point into has loaded
+  pub pending_selection: Option<PendingSelection>,
+  pub jump_list: JumpList,
+  // Set by the breadcrumb key until a level to go back to is picked by its number
+  pub picking_breadcrumb: bool,
+  // Where the breadcrumb was drawn in the last frame, to find the level clicked on
+  pub breadcrumb_area: Cell<Option<Rect>>,
+  // The workspaces not being shown, whose state is swapped in when switching to them
+  pub workspaces: Workspaces,
+  // Whether the main area shows the route on top beside the one it was opened from
+  pub split_view: bool,
+  // How deep the navigation stack was when focus moved to the split view's left pane, which
+  // shows the route on top while the stack is that deep
+  pub split_left_focused_at: Option<usize>,
   navigation_stack: Vec<Route>,
   pub audio_analysis: Option<AudioAnalysis>,
+  // Whether the analysis heatmap shows timbre rather than pitches
+  pub analysis_shows_timbre: bool,
+  // Audio features by track ID, `None` for tracks Spotify had none for
+  pub audio_features: HashMap<String, Option<AudioFeatures>>,
+  pub show_audio_features: bool,
+  // The last few tracks played, newest last, to seed autoplay with
+  pub recent_track_ids: VecDeque<String>,
   pub home_scroll: u16,
   pub user_config: UserConfig,
   pub artists: Vec<FullArtist>,
@@ -279,6 +893,8 @@ pub struct App {
   pub saved_album_tracks_index: usize,
   pub api_error: String,
   pub current_playback_context: Option<CurrentPlaybackContext>,
+  // The first item of the queue, shown in the playbar
+  pub up_next: Option<PlayableItem>,
   pub devices: Option<DevicePayload>,
   // Inputs:
   // input is the string for input;
@@ -319,20 +935,50 @@ pub struct App {
   pub selected_show_simplified: Option<SelectedShow>,
   pub selected_show_full: Option<SelectedFullShow>,
   pub user: Option<PrivateUser>,
+  // The user's profile picture, pixelated like the album art
+  pub profile_image: Option<PixelatedAlbumArt>,
   pub album_list_index: usize,
   pub artists_list_index: usize,
   pub clipboard: Option<Clipboard>,
   pub shows_list_index: usize,
+  pub made_for_you_index: usize,
+  // Albums from the browse endpoint, a page at a time
+  pub new_releases: ScrollableResultPages<Page<SimplifiedAlbum>>,
+  pub new_releases_index: usize,
+  // Spotify's featured playlists and the editorial message they come with
+  pub featured_playlists: Option<FeaturedPlaylists>,
+  pub featured_playlists_index: usize,
+  // Browse categories, then the playlists of the one picked
+  pub categories: Vec<Category>,
+  pub categories_index: usize,
+  pub category_playlists: Vec<SimplifiedPlaylist>,
+  pub category_playlists_index: usize,
   pub episode_list_index: usize,
   pub is_loading: bool,
-  io_tx: Option<Sender<IoEvent>>,
+  io_tx: Option<Sender<DispatchedEvent>>,
   pub is_fetching_current_playback: bool,
   pub spotify_token_expiry: SystemTime,
-  pub dialog: Option<String>,
+  pub dialog: Option<Dialog>,
+  // Whether Ok rather than Cancel is highlighted in the dialog
   pub confirm: bool,
-  pub log_messages: Vec<String>,
+  pub log_messages: Vec<LogMessage>,
+  // The last few errors, oldest first, kept apart from the log so they survive a busy session
+  pub recent_errors: VecDeque<String>,
   pub log_stream_selected_index: usize,
   pub log_stream_scroll_offset: usize,
+  // The least important level the log stream shows
+  pub log_stream_level: LogLevel,
+  // Text log stream lines must contain, and whether it is still being typed
+  pub log_stream_search: String,
+  pub log_stream_search_input: bool,
+  // Warnings and errors logged elsewhere through tracing, for the log stream to show
+  pub traced_log_messages: Option<Receiver<(LogLevel, String)>>,
+  pub show_debug_overlay: bool,
+  // How far the help overlay is scrolled, while it is open
+  pub help_scroll: Option<u16>,
+  pub frame_stats: FrameStats,
+  // Kept up to date by the network thread
+  pub network_stats: Arc<NetworkStats>,
   pub focus_manager: FocusManager,
   pub album_art_manager: Option<AlbumArtManager>,
   pub current_album_art: Option<PixelatedAlbumArt>,
@@ -346,10 +992,23 @@ impl Default for App {
   fn default() -> Self {
     App {
       audio_analysis: None,
+      analysis_shows_timbre: false,
+      audio_features: HashMap::new(),
+      recent_track_ids: VecDeque::new(),
+      show_audio_features: false,
       album_table_context: AlbumTableContext::Full,
       album_list_index: 0,
       artists_list_index: 0,
       shows_list_index: 0,
+      made_for_you_index: 0,
+      new_releases: ScrollableResultPages::new(),
+      new_releases_index: 0,
+      featured_playlists: None,
+      featured_playlists_index: 0,
+      categories: vec![],
+      categories_index: 0,
+      category_playlists: vec![],
+      category_playlists_index: 0,
       episode_list_index: 0,
       artists: vec![],
       artist: None,
@@ -367,6 +1026,7 @@ impl Default for App {
         saved_shows: ScrollableResultPages::new(),
         saved_artists: ScrollableResultPages::new(),
         show_episodes: ScrollableResultPages::new(),
+        made_for_you_playlists: vec![],
         selected_index: 0,
       },
       liked_song_ids_set: HashSet::new(),
@@ -378,6 +1038,7 @@ impl Default for App {
       small_search_limit: 4,
       api_error: String::new(),
       current_playback_context: None,
+      up_next: None,
       devices: None,
       input: vec![],
       input_idx: 0,
@@ -388,20 +1049,7 @@ impl Default for App {
       recommended_tracks: vec![],
       recommendations_context: None,
       recommendations_seed: "".to_string(),
-      search_results: SearchResult {
-        hovered_block: SearchResultBlock::SongSearch,
-        selected_block: SearchResultBlock::Empty,
-        albums: None,
-        artists: None,
-        playlists: None,
-        shows: None,
-        selected_album_index: None,
-        selected_artists_index: None,
-        selected_playlists_index: None,
-        selected_tracks_index: None,
-        selected_shows_index: None,
-        tracks: None,
-      },
+      search_results: SearchResult::default(),
       song_progress_ms: 0,
       seek_ms: None,
       selected_device_index: None,
@@ -412,9 +1060,49 @@ impl Default for App {
       selected_show_simplified: None,
       selected_show_full: None,
       user: None,
+      profile_image: None,
       instant_since_last_current_playback_poll: Instant::now(),
       instant_since_last_playback_toggle: Instant::now(),
       instant_since_last_device_poll: Instant::now(),
+      instant_since_last_recently_played_poll: Instant::now(),
+      instant_since_last_theme_check: Instant::now(),
+      episode_positions: EpisodePositions::default(),
+      pending_count: None,
+      motion_count: 1,
+      toast: None,
+      error_banner: None,
+      command_line: None,
+      sleep_timer: None,
+      muted_volume: None,
+      ab_loop: None,
+      streaming_device: None,
+      awaited_device: None,
+      hooked_playback: None,
+      written_now_playing: None,
+      volume_changed_at: None,
+      playlist_picker: None,
+      open_in_menu: None,
+      share_menu: None,
+      playlist_filter: None,
+      playlist_spotify_order: vec![],
+      playlist_duplicates: None,
+      input_purpose: InputPurpose::Search,
+      search_history: SearchHistory::default(),
+      search_history_recall: None,
+      reverse_search: None,
+      pending_search: None,
+      top_time_range: TopTimeRange::default(),
+      showing_top_artists: false,
+      should_quit: false,
+      suspend_requested: false,
+      resume_requested: false,
+      pending_selection: None,
+      jump_list: JumpList::default(),
+      picking_breadcrumb: false,
+      breadcrumb_area: Cell::new(None),
+      workspaces: Workspaces::default(),
+      split_view: false,
+      split_left_focused_at: None,
       clipboard: Clipboard::new().ok(),
       is_loading: false,
       io_tx: None,
@@ -423,9 +1111,18 @@ impl Default for App {
       dialog: None,
       confirm: false,
       log_messages: Vec::new(),
+      recent_errors: VecDeque::new(),
       log_stream_selected_index: 0,
       log_stream_scroll_offset: 0,
-      focus_manager: FocusManager::new(),
+      log_stream_level: LogLevel::Info,
+      log_stream_search: String::new(),
+      log_stream_search_input: false,
+      traced_log_messages: None,
+      show_debug_overlay: false,
+      help_scroll: None,
+      frame_stats: FrameStats::default(),
+      network_stats: Arc::default(),
+      focus_manager: FocusManager::from_route(&DEFAULT_ROUTE),
       album_art_manager: AlbumArtManager::new().ok(),
       current_album_art: None,
       current_album_art_url: None,
@@ -438,22 +1135,30 @@ impl Default for App {
 
 impl App {
   pub fn new(
-    io_tx: Sender<IoEvent>,
+    io_tx: Sender<DispatchedEvent>,
     user_config: UserConfig,
     spotify_token_expiry: SystemTime,
   ) -> App {
     App {
       io_tx: Some(io_tx),
+      idle_animation: user_config.behavior.idle_animation,
       user_config,
       spotify_token_expiry,
+      episode_positions: EpisodePositions::load(),
+      search_history: SearchHistory::load(),
       ..App::default()
     }
   }
 
   // Send a network event to the network thread
   pub fn dispatch(&mut self, action: IoEvent) {
+    // Spotify refuses these for free accounts, with an error for every key press
+    if action.requires_premium() && self.is_free_tier() {
+      self.show_toast("Controlling playback needs Spotify Premium".to_string());
+      return;
+    }
     if let Some(io_tx) = &self.io_tx {
-      if let Err(e) = io_tx.send(action) {
+      if let Err(e) = io_tx.send((action, Some(self.workspaces.active()))) {
         self.handle_error(anyhow::anyhow!("Failed to dispatch event: {}", e));
       };
     }
@@ -500,19 +1205,65 @@ impl App {
 
   pub fn update_on_tick(&mut self) {
     self.poll_current_playback();
+    location::apply_loaded(self);
+
+    if let Some((_, raised_at)) = &self.toast {
+      if raised_at.elapsed().as_millis() >= TOAST_DURATION_MS {
+        self.toast = None;
+      }
+    }
+    if let Some((_, raised_at)) = &self.error_banner {
+      if raised_at.elapsed().as_millis() >= ERROR_BANNER_DURATION_MS {
+        self.error_banner = None;
+      }
+    }
+
+    if self
+      .sleep_timer
+      .as_ref()
+      .is_some_and(|timer| timer.remaining().is_zero())
+    {
+      self.end_sleep_timer();
+    }
+
+    if let Some(edited_at) = self.pending_search {
+      let delay = u128::from(self.user_config.behavior.search_delay_milliseconds);
+      if edited_at.elapsed().as_millis() >= delay {
+        self.pending_search = None;
+        self.search_as_you_type();
+      }
+    }
     
-    // Poll devices every 30 seconds
-    let device_poll_interval_ms = 30_000;
+    // Poll devices every 30 seconds, or every few while waiting for a device to come up
+    let device_poll_interval_ms = if self.awaited_device.is_some() {
+      3_000
+    } else {
+      30_000
+    };
     let device_elapsed = self.instant_since_last_device_poll.elapsed().as_millis();
     
     if device_elapsed >= device_poll_interval_ms {
       self.dispatch(IoEvent::GetDevices);
       self.instant_since_last_device_poll = Instant::now();
     }
+
+    if self.get_current_route().id == RouteId::RecentlyPlayed
+      && self
+        .instant_since_last_recently_played_poll
+        .elapsed()
+        .as_millis()
+        >= RECENTLY_PLAYED_POLL_INTERVAL_MS
+    {
+      self.dispatch(IoEvent::GetRecentlyPlayed);
+      self.instant_since_last_recently_played_poll = Instant::now();
+    }
+
+    self.check_theme_source();
     if let Some(CurrentPlaybackContext {
       item: Some(item),
       is_playing,
       progress,
+      repeat_state,
       ..
     }) = &self.current_playback_context
     {
@@ -532,11 +1283,88 @@ impl App {
         PlayableItem::Episode(episode) => episode.duration.num_milliseconds() as u32,
       };
 
-      if elapsed < u128::from(duration_ms) {
-        self.song_progress_ms = elapsed;
-      } else {
-        self.song_progress_ms = duration_ms.into();
+      self.song_progress_ms = clamp_progress(
+        elapsed,
+        duration_ms,
+        *repeat_state == rspotify::model::enums::RepeatState::Track,
+      );
+
+      if let PlayableItem::Episode(episode) = item {
+        self.episode_positions.record(
+          &episode.id.to_string(),
+          self.song_progress_ms as u32,
+          duration_ms,
+        );
+      }
+    }
+
+    self.check_ab_loop();
+    self.run_playback_hooks();
+    self.write_now_playing_files();
+    self.show_traced_log_messages();
+  }
+
+  fn show_traced_log_messages(&mut self) {
+    let messages = match &self.traced_log_messages {
+      Some(messages) => messages.try_iter().collect::<Vec<_>>(),
+      None => return,
+    };
+    for (level, message) in messages {
+      self.log(level, message);
+    }
+  }
+
+  /// Run the configured hooks for whatever changed since the last tick, be it from a poll or
+  /// from a key press
+  fn run_playback_hooks(&mut self) {
+    let behavior = &self.user_config.behavior;
+    if behavior.on_track_change.is_empty()
+      && behavior.on_pause.is_empty()
+      && behavior.on_resume.is_empty()
+    {
+      return;
+    }
+    let context = self.current_playback_context.as_ref();
+    let current: PlaybackState = (
+      context.and_then(|context| match context.item.as_ref()? {
+        PlayableItem::Track(track) => track.id.as_ref().map(|id| id.to_string()),
+        PlayableItem::Episode(episode) => Some(episode.id.to_string()),
+      }),
+      context.is_some_and(|context| context.is_playing),
+    );
+    let events = hooks::events(self.hooked_playback.as_ref(), &current);
+    self.hooked_playback = Some(current);
+
+    for event in events {
+      let command = match event {
+        PlaybackEvent::TrackChange => &self.user_config.behavior.on_track_change,
+        PlaybackEvent::Pause => &self.user_config.behavior.on_pause,
+        PlaybackEvent::Resume => &self.user_config.behavior.on_resume,
+      };
+      if command.is_empty() {
+        continue;
       }
+      let env = hooks::environment(event, self.current_playback_context.as_ref());
+      if let Err(e) = hooks::run(command, env) {
+        self.add_log_message(e.to_string());
+      }
+    }
+  }
+
+  fn check_theme_source(&mut self) {
+    let hot_reload = match &self.user_config.theme_source {
+      Some(source) => source.hot_reload,
+      None => false,
+    };
+    if !hot_reload || self.instant_since_last_theme_check.elapsed().as_millis() < 2_000 {
+      return;
+    }
+    self.instant_since_last_theme_check = Instant::now();
+
+    match self.user_config.reload_theme_source() {
+      Ok(true) => self.add_log_message("Theme reloaded from external palette".to_string()),
+      Ok(false) => {}
+      Err(e) => self.log(LogLevel::Warn, format!("Failed to reload theme: {}", e)),
     }
   }
 
@@ -606,7 +1434,7 @@ impl App {
       );
 
       if next_volume != current_volume {
-        self.dispatch(IoEvent::SetVolume(next_volume));
+        self.set_volume(next_volume);
       }
     }
   }
@@ -620,129 +1448,705 @@ impl App {
       );
 
       if next_volume != current_volume {
-        self.dispatch(IoEvent::SetVolume(next_volume as u8));
+        self.set_volume(next_volume as u8);
+      }
+    }
+  }
+
+  /// Change the volume, showing it in the playbar straight away. Changing it by hand while
+  /// muted forgets the volume to unmute to.
+  pub fn set_volume(&mut self, volume: u8) {
+    self.muted_volume = None;
+    self.change_volume(volume);
+  }
+
+  /// Mute, remembering the volume to go back to, or unmute to it
+  pub fn toggle_mute(&mut self) {
+    if let Some(volume) = self.muted_volume.take() {
+      self.change_volume(volume);
+      return;
+    }
+    let current_volume = self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.device.volume_percent);
+    match current_volume {
+      Some(volume) if volume > 0 => {
+        self.change_volume(0);
+        if !self.is_free_tier() {
+          self.muted_volume = Some(volume as u8);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn change_volume(&mut self, volume: u8) {
+    // Free accounts can't change the volume, dispatching only tells them so
+    if !self.is_free_tier() {
+      if let Some(context) = &mut self.current_playback_context {
+        context.device.volume_percent = Some(u32::from(volume));
       }
+      self.volume_changed_at = Some(Instant::now());
     }
+    self.dispatch(IoEvent::SetVolume(volume));
+  }
+
+  /// Whether the volume changed just now, for the playbar to show it
+  pub fn is_volume_changing(&self) -> bool {
+    self
+      .volume_changed_at
+      .is_some_and(|changed_at| changed_at.elapsed().as_millis() < VOLUME_GAUGE_MS)
   }
 
   pub fn handle_error(&mut self, e: anyhow::Error) {
-    // Log the error to the log stream with ERROR prefix
-    let error_message = format!("ERROR: {}", e);
-    self.add_log_message(error_message);
-    
-    // Auto-open log stream when error occurs (only if not already viewing it)
+    self.log(LogLevel::Error, e.to_string());
+
+    let timestamp = chrono::Utc::now().format("%H:%M:%S");
+    self.recent_errors.push_back(format!("[{}] {}", timestamp, e));
+    if self.recent_errors.len() > RECENT_ERROR_LIMIT {
+      self.recent_errors.pop_front();
+    }
+
+    // The log stream shows the whole error already
     if self.get_current_route().active_block != ActiveBlock::LogStream {
-      self.push_navigation_stack(RouteId::LogStream, ActiveBlock::LogStream);
+      let summary = e.to_string().lines().next().unwrap_or_default().to_string();
+      self.error_banner = Some((summary, Instant::now()));
     }
     
     // Clear api_error to prevent UI artifacts
     self.api_error = String::new();
   }
 
+  /// An error that leaves the app unable to do anything, such as losing authentication, which
+  /// opens the log stream rather than waiting for the user to look
+  pub fn handle_fatal_error(&mut self, e: anyhow::Error) {
+    self.handle_error(e);
+    if self.get_current_route().active_block != ActiveBlock::LogStream {
+      self.error_banner = None;
+      self.push_navigation_stack(RouteId::LogStream, ActiveBlock::LogStream);
+    }
+  }
+
+  /// Log an everyday message, at info level
   pub fn add_log_message(&mut self, message: String) {
+    self.log(LogLevel::Info, message);
+  }
+
+  pub fn log(&mut self, level: LogLevel, message: String) {
     let timestamp = chrono::Utc::now().format("%H:%M:%S");
-    let formatted_message = format!("[{}] {}", timestamp, message);
-    
-    // Write to disk for debugging
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-      .create(true)
-      .append(true)
-      .open("/tmp/spotify-tui-log-stream.log") 
-    {
-      use std::io::Write;
-      let _ = writeln!(file, "=== LOG MESSAGE ===");
-      let _ = writeln!(file, "{}", formatted_message);
-      let _ = writeln!(file, "Raw message: {:?}", message);
-      let _ = writeln!(file, "Contains newlines: {}", message.contains('\n'));
-      let _ = writeln!(file, "==================\n");
+    let formatted_message = format!("[{}] {:<5} {}", timestamp, level.label(), message);
+    match level {
+      LogLevel::Debug => tracing::debug!(target: LOG_STREAM_TARGET, "{}", message),
+      LogLevel::Info => tracing::info!(target: LOG_STREAM_TARGET, "{}", message),
+      LogLevel::Warn => tracing::warn!(target: LOG_STREAM_TARGET, "{}", message),
+      LogLevel::Error => tracing::error!(target: LOG_STREAM_TARGET, "{}", message),
     }
-    
-    self.log_messages.push(formatted_message);
-    
+
+    self.log_messages.push(LogMessage {
+      level,
+      text: formatted_message,
+    });
+
     // Keep only the last 100 messages to prevent memory issues
-    if self.log_messages.len() > 100 {
-      self.log_messages.remove(0);
-      // Adjust selection index when removing messages from the beginning
-      if self.log_stream_selected_index > 0 {
-        self.log_stream_selected_index -= 1;
-      }
-      if self.log_stream_scroll_offset > 0 {
-        self.log_stream_scroll_offset -= 1;
+    if self.log_messages.len() > LOG_MESSAGE_LIMIT {
+      let removed = self.log_messages.remove(0);
+      // Adjust selection index when removing a shown message from the beginning
+      if removed.is_shown(self.log_stream_level, &self.log_stream_search) {
+        self.log_stream_selected_index = self.log_stream_selected_index.saturating_sub(1);
+        self.log_stream_scroll_offset = self.log_stream_scroll_offset.saturating_sub(1);
       }
     }
     
     // If we're not actively viewing the log stream, auto-scroll to show latest messages
     if self.get_current_route().active_block != ActiveBlock::LogStream {
-      self.log_stream_selected_index = self.log_messages.len().saturating_sub(1);
-      let visible_height = 10; // Default visible height
-      self.log_stream_scroll_offset = self.log_messages.len().saturating_sub(visible_height);
+      self.scroll_log_stream_to_end();
     }
   }
 
-  pub fn toggle_playback(&mut self) {
-    // Add a cooldown to prevent rapid toggling
-    let elapsed = self.instant_since_last_playback_toggle.elapsed().as_millis();
-    if elapsed < 500 { // 500ms cooldown
-      return;
-    }
-    
-    self.instant_since_last_playback_toggle = Instant::now();
-    
-    if let Some(CurrentPlaybackContext {
-      is_playing: true, ..
-    }) = &self.current_playback_context
+  /// The messages the log stream shows with its level filter and search
+  pub fn shown_log_messages(&self) -> Vec<&LogMessage> {
+    self
+      .log_messages
+      .iter()
+      .filter(|message| message.is_shown(self.log_stream_level, &self.log_stream_search))
+      .collect()
+  }
+
+  /// Select the latest shown message
+  pub fn scroll_log_stream_to_end(&mut self) {
+    let shown = self.shown_log_messages().len();
+    self.log_stream_selected_index = shown.saturating_sub(1);
+    let visible_height = 10; // Default visible height
+    self.log_stream_scroll_offset = shown.saturating_sub(visible_height);
+  }
+
+  /// Show one level less in the log stream, or everything again after errors only
+  pub fn cycle_log_stream_level(&mut self) {
+    self.log_stream_level = self.log_stream_level.next();
+    self.scroll_log_stream_to_end();
+  }
+
+  pub fn copy_selected_log_message(&mut self) {
+    let text = match self
+      .shown_log_messages()
+      .get(self.log_stream_selected_index)
     {
-      self.dispatch(IoEvent::PausePlayback);
-    } else {
-      // When no offset or uris are passed, spotify will resume current playback
-      self.dispatch(IoEvent::StartPlayback(None, None));
+      Some(message) => message.text.clone(),
+      None => return,
+    };
+    self.copy_to_clipboard(text, "log line");
+  }
+
+  /// Pictures held in memory, as a count and the bytes their pixels take up
+  pub fn image_memory_usage(&self) -> (usize, usize) {
+    let (mut count, mut bytes) = self
+      .album_art_manager
+      .as_ref()
+      .map(|manager| manager.memory_usage())
+      .unwrap_or((0, 0));
+    for image in [&self.current_album_art, &self.profile_image]
+      .into_iter()
+      .flatten()
+    {
+      count += 1;
+      bytes += image.memory_bytes();
     }
+    (count, bytes)
   }
 
-  pub fn previous_track(&mut self) {
-    if self.song_progress_ms >= 3_000 {
-      self.dispatch(IoEvent::Seek(0));
-    } else {
-      self.dispatch(IoEvent::PreviousTrack);
+  /// Whether the user is known to be on the free tier, which can't control playback
+  pub fn is_free_tier(&self) -> bool {
+    self.user.as_ref().and_then(|user| user.product) == Some(SubscriptionLevel::Free)
+  }
+
+  /// Show a short notice to the user, also recording it in the log stream
+  pub fn show_toast(&mut self, message: String) {
+    self.add_log_message(message.clone());
+    self.toast = Some((message, Instant::now()));
+  }
+
+  /// Spotify's queue can only append, so "play next" jumps to the track within the context that
+  /// is already playing, and otherwise falls back to adding it to the end of the queue
+  pub fn play_next(&mut self, context_uri: Option<String>, track_uri: String) {
+    let playing_context_uri = self
+      .current_playback_context
+      .as_ref()
+      .and_then(|playback| playback.context.as_ref())
+      .map(|context| context.uri.clone());
+
+    match context_uri {
+      Some(uri) if playing_context_uri.as_deref() == Some(uri.as_str()) => {
+        self.dispatch(IoEvent::StartPlayback(
+          Some(uri),
+          Some(PlaybackOffset::Uri(track_uri)),
+        ));
+      }
+      _ => {
+        self.dispatch(IoEvent::AddItemToQueue(track_uri));
+        self.show_toast(
+          "Not in the playing context, so added to the end of the queue instead".to_string(),
+        );
+      }
     }
   }
 
-  // The navigation_stack actually only controls the large block to the right of `library` and
-  // `playlists`
-  pub fn push_navigation_stack(&mut self, next_route_id: RouteId, next_active_block: ActiveBlock) {
-    if !self
+  /// Open the add-to-playlist dialog for the given tracks
+  pub fn open_playlist_picker(&mut self, track_uris: Vec<String>) {
+    if track_uris.is_empty() {
+      return;
+    }
+    self.playlist_picker = Some(PlaylistPicker {
+      track_uris,
+      ..Default::default()
+    });
+    self.push_navigation_stack(
+      RouteId::Dialog,
+      ActiveBlock::Dialog(DialogContext::AddToPlaylist),
+    );
+  }
+
+  /// Ask the user about `dialog` with the Ok/Cancel dialog. Cancel starts out highlighted for
+  /// anything destructive
+  pub fn open_dialog(&mut self, dialog: Dialog) {
+    self.confirm = matches!(dialog, Dialog::ResumeEpisode { .. });
+    self.dialog = Some(dialog);
+    self.push_navigation_stack(RouteId::Dialog, ActiveBlock::Dialog(DialogContext::Confirm));
+  }
+
+  /// Open the "open in" menu for the track selected in a track table, otherwise for what is
+  /// playing
+  pub fn open_open_in_menu(&mut self) {
+    let selected = if self.get_current_route().active_block == ActiveBlock::TrackTable {
+      self.track_table.tracks.get(self.track_table.selected_index)
+    } else {
+      None
+    };
+    let playing = self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.item.as_ref());
+    let targets = match (selected, playing) {
+      (Some(track), _) | (None, Some(PlayableItem::Track(track))) => open_in::track_targets(track),
+      (None, Some(PlayableItem::Episode(episode))) => vec![open_in::OpenTarget::new(
+        "episode",
+        &episode.name,
+        &episode.id.to_string(),
+      )],
+      (None, None) => vec![],
+    };
+    if targets.is_empty() {
+      self.show_toast("Nothing to open".to_string());
+      return;
+    }
+
+    self.open_in_menu = Some(OpenInMenu {
+      targets,
+      selected_index: 0,
+      in_app: open_in::has_desktop_client(),
+    });
+    self.push_navigation_stack(RouteId::Dialog, ActiveBlock::Dialog(DialogContext::OpenIn));
+  }
+
+  /// Show a freshly fetched play history. Plays newer than the ones already shown are put in
+  /// front and the selection stays on the same entry, so an open view reads like a live feed.
+  /// Returns how many new plays were added.
+  pub fn merge_recently_played(&mut self, history: CursorBasedPage<PlayHistory>) -> usize {
+    let current = match self.recently_played.result.as_mut() {
+      Some(current) => current,
+      None => {
+        let count = history.items.len();
+        self.recently_played.result = Some(history);
+        self.recently_played.index = 0;
+        return count;
+      }
+    };
+
+    let newest_known = current.items.first().map(|play| play.played_at);
+    let mut new_plays = history
+      .items
+      .into_iter()
+      .filter(|play| newest_known.is_none_or(|newest| play.played_at > newest))
+      .collect::<Vec<PlayHistory>>();
+    let count = new_plays.len();
+    if count > 0 {
+      new_plays.append(&mut current.items);
+      current.items = new_plays;
+      self.recently_played.index += count;
+    }
+    count
+  }
+
+  /// The playlist whose tracks the track table shows, if it shows one
+  pub fn viewed_playlist(&self) -> Option<&SimplifiedPlaylist> {
+    match self.track_table.context {
+      Some(TrackTableContext::MyPlaylists) => {
+        let index = self
+          .active_playlist_index
+          .or(self.selected_playlist_index)?;
+        self.playlists.as_ref()?.items.get(index)
+      }
+      Some(TrackTableContext::MadeForYou) => self
+        .library
+        .made_for_you_playlists
+        .get(self.made_for_you_index),
+      Some(TrackTableContext::FeaturedPlaylists) => self.selected_featured_playlist(),
+      Some(TrackTableContext::CategoryPlaylists) => {
+        self.category_playlists.get(self.category_playlists_index)
+      }
+      _ => None,
+    }
+  }
+
+  /// The featured playlist under the cursor
+  pub fn selected_featured_playlist(&self) -> Option<&SimplifiedPlaylist> {
+    self
+      .featured_playlists
+      .as_ref()?
+      .playlists
+      .items
+      .get(self.featured_playlists_index)
+  }
+
+  /// The category whose playlists are listed, or would be on Enter
+  pub fn selected_category(&self) -> Option<&Category> {
+    self.categories.get(self.categories_index)
+  }
+
+  /// Whether the user may change the playlist's tracks: they own it or it is collaborative
+  pub fn can_edit_playlist(&self, playlist: &SimplifiedPlaylist) -> bool {
+    playlist.collaborative
+      || self
+        .user
+        .as_ref()
+        .map(|user| user.id == playlist.owner.id)
+        .unwrap_or(false)
+  }
+
+  /// Whether 1/2/3 switch the time range, i.e. Top Tracks or Top Artists has focus
+  pub fn is_top_view_active(&self) -> bool {
+    match self.get_current_route().active_block {
+      ActiveBlock::TrackTable => self.track_table.context == Some(TrackTableContext::TopTracks),
+      ActiveBlock::Artists => self.showing_top_artists,
+      _ => false,
+    }
+  }
+
+  /// Playlists the user can add tracks to, i.e. their own and collaborative ones, that match the
+  /// picker's filter, best match first
+  pub fn playlist_picker_matches(&self) -> Vec<&SimplifiedPlaylist> {
+    let (playlists, picker) = match (&self.playlists, &self.playlist_picker) {
+      (Some(playlists), Some(picker)) => (playlists, picker),
+      _ => return vec![],
+    };
+    let editable = playlists
+      .items
+      .iter()
+      .filter(|playlist| {
+        playlist.collaborative
+          || self
+            .user
+            .as_ref()
+            .map(|user| user.id == playlist.owner.id)
+            .unwrap_or(false)
+      })
+      .collect::<Vec<&SimplifiedPlaylist>>();
+    let names = editable
+      .iter()
+      .map(|playlist| playlist.name.as_str())
+      .collect::<Vec<&str>>();
+    fuzzy::rank(&picker.filter, &names)
+      .into_iter()
+      .map(|index| editable[index])
+      .collect()
+  }
+
+  /// Put the sidebar playlists in the configured order, pinned ones first. The selection and the
+  /// open playlist stay on the same playlists.
+  pub fn sort_playlists(&mut self) {
+    let playlists = match self.playlists.as_mut() {
+      Some(playlists) => playlists,
+      None => return,
+    };
+    let ids = playlists
+      .items
+      .iter()
+      .map(|playlist| playlist.id.to_string())
+      .collect::<Vec<String>>();
+    let order = {
+      let entries = playlists
+        .items
+        .iter()
+        .zip(&ids)
+        .map(|(playlist, id)| SortEntry {
+          id,
+          name: &playlist.name,
+          own: self.user.as_ref().map(|user| &user.id) == Some(&playlist.owner.id),
+          spotify_position: self
+            .playlist_spotify_order
+            .iter()
+            .position(|known| known == id),
+        })
+        .collect::<Vec<SortEntry>>();
+      playlist_sort::order(
+        &entries,
+        self.user_config.behavior.playlist_sort,
+        &self.user_config.behavior.pinned_playlists,
+      )
+    };
+
+    let mut items = std::mem::take(&mut playlists.items)
+      .into_iter()
+      .map(Some)
+      .collect::<Vec<_>>();
+    playlists.items = order
+      .iter()
+      .filter_map(|&index| items[index].take())
+      .collect();
+    let new_index = |old_index: Option<usize>| {
+      old_index.map(|old| order.iter().position(|&index| index == old).unwrap_or(old))
+    };
+    self.selected_playlist_index = new_index(self.selected_playlist_index);
+    self.active_playlist_index = new_index(self.active_playlist_index);
+  }
+
+  /// Switch the sidebar to the next sort and remember it in the config
+  pub fn cycle_playlist_sort(&mut self) {
+    let sort = self.user_config.behavior.playlist_sort.next();
+    self.user_config.behavior.playlist_sort = sort;
+    self.sort_playlists();
+    self.show_toast(format!("Playlists sorted by {}", sort.as_config_str()));
+    if let Err(e) = self
+      .user_config
+      .save_behavior_value("playlist_sort", sort.as_config_str())
+    {
+      self.log(
+        LogLevel::Warn,
+        format!("Couldn't save the playlist sort: {}", e),
+      );
+    }
+  }
+
+  /// Pin the selected playlist to the top of the sidebar, or unpin it, and remember it in the
+  /// config. Pins made here are stored by ID so they survive renames.
+  pub fn toggle_pinned_playlist(&mut self) {
+    let playlist = match self
+      .playlists
+      .as_ref()
+      .zip(self.selected_playlist_index)
+      .and_then(|(playlists, index)| playlists.items.get(index))
+    {
+      Some(playlist) => playlist,
+      None => return,
+    };
+    let id = playlist.id.to_string();
+    let entry = SortEntry {
+      id: &id,
+      name: &playlist.name,
+      own: false,
+      spotify_position: None,
+    };
+    let pinned = &mut self.user_config.behavior.pinned_playlists;
+    let message = if playlist_sort::is_pinned(&entry, pinned) {
+      pinned.retain(|pin| !playlist_sort::is_pinned(&entry, std::slice::from_ref(pin)));
+      format!("Unpinned {}", playlist.name)
+    } else {
+      pinned.push(id.rsplit(':').next().unwrap_or(&id).to_string());
+      format!("Pinned {}", playlist.name)
+    };
+
+    let value = format!(
+      "[{}]",
+      pinned
+        .iter()
+        .map(|pin| serde_json::to_string(pin).unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join(", ")
+    );
+    self.sort_playlists();
+    self.show_toast(message);
+    if let Err(e) = self
+      .user_config
+      .save_behavior_value("pinned_playlists", &value)
+    {
+      self.log(
+        LogLevel::Warn,
+        format!("Couldn't save the pinned playlists: {}", e),
+      );
+    }
+  }
+
+  /// Indices into `playlists` of the ones matching the sidebar filter, best match first. Every
+  /// playlist in sidebar order when no filter is open.
+  pub fn playlist_filter_matches(&self) -> Vec<usize> {
+    let playlists = match &self.playlists {
+      Some(playlists) => playlists,
+      None => return vec![],
+    };
+    let names = playlists
+      .items
+      .iter()
+      .map(|playlist| playlist.name.as_str())
+      .collect::<Vec<&str>>();
+    fuzzy::rank(self.playlist_filter.as_deref().unwrap_or_default(), &names)
+  }
+
+  pub fn toggle_playback(&mut self) {
+    // Add a cooldown to prevent rapid toggling
+    let elapsed = self.instant_since_last_playback_toggle.elapsed().as_millis();
+    if elapsed < 500 { // 500ms cooldown
+      return;
+    }
+    
+    self.instant_since_last_playback_toggle = Instant::now();
+    
+    if let Some(CurrentPlaybackContext {
+      is_playing: true, ..
+    }) = &self.current_playback_context
+    {
+      self.dispatch(IoEvent::PausePlayback);
+    } else {
+      // When no offset or uris are passed, spotify will resume current playback
+      self.dispatch(IoEvent::StartPlayback(None, None));
+    }
+  }
+
+  pub fn previous_track(&mut self) {
+    if self.song_progress_ms >= 3_000 {
+      self.dispatch(IoEvent::Seek(0));
+    } else {
+      self.dispatch(IoEvent::PreviousTrack);
+    }
+  }
+
+  // The navigation_stack actually only controls the large block to the right of `library` and
+  // `playlists`
+  pub fn push_navigation_stack(&mut self, next_route_id: RouteId, next_active_block: ActiveBlock) {
+    // Overlays aren't places to jump back to from, and leave the split view's panes as they were
+    if !next_route_id.is_overlay() {
+      if let Some(jump) = Jump::current(self) {
+        self.jump_list.record(jump);
+      }
+      self.split_left_focused_at = None;
+    }
+    if !self
       .navigation_stack
       .last()
       .map(|last_route| last_route.id == next_route_id)
       .unwrap_or(false)
     {
-      self.add_log_message(format!("Pushing to navigation stack: {:?} / {:?}", next_route_id, next_active_block));
+      self.log(
+        LogLevel::Debug,
+        format!(
+          "Pushing to navigation stack: {:?} / {:?}",
+          next_route_id, next_active_block
+        ),
+      );
       self.navigation_stack.push(Route {
         id: next_route_id,
         active_block: next_active_block,
         hovered_block: next_active_block,
+        loading: false,
+        updated_at: None,
       });
-      self.add_log_message(format!("Navigation stack after push: {:?}", 
-        self.navigation_stack.iter().map(|r| format!("{:?}", r.active_block)).collect::<Vec<_>>()));
+      self.sync_focus();
+      self.log(
+        LogLevel::Debug,
+        format!(
+          "Navigation stack after push: {:?}",
+          self
+            .navigation_stack
+            .iter()
+            .map(|r| format!("{:?}", r.active_block))
+            .collect::<Vec<_>>()
+        ),
+      );
+    }
+  }
+
+  /// Navigate to a route whose data has just been requested. It shows as loading until
+  /// `finish_loading_route` is called for it, so call this after dispatching the fetch.
+  pub fn push_loading_route(&mut self, route_id: RouteId, active_block: ActiveBlock) {
+    self.push_navigation_stack(route_id.clone(), active_block);
+    if let Some(route) = self.navigation_stack.last_mut() {
+      if route.id == route_id {
+        route.loading = true;
+      }
+    }
+  }
+
+  /// End the loading state of the latest `route_id` route, and stamp it as updated now if its
+  /// data arrived
+  pub fn finish_loading_route(&mut self, route_id: RouteId, updated: bool) {
+    if let Some(route) = self
+      .navigation_stack
+      .iter_mut()
+      .rev()
+      .find(|route| route.id == route_id)
+    {
+      route.loading = false;
+      if updated {
+        route.updated_at = Some(chrono::Local::now());
+      }
     }
   }
 
   pub fn pop_navigation_stack(&mut self) -> Option<Route> {
-    self.add_log_message(format!("Popping navigation stack. Current size: {}", self.navigation_stack.len()));
+    self.log(
+      LogLevel::Debug,
+      format!(
+        "Popping navigation stack. Current size: {}",
+        self.navigation_stack.len()
+      ),
+    );
     if self.navigation_stack.len() == 1 {
       None
     } else {
       let popped = self.navigation_stack.pop();
-      self.add_log_message(format!("Navigation stack after pop: {:?}", 
-        self.navigation_stack.iter().map(|r| format!("{:?}", r.active_block)).collect::<Vec<_>>()));
+      self.sync_focus();
+      self.log(
+        LogLevel::Debug,
+        format!(
+          "Navigation stack after pop: {:?}",
+          self
+            .navigation_stack
+            .iter()
+            .map(|r| format!("{:?}", r.active_block))
+            .collect::<Vec<_>>()
+        ),
+      );
       popped
     }
   }
 
   pub fn clear_navigation_stack(&mut self) {
-    self.add_log_message("Clearing navigation stack to return to root".to_string());
+    self.log(
+      LogLevel::Debug,
+      "Clearing navigation stack to return to root".to_string(),
+    );
     self.navigation_stack.clear();
     self.navigation_stack.push(DEFAULT_ROUTE);
+    self.sync_focus();
+  }
+
+  /// Go back to the route `level` places from the bottom of the stack, dropping the ones above it
+  pub fn pop_navigation_stack_to(&mut self, level: usize) {
+    if level + 1 < self.navigation_stack.len() {
+      self.navigation_stack.truncate(level + 1);
+      self.sync_focus();
+    }
+  }
+
+  /// Trade the navigation stack for another workspace's, an empty one starting at the default
+  /// route
+  pub fn swap_navigation_stack(&mut self, stack: &mut Vec<Route>) {
+    std::mem::swap(&mut self.navigation_stack, stack);
+    if self.navigation_stack.is_empty() {
+      self.navigation_stack.push(DEFAULT_ROUTE);
+    }
+    self.sync_focus();
+  }
+
+  /// The routes in the split view's left and right panes: the one on top and the one under it,
+  /// unless either is an overlay or the home screen, or both are the same kind of view and so
+  /// drawn from the same state
+  pub fn split_view_routes(&self) -> Option<(&Route, &Route)> {
+    if !self.split_view {
+      return None;
+    }
+    let (below, top) = match self.navigation_stack.as_slice() {
+      [.., below, top] => (below, top),
+      _ => return None,
+    };
+    let shown = |route: &Route| route.id != RouteId::Home && !route.id.is_overlay();
+    if below.id == top.id || !shown(below) || !shown(top) {
+      return None;
+    }
+    if self.split_left_focused_at == Some(self.navigation_stack.len()) {
+      Some((top, below))
+    } else {
+      Some((below, top))
+    }
+  }
+
+  /// Move focus to the split view's other pane by putting its route on top
+  pub fn switch_split_pane(&mut self) {
+    if self.split_view_routes().is_none() {
+      return;
+    }
+    let depth = self.navigation_stack.len();
+    self.navigation_stack.swap(depth - 2, depth - 1);
+    self.split_left_focused_at = match self.split_left_focused_at {
+      Some(focused_at) if focused_at == depth => None,
+      _ => Some(depth),
+    };
+    self.sync_focus();
+  }
+
+  // The focus manager follows whatever route is on top of the stack
+  fn sync_focus(&mut self) {
+    let route = self.navigation_stack.last().unwrap_or(&DEFAULT_ROUTE);
+    self.focus_manager.sync_with_route(route);
   }
 
   pub fn get_current_route(&self) -> &Route {
@@ -750,7 +2154,8 @@ impl App {
     self.navigation_stack.last().unwrap_or(&DEFAULT_ROUTE)
   }
 
-  pub fn get_navigation_breadcrumb(&self) -> String {
+  /// A name for each route on the stack, from the bottom up
+  pub fn get_navigation_breadcrumb(&self) -> Vec<String> {
     let mut breadcrumb_parts = Vec::new();
     
     for route in &self.navigation_stack {
@@ -775,6 +2180,19 @@ impl App {
             Some(TrackTableContext::RecommendedTracks) => "Recommended",
             Some(TrackTableContext::AlbumSearch) => "Album",
             Some(TrackTableContext::PlaylistSearch) => "Search Results",
+            Some(TrackTableContext::TopTracks) => "Top Tracks",
+            Some(TrackTableContext::MadeForYou) => self
+              .viewed_playlist()
+              .map(|playlist| playlist.name.as_str())
+              .unwrap_or("Made For You"),
+            Some(TrackTableContext::FeaturedPlaylists) => self
+              .viewed_playlist()
+              .map(|playlist| playlist.name.as_str())
+              .unwrap_or("Featured Playlists"),
+            Some(TrackTableContext::CategoryPlaylists) => self
+              .viewed_playlist()
+              .map(|playlist| playlist.name.as_str())
+              .unwrap_or("Playlist"),
             None => "Tracks",
           }
         }
@@ -792,10 +2210,21 @@ impl App {
         RouteId::Artists => "Artists",
         RouteId::Podcasts => "Podcasts",
         RouteId::PodcastEpisodes => "Episodes",
+        RouteId::MadeForYou => "Made For You",
+        RouteId::NewReleases => "New Releases",
+        RouteId::FeaturedPlaylists => "Featured Playlists",
+        RouteId::Categories => "Categories",
+        RouteId::CategoryPlaylists => self
+          .selected_category()
+          .map(|category| category.name.as_str())
+          .unwrap_or("Category"),
+        RouteId::Profile => "Profile",
         RouteId::Recommendations => "Recommendations",
         RouteId::Analysis => "Audio Analysis",
         RouteId::BasicView => "Basic View",
         RouteId::LogStream => "Log Stream",
+        RouteId::Duplicates => "Duplicates",
+        RouteId::ColorStory => "Color Story",
         RouteId::SelectedDevice => "Devices",
         RouteId::Error => "Error",
         RouteId::Dialog => "Dialog",
@@ -803,7 +2232,7 @@ impl App {
       breadcrumb_parts.push(part.to_string());
     }
     
-    breadcrumb_parts.join(" > ")
+    breadcrumb_parts
   }
 
   fn get_current_route_mut(&mut self) -> &mut Route {
@@ -822,68 +2251,521 @@ impl App {
     if let Some(hovered_block) = hovered_block {
       current_route.hovered_block = hovered_block;
     }
+    self.sync_focus();
   }
 
-  pub fn copy_song_url(&mut self) {
-    let clipboard = match &mut self.clipboard {
-      Some(ctx) => ctx,
+  /// Show the next or previous page of the search results in focus, fetching pages that weren't
+  /// seen yet
+  pub fn change_search_results_page(&mut self, forward: bool) {
+    let results = &mut self.search_results;
+    let block = match results.selected_block {
+      SearchResultBlock::Empty => results.hovered_block,
+      block => block,
+    };
+    let (search_type, offset) = match block {
+      SearchResultBlock::SongSearch => (
+        SearchType::Track,
+        step_search_page(
+          &mut results.track_pages,
+          &mut results.tracks,
+          &mut results.selected_tracks_index,
+          forward,
+        ),
+      ),
+      SearchResultBlock::ArtistSearch => (
+        SearchType::Artist,
+        step_search_page(
+          &mut results.artist_pages,
+          &mut results.artists,
+          &mut results.selected_artists_index,
+          forward,
+        ),
+      ),
+      SearchResultBlock::AlbumSearch => (
+        SearchType::Album,
+        step_search_page(
+          &mut results.album_pages,
+          &mut results.albums,
+          &mut results.selected_album_index,
+          forward,
+        ),
+      ),
+      SearchResultBlock::PlaylistSearch => (
+        SearchType::Playlist,
+        step_search_page(
+          &mut results.playlist_pages,
+          &mut results.playlists,
+          &mut results.selected_playlists_index,
+          forward,
+        ),
+      ),
+      SearchResultBlock::ShowSearch => (
+        SearchType::Show,
+        step_search_page(
+          &mut results.show_pages,
+          &mut results.shows,
+          &mut results.selected_shows_index,
+          forward,
+        ),
+      ),
+      SearchResultBlock::Empty => return,
+    };
+    if let Some(offset) = offset {
+      self.dispatch(IoEvent::GetSearchResultsPage(search_type, offset));
+    }
+  }
+
+  /// Search for what is in the search box and show the results while it keeps focus. A newer
+  /// search cancels this one if it is still running.
+  pub fn search_as_you_type(&mut self) {
+    let query: String = self.input.iter().collect();
+    let query = query.trim();
+    // Links are opened on Enter rather than searched for
+    if query.is_empty() || open_in::link_uri(query).is_some() {
+      return;
+    }
+    self.dispatch(IoEvent::GetSearchResults(query.to_string()));
+    self.push_navigation_stack(RouteId::Search, ActiveBlock::Input);
+  }
+
+  /// Like or unlike whatever is playing from any block. The ♥ in the playbar flips right away;
+  /// the network layer settles it with Spotify's answer.
+  /// The ID of the playing track, if a track rather than an episode is playing
+  pub fn playing_track_id(&self) -> Option<String> {
+    match self.current_playback_context.as_ref()?.item.as_ref()? {
+      PlayableItem::Track(track) => track.id.as_ref().map(|id| id.to_string()),
+      PlayableItem::Episode(_) => None,
+    }
+  }
+
+  /// Note the playing track as a seed for autoplay
+  pub fn remember_played_track(&mut self) {
+    if let Some(track_id) = self.playing_track_id() {
+      self.recent_track_ids.retain(|id| *id != track_id);
+      self.recent_track_ids.push_back(track_id);
+      if self.recent_track_ids.len() > AUTOPLAY_SEED_TRACKS {
+        self.recent_track_ids.pop_front();
+      }
+    }
+  }
+
+  /// Keep the music going after the context ran out, with tracks like the ones just played
+  pub fn start_autoplay(&mut self) {
+    if self.recent_track_ids.is_empty() {
+      return;
+    }
+    let seeds = self.recent_track_ids.iter().cloned().collect();
+    self.dispatch(IoEvent::StartAutoplay(seeds));
+  }
+
+  pub fn start_sleep_timer(&mut self, duration: Duration, action: SleepAction) {
+    let minutes = duration.as_secs().div_ceil(60);
+    let message = match action {
+      SleepAction::Pause => format!("Pausing playback in {} min", minutes),
+      SleepAction::Quit => format!("Quitting in {} min", minutes),
+    };
+    self.sleep_timer = Some(SleepTimer {
+      duration,
+      started_at: Instant::now(),
+      action,
+    });
+    self.show_toast(message);
+  }
+
+  pub fn cancel_sleep_timer(&mut self) {
+    if self.sleep_timer.take().is_some() {
+      self.show_toast("Sleep timer cancelled".to_string());
+    }
+  }
+
+  /// Step the sleep timer through the presets, then off. A timer set another way starts over
+  /// from the first preset.
+  pub fn cycle_sleep_timer(&mut self) {
+    let next = match &self.sleep_timer {
+      None => SLEEP_TIMER_PRESETS.first(),
+      Some(timer) => SLEEP_TIMER_PRESETS
+        .iter()
+        .position(|minutes| timer.duration == Duration::from_secs(minutes * 60))
+        .map_or(SLEEP_TIMER_PRESETS.first(), |index| {
+          SLEEP_TIMER_PRESETS.get(index + 1)
+        }),
+    };
+    match next {
+      Some(minutes) => {
+        let action = self
+          .sleep_timer
+          .as_ref()
+          .map_or(SleepAction::Pause, |timer| timer.action);
+        self.start_sleep_timer(Duration::from_secs(minutes * 60), action);
+      }
+      None => self.cancel_sleep_timer(),
+    }
+  }
+
+  /// Start spotui's own playback device, or stop it if it is running
+  pub fn toggle_streaming(&mut self) {
+    let event = if self.streaming_device.is_some() {
+      IoEvent::StopStreaming
+    } else {
+      IoEvent::StartStreaming
+    };
+    self.dispatch(event);
+  }
+
+  /// Mark where the A–B loop starts, at the playing track's position
+  pub fn mark_loop_start(&mut self) {
+    if self.is_free_tier() {
+      self.show_toast("Looping needs Spotify Premium to seek".to_string());
+      return;
+    }
+    let track_id = match self.playing_track_id() {
+      Some(track_id) => track_id,
       None => return,
     };
+    let start_ms = self.song_progress_ms as u32;
+    self.ab_loop = Some(AbLoop {
+      track_id,
+      start_ms,
+      end_ms: None,
+    });
+    self.show_toast(format!(
+      "Loop starts at {}",
+      millis_to_minutes(u128::from(start_ms))
+    ));
+  }
+
+  /// Mark where the A–B loop ends and start looping, or stop a loop that is running
+  pub fn mark_loop_end(&mut self) {
+    let start_ms = match &self.ab_loop {
+      Some(AbLoop {
+        end_ms: Some(_), ..
+      }) => {
+        self.ab_loop = None;
+        self.show_toast("Loop cleared".to_string());
+        return;
+      }
+      Some(ab_loop) => ab_loop.start_ms,
+      None => {
+        self.show_toast("Mark where the loop starts first".to_string());
+        return;
+      }
+    };
+    let end_ms = self.song_progress_ms as u32;
+    if end_ms <= start_ms {
+      self.show_toast("The loop has to end after it starts".to_string());
+      return;
+    }
+    if let Some(ab_loop) = &mut self.ab_loop {
+      ab_loop.end_ms = Some(end_ms);
+    }
+    self.show_toast(format!(
+      "Looping {}–{}",
+      millis_to_minutes(u128::from(start_ms)),
+      millis_to_minutes(u128::from(end_ms))
+    ));
+  }
 
-    if let Some(CurrentPlaybackContext {
-      item: Some(item), ..
-    }) = &self.current_playback_context
-    {
-      match item {
-        PlayableItem::Track(track) => {
-          if let Err(e) = clipboard.set_text(format!(
-            "https://open.spotify.com/track/{}",
-            track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string())
-          )) {
-            self.handle_error(anyhow!("failed to set clipboard content: {}", e));
-          }
-        }
-        PlayableItem::Episode(episode) => {
-          if let Err(e) = clipboard.set_text(format!(
-            "https://open.spotify.com/episode/{}",
-            episode.id.to_owned()
-          )) {
-            self.handle_error(anyhow!("failed to set clipboard content: {}", e));
-          }
+  fn check_ab_loop(&mut self) {
+    let ab_loop = match &self.ab_loop {
+      Some(ab_loop) => ab_loop,
+      None => return,
+    };
+    let track_id = self.playing_track_id();
+    if track_id.as_deref() != Some(ab_loop.track_id.as_str()) {
+      // Another track came on, so this loop is done with
+      self.ab_loop = None;
+      return;
+    }
+    let is_playing = self
+      .current_playback_context
+      .as_ref()
+      .is_some_and(|context| context.is_playing);
+    if !is_playing || !ab_loop.is_past_end(&ab_loop.track_id, self.song_progress_ms) {
+      return;
+    }
+
+    let start_ms = ab_loop.start_ms;
+    self.dispatch(IoEvent::Seek(start_ms));
+    // Count progress from the start again, so the next ticks don't seek once more before the
+    // next poll catches up
+    if let Some(context) = &mut self.current_playback_context {
+      context.progress = Some(chrono::Duration::milliseconds(i64::from(start_ms)));
+    }
+    self.instant_since_last_current_playback_poll = Instant::now();
+    self.song_progress_ms = u128::from(start_ms);
+  }
+
+  fn end_sleep_timer(&mut self) {
+    let action = match self.sleep_timer.take() {
+      Some(timer) => timer.action,
+      None => return,
+    };
+    match action {
+      SleepAction::Pause => {
+        let is_playing = self
+          .current_playback_context
+          .as_ref()
+          .is_some_and(|context| context.is_playing);
+        if is_playing {
+          self.dispatch(IoEvent::PausePlayback);
         }
+        self.show_toast("Sleep timer ended".to_string());
       }
+      SleepAction::Quit => self.should_quit = true,
     }
   }
 
-  pub fn copy_album_url(&mut self) {
-    let clipboard = match &mut self.clipboard {
-      Some(ctx) => ctx,
+  pub fn toggle_audio_features(&mut self) {
+    self.show_audio_features = !self.show_audio_features;
+    self.fetch_audio_features();
+  }
+
+  /// Fetch the playing track's audio features while the panel shows them, unless they are
+  /// cached already
+  pub fn fetch_audio_features(&mut self) {
+    if !self.show_audio_features {
+      return;
+    }
+    if let Some(track_id) = self.playing_track_id() {
+      if !self.audio_features.contains_key(&track_id) {
+        self.dispatch(IoEvent::GetAudioFeatures(track_id));
+      }
+    }
+  }
+
+  pub fn toggle_like_current_track(&mut self) {
+    let item = self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.item.as_ref());
+    let track_id = match item {
+      Some(PlayableItem::Track(track)) => track.id.as_ref().map(|id| id.to_string()),
+      Some(PlayableItem::Episode(_)) => {
+        self.show_toast("Only tracks can be liked".to_string());
+        return;
+      }
+      None => None,
+    };
+    let track_id = match track_id {
+      Some(track_id) => track_id,
       None => return,
     };
 
-    if let Some(CurrentPlaybackContext {
-      item: Some(item), ..
-    }) = &self.current_playback_context
+    if !self.liked_song_ids_set.remove(&track_id) {
+      self.liked_song_ids_set.insert(track_id.clone());
+    }
+    self.dispatch(IoEvent::ToggleSaveTrack(track_id));
+  }
+
+  /// What the share menu shares: the item selected in the focused list, otherwise what is playing
+  fn share_item(&self) -> Option<ShareItem> {
+    let selected = match self.get_current_route().active_block {
+      ActiveBlock::TrackTable => self
+        .track_table
+        .tracks
+        .get(self.track_table.selected_index)
+        .and_then(share::track_item),
+      ActiveBlock::MyPlaylists => self
+        .selected_playlist_index
+        .and_then(|index| self.playlists.as_ref()?.items.get(index))
+        .map(|playlist| {
+          ShareItem::new(
+            "playlist",
+            &playlist.name,
+            playlist.owner.display_name.clone(),
+            &playlist.id.to_string(),
+          )
+        }),
+      ActiveBlock::AlbumList => self
+        .library
+        .saved_albums
+        .get_results(None)
+        .and_then(|albums| albums.items.get(self.album_list_index))
+        .map(|saved_album| {
+          let album = &saved_album.album;
+          ShareItem::new(
+            "album",
+            &album.name,
+            Some(share::artist_names(&album.artists)),
+            &album.id.to_string(),
+          )
+        }),
+      ActiveBlock::EpisodeTable => self
+        .library
+        .show_episodes
+        .get_results(None)
+        .and_then(|episodes| episodes.items.get(self.episode_list_index))
+        .map(|episode| ShareItem::new("episode", &episode.name, None, &episode.id.to_string())),
+      ActiveBlock::SearchResultBlock => self.search_result_share_item(),
+      _ => None,
+    };
+
+    if selected.is_some() {
+      return selected;
+    }
+    match self.current_playback_context.as_ref()?.item.as_ref()? {
+      PlayableItem::Track(track) => share::track_item(track),
+      PlayableItem::Episode(episode) => Some(ShareItem::new(
+        "episode",
+        &episode.name,
+        Some(episode.show.name.clone()),
+        &episode.id.to_string(),
+      )),
+    }
+  }
+
+  fn search_result_share_item(&self) -> Option<ShareItem> {
+    let results = &self.search_results;
+    match results.selected_block {
+      SearchResultBlock::SongSearch => results
+        .tracks
+        .as_ref()?
+        .items
+        .get(results.selected_tracks_index?)
+        .and_then(share::track_item),
+      SearchResultBlock::AlbumSearch => {
+        let album = results
+          .albums
+          .as_ref()?
+          .items
+          .get(results.selected_album_index?)?;
+        Some(ShareItem::new(
+          "album",
+          &album.name,
+          Some(share::artist_names(&album.artists)),
+          &album.id.as_ref()?.to_string(),
+        ))
+      }
+      SearchResultBlock::PlaylistSearch => {
+        let playlist = results
+          .playlists
+          .as_ref()?
+          .items
+          .get(results.selected_playlists_index?)?;
+        Some(ShareItem::new(
+          "playlist",
+          &playlist.name,
+          playlist.owner.display_name.clone(),
+          &playlist.id.to_string(),
+        ))
+      }
+      _ => None,
+    }
+  }
+
+  /// Open the share menu, which copies the selected or playing item in a choice of formats
+  pub fn open_share_menu(&mut self) {
+    match self.share_item() {
+      Some(item) => {
+        self.share_menu = Some(ShareMenu {
+          item,
+          selected_index: 0,
+        });
+        self.push_navigation_stack(RouteId::Dialog, ActiveBlock::Dialog(DialogContext::Share));
+      }
+      None => self.show_toast("Nothing to share".to_string()),
+    }
+  }
+
+  /// Put `text` on the clipboard, confirming with a toast that names `what` was copied
+  pub fn copy_to_clipboard(&mut self, text: String, what: &str) {
+    let clipboard = match &mut self.clipboard {
+      Some(clipboard) => clipboard,
+      None => {
+        self.show_toast("No clipboard available".to_string());
+        return;
+      }
+    };
+    match clipboard.set_text(text) {
+      Ok(()) => self.show_toast(format!("Copied {}", what)),
+      Err(e) => self.handle_error(anyhow!("failed to set clipboard content: {}", e)),
+    }
+  }
+
+  /// Copy what is playing, formatted with the `now_playing_template` behavior option
+  pub fn copy_now_playing(&mut self) {
+    let values = match self.now_playing_values() {
+      Some(values) => values,
+      None => return,
+    };
+    let text = template::fill(&self.user_config.behavior.now_playing_template, &values);
+    self.copy_to_clipboard(text, "now playing");
+  }
+
+  /// What is playing, for the `NOW_PLAYING_FIELDS` placeholders of a template
+  fn now_playing_values(&self) -> Option<[(&'static str, String); 4]> {
+    let item = self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.item.as_ref());
+    let (track, artist, album, url) = match item {
+      Some(PlayableItem::Track(track)) => (
+        track.name.clone(),
+        track
+          .artists
+          .iter()
+          .map(|artist| artist.name.as_str())
+          .collect::<Vec<&str>>()
+          .join(", "),
+        track.album.name.clone(),
+        track
+          .id
+          .as_ref()
+          .map(|id| open_in::uri("track", &id.to_string())),
+      ),
+      Some(PlayableItem::Episode(episode)) => (
+        episode.name.clone(),
+        episode.show.publisher.clone(),
+        episode.show.name.clone(),
+        Some(open_in::uri("episode", &episode.id.to_string())),
+      ),
+      None => return None,
+    };
+    let url = url
+      .and_then(|uri| open_in::web_url(&uri))
+      .unwrap_or_default();
+    Some([
+      ("track", track),
+      ("artist", artist),
+      ("album", album),
+      ("url", url),
+    ])
+  }
+
+  /// Keep the now playing files in step with playback. The text empties when nothing plays; the
+  /// cover is only replaced by the next one.
+  fn write_now_playing_files(&mut self) {
+    let behavior = &self.user_config.behavior;
+    if behavior.now_playing_file.is_empty() && behavior.now_playing_art_file.is_empty() {
+      return;
+    }
+    let text = self
+      .now_playing_values()
+      .map(|values| template::fill(&behavior.now_playing_file_template, &values))
+      .unwrap_or_default();
+    let art_url = match self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.item.as_ref())
     {
-      match item {
-        PlayableItem::Track(track) => {
-          if let Err(e) = clipboard.set_text(format!(
-            "https://open.spotify.com/album/{}",
-            track.album.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string())
-          )) {
-            self.handle_error(anyhow!("failed to set clipboard content: {}", e));
-          }
-        }
-        PlayableItem::Episode(episode) => {
-          if let Err(e) = clipboard.set_text(format!(
-            "https://open.spotify.com/show/{}",
-            episode.show.id.to_owned()
-          )) {
-            self.handle_error(anyhow!("failed to set clipboard content: {}", e));
-          }
-        }
+      Some(PlayableItem::Track(track)) => track.album.images.first().map(|image| image.url.clone()),
+      Some(PlayableItem::Episode(episode)) => episode.images.first().map(|image| image.url.clone()),
+      None => None,
+    };
+    let (written_text, written_art_url) = self.written_now_playing.clone().unwrap_or_default();
+
+    if text != written_text && !behavior.now_playing_file.is_empty() {
+      if let Err(e) = now_playing::write_text(&behavior.now_playing_file, &text) {
+        self.add_log_message(e.to_string());
+      }
+    }
+    let art_changed = art_url.is_some() && art_url != written_art_url;
+    if art_changed && !self.user_config.behavior.now_playing_art_file.is_empty() {
+      if let Some(url) = &art_url {
+        self.dispatch(IoEvent::WriteNowPlayingArt(url.clone()));
       }
     }
+    self.written_now_playing = Some((text, art_url.or(written_art_url)));
   }
 
   pub fn set_saved_tracks_to_table(&mut self, saved_track_page: &Page<SavedTrack>) {
@@ -969,10 +2851,20 @@ impl App {
     }
   }
 
+  /// Flip shuffle, showing the new state straight away. The next poll corrects it if Spotify
+  /// didn't follow.
   pub fn shuffle(&mut self) {
-    if let Some(context) = &self.current_playback_context.clone() {
-      self.dispatch(IoEvent::Shuffle(context.shuffle_state));
+    let shuffle_state = match &self.current_playback_context {
+      Some(context) => !context.shuffle_state,
+      None => return,
     };
+    // Free accounts can't shuffle, dispatching only tells them so
+    if !self.is_free_tier() {
+      if let Some(context) = &mut self.current_playback_context {
+        context.shuffle_state = shuffle_state;
+      }
+    }
+    self.dispatch(IoEvent::Shuffle(shuffle_state));
   }
 
   pub fn get_current_user_saved_albums_next(&mut self) {
@@ -999,35 +2891,29 @@ impl App {
   }
 
   pub fn current_user_saved_album_delete(&mut self, block: ActiveBlock) {
-    match block {
-      ActiveBlock::SearchResultBlock => {
-        if let Some(albums) = &self.search_results.albums {
-          if let Some(selected_index) = self.search_results.selected_album_index {
-            let selected_album = &albums.items[selected_index];
-            if let Some(album_id) = selected_album.id.as_ref().map(|id| id.to_string()) {
-              // self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id);
-            }
-          }
-        }
-      }
-      ActiveBlock::AlbumList => {
-        if let Some(albums) = self.library.saved_albums.get_results(None) {
-          if let Some(selected_album) = albums.items.get(self.album_list_index) {
-            let album_id = selected_album.album.id.to_string();
-            // self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id);
-          }
-        }
-      }
-      ActiveBlock::ArtistBlock => {
-        if let Some(artist) = &self.artist {
-          if let Some(selected_album) = artist.albums.items.get(artist.selected_album_index) {
-            if let Some(album_id) = selected_album.id.as_ref().map(|id| id.to_string()) {
-              // self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id);
-            }
-          }
-        }
-      }
-      _ => (),
+    let album = match block {
+      ActiveBlock::SearchResultBlock => self
+        .search_results
+        .albums
+        .as_ref()
+        .zip(self.search_results.selected_album_index)
+        .and_then(|(albums, index)| albums.items.get(index))
+        .and_then(|album| Some((album.id.as_ref()?.to_string(), album.name.clone()))),
+      ActiveBlock::AlbumList => self
+        .library
+        .saved_albums
+        .get_results(None)
+        .and_then(|albums| albums.items.get(self.album_list_index))
+        .map(|saved| (saved.album.id.to_string(), saved.album.name.clone())),
+      ActiveBlock::ArtistBlock => self
+        .artist
+        .as_ref()
+        .and_then(|artist| artist.albums.items.get(artist.selected_album_index))
+        .and_then(|album| Some((album.id.as_ref()?.to_string(), album.name.clone()))),
+      _ => None,
+    };
+    if let Some((id, name)) = album {
+      self.open_dialog(Dialog::RemoveSavedAlbum { id, name });
     }
   }
 
@@ -1079,6 +2965,35 @@ impl App {
     }
   }
 
+  pub fn get_new_releases_next(&mut self) {
+    let next_index = self.new_releases.index + 1;
+    if self.new_releases.get_results(Some(next_index)).is_some() {
+      self.new_releases.index = next_index;
+      self.new_releases_index = 0;
+    } else if let Some(new_releases) = self.new_releases.get_results(None) {
+      if new_releases.next.is_some() {
+        let offset = new_releases.offset + new_releases.limit;
+        self.dispatch(IoEvent::GetNewReleases(Some(offset)));
+      }
+    }
+  }
+
+  pub fn get_new_releases_previous(&mut self) {
+    if self.new_releases.index > 0 {
+      self.new_releases.index -= 1;
+      self.new_releases_index = 0;
+    }
+  }
+
+  /// The new release under the cursor
+  pub fn selected_new_release(&self) -> Option<&SimplifiedAlbum> {
+    self
+      .new_releases
+      .get_results(None)?
+      .items
+      .get(self.new_releases_index)
+  }
+
   pub fn get_episode_table_next(&mut self, show_id: String) {
     match self
       .library
@@ -1102,55 +3017,74 @@ impl App {
     }
   }
 
-  pub fn user_unfollow_artists(&mut self, block: ActiveBlock) {
-    match block {
+  /// The artist selected in `block`, for following or unfollowing
+  fn selected_artist(&self, block: ActiveBlock) -> Option<(String, String)> {
+    let artist = match block {
       ActiveBlock::SearchResultBlock => {
-        if let Some(artists) = &self.search_results.artists {
-          if let Some(selected_index) = self.search_results.selected_artists_index {
-            let selected_artist: &FullArtist = &artists.items[selected_index];
-            let artist_id = selected_artist.id.to_string();
-            // self.dispatch(IoEvent::UserUnfollowArtists(vec![artist_id]);
-          }
-        }
-      }
-      ActiveBlock::AlbumList => {
-        if let Some(artists) = self.library.saved_artists.get_results(None) {
-          if let Some(selected_artist) = artists.items.get(self.artists_list_index) {
-            let artist_id = selected_artist.id.to_string();
-            // self.dispatch(IoEvent::UserUnfollowArtists(vec![artist_id]);
-          }
-        }
+        let artists = self.search_results.artists.as_ref()?;
+        artists
+          .items
+          .get(self.search_results.selected_artists_index?)?
       }
+      // The followed or top artists in the library, as listed
+      ActiveBlock::AlbumList => self.artists.get(self.artists_list_index)?,
       ActiveBlock::ArtistBlock => {
-        if let Some(artist) = &self.artist {
-          let selected_artis = &artist.related_artists[artist.selected_related_artist_index];
-          let artist_id = selected_artis.id.to_string();
-          // self.dispatch(IoEvent::UserUnfollowArtists(vec![artist_id]);
-        }
+        let artist = self.artist.as_ref()?;
+        artist
+          .related_artists
+          .get(artist.selected_related_artist_index)?
       }
-      _ => (),
+      _ => return None,
     };
+    Some((artist.id.to_string(), artist.name.clone()))
+  }
+
+  pub fn user_unfollow_artists(&mut self, block: ActiveBlock) {
+    if let Some((id, name)) = self.selected_artist(block) {
+      self.open_dialog(Dialog::UnfollowArtist { id, name });
+    }
   }
 
   pub fn user_follow_artists(&mut self, block: ActiveBlock) {
-    match block {
-      ActiveBlock::SearchResultBlock => {
-        if let Some(artists) = &self.search_results.artists {
-          if let Some(selected_index) = self.search_results.selected_artists_index {
-            let selected_artist: &FullArtist = &artists.items[selected_index];
-            let artist_id = selected_artist.id.to_string();
-            // self.dispatch(IoEvent::UserFollowArtists(vec![artist_id]);
-          }
-        }
-      }
-      ActiveBlock::ArtistBlock => {
-        if let Some(artist) = &self.artist {
-          let selected_artis = &artist.related_artists[artist.selected_related_artist_index];
-          let artist_id = selected_artis.id.to_string();
-          // self.dispatch(IoEvent::UserFollowArtists(vec![artist_id]);
-        }
-      }
-      _ => (),
+    if let Some((artist_id, name)) = self.selected_artist(block) {
+      self.follow_artist(artist_id, &name);
+    }
+  }
+
+  // The follow state is updated right away, and checked again if the request fails
+  fn follow_artist(&mut self, artist_id: String, name: &str) {
+    self.followed_artist_ids_set.insert(artist_id.clone());
+    self.show_toast(format!("Following {}", name));
+    self.dispatch(IoEvent::UserFollowArtists(vec![artist_id]));
+  }
+
+  pub fn unfollow_artist(&mut self, artist_id: String, name: &str) {
+    self.followed_artist_ids_set.remove(&artist_id);
+    self.show_toast(format!("Unfollowed {}", name));
+    self.dispatch(IoEvent::UserUnfollowArtists(vec![artist_id]));
+  }
+
+  /// Follow the first artist of the playing track, or unfollow them if they are followed already
+  pub fn toggle_follow_current_artist(&mut self) {
+    let artist = match self
+      .current_playback_context
+      .as_ref()
+      .and_then(|context| context.item.as_ref())
+    {
+      Some(PlayableItem::Track(track)) => track.artists.first(),
+      _ => None,
+    };
+    let artist =
+      artist.and_then(|artist| Some((artist.id.as_ref()?.to_string(), artist.name.clone())));
+    let (artist_id, name) = match artist {
+      Some(artist) => artist,
+      None => return,
+    };
+
+    if self.followed_artist_ids_set.contains(&artist_id) {
+      self.unfollow_artist(artist_id, &name);
+    } else {
+      self.follow_artist(artist_id, &name);
     }
   }
 
@@ -1173,30 +3107,6 @@ impl App {
     }
   }
 
-  pub fn user_unfollow_playlist(&mut self) {
-    if let (Some(playlists), Some(selected_index), Some(user)) =
-      (&self.playlists, self.selected_playlist_index, &self.user)
-    {
-      let selected_playlist = &playlists.items[selected_index];
-      let selected_id = selected_playlist.id.to_string();
-      let user_id = user.id.clone();
-      // self.dispatch(IoEvent::UserUnfollowPlaylist(user_id, selected_id))
-    }
-  }
-
-  pub fn user_unfollow_playlist_search_result(&mut self) {
-    if let (Some(playlists), Some(selected_index), Some(user)) = (
-      &self.search_results.playlists,
-      self.search_results.selected_playlists_index,
-      &self.user,
-    ) {
-      let selected_playlist = &playlists.items[selected_index];
-      let selected_id = selected_playlist.id.to_string();
-      let user_id = user.id.clone();
-      // self.dispatch(IoEvent::UserUnfollowPlaylist(user_id, selected_id))
-    }
-  }
-
   pub fn user_follow_show(&mut self, block: ActiveBlock) {
     match block {
       ActiveBlock::SearchResultBlock => {
@@ -1263,31 +3173,39 @@ impl App {
   }
 
   pub fn get_audio_analysis(&mut self) {
-    if let Some(CurrentPlaybackContext {
-      item: Some(item), ..
-    }) = &self.current_playback_context
-    {
-      match item {
-        PlayableItem::Track(track) => {
-          if self.get_current_route().id != RouteId::Analysis {
-            let uri = format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
-            self.dispatch(IoEvent::GetAudioAnalysis(uri));
-            self.push_navigation_stack(RouteId::Analysis, ActiveBlock::Analysis);
-          }
-        }
-        PlayableItem::Episode(_episode) => {
-          // No audio analysis available for podcast uris, so just default to the empty analysis
-          // view to avoid a 400 error code
-          self.push_navigation_stack(RouteId::Analysis, ActiveBlock::Analysis);
-        }
-      }
+    let is_playing_item = self
+      .current_playback_context
+      .as_ref()
+      .is_some_and(|context| context.item.is_some());
+    if is_playing_item && self.get_current_route().id != RouteId::Analysis {
+      self.load_audio_analysis();
+      self.push_navigation_stack(RouteId::Analysis, ActiveBlock::Analysis);
+    }
+  }
+
+  /// Replace the analysis shown with the playing track's. Episodes have none, so for those the
+  /// view is left empty rather than asking Spotify and getting a 400.
+  pub fn load_audio_analysis(&mut self) {
+    self.audio_analysis = None;
+    if let Some(track_id) = self.playing_track_id() {
+      self.dispatch(IoEvent::GetAudioAnalysis(track_id));
     }
   }
 
+  /// Move repeat on to its next state, showing it straight away. The next poll corrects it if
+  /// Spotify didn't follow.
   pub fn repeat(&mut self) {
-    if let Some(context) = &self.current_playback_context.clone() {
-      self.dispatch(IoEvent::Repeat(context.repeat_state.into()));
+    let repeat_state = match &self.current_playback_context {
+      Some(context) => RepeatState::from(context.repeat_state).next(),
+      None => return,
+    };
+    // Free accounts can't change repeat, dispatching only tells them so
+    if !self.is_free_tier() {
+      if let Some(context) = &mut self.current_playback_context {
+        context.repeat_state = repeat_state.clone().into();
+      }
     }
+    self.dispatch(IoEvent::Repeat(repeat_state));
   }
 
   pub fn get_artist(&mut self, artist_id: String, input_artist_name: String) {
@@ -1302,42 +3220,7 @@ impl App {
       .and_then(|user| user.country)
   }
 
-  // Focus Manager Methods
-  
-  /// Set focus to a component using the centralized focus manager
-  pub fn focus_component(&mut self, component: ComponentId) {
-    self.focus_manager.set_focus(component);
-  }
-
-  /// Set hover to a component using the centralized focus manager
-  pub fn hover_component(&mut self, component: ComponentId) {
-    self.focus_manager.set_hover(component);
-  }
-
-  /// Navigate to a component (sets hover, not focus)
-  pub fn navigate_to_component(&mut self, component: ComponentId) {
-    self.focus_manager.navigate_to(component);
-  }
-
-  /// Enter a component directly (sets both focus and hover)
-  pub fn enter_component(&mut self, component: ComponentId) {
-    self.focus_manager.enter_component(component);
-  }
-
-  /// Clear focus while keeping hover
-  pub fn clear_focus(&mut self) {
-    self.focus_manager.clear_focus();
-  }
-
-  /// Clear hover
-  pub fn clear_hover(&mut self) {
-    self.focus_manager.clear_hover();
-  }
-
-  /// Clear all focus states
-  pub fn clear_all_focus(&mut self) {
-    self.focus_manager.clear_all();
-  }
+  // Focus Manager Methods. Focus only changes through the navigation stack, these just read it.
 
   /// Get focus state of a component
   pub fn get_component_focus_state(&self, component: &ComponentId) -> FocusState {
@@ -1364,6 +3247,15 @@ impl App {
     self.focus_manager.get_hovered()
   }
 
+  /// Whether `block` is (active, hovered), the pair the draw functions highlight with
+  pub fn block_highlight_state(&self, block: ActiveBlock) -> (bool, bool) {
+    let component = FocusManager::from_active_block(block);
+    (
+      self.is_component_focused(&component),
+      self.is_component_hovered(&component),
+    )
+  }
+
   /// Update album art for current playing track
   pub fn update_album_art(&mut self) {
     if let Some(context) = &self.current_playback_context {
@@ -1414,3 +3306,363 @@ impl App {
   }
 
 }
+
+/// Clamp the locally extrapolated progress to the track duration. When repeating a single
+/// track, Spotify restarts it without us polling, so wrap back around instead of pinning at 100%.
+fn clamp_progress(elapsed: u128, duration_ms: u32, repeat_track: bool) -> u128 {
+  let duration = u128::from(duration_ms);
+  if elapsed < duration {
+    elapsed
+  } else if repeat_track && duration > 0 {
+    elapsed % duration
+  } else {
+    duration
+  }
+}
+
+/// A track with only an ID, named after it, for tests that fill a track table
+#[cfg(test)]
+pub fn test_track(id: &str) -> FullTrack {
+  serde_json::from_value(serde_json::json!({
+    "album": { "artists": [], "external_urls": {}, "images": [], "name": "Album" },
+    "artists": [],
+    "disc_number": 1,
+    "duration_ms": 180_000,
+    "explicit": false,
+    "external_ids": {},
+    "external_urls": {},
+    "id": id,
+    "is_local": false,
+    "name": id,
+    "popularity": 0,
+    "track_number": 1,
+  }))
+  .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_clamp_progress() {
+    assert_eq!(clamp_progress(1_000, 5_000, false), 1_000);
+    assert_eq!(clamp_progress(6_000, 5_000, false), 5_000);
+    assert_eq!(clamp_progress(6_000, 5_000, true), 1_000);
+    assert_eq!(clamp_progress(5_000, 5_000, true), 0);
+    assert_eq!(clamp_progress(100, 0, true), 0);
+  }
+
+  #[test]
+  fn test_errors_show_a_banner_and_only_fatal_ones_open_the_log() {
+    let mut app = App::default();
+    app.handle_error(anyhow!("Failed to get queue\nstatus 502"));
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+    assert_eq!(
+      app
+        .error_banner
+        .as_ref()
+        .map(|(summary, _)| summary.as_str()),
+      Some("Failed to get queue")
+    );
+
+    app.handle_fatal_error(anyhow!("Authentication failed"));
+    assert_eq!(app.get_current_route().id, RouteId::LogStream);
+    assert!(app.error_banner.is_none());
+  }
+
+  #[test]
+  fn test_play_next_outside_playing_context_appends() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App {
+      io_tx: Some(tx),
+      ..App::default()
+    };
+
+    app.play_next(
+      Some("spotify:playlist:abc".to_string()),
+      "spotify:track:xyz".to_string(),
+    );
+
+    assert!(matches!(
+      rx.try_recv(),
+      Ok((IoEvent::AddItemToQueue(uri), _)) if uri == "spotify:track:xyz"
+    ));
+    assert!(app.toast.is_some());
+  }
+
+  #[test]
+  fn test_free_tier_skips_playback_control() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App {
+      io_tx: Some(tx),
+      user: serde_json::from_value(serde_json::json!({
+        "id": "listener",
+        "href": "https://api.spotify.com/v1/users/listener",
+        "external_urls": {},
+        "product": "free",
+      }))
+      .ok(),
+      ..App::default()
+    };
+    assert!(app.is_free_tier());
+
+    app.dispatch(IoEvent::NextTrack);
+    assert!(rx.try_recv().is_err());
+    assert!(app.toast.is_some());
+
+    app.dispatch(IoEvent::GetPlaylists);
+    assert!(matches!(rx.try_recv(), Ok((IoEvent::GetPlaylists, _))));
+  }
+
+  #[test]
+  fn test_cycle_sleep_timer() {
+    let mut app = App::default();
+    let minutes = |app: &App| {
+      app
+        .sleep_timer
+        .as_ref()
+        .map(|timer| timer.duration.as_secs() / 60)
+    };
+
+    app.cycle_sleep_timer();
+    assert_eq!(minutes(&app), Some(15));
+    app.cycle_sleep_timer();
+    assert_eq!(minutes(&app), Some(30));
+
+    app.start_sleep_timer(Duration::from_secs(90), SleepAction::Quit);
+    app.cycle_sleep_timer();
+    assert_eq!(minutes(&app), Some(15));
+    assert_eq!(
+      app.sleep_timer.as_ref().map(|timer| timer.action),
+      Some(SleepAction::Quit)
+    );
+
+    for _ in 0..SLEEP_TIMER_PRESETS.len() - 1 {
+      app.cycle_sleep_timer();
+    }
+    assert_eq!(minutes(&app), Some(60));
+    app.cycle_sleep_timer();
+    assert!(app.sleep_timer.is_none());
+  }
+
+  #[test]
+  fn test_track_table_positions_follow_removals() {
+    let mut track_table = TrackTable {
+      tracks: vec![test_track("1111"), test_track("2222"), test_track("3333")],
+      selected_index: 2,
+      // An episode sat between the first two tracks
+      context_positions: vec![0, 2, 3],
+      ..Default::default()
+    };
+    assert_eq!(track_table.context_position(1), 2);
+
+    let removed_id = track_table.tracks[1].id.as_ref().unwrap().to_string();
+    track_table.remove_tracks(&[removed_id]);
+    assert_eq!(track_table.tracks.len(), 2);
+    assert_eq!(track_table.context_positions, vec![0, 2]);
+    assert_eq!(track_table.selected_index, 1);
+
+    // Without positions rows are their own positions
+    track_table.context_positions.clear();
+    assert_eq!(track_table.context_position(1), 1);
+  }
+
+  #[test]
+  fn test_start_autoplay_seeds_with_recent_tracks() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App {
+      io_tx: Some(tx),
+      ..App::default()
+    };
+
+    app.start_autoplay();
+    assert!(rx.try_recv().is_err());
+
+    app.recent_track_ids = VecDeque::from(vec!["a".to_string(), "b".to_string()]);
+    app.start_autoplay();
+    assert!(matches!(
+      rx.try_recv(),
+      Ok((IoEvent::StartAutoplay(seeds), _)) if seeds == ["a", "b"]
+    ));
+  }
+
+  #[test]
+  fn test_ab_loop_end() {
+    let ab_loop = AbLoop {
+      track_id: "a".to_string(),
+      start_ms: 10_000,
+      end_ms: Some(20_000),
+    };
+    assert!(!ab_loop.is_past_end("a", 19_999));
+    assert!(ab_loop.is_past_end("a", 20_000));
+    assert!(!ab_loop.is_past_end("b", 25_000));
+
+    let marked = AbLoop {
+      end_ms: None,
+      ..ab_loop
+    };
+    assert!(!marked.is_past_end("a", 25_000));
+  }
+
+  #[test]
+  fn test_mark_loop_end_needs_a_start() {
+    let mut app = App {
+      song_progress_ms: 5_000,
+      ..App::default()
+    };
+    app.mark_loop_end();
+    assert_eq!(app.ab_loop, None);
+
+    app.ab_loop = Some(AbLoop {
+      track_id: "a".to_string(),
+      start_ms: 10_000,
+      end_ms: None,
+    });
+    app.mark_loop_end();
+    assert_eq!(
+      app.ab_loop.as_ref().and_then(|ab_loop| ab_loop.end_ms),
+      None
+    );
+
+    app.song_progress_ms = 15_000;
+    app.mark_loop_end();
+    assert_eq!(
+      app.ab_loop.as_ref().and_then(|ab_loop| ab_loop.end_ms),
+      Some(15_000)
+    );
+
+    app.mark_loop_end();
+    assert_eq!(app.ab_loop, None);
+  }
+
+  #[test]
+  fn test_unmute_restores_previous_volume() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App {
+      io_tx: Some(tx),
+      muted_volume: Some(40),
+      ..App::default()
+    };
+    app.toggle_mute();
+    assert!(matches!(rx.try_recv(), Ok((IoEvent::SetVolume(40), _))));
+    assert_eq!(app.muted_volume, None);
+    assert!(app.is_volume_changing());
+  }
+
+  #[test]
+  fn test_sleep_timer_quits_when_it_ends() {
+    let mut app = App::default();
+    app.start_sleep_timer(Duration::ZERO, SleepAction::Quit);
+    app.update_on_tick();
+    assert!(app.should_quit);
+    assert!(app.sleep_timer.is_none());
+  }
+
+  #[test]
+  fn test_focus_follows_navigation_stack() {
+    let mut app = App::default();
+    assert_eq!(app.block_highlight_state(ActiveBlock::Library), (false, true));
+
+    app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    assert_eq!(app.block_highlight_state(ActiveBlock::TrackTable), (true, true));
+    assert_eq!(app.block_highlight_state(ActiveBlock::Library), (false, false));
+
+    app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::MyPlaylists));
+    assert_eq!(app.get_focused_component(), None);
+    assert_eq!(app.block_highlight_state(ActiveBlock::MyPlaylists), (false, true));
+
+    app.pop_navigation_stack();
+    assert_eq!(app.block_highlight_state(ActiveBlock::Library), (false, true));
+  }
+
+  #[test]
+  fn test_split_view_panes() {
+    let mut app = App {
+      split_view: true,
+      ..App::default()
+    };
+    let panes = |app: &App| {
+      app
+        .split_view_routes()
+        .map(|(left, right)| (left.id.clone(), right.id.clone()))
+    };
+    app.push_navigation_stack(RouteId::Artist, ActiveBlock::ArtistBlock);
+    assert_eq!(panes(&app), None);
+    app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    assert_eq!(panes(&app), Some((RouteId::Artist, RouteId::TrackTable)));
+
+    // Focus moves to the left pane without the panes changing places
+    app.switch_split_pane();
+    assert_eq!(app.get_current_route().id, RouteId::Artist);
+    assert_eq!(panes(&app), Some((RouteId::Artist, RouteId::TrackTable)));
+
+    // What is opened from there shows on the right, beside it
+    app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    assert_eq!(panes(&app), Some((RouteId::Artist, RouteId::AlbumList)));
+  }
+
+  #[test]
+  fn test_loading_route() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
+    assert!(app.get_current_route().loading);
+
+    // A failed fetch only ends the loading state
+    app.finish_loading_route(RouteId::Artist, false);
+    assert!(!app.get_current_route().loading);
+    assert!(app.get_current_route().updated_at.is_none());
+
+    // Reopening the route it is already on shows the skeleton again
+    app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
+    assert!(app.get_current_route().loading);
+    app.finish_loading_route(RouteId::Artist, true);
+    assert!(!app.get_current_route().loading);
+    assert!(app.get_current_route().updated_at.is_some());
+  }
+
+  #[test]
+  fn test_open_playlist_picker() {
+    let mut app = App::default();
+    app.open_playlist_picker(vec![]);
+    assert!(app.playlist_picker.is_none());
+
+    app.open_playlist_picker(vec!["spotify:track:xyz".to_string()]);
+    assert!(app.playlist_picker.is_some());
+    assert_eq!(
+      app.get_current_route().active_block,
+      ActiveBlock::Dialog(DialogContext::AddToPlaylist)
+    );
+    // Nothing to pick from before the playlists have loaded
+    assert!(app.playlist_picker_matches().is_empty());
+  }
+
+  #[test]
+  fn test_step_search_page() {
+    let page = |offset, next: Option<&str>| Page {
+      href: String::new(),
+      limit: 4,
+      next: next.map(String::from),
+      offset,
+      previous: None,
+      total: 6,
+      items: vec![offset],
+    };
+    let step = step_search_page;
+    let (mut pages, mut shown, mut index) = (ScrollableResultPages::new(), None, Some(3));
+    assert_eq!(step(&mut pages, &mut shown, &mut index, true), None);
+
+    pages.add_pages(page(0, Some("next")));
+    assert_eq!(step(&mut pages, &mut shown, &mut index, false), None);
+    assert_eq!(index, Some(3));
+    assert_eq!(step(&mut pages, &mut shown, &mut index, true), Some(4));
+    assert_eq!(index, Some(0));
+
+    pages.add_pages(page(4, None));
+    assert_eq!(step(&mut pages, &mut shown, &mut index, true), None);
+    assert_eq!(step(&mut pages, &mut shown, &mut index, false), None);
+    assert_eq!(shown.as_ref().map(|page| page.items.clone()), Some(vec![0]));
+    assert_eq!(step(&mut pages, &mut shown, &mut index, true), None);
+    assert_eq!(shown.map(|page| page.items), Some(vec![4]));
+  }
+}
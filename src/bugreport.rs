@@ -0,0 +1,149 @@
+use crate::{app::App, log_file};
+use anyhow::Result;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// Config keys whose values never belong in a public issue
+const REDACTED_KEY_MARKERS: [&str; 4] = ["client_id", "secret", "token", "password"];
+
+/// `:bugreport`: write everything useful for debugging a playback failure to one text file and
+/// return where it went. The file sits next to config.yml, or in the temp dir without one.
+pub fn write(app: &App) -> Result<PathBuf> {
+  let config_path = app
+    .user_config
+    .path_to_config
+    .as_ref()
+    .map(|paths| paths.config_file_path.clone());
+  let directory = config_path
+    .as_ref()
+    .and_then(|path| path.parent())
+    .map(Path::to_path_buf)
+    .unwrap_or_else(std::env::temp_dir);
+
+  let mut configs = vec![];
+  if let Some(config_path) = config_path {
+    let client_path = config_path.with_file_name("client.yml");
+    for path in [config_path, client_path] {
+      let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| format!("(couldn't read {}: {})", path.display(), e));
+      configs.push((path.display().to_string(), contents));
+    }
+  }
+
+  let report = build(app, &configs);
+  let file_name = format!(
+    "spt-bugreport-{}.txt",
+    chrono::Utc::now().format("%Y%m%d-%H%M%S")
+  );
+  let path = directory.join(file_name);
+  fs::write(&path, report)?;
+  Ok(path)
+}
+
+fn build(app: &App, configs: &[(String, String)]) -> String {
+  let mut sections = vec![format!(
+    "spotui bug report\nGenerated: {}\nVersion: spt {}\nPlatform: {} {}\nTerminal: {}\nLog file: {}",
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+    env!("CARGO_PKG_VERSION"),
+    std::env::consts::OS,
+    std::env::consts::ARCH,
+    std::env::var("TERM_PROGRAM")
+      .or_else(|_| std::env::var("TERM"))
+      .unwrap_or_else(|_| "unknown".to_string()),
+    log_file::path().map_or("none".to_string(), |path| path.display().to_string()),
+  )];
+
+  let playback = match &app.current_playback_context {
+    Some(context) => format!(
+      "Device: {}\nPlaying: {}",
+      context.device.name, context.is_playing
+    ),
+    None => "No active playback".to_string(),
+  };
+  sections.push(section("Playback", &playback));
+
+  for (path, contents) in configs {
+    sections.push(section(&format!("{} (redacted)", path), &redact(contents)));
+  }
+
+  let errors = app
+    .recent_errors
+    .iter()
+    .cloned()
+    .collect::<Vec<String>>()
+    .join("\n");
+  sections.push(section(
+    &format!("Last {} errors", app.recent_errors.len()),
+    &errors,
+  ));
+  let log = app
+    .log_messages
+    .iter()
+    .map(|message| message.text.as_str())
+    .collect::<Vec<_>>()
+    .join("\n");
+  sections.push(section(
+    &format!("Log ({} messages)", app.log_messages.len()),
+    &log,
+  ));
+
+  sections.join("\n\n") + "\n"
+}
+
+fn section(title: &str, body: &str) -> String {
+  let body = if body.trim().is_empty() {
+    "(none)"
+  } else {
+    body
+  };
+  format!("== {} ==\n{}", title, body)
+}
+
+/// Replace the values of credential-like keys in a YAML config, keeping everything else as is
+fn redact(config: &str) -> String {
+  config
+    .lines()
+    .map(|line| match line.split_once(':') {
+      Some((key, value))
+        if !value.trim().is_empty()
+          && REDACTED_KEY_MARKERS
+            .iter()
+            .any(|marker| key.to_lowercase().contains(marker)) =>
+      {
+        format!("{}: <redacted>", key)
+      }
+      _ => line.to_string(),
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_redact() {
+    let config = "client_id: abc123\nclient_secret: \"s3cret\"\ndevice_id: kitchen\nport: 8888";
+    assert_eq!(
+      redact(config),
+      "client_id: <redacted>\nclient_secret: <redacted>\ndevice_id: kitchen\nport: 8888"
+    );
+    let behavior = "behavior:\n  tick_rate_milliseconds: 250";
+    assert_eq!(redact(behavior), behavior);
+  }
+
+  #[test]
+  fn test_build() {
+    let mut app = App::default();
+    app.handle_error(anyhow::anyhow!("Failed to start playback"));
+    let configs = vec![("client.yml".to_string(), "client_secret: xyz".to_string())];
+    let report = build(&app, &configs);
+    assert!(report.contains("== client.yml (redacted) ==\nclient_secret: <redacted>"));
+    assert!(report.contains("== Last 1 errors =="));
+    assert!(report.contains("Failed to start playback"));
+    assert!(!report.contains("xyz"));
+  }
+}
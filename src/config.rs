@@ -1,4 +1,5 @@
 use super::banner::BANNER;
+use super::user_config::find_top_level_line;
 use anyhow::{anyhow, Error, Result};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -156,6 +157,62 @@ impl ClientConfig {
     }
   }
 
+  /// Every problem in a `client.yml`, for `spt check-config`
+  pub fn check_config(config: &str) -> Vec<String> {
+    let value: serde_yaml::Value = match serde_yaml::from_str(config) {
+      Ok(value) => value,
+      Err(e) => {
+        return vec![match e.location() {
+          Some(location) => format!("line {}: {}", location.line(), e),
+          None => e.to_string(),
+        }]
+      }
+    };
+    let entries = match value.as_mapping() {
+      Some(entries) => entries,
+      None => return vec!["Expected client_id and client_secret to be set".to_string()],
+    };
+
+    let mut problems = vec![];
+    let at_line = |key: &str, message: String| match find_top_level_line(config, key) {
+      Some(line) => format!("line {}: {}", line, message),
+      None => message,
+    };
+
+    for (key, entry) in entries {
+      let key = key.as_str().unwrap_or_default();
+      match key {
+        "client_id" | "client_secret" => match entry.as_str() {
+          Some(client_key) => {
+            if let Err(e) = ClientConfig::validate_client_key(client_key) {
+              problems.push(at_line(key, format!("{}: {}", key, e)));
+            }
+          }
+          None => problems.push(at_line(key, format!("{} should be a string", key))),
+        },
+        "device_id" => {
+          if !entry.is_string() && !entry.is_null() {
+            problems.push(at_line(key, "device_id should be a string".to_string()));
+          }
+        }
+        "port" => {
+          if entry.as_u64().and_then(|port| u16::try_from(port).ok()).is_none() && !entry.is_null()
+          {
+            problems.push(at_line(key, "port should be a number up to 65535".to_string()));
+          }
+        }
+        _ => problems.push(at_line(key, format!("unknown setting \"{}\"", key))),
+      }
+    }
+
+    for required in ["client_id", "client_secret"] {
+      if !entries.contains_key(required) {
+        problems.push(format!("{} is missing", required));
+      }
+    }
+    problems
+  }
+
   fn get_client_key_from_input(type_label: &'static str) -> Result<String> {
     let mut client_key = String::new();
     const MAX_RETRIES: u8 = 5;
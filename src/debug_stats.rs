@@ -0,0 +1,152 @@
+//! Numbers behind the debug overlay: how long frames take to draw, and how the network thread is
+//! keeping up with the events sent to it.
+
+use std::{
+  collections::VecDeque,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+  },
+  time::{Duration, Instant},
+};
+
+// Frames are counted over the last second for the frame rate
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct FrameStats {
+  last_frame: Duration,
+  frames: VecDeque<Instant>,
+}
+
+impl FrameStats {
+  /// Note a frame finished drawing at `at`, having taken `draw_time`
+  pub fn record(&mut self, draw_time: Duration, at: Instant) {
+    self.last_frame = draw_time;
+    self.frames.push_back(at);
+    while let Some(&first) = self.frames.front() {
+      if at.duration_since(first) < FPS_WINDOW {
+        break;
+      }
+      self.frames.pop_front();
+    }
+  }
+
+  pub fn frame_time(&self) -> Duration {
+    self.last_frame
+  }
+
+  pub fn fps(&self) -> usize {
+    self.frames.len()
+  }
+}
+
+/// Shared between the network thread, which keeps it up to date, and the UI, which shows it
+#[derive(Default)]
+pub struct NetworkStats {
+  received: AtomicUsize,
+  taken: AtomicUsize,
+  // The event being handled and when it started
+  running: Mutex<Option<(String, Instant)>>,
+  last_poll: Mutex<Option<Duration>>,
+}
+
+impl NetworkStats {
+  pub fn event_received(&self) {
+    self.received.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// `count` events left the queue, to be handled or because newer ones superseded them
+  pub fn events_taken(&self, count: usize) {
+    self.taken.fetch_add(count, Ordering::Relaxed);
+  }
+
+  /// Events waiting behind the one being handled
+  pub fn queue_depth(&self) -> usize {
+    self
+      .received
+      .load(Ordering::Relaxed)
+      .saturating_sub(self.taken.load(Ordering::Relaxed))
+  }
+
+  /// Mark `event` as in flight until the returned guard is dropped, which also covers a request
+  /// being abandoned part way
+  pub fn start_request(&self, event: &str) -> RunningRequest<'_> {
+    if let Ok(mut running) = self.running.lock() {
+      *running = Some((event.to_string(), Instant::now()));
+    }
+    RunningRequest { stats: self }
+  }
+
+  /// The event in flight and how long it has been running
+  pub fn running(&self) -> Option<(String, Duration)> {
+    self
+      .running
+      .lock()
+      .ok()?
+      .as_ref()
+      .map(|(event, started)| (event.clone(), started.elapsed()))
+  }
+
+  pub fn record_poll(&self, latency: Duration) {
+    if let Ok(mut last_poll) = self.last_poll.lock() {
+      *last_poll = Some(latency);
+    }
+  }
+
+  /// How long the last playback poll took to come back
+  pub fn last_poll(&self) -> Option<Duration> {
+    *self.last_poll.lock().ok()?
+  }
+}
+
+pub struct RunningRequest<'a> {
+  stats: &'a NetworkStats,
+}
+
+impl Drop for RunningRequest<'_> {
+  fn drop(&mut self) {
+    if let Ok(mut running) = self.stats.running.lock() {
+      *running = None;
+    }
+  }
+}
+
+/// An event's variant name out of its `Debug` output, leaving out the arguments
+pub fn event_name(debug: &str) -> &str {
+  debug.split(['(', ' ', '{']).next().unwrap_or(debug)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn frame_rate_counts_the_last_second() {
+    let start = Instant::now();
+    let mut stats = FrameStats::default();
+    for frame in 0..8 {
+      stats.record(
+        Duration::from_millis(frame),
+        start + Duration::from_millis(250 * frame),
+      );
+    }
+    assert_eq!(stats.fps(), 4);
+    assert_eq!(stats.frame_time(), Duration::from_millis(7));
+  }
+
+  #[test]
+  fn requests_are_in_flight_until_dropped() {
+    let stats = NetworkStats::default();
+    for _ in 0..3 {
+      stats.event_received();
+    }
+    stats.events_taken(1);
+    assert_eq!(stats.queue_depth(), 2);
+
+    let request = stats.start_request(event_name("GetArtist(ArtistId(\"abc\"))"));
+    assert_eq!(stats.running().unwrap().0, "GetArtist");
+    drop(request);
+    assert!(stats.running().is_none());
+  }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+/// A track of the playlist being scanned, at its position in the playlist
+pub struct ScannedTrack {
+  pub position: u32,
+  pub id: String,
+  pub name: String,
+  pub artists: String,
+}
+
+/// A later copy of a track that already appears earlier in the playlist
+#[derive(Clone, Debug, PartialEq)]
+pub struct Duplicate {
+  pub position: u32,
+  pub original_position: u32,
+  pub id: String,
+  pub name: String,
+  pub artists: String,
+}
+
+/// The `:dedupe` review: the playlist being cleaned up and what the scan found, `None` while the
+/// scan is still running
+#[derive(Clone, Default)]
+pub struct PlaylistDuplicates {
+  pub playlist_id: String,
+  pub playlist_name: String,
+  pub duplicates: Option<Vec<Duplicate>>,
+  pub selected_index: usize,
+}
+
+/// Every track that repeats an earlier one, either with the same ID or, for relinked tracks that
+/// got a new ID, with the same title and artists
+pub fn find(tracks: &[ScannedTrack]) -> Vec<Duplicate> {
+  let mut first_by_id: HashMap<&str, u32> = HashMap::new();
+  let mut first_by_title: HashMap<(String, String), u32> = HashMap::new();
+  let mut duplicates = vec![];
+
+  for track in tracks {
+    let title_key = (track.name.to_lowercase(), track.artists.to_lowercase());
+    let original_position = first_by_id
+      .get(track.id.as_str())
+      .or_else(|| first_by_title.get(&title_key))
+      .copied();
+
+    match original_position {
+      Some(original_position) => duplicates.push(Duplicate {
+        position: track.position,
+        original_position,
+        id: track.id.clone(),
+        name: track.name.clone(),
+        artists: track.artists.clone(),
+      }),
+      None => {
+        first_by_id.insert(&track.id, track.position);
+        first_by_title.insert(title_key, track.position);
+      }
+    }
+  }
+  duplicates
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn track(position: u32, id: &str, name: &str, artists: &str) -> ScannedTrack {
+    ScannedTrack {
+      position,
+      id: id.to_string(),
+      name: name.to_string(),
+      artists: artists.to_string(),
+    }
+  }
+
+  #[test]
+  fn test_find() {
+    let tracks = vec![
+      track(0, "a", "Heroes", "David Bowie"),
+      track(1, "b", "Changes", "David Bowie"),
+      track(2, "a", "Heroes", "David Bowie"),
+      track(3, "c", "heroes", "David Bowie"),
+      track(4, "d", "Heroes", "Motörhead"),
+    ];
+    let positions = find(&tracks)
+      .iter()
+      .map(|duplicate| (duplicate.position, duplicate.original_position))
+      .collect::<Vec<(u32, u32)>>();
+    assert_eq!(positions, vec![(2, 0), (3, 0)]);
+  }
+}
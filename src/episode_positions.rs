@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+const CONFIG_DIR: &str = ".config";
+const APP_CONFIG_DIR: &str = "spotify-tui";
+const FILE_NAME: &str = "episode_positions.json";
+
+// Positions this close to either end of an episode aren't worth offering to resume
+const MIN_RESUME_MS: u32 = 10_000;
+const FINISHED_MARGIN_MS: u32 = 30_000;
+
+/// Playback positions of podcast episodes, remembered locally so they survive restarts without
+/// waiting for Spotify's own `resume_point` to catch up.
+#[derive(Default)]
+pub struct EpisodePositions {
+  path: Option<PathBuf>,
+  positions: HashMap<String, u32>,
+  dirty: bool,
+}
+
+impl EpisodePositions {
+  /// Load saved positions, starting empty if the file is missing or unreadable
+  pub fn load() -> EpisodePositions {
+    let path = dirs::home_dir().map(|home| home.join(CONFIG_DIR).join(APP_CONFIG_DIR).join(FILE_NAME));
+    let positions = path
+      .as_ref()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|contents| serde_json::from_str(&contents).ok())
+      .unwrap_or_default();
+
+    EpisodePositions {
+      path,
+      positions,
+      dirty: false,
+    }
+  }
+
+  /// The saved position for an episode, if it is worth resuming from
+  pub fn get(&self, episode_id: &str) -> Option<u32> {
+    self.positions.get(episode_id).copied()
+  }
+
+  pub fn record(&mut self, episode_id: &str, position_ms: u32, duration_ms: u32) {
+    if position_ms < MIN_RESUME_MS || position_ms + FINISHED_MARGIN_MS >= duration_ms {
+      if self.positions.remove(episode_id).is_some() {
+        self.dirty = true;
+      }
+    } else if self.positions.get(episode_id) != Some(&position_ms) {
+      self.positions.insert(episode_id.to_string(), position_ms);
+      self.dirty = true;
+    }
+  }
+
+  pub fn save(&mut self) -> Result<()> {
+    if !self.dirty {
+      return Ok(());
+    }
+    let path = match &self.path {
+      Some(path) => path,
+      None => return Err(anyhow!("No $HOME directory found for episode positions")),
+    };
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&self.positions)?)?;
+    self.dirty = false;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_record_skips_start_and_end() {
+    let mut positions = EpisodePositions::default();
+
+    positions.record("episode", 5_000, 3_600_000);
+    assert_eq!(positions.get("episode"), None);
+
+    positions.record("episode", 600_000, 3_600_000);
+    assert_eq!(positions.get("episode"), Some(600_000));
+
+    // Finishing the episode forgets the position
+    positions.record("episode", 3_590_000, 3_600_000);
+    assert_eq!(positions.get("episode"), None);
+  }
+}
@@ -0,0 +1,141 @@
+use super::Key;
+use std::time::{Duration, Instant};
+
+/// A sequence of keys such as `g g` bound to an action name
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chord {
+  pub keys: Vec<Key>,
+  pub action: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ChordEvent {
+  /// Keys that turned out not to be part of a chord and should be handled as usual.
+  /// Empty while a chord is still being typed.
+  Keys(Vec<Key>),
+  /// A complete chord was typed
+  Action(String),
+}
+
+/// Buffers key presses that could start a chord until they either complete one, stop matching
+/// or time out.
+pub struct ChordState {
+  pending: Vec<Key>,
+  last_key: Instant,
+}
+
+impl Default for ChordState {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ChordState {
+  pub fn new() -> ChordState {
+    ChordState {
+      pending: vec![],
+      last_key: Instant::now(),
+    }
+  }
+
+  pub fn handle_key(&mut self, key: Key, chords: &[Chord]) -> ChordEvent {
+    self.pending.push(key);
+    self.last_key = Instant::now();
+
+    if let Some(chord) = chords.iter().find(|chord| chord.keys == self.pending) {
+      self.pending.clear();
+      return ChordEvent::Action(chord.action.clone());
+    }
+
+    if chords.iter().any(|chord| chord.keys.starts_with(&self.pending)) {
+      return ChordEvent::Keys(vec![]);
+    }
+
+    // The sequence broke off: replay what was buffered, but let the new key start a chord of
+    // its own
+    let mut flushed = std::mem::take(&mut self.pending);
+    if flushed.len() > 1 && chords.iter().any(|chord| chord.keys.first() == Some(&key)) {
+      flushed.pop();
+      self.pending.push(key);
+    }
+    ChordEvent::Keys(flushed)
+  }
+
+  /// Give up on a half-typed chord once `timeout` has passed, returning its keys for replay
+  pub fn flush_expired(&mut self, timeout: Duration) -> Vec<Key> {
+    if !self.pending.is_empty() && self.last_key.elapsed() >= timeout {
+      std::mem::take(&mut self.pending)
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chords() -> Vec<Chord> {
+    vec![
+      Chord {
+        keys: vec![Key::Char('g'), Key::Char('g')],
+        action: "top".to_string(),
+      },
+      Chord {
+        keys: vec![Key::Char('\\'), Key::Char('n')],
+        action: "next_track".to_string(),
+      },
+    ]
+  }
+
+  #[test]
+  fn test_chord_matches() {
+    let mut state = ChordState::new();
+    assert_eq!(
+      state.handle_key(Key::Char('g'), &chords()),
+      ChordEvent::Keys(vec![])
+    );
+    assert_eq!(
+      state.handle_key(Key::Char('g'), &chords()),
+      ChordEvent::Action("top".to_string())
+    );
+  }
+
+  #[test]
+  fn test_non_chord_keys_pass_through() {
+    let mut state = ChordState::new();
+    assert_eq!(
+      state.handle_key(Key::Char('j'), &chords()),
+      ChordEvent::Keys(vec![Key::Char('j')])
+    );
+
+    state.handle_key(Key::Char('g'), &chords());
+    assert_eq!(
+      state.handle_key(Key::Char('j'), &chords()),
+      ChordEvent::Keys(vec![Key::Char('g'), Key::Char('j')])
+    );
+
+    // A broken sequence can hand over to the start of another chord
+    state.handle_key(Key::Char('g'), &chords());
+    assert_eq!(
+      state.handle_key(Key::Char('\\'), &chords()),
+      ChordEvent::Keys(vec![Key::Char('g')])
+    );
+    assert_eq!(
+      state.handle_key(Key::Char('n'), &chords()),
+      ChordEvent::Action("next_track".to_string())
+    );
+  }
+
+  #[test]
+  fn test_flush_expired() {
+    let mut state = ChordState::new();
+    state.handle_key(Key::Char('g'), &chords());
+    assert!(state.flush_expired(Duration::from_secs(60)).is_empty());
+    assert_eq!(
+      state.flush_expired(Duration::from_millis(0)),
+      vec![Key::Char('g')]
+    );
+    assert!(state.flush_expired(Duration::from_millis(0)).is_empty());
+  }
+}
@@ -1,10 +1,10 @@
 use crate::event::Key;
-use crossterm::event;
+use crossterm::event::{self, MouseButton, MouseEventKind};
 use std::{
     sync::{
         mpsc::{self, TryRecvError},
         Arc,
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
     },
     thread,
     time::{Duration, Instant},
@@ -36,6 +36,28 @@ pub enum Event<I> {
   Tick,
   /// Terminal was resized
   Resize(u16, u16),
+  /// Text was pasted, all at once rather than as key presses
+  Paste(String),
+  /// The left mouse button was pressed at this column and row
+  Click(u16, u16),
+  /// App state changed outside of the event loop and should be drawn now
+  Redraw,
+}
+
+/// Lets other threads, like the network handler, ask for a redraw without waiting for the
+/// next tick. Requests made before the loop gets to the first one are merged into it.
+#[derive(Clone)]
+pub struct RedrawNotifier {
+  tx: mpsc::Sender<Event<Key>>,
+  pending: Arc<AtomicBool>,
+}
+
+impl RedrawNotifier {
+  pub fn notify(&self) {
+    if !self.pending.swap(true, Ordering::AcqRel) {
+      let _ = self.tx.send(Event::Redraw);
+    }
+  }
 }
 
 /// A small event handler that wrap crossterm input and tick event. Each event
@@ -47,6 +69,8 @@ pub struct Events {
   _tick_tx: mpsc::Sender<Event<Key>>,
   // Shared tick rate that can be updated dynamically
   tick_rate_ms: Arc<AtomicU64>,
+  // Set while a redraw request is queued
+  redraw_pending: Arc<AtomicBool>,
 }
 
 impl Events {
@@ -90,7 +114,22 @@ impl Events {
                   break; // Channel closed, exit thread
                 }
               }
-              Ok(_) => {} // Ignore other events like mouse
+              Ok(event::Event::Paste(text)) => {
+                if input_tx.send(Event::Paste(text)).is_err() {
+                  break; // Channel closed, exit thread
+                }
+              }
+              Ok(event::Event::Mouse(mouse)) => {
+                // Only left clicks do anything, so drags and scrolling aren't sent on
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                  continue;
+                }
+                let click = Event::Click(mouse.column, mouse.row);
+                if input_tx.send(click).is_err() {
+                  break; // Channel closed, exit thread
+                }
+              }
+              Ok(_) => {} // Ignore other events like focus changes
               Err(_) => {
                 // Error reading event, continue to next iteration
                 // This prevents the thread from crashing on resize errors
@@ -134,19 +173,37 @@ impl Events {
       rx, 
       _input_tx: _input_tx_handle,
       _tick_tx: _tick_tx_handle,
-      tick_rate_ms 
+      tick_rate_ms,
+      redraw_pending: Arc::new(AtomicBool::new(false)),
     }
   }
 
   /// Attempts to read an event.
   /// This function will block the current thread.
   pub fn next(&self) -> Result<Event<Key>, mpsc::RecvError> {
-    self.rx.recv()
+    let event = self.rx.recv()?;
+    self.clear_redraw(&event);
+    Ok(event)
   }
   
   /// Try to read an event without blocking
   pub fn try_next(&self) -> Result<Event<Key>, TryRecvError> {
-    self.rx.try_recv()
+    let event = self.rx.try_recv()?;
+    self.clear_redraw(&event);
+    Ok(event)
+  }
+
+  fn clear_redraw(&self, event: &Event<Key>) {
+    if let Event::Redraw = event {
+      self.redraw_pending.store(false, Ordering::Release);
+    }
+  }
+
+  pub fn redraw_notifier(&self) -> RedrawNotifier {
+    RedrawNotifier {
+      tx: self._tick_tx.clone(),
+      pending: Arc::clone(&self.redraw_pending),
+    }
   }
   
   /// Update the tick rate dynamically
@@ -22,6 +22,15 @@ pub enum Key {
   /// Down arrow
   Down,
 
+  /// Left arrow with Shift held
+  ShiftLeft,
+  /// Right arrow with Shift held
+  ShiftRight,
+  /// Up arrow with Shift held
+  ShiftUp,
+  /// Down arrow with Shift held
+  ShiftDown,
+
   /// Insert key
   Ins,
   /// Delete key
@@ -74,7 +83,7 @@ impl Key {
   ///
   /// # Panics
   ///
-  /// If `n == 0 || n > 12`
+  /// If `n > 12`
   pub fn from_f(n: u8) -> Key {
     match n {
       0 => Key::F0,
@@ -105,6 +114,10 @@ impl fmt::Display for Key {
       Key::Ctrl(c) => write!(f, "<Ctrl+{}>", c),
       Key::Char(c) => write!(f, "{}", c),
       Key::Left | Key::Right | Key::Up | Key::Down => write!(f, "<{:?} Arrow Key>", self),
+      Key::ShiftLeft => write!(f, "<Shift+Left Arrow Key>"),
+      Key::ShiftRight => write!(f, "<Shift+Right Arrow Key>"),
+      Key::ShiftUp => write!(f, "<Shift+Up Arrow Key>"),
+      Key::ShiftDown => write!(f, "<Shift+Down Arrow Key>"),
       Key::Enter
       | Key::Tab
       | Key::Backspace
@@ -114,7 +127,20 @@ impl fmt::Display for Key {
       | Key::Home
       | Key::End
       | Key::PageUp
-      | Key::PageDown => write!(f, "<{:?}>", self),
+      | Key::PageDown
+      | Key::F0
+      | Key::F1
+      | Key::F2
+      | Key::F3
+      | Key::F4
+      | Key::F5
+      | Key::F6
+      | Key::F7
+      | Key::F8
+      | Key::F9
+      | Key::F10
+      | Key::F11
+      | Key::F12 => write!(f, "<{:?}>", self),
       _ => write!(f, "{:?}", self),
     }
   }
@@ -131,6 +157,28 @@ impl From<event::KeyEvent> for Key {
         code: event::KeyCode::Backspace,
         ..
       } => Key::Backspace,
+
+      // Shift-arrows before plain ones, which any other modifier still counts as
+      event::KeyEvent {
+        code: event::KeyCode::Left,
+        modifiers: event::KeyModifiers::SHIFT,
+        ..
+      } => Key::ShiftLeft,
+      event::KeyEvent {
+        code: event::KeyCode::Right,
+        modifiers: event::KeyModifiers::SHIFT,
+        ..
+      } => Key::ShiftRight,
+      event::KeyEvent {
+        code: event::KeyCode::Up,
+        modifiers: event::KeyModifiers::SHIFT,
+        ..
+      } => Key::ShiftUp,
+      event::KeyEvent {
+        code: event::KeyCode::Down,
+        modifiers: event::KeyModifiers::SHIFT,
+        ..
+      } => Key::ShiftDown,
       event::KeyEvent {
         code: event::KeyCode::Left,
         ..
@@ -171,10 +219,11 @@ impl From<event::KeyEvent> for Key {
         code: event::KeyCode::Insert,
         ..
       } => Key::Ins,
+      // Some terminals report Shift+F1 and so on as F13 and up, which nothing can bind
       event::KeyEvent {
         code: event::KeyCode::F(n),
         ..
-      } => Key::from_f(n),
+      } if n <= 12 => Key::from_f(n),
       event::KeyEvent {
         code: event::KeyCode::Enter,
         ..
@@ -184,12 +233,17 @@ impl From<event::KeyEvent> for Key {
         ..
       } => Key::Tab,
 
-      // First check for char + modifier
+      // First check for char + modifier. Alt+Shift+letter arrives as the uppercase letter with
+      // both modifiers, and is bound as alt-A
       event::KeyEvent {
         code: event::KeyCode::Char(c),
-        modifiers: event::KeyModifiers::ALT,
+        modifiers,
         ..
-      } => Key::Alt(c),
+      } if modifiers == event::KeyModifiers::ALT
+        || modifiers == event::KeyModifiers::ALT | event::KeyModifiers::SHIFT =>
+      {
+        Key::Alt(c)
+      }
       event::KeyEvent {
         code: event::KeyCode::Char(c),
         modifiers: event::KeyModifiers::CONTROL,
@@ -1,7 +1,9 @@
+mod chord;
 mod events;
 mod key;
 
 pub use self::{
-  events::{Event, Events},
+  chord::{Chord, ChordEvent, ChordState},
+  events::{Event, Events, RedrawNotifier},
   key::Key,
 };
@@ -1,4 +1,4 @@
-use crate::app::{ActiveBlock, SearchResultBlock, ArtistBlock};
+use crate::app::{ActiveBlock, SearchResultBlock, ArtistBlock, Route};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FocusState {
@@ -25,16 +25,26 @@ pub enum ComponentId {
     RecentlyPlayed,
     Artists,
     Podcasts,
+    MadeForYou,
+    NewReleases,
+    FeaturedPlaylists,
+    Categories,
+    CategoryPlaylists,
+    Profile,
     Home,
     SelectDevice,
     PlayBar,
     BasicView,
     LogStream,
+    Duplicates,
+    ColorStory,
     Analysis,
     Dialog,
     Empty,
 }
 
+/// Focus and hover of the UI. The app keeps it in step with the current route on every push, pop
+/// and route state change, so the route stack stays the history while this is what the UI reads.
 pub struct FocusManager {
     /// Currently focused component (only one can be focused at a time)
     focused_component: Option<ComponentId>,
@@ -56,6 +66,23 @@ impl FocusManager {
         Self::default()
     }
 
+    /// Focus and hover as described by `route`
+    pub fn from_route(route: &Route) -> Self {
+        let mut focus_manager = Self::new();
+        focus_manager.sync_with_route(route);
+        focus_manager
+    }
+
+    /// Take over the route's active block as focus and its hovered block as hover. An `Empty`
+    /// active block means nothing is focused.
+    pub fn sync_with_route(&mut self, route: &Route) {
+        self.focused_component = match route.active_block {
+            ActiveBlock::Empty => None,
+            block => Some(Self::from_active_block(block)),
+        };
+        self.hovered_component = Some(Self::from_active_block(route.hovered_block));
+    }
+
     /// Set focus to a component, clearing any previous focus
     pub fn set_focus(&mut self, component: ComponentId) {
         self.focused_component = Some(component);
@@ -116,18 +143,6 @@ impl FocusManager {
         self.hovered_component.as_ref()
     }
 
-    /// Navigate between components (for arrow key navigation)
-    /// This affects hover state, not focus
-    pub fn navigate_to(&mut self, component: ComponentId) {
-        self.set_hover(component);
-    }
-
-    /// Enter a component (for direct key shortcuts like P, L, S)
-    /// This sets both focus and hover
-    pub fn enter_component(&mut self, component: ComponentId) {
-        self.set_focus(component);
-    }
-
     /// Convert from legacy ActiveBlock to ComponentId
     pub fn from_active_block(block: ActiveBlock) -> ComponentId {
         match block {
@@ -143,11 +158,19 @@ impl FocusManager {
             ActiveBlock::RecentlyPlayed => ComponentId::RecentlyPlayed,
             ActiveBlock::Artists => ComponentId::Artists,
             ActiveBlock::Podcasts => ComponentId::Podcasts,
+            ActiveBlock::MadeForYou => ComponentId::MadeForYou,
+            ActiveBlock::NewReleases => ComponentId::NewReleases,
+            ActiveBlock::FeaturedPlaylists => ComponentId::FeaturedPlaylists,
+            ActiveBlock::Categories => ComponentId::Categories,
+            ActiveBlock::CategoryPlaylists => ComponentId::CategoryPlaylists,
+            ActiveBlock::Profile => ComponentId::Profile,
             ActiveBlock::Home => ComponentId::Home,
             ActiveBlock::SelectDevice => ComponentId::SelectDevice,
             ActiveBlock::PlayBar => ComponentId::PlayBar,
             ActiveBlock::BasicView => ComponentId::BasicView,
             ActiveBlock::LogStream => ComponentId::LogStream,
+            ActiveBlock::Duplicates => ComponentId::Duplicates,
+            ActiveBlock::ColorStory => ComponentId::ColorStory,
             ActiveBlock::Analysis => ComponentId::Analysis,
             ActiveBlock::Dialog(_) => ComponentId::Dialog,
             ActiveBlock::Empty => ComponentId::Empty,
@@ -170,11 +193,19 @@ impl FocusManager {
             ComponentId::RecentlyPlayed => ActiveBlock::RecentlyPlayed,
             ComponentId::Artists => ActiveBlock::Artists,
             ComponentId::Podcasts => ActiveBlock::Podcasts,
+            ComponentId::MadeForYou => ActiveBlock::MadeForYou,
+            ComponentId::NewReleases => ActiveBlock::NewReleases,
+            ComponentId::FeaturedPlaylists => ActiveBlock::FeaturedPlaylists,
+            ComponentId::Categories => ActiveBlock::Categories,
+            ComponentId::CategoryPlaylists => ActiveBlock::CategoryPlaylists,
+            ComponentId::Profile => ActiveBlock::Profile,
             ComponentId::Home => ActiveBlock::Home,
             ComponentId::SelectDevice => ActiveBlock::SelectDevice,
             ComponentId::PlayBar => ActiveBlock::PlayBar,
             ComponentId::BasicView => ActiveBlock::BasicView,
             ComponentId::LogStream => ActiveBlock::LogStream,
+            ComponentId::Duplicates => ActiveBlock::Duplicates,
+            ComponentId::ColorStory => ActiveBlock::ColorStory,
             ComponentId::Analysis => ActiveBlock::Analysis,
             ComponentId::Dialog => ActiveBlock::Dialog(Default::default()),
             ComponentId::Empty => ActiveBlock::Empty,
@@ -185,6 +216,7 @@ impl FocusManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::RouteId;
 
     #[test]
     fn test_focus_states() {
@@ -222,6 +254,29 @@ mod tests {
         assert!(!fm.is_hovered(&comp));
     }
 
+    #[test]
+    fn test_sync_with_route() {
+        let mut fm = FocusManager::from_route(&Route {
+            id: RouteId::Home,
+            active_block: ActiveBlock::Empty,
+            hovered_block: ActiveBlock::Library,
+            loading: false,
+            updated_at: None,
+        });
+        assert_eq!(fm.get_focused(), None);
+        assert_eq!(fm.get_focus_state(&ComponentId::Library), FocusState::Hovered);
+
+        fm.sync_with_route(&Route {
+            id: RouteId::TrackTable,
+            active_block: ActiveBlock::TrackTable,
+            hovered_block: ActiveBlock::TrackTable,
+            loading: false,
+            updated_at: None,
+        });
+        assert_eq!(fm.get_focus_state(&ComponentId::TrackTable), FocusState::Focused);
+        assert_eq!(fm.get_focus_state(&ComponentId::Library), FocusState::Unfocused);
+    }
+
     #[test]
     fn test_single_focus() {
         let mut fm = FocusManager::new();
@@ -0,0 +1,66 @@
+/// Score `text` against `pattern` as a case-insensitive subsequence, `None` if it doesn't match.
+/// Runs of consecutive characters and matches at the start of words score higher, so "dm"
+/// prefers "Daily Mix" over "Random".
+pub fn score(pattern: &str, text: &str) -> Option<i64> {
+  let mut text_chars = text.chars().flat_map(char::to_lowercase).peekable();
+  let mut total = 0;
+  let mut previous_matched = false;
+  let mut previous_char: Option<char> = None;
+
+  for pattern_char in pattern.chars().flat_map(char::to_lowercase) {
+    if pattern_char.is_whitespace() {
+      continue;
+    }
+    loop {
+      let text_char = text_chars.next()?;
+      let at_word_start = previous_char.is_none_or(|c| !c.is_alphanumeric());
+      previous_char = Some(text_char);
+      if text_char == pattern_char {
+        total += 1;
+        if previous_matched {
+          total += 4;
+        }
+        if at_word_start {
+          total += 3;
+        }
+        previous_matched = true;
+        break;
+      }
+      previous_matched = false;
+    }
+  }
+  Some(total)
+}
+
+/// Indices of the `candidates` matching `pattern`, best match first. Ties keep their original
+/// order, and an empty pattern keeps every candidate.
+pub fn rank<S: AsRef<str>>(pattern: &str, candidates: &[S]) -> Vec<usize> {
+  let mut scored = candidates
+    .iter()
+    .enumerate()
+    .filter_map(|(index, candidate)| score(pattern, candidate.as_ref()).map(|s| (index, s)))
+    .collect::<Vec<(usize, i64)>>();
+  scored.sort_by(|(_, a), (_, b)| b.cmp(a));
+  scored.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_score() {
+    assert!(score("dmx", "Daily Mix").is_some());
+    assert!(score("DAILY", "daily mix").is_some());
+    assert_eq!(score("xyz", "Daily Mix"), None);
+    assert_eq!(score("", "anything"), Some(0));
+  }
+
+  #[test]
+  fn test_rank() {
+    let playlists = ["Random", "Daily Mix 1", "Discover Weekly", "Chill"];
+    assert_eq!(rank("dm", &playlists), vec![1, 0]);
+    assert_eq!(rank("", &playlists), vec![0, 1, 2, 3]);
+    assert_eq!(rank("ch", &playlists), vec![3]);
+  }
+}
@@ -9,15 +9,21 @@ pub fn handler(key: Key, app: &mut App) {
     k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
     k if common_key_events::down_event(k) => {
       if let Some(albums) = &mut app.library.saved_albums.get_results(None) {
-        let next_index =
-          common_key_events::on_down_press_handler(&albums.items, Some(app.album_list_index));
+        let next_index = common_key_events::on_down_press_handler(
+          &albums.items,
+          Some(app.album_list_index),
+          app.motion_count,
+        );
         app.album_list_index = next_index;
       }
     }
     k if common_key_events::up_event(k) => {
       if let Some(albums) = &mut app.library.saved_albums.get_results(None) {
-        let next_index =
-          common_key_events::on_up_press_handler(&albums.items, Some(app.album_list_index));
+        let next_index = common_key_events::on_up_press_handler(
+          &albums.items,
+          Some(app.album_list_index),
+          app.motion_count,
+        );
         app.album_list_index = next_index;
       }
     }
@@ -53,7 +59,6 @@ pub fn handler(key: Key, app: &mut App) {
     }
     k if k == app.user_config.keys.next_page => app.get_current_user_saved_albums_next(),
     k if k == app.user_config.keys.previous_page => app.get_current_user_saved_albums_previous(),
-    Key::Char('D') => app.current_user_saved_album_delete(ActiveBlock::AlbumList),
     _ => {}
   };
 }
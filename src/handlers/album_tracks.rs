@@ -2,7 +2,8 @@ use super::common_key_events;
 use crate::{
   app::{AlbumTableContext, App, RecommendationsContext},
   event::Key,
-  network::IoEvent,
+  network::{IoEvent, PlaybackOffset},
+  open_in,
 };
 
 pub fn handler(key: Key, app: &mut App) {
@@ -14,6 +15,7 @@ pub fn handler(key: Key, app: &mut App) {
           let next_index = common_key_events::on_down_press_handler(
             &selected_album.album.tracks.items,
             Some(app.saved_album_tracks_index),
+            app.motion_count,
           );
           app.saved_album_tracks_index = next_index;
         };
@@ -23,6 +25,7 @@ pub fn handler(key: Key, app: &mut App) {
           let next_index = common_key_events::on_down_press_handler(
             &selected_album_simplified.tracks.items,
             Some(selected_album_simplified.selected_index),
+            app.motion_count,
           );
           selected_album_simplified.selected_index = next_index;
         }
@@ -34,6 +37,7 @@ pub fn handler(key: Key, app: &mut App) {
           let next_index = common_key_events::on_up_press_handler(
             &selected_album.album.tracks.items,
             Some(app.saved_album_tracks_index),
+            app.motion_count,
           );
           app.saved_album_tracks_index = next_index;
         };
@@ -43,6 +47,7 @@ pub fn handler(key: Key, app: &mut App) {
           let next_index = common_key_events::on_up_press_handler(
             &selected_album_simplified.tracks.items,
             Some(selected_album_simplified.selected_index),
+            app.motion_count,
           );
           selected_album_simplified.selected_index = next_index;
         }
@@ -56,61 +61,27 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Enter => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = app.selected_album_full.clone() {
-          // Get the selected track URI for offset
-          let track_uri = selected_album
-            .album
-            .tracks
-            .items
-            .get(app.saved_album_tracks_index)
-            .and_then(|track| track.id.as_ref().map(|id| {
-              let id_str = id.to_string();
-              if id_str.starts_with("spotify:track:") {
-                id_str
-              } else {
-                format!("spotify:track:{}", id_str)
-              }
-            }));
-          
-          let album_id_str = selected_album.album.id.to_string();
-          let album_uri = if album_id_str.starts_with("spotify:album:") {
-            album_id_str
-          } else {
-            format!("spotify:album:{}", album_id_str)
-          };
-          
-          app.dispatch(IoEvent::StartPlayback(
-            Some(album_uri),
-            track_uri
-          ));
+          // Start at the selected track by its place in the album
+          let tracks = &selected_album.album.tracks;
+          let offset =
+            PlaybackOffset::Position(tracks.offset + app.saved_album_tracks_index as u32);
+          let album_uri = open_in::uri("album", &selected_album.album.id.to_string());
+          app.dispatch(IoEvent::StartPlayback(Some(album_uri), Some(offset)));
         };
       }
       AlbumTableContext::Simplified => {
         if let Some(selected_album_simplified) = &app.selected_album_simplified.clone() {
-          // Get the selected track URI for offset
-          let track_uri = selected_album_simplified
-            .tracks
-            .items
-            .get(selected_album_simplified.selected_index)
-            .and_then(|track| track.id.as_ref().map(|id| {
-              let id_str = id.to_string();
-              if id_str.starts_with("spotify:track:") {
-                id_str
-              } else {
-                format!("spotify:track:{}", id_str)
-              }
-            }));
-          
-          let album_id_str = selected_album_simplified.album.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string());
-          let album_uri = if album_id_str.starts_with("spotify:album:") {
-            album_id_str
-          } else {
-            format!("spotify:album:{}", album_id_str)
-          };
-          
-          app.dispatch(IoEvent::StartPlayback(
-            Some(album_uri),
-            track_uri
-          ));
+          // Start at the selected track by its place in the album
+          let offset = PlaybackOffset::Position(
+            selected_album_simplified.tracks.offset
+              + selected_album_simplified.selected_index as u32,
+          );
+          let album_uri = selected_album_simplified
+            .album
+            .id
+            .as_ref()
+            .map(|id| open_in::uri("album", &id.to_string()));
+          app.dispatch(IoEvent::StartPlayback(album_uri, Some(offset)));
         };
       }
     },
@@ -118,6 +89,37 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Char('r') => {
       handle_recommended_tracks(app);
     }
+    _ if key == app.user_config.keys.play_next => {
+      let selected = match app.album_table_context {
+        AlbumTableContext::Full => app.selected_album_full.as_ref().and_then(|selected_album| {
+          selected_album
+            .album
+            .tracks
+            .items
+            .get(app.saved_album_tracks_index)
+            .map(|track| (selected_album.album.id.to_string(), track.id.clone()))
+        }),
+        AlbumTableContext::Simplified => {
+          app
+            .selected_album_simplified
+            .as_ref()
+            .and_then(|selected_album_simplified| {
+              let album_id = selected_album_simplified.album.id.as_ref()?.to_string();
+              selected_album_simplified
+                .tracks
+                .items
+                .get(selected_album_simplified.selected_index)
+                .map(|track| (album_id, track.id.clone()))
+            })
+        }
+      };
+
+      if let Some((album_id, Some(track_id))) = selected {
+        let album_uri = open_in::uri("album", &album_id);
+        let track_uri = open_in::uri("track", &track_id.to_string());
+        app.play_next(Some(album_uri), track_uri);
+      }
+    }
     _ if key == app.user_config.keys.add_item_to_queue => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = app.selected_album_full.clone() {
@@ -127,7 +129,10 @@ pub fn handler(key: Key, app: &mut App) {
             .items
             .get(app.saved_album_tracks_index)
           {
-            app.dispatch(IoEvent::AddItemToQueue(format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()))));
+            if let Some(id) = &track.id {
+              let uri = open_in::uri("track", &id.to_string());
+              app.dispatch(IoEvent::AddItemToQueue(uri));
+            }
           }
         };
       }
@@ -138,15 +143,51 @@ pub fn handler(key: Key, app: &mut App) {
             .items
             .get(selected_album_simplified.selected_index)
           {
-            app.dispatch(IoEvent::AddItemToQueue(format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()))));
+            if let Some(id) = &track.id {
+              let uri = open_in::uri("track", &id.to_string());
+              app.dispatch(IoEvent::AddItemToQueue(uri));
+            }
           }
         };
       }
     },
+    _ if key == app.user_config.keys.add_to_playlist => {
+      if let Some(track_uri) = selected_track_uri(app) {
+        app.open_playlist_picker(vec![track_uri]);
+      }
+    }
     _ => {}
   };
 }
 
+fn selected_track_uri(app: &App) -> Option<String> {
+  let track_id = match app.album_table_context {
+    AlbumTableContext::Full => app.selected_album_full.as_ref().and_then(|selected_album| {
+      selected_album
+        .album
+        .tracks
+        .items
+        .get(app.saved_album_tracks_index)?
+        .id
+        .clone()
+    }),
+    AlbumTableContext::Simplified => {
+      app
+        .selected_album_simplified
+        .as_ref()
+        .and_then(|selected_album_simplified| {
+          selected_album_simplified
+            .tracks
+            .items
+            .get(selected_album_simplified.selected_index)?
+            .id
+            .clone()
+        })
+    }
+  }?;
+  Some(open_in::uri("track", &track_id.to_string()))
+}
+
 fn handle_high_event(app: &mut App) {
   match app.album_table_context {
     AlbumTableContext::Full => {
@@ -1,3 +1,21 @@
 use crate::{app::App, event::Key};
 
-pub fn handler(_key: Key, _app: &mut App) {}
+pub fn handler(key: Key, app: &mut App) {
+  if key == Key::Char('t') {
+    app.analysis_shows_timbre = !app.analysis_shows_timbre;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_t_press() {
+    let mut app = App::default();
+    handler(Key::Char('t'), &mut app);
+    assert!(app.analysis_shows_timbre);
+    handler(Key::Char('t'), &mut app);
+    assert!(!app.analysis_shows_timbre);
+  }
+}
@@ -1,7 +1,8 @@
 use super::common_key_events;
 use crate::app::{ActiveBlock, App, ArtistBlock, RecommendationsContext, RouteId, TrackTableContext};
 use crate::event::Key;
-use crate::network::IoEvent;
+use crate::network::{IoEvent, PlaybackOffset};
+use crate::open_in;
 
 fn handle_down_press_on_selected_block(app: &mut App) {
   if let Some(artist) = &mut app.artist {
@@ -10,6 +11,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &artist.top_tracks,
           Some(artist.selected_top_track_index),
+          app.motion_count,
         );
         artist.selected_top_track_index = next_index;
       }
@@ -17,6 +19,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &artist.albums.items,
           Some(artist.selected_album_index),
+          app.motion_count,
         );
         artist.selected_album_index = next_index;
       }
@@ -24,6 +27,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &artist.related_artists,
           Some(artist.selected_related_artist_index),
+          app.motion_count,
         );
         artist.selected_related_artist_index = next_index;
       }
@@ -56,6 +60,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &artist.top_tracks,
           Some(artist.selected_top_track_index),
+          app.motion_count,
         );
         artist.selected_top_track_index = next_index;
       }
@@ -63,6 +68,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &artist.albums.items,
           Some(artist.selected_album_index),
+          app.motion_count,
         );
         artist.selected_album_index = next_index;
       }
@@ -70,6 +76,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &artist.related_artists,
           Some(artist.selected_related_artist_index),
+          app.motion_count,
         );
         artist.selected_related_artist_index = next_index;
       }
@@ -189,10 +196,12 @@ fn handle_enter_event_on_selected_block(app: &mut App) {
       ArtistBlock::TopTracks => {
         let selected_index = artist.selected_top_track_index;
         if let Some(selected_track) = artist.top_tracks.get(selected_index) {
-          if let Some(track_id) = &selected_track.id {
-            let track_uri = format!("spotify:track:{}", track_id);
+          if let Some(track_uri) = open_in::track_uri(selected_track) {
             // Play the selected track
-            app.dispatch(IoEvent::StartPlayback(None, Some(track_uri)));
+            app.dispatch(IoEvent::StartPlayback(
+              None,
+              Some(PlaybackOffset::Uri(track_uri)),
+            ));
           }
         }
       }
@@ -302,15 +311,10 @@ pub fn handler(key: Key, app: &mut App) {
         ArtistBlock::RelatedArtists => app.user_follow_artists(ActiveBlock::ArtistBlock),
         _ => (),
       },
-      Key::Char('D') => match artist.artist_selected_block {
-        ArtistBlock::Albums => app.current_user_saved_album_delete(ActiveBlock::ArtistBlock),
-        ArtistBlock::RelatedArtists => app.user_unfollow_artists(ActiveBlock::ArtistBlock),
-        _ => (),
-      },
       _ if key == app.user_config.keys.add_item_to_queue => {
         if let ArtistBlock::TopTracks = artist.artist_selected_block {
-          if let Some(track) = artist.top_tracks.get(artist.selected_top_track_index) {
-            let uri = format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
+          let track = artist.top_tracks.get(artist.selected_top_track_index);
+          if let Some(uri) = track.and_then(open_in::track_uri) {
             app.dispatch(IoEvent::AddItemToQueue(uri));
           };
         }
@@ -320,6 +324,19 @@ pub fn handler(key: Key, app: &mut App) {
   }
 }
 
+// Delete, unfollow or remove the selected item, for the delete chord
+pub fn handle_delete_event(app: &mut App) {
+  let selected_block = app
+    .artist
+    .as_ref()
+    .map(|artist| artist.artist_selected_block);
+  match selected_block {
+    Some(ArtistBlock::Albums) => app.current_user_saved_album_delete(ActiveBlock::ArtistBlock),
+    Some(ArtistBlock::RelatedArtists) => app.user_unfollow_artists(ActiveBlock::ArtistBlock),
+    _ => (),
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
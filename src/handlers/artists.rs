@@ -1,6 +1,6 @@
 use super::common_key_events;
 use crate::{
-  app::{ActiveBlock, App, RecommendationsContext, RouteId},
+  app::{ActiveBlock, App, RecommendationsContext, RouteId, TopTimeRange},
   event::Key,
   network::IoEvent,
 };
@@ -10,15 +10,21 @@ pub fn handler(key: Key, app: &mut App) {
     k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
     k if common_key_events::down_event(k) => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
-        let next_index =
-          common_key_events::on_down_press_handler(&artists.items, Some(app.artists_list_index));
+        let next_index = common_key_events::on_down_press_handler(
+          &artists.items,
+          Some(app.artists_list_index),
+          app.motion_count,
+        );
         app.artists_list_index = next_index;
       }
     }
     k if common_key_events::up_event(k) => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
-        let next_index =
-          common_key_events::on_up_press_handler(&artists.items, Some(app.artists_list_index));
+        let next_index = common_key_events::on_up_press_handler(
+          &artists.items,
+          Some(app.artists_list_index),
+          app.motion_count,
+        );
         app.artists_list_index = next_index;
       }
     }
@@ -45,10 +51,9 @@ pub fn handler(key: Key, app: &mut App) {
       if !artists.is_empty() {
         let artist = &artists[app.artists_list_index];
         app.get_artist(artist.id.to_string(), artist.name.clone());
-        app.push_navigation_stack(RouteId::Artist, ActiveBlock::ArtistBlock);
+        app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
       }
     }
-    Key::Char('D') => app.user_unfollow_artists(ActiveBlock::AlbumList),
     Key::Char('e') => {
       let artists = app.artists.to_owned();
       let artist = artists.get(app.artists_list_index);
@@ -71,6 +76,12 @@ pub fn handler(key: Key, app: &mut App) {
         app.get_recommendations_for_seed(artist_id_list, None, None);
       }
     }
+    k if app.showing_top_artists && TopTimeRange::from_key(k).is_some() => {
+      if let Some(time_range) = TopTimeRange::from_key(k) {
+        app.top_time_range = time_range;
+        app.dispatch(IoEvent::GetTopArtists);
+      }
+    }
     k if k == app.user_config.keys.next_page => app.get_current_user_saved_artists_next(),
     k if k == app.user_config.keys.previous_page => app.get_current_user_saved_artists_previous(),
     _ => {}
@@ -0,0 +1,90 @@
+//! Going back up the navigation stack from the breadcrumb, by typing a level's number after the
+//! breadcrumb key or by clicking on the level
+
+use crate::app::App;
+use crate::event::Key;
+use crate::ui::util::{breadcrumb_level_number, display_width, BREADCRUMB_SEPARATOR};
+
+/// Start picking a level, unless there is nowhere to go back to
+pub fn start(app: &mut App) {
+  if app.get_navigation_breadcrumb().len() > 1 {
+    app.picking_breadcrumb = true;
+  } else {
+    app.show_toast("Nothing to go back to".to_string());
+  }
+}
+
+/// Takes the key after the breadcrumb key: a level's number goes back to it, anything else stops
+/// picking
+pub fn handler(key: Key, app: &mut App) {
+  app.picking_breadcrumb = false;
+  if let Key::Char(c) = key {
+    if let Some(number) = c.to_digit(10).filter(|&number| number > 0) {
+      app.pop_navigation_stack_to(number as usize - 1);
+    }
+  }
+}
+
+/// Go back to the level clicked on, if the click was on the breadcrumb
+pub fn click(column: u16, row: u16, app: &mut App) {
+  let area = match app.breadcrumb_area.get() {
+    Some(area) => area,
+    None => return,
+  };
+  if row < area.y || row >= area.bottom() || column >= area.right().saturating_sub(1) {
+    return;
+  }
+  if let Some(level) = level_at(app, area.x + 1, column) {
+    app.picking_breadcrumb = false;
+    app.pop_navigation_stack_to(level);
+  }
+}
+
+// The level drawn over `column` when the breadcrumb's text starts at `start`
+fn level_at(app: &App, mut start: u16, column: u16) -> Option<usize> {
+  for (level, part) in app.get_navigation_breadcrumb().iter().enumerate() {
+    let number = breadcrumb_level_number(app, level).unwrap_or_default();
+    let end = start + display_width(&number) + display_width(part);
+    if (start..end).contains(&column) {
+      return Some(level);
+    }
+    start = end + display_width(BREADCRUMB_SEPARATOR);
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::{ActiveBlock, RouteId};
+  use ratatui::layout::Rect;
+
+  #[test]
+  fn goes_back_to_the_level_picked_or_clicked() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
+    app.push_navigation_stack(RouteId::Podcasts, ActiveBlock::Podcasts);
+    assert_eq!(
+      app.get_navigation_breadcrumb(),
+      ["Library", "Albums", "Artists", "Podcasts"]
+    );
+
+    // Anything but a level's number stops picking without going anywhere
+    start(&mut app);
+    handler(Key::Char('j'), &mut app);
+    assert!(!app.picking_breadcrumb);
+    assert_eq!(app.get_current_route().id, RouteId::Podcasts);
+
+    start(&mut app);
+    handler(Key::Char('3'), &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::Artists);
+
+    // "Library > Albums > Artists" inside the border, so column 11 is the "A" of "Albums"
+    app.breadcrumb_area.set(Some(Rect::new(0, 0, 40, 3)));
+    click(9, 1, &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::Artists);
+    click(11, 1, &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::AlbumList);
+  }
+}
@@ -0,0 +1,69 @@
+use super::common_key_events;
+use crate::{
+  app::{ActiveBlock, App, RouteId},
+  event::Key,
+  network::IoEvent,
+};
+
+pub fn handler(key: Key, app: &mut App) {
+  let categories = &app.categories;
+  match key {
+    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
+    k if common_key_events::down_event(k) && !categories.is_empty() => {
+      let next_index = common_key_events::on_down_press_handler(
+        categories,
+        Some(app.categories_index),
+        app.motion_count,
+      );
+      app.categories_index = next_index;
+    }
+    k if common_key_events::up_event(k) && !categories.is_empty() => {
+      let next_index = common_key_events::on_up_press_handler(
+        categories,
+        Some(app.categories_index),
+        app.motion_count,
+      );
+      app.categories_index = next_index;
+    }
+    k if common_key_events::high_event(k) && !categories.is_empty() => {
+      app.categories_index = common_key_events::on_high_press_handler();
+    }
+    k if common_key_events::middle_event(k) && !categories.is_empty() => {
+      app.categories_index = common_key_events::on_middle_press_handler(categories);
+    }
+    k if common_key_events::low_event(k) && !categories.is_empty() => {
+      app.categories_index = common_key_events::on_low_press_handler(categories);
+    }
+    Key::Enter => {
+      if let Some(category) = app.selected_category() {
+        let category_id = category.id.clone();
+        // Don't show the previous category's playlists if these fail to load
+        app.category_playlists.clear();
+        app.dispatch(IoEvent::GetCategoryPlaylists(category_id));
+        app.push_loading_route(RouteId::CategoryPlaylists, ActiveBlock::CategoryPlaylists);
+      }
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_keys_before_categories_load() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::Categories, ActiveBlock::Categories);
+
+    handler(Key::Down, &mut app);
+    handler(Key::Enter, &mut app);
+    assert_eq!(app.categories_index, 0);
+    assert_eq!(app.get_current_route().id, RouteId::Categories);
+
+    handler(Key::Left, &mut app);
+    let current_route = app.get_current_route();
+    assert_eq!(current_route.active_block, ActiveBlock::Empty);
+    assert_eq!(current_route.hovered_block, ActiveBlock::Library);
+  }
+}
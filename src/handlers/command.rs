@@ -0,0 +1,423 @@
+use super::super::app::{ActiveBlock, App, CommandLine, RouteId, SleepAction};
+use super::track_table;
+use crate::bugreport;
+use crate::duplicates::PlaylistDuplicates;
+use crate::event::Key;
+use crate::network::IoEvent;
+use anyhow::{anyhow, Result};
+use rspotify::model::playlist::SimplifiedPlaylist;
+use std::time::Duration;
+
+/// Commands understood by the `:` command line
+pub const COMMANDS: [&str; 13] = [
+  "add",
+  "bugreport",
+  "colors",
+  "dedupe",
+  "device",
+  "import",
+  "play",
+  "q",
+  "quit",
+  "seek",
+  "sleep",
+  "suspend",
+  "vol",
+];
+
+// Handle a key press while the command line is open
+pub fn handler(key: Key, app: &mut App) {
+  let command_line = match app.command_line.as_mut() {
+    Some(command_line) => command_line,
+    None => return,
+  };
+
+  match key {
+    Key::Esc => {
+      app.command_line = None;
+    }
+    Key::Enter => {
+      let input = command_line.input.clone();
+      app.command_line = None;
+      if let Err(e) = execute(&input, app) {
+        app.show_toast(e.to_string());
+      }
+    }
+    Key::Tab => {
+      complete(app);
+    }
+    Key::Backspace => {
+      // Like vim, backspacing over an empty command line leaves command mode
+      if command_line.input.pop().is_none() {
+        app.command_line = None;
+      } else {
+        reset_completion(command_line);
+      }
+    }
+    Key::Ctrl('u') => {
+      command_line.input.clear();
+      reset_completion(command_line);
+    }
+    Key::Char(c) => {
+      command_line.input.push(c);
+      reset_completion(command_line);
+    }
+    _ => {}
+  }
+}
+
+fn reset_completion(command_line: &mut CommandLine) {
+  command_line.completion_base.clear();
+  command_line.completions.clear();
+  command_line.completion_index = None;
+}
+
+fn device_names(app: &App) -> Vec<String> {
+  app
+    .devices
+    .as_ref()
+    .map(|payload| {
+      payload
+        .devices
+        .iter()
+        .map(|device| device.name.clone())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn playlist_names(app: &App) -> Vec<String> {
+  app
+    .playlists
+    .as_ref()
+    .map(|playlists| {
+      playlists
+        .items
+        .iter()
+        .map(|playlist| playlist.name.clone())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// The playlist being viewed, otherwise the one selected in the sidebar
+fn target_playlist(app: &App) -> Option<&SimplifiedPlaylist> {
+  app.viewed_playlist().or_else(|| {
+    let index = app.selected_playlist_index?;
+    app.playlists.as_ref()?.items.get(index)
+  })
+}
+
+fn matching_names(names: &[String], argument: &str) -> Vec<String> {
+  let argument = argument.trim_start().to_lowercase();
+  names
+    .iter()
+    .filter(|name| name.to_lowercase().starts_with(&argument))
+    .cloned()
+    .collect()
+}
+
+/// Candidates for the word under completion, with the input that comes before that word
+fn completion_candidates(
+  input: &str,
+  device_names: &[String],
+  playlist_names: &[String],
+) -> (String, Vec<String>) {
+  match input.split_once(' ') {
+    None => (
+      String::new(),
+      COMMANDS
+        .iter()
+        .filter(|command| command.starts_with(input))
+        .map(|command| command.to_string())
+        .collect(),
+    ),
+    Some(("device", argument)) => (
+      "device ".to_string(),
+      matching_names(device_names, argument),
+    ),
+    Some(("add", argument)) => ("add ".to_string(), matching_names(playlist_names, argument)),
+    Some(_) => (String::new(), vec![]),
+  }
+}
+
+/// Complete the word being typed, cycling through the candidates on repeated presses
+fn complete(app: &mut App) {
+  let device_names = device_names(app);
+  let playlist_names = playlist_names(app);
+  let command_line = match app.command_line.as_mut() {
+    Some(command_line) => command_line,
+    None => return,
+  };
+
+  if command_line.completion_index.is_none() {
+    let (base, completions) =
+      completion_candidates(&command_line.input, &device_names, &playlist_names);
+    command_line.completion_base = base;
+    command_line.completions = completions;
+  }
+  if command_line.completions.is_empty() {
+    return;
+  }
+
+  let next_index = command_line
+    .completion_index
+    .map(|index| (index + 1) % command_line.completions.len())
+    .unwrap_or(0);
+  command_line.completion_index = Some(next_index);
+  command_line.input = format!(
+    "{}{}",
+    command_line.completion_base, command_line.completions[next_index]
+  );
+}
+
+/// Parse a seek position such as `90`, `1:30` or `1:02:03` into milliseconds
+fn parse_position(position: &str) -> Result<u32> {
+  let invalid = || anyhow!("Invalid position \"{}\", expected e.g. 1:30", position);
+  let mut seconds: u32 = 0;
+  let parts = position.split(':').collect::<Vec<&str>>();
+  if parts.is_empty() || parts.len() > 3 {
+    return Err(invalid());
+  }
+  for part in parts {
+    let value: u32 = part.trim().parse().map_err(|_| invalid())?;
+    seconds = seconds
+      .checked_mul(60)
+      .and_then(|total| total.checked_add(value))
+      .ok_or_else(invalid)?;
+  }
+  seconds.checked_mul(1000).ok_or_else(invalid)
+}
+
+/// Parse a sleep timer length such as `30m`, `1h`, `90s` or `45` (minutes)
+fn parse_duration(duration: &str) -> Result<Duration> {
+  let invalid = || anyhow!("Invalid duration \"{}\", expected e.g. 30m or 1h", duration);
+  let (number, unit_seconds) = match duration.char_indices().last() {
+    Some((index, 's')) => (&duration[..index], 1),
+    Some((index, 'm')) => (&duration[..index], 60),
+    Some((index, 'h')) => (&duration[..index], 60 * 60),
+    _ => (duration, 60),
+  };
+  let value: u64 = number.trim().parse().map_err(|_| invalid())?;
+  match value.checked_mul(unit_seconds) {
+    Some(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+    _ => Err(invalid()),
+  }
+}
+
+fn execute(input: &str, app: &mut App) -> Result<()> {
+  let input = input.trim();
+  let (command, argument) = match input.split_once(' ') {
+    Some((command, argument)) => (command, argument.trim()),
+    None => (input, ""),
+  };
+
+  match command {
+    "" => {}
+    "q" | "quit" => {
+      app.should_quit = true;
+    }
+    "play" => {
+      if argument.is_empty() {
+        return Err(anyhow!("Usage: :play <query>"));
+      }
+      app.dispatch(IoEvent::SearchAndPlay(argument.to_string()));
+    }
+    "device" => {
+      let devices = app
+        .devices
+        .as_ref()
+        .map(|payload| payload.devices.clone())
+        .unwrap_or_default();
+      let argument_lower = argument.to_lowercase();
+      let device = devices
+        .iter()
+        .find(|device| device.name.to_lowercase() == argument_lower)
+        .or_else(|| {
+          devices
+            .iter()
+            .find(|device| device.name.to_lowercase().contains(&argument_lower))
+        });
+      match device.and_then(|device| device.id.clone().map(|id| (id, device.name.clone()))) {
+        Some((id, name)) if !argument.is_empty() => {
+          app.add_log_message(format!("Transferring playback to {}", name));
+          app.dispatch(IoEvent::TransferPlaybackToDevice(id));
+        }
+        _ if argument.is_empty() => return Err(anyhow!("Usage: :device <name>")),
+        _ => return Err(anyhow!("No device matching \"{}\"", argument)),
+      }
+    }
+    "add" => {
+      if argument.is_empty() {
+        return Err(anyhow!("Usage: :add <playlist>"));
+      }
+      if app.get_current_route().active_block != ActiveBlock::TrackTable {
+        return Err(anyhow!(
+          "Select tracks in a track table to add them to a playlist"
+        ));
+      }
+      let argument_lower = argument.to_lowercase();
+      let playlist = app.playlists.as_ref().and_then(|playlists| {
+        playlists
+          .items
+          .iter()
+          .find(|playlist| playlist.name.to_lowercase() == argument_lower)
+          .or_else(|| {
+            playlists
+              .items
+              .iter()
+              .find(|playlist| playlist.name.to_lowercase().contains(&argument_lower))
+          })
+          .map(|playlist| (playlist.id.to_string(), playlist.name.clone()))
+      });
+      let (playlist_id, playlist_name) =
+        playlist.ok_or_else(|| anyhow!("No playlist matching \"{}\"", argument))?;
+
+      let track_uris = track_table::selected_track_uris(app);
+      app.track_table.visual_anchor = None;
+      app.add_log_message(format!(
+        "Adding {} tracks to {}",
+        track_uris.len(),
+        playlist_name
+      ));
+      app.dispatch(IoEvent::AddTracksToPlaylist(playlist_id, track_uris));
+    }
+    "import" => {
+      if argument.is_empty() {
+        return Err(anyhow!("Usage: :import <file>"));
+      }
+      let playlist =
+        target_playlist(app).map(|playlist| (playlist.id.to_string(), playlist.name.clone()));
+      let (playlist_id, playlist_name) =
+        playlist.ok_or_else(|| anyhow!("Select a playlist to import into"))?;
+
+      app.add_log_message(format!("Importing {} into {}", argument, playlist_name));
+      app.dispatch(IoEvent::ImportTracks(argument.to_string(), playlist_id));
+    }
+    "dedupe" => {
+      let playlist = target_playlist(app).ok_or_else(|| anyhow!("Select a playlist to dedupe"))?;
+      if !app.can_edit_playlist(playlist) {
+        return Err(anyhow!(
+          "Only the owner can remove tracks from {}",
+          playlist.name
+        ));
+      }
+      let playlist_id = playlist.id.to_string();
+      let playlist_name = playlist.name.clone();
+      app.playlist_duplicates = Some(PlaylistDuplicates {
+        playlist_id: playlist_id.clone(),
+        playlist_name,
+        ..Default::default()
+      });
+      app.push_navigation_stack(RouteId::Duplicates, ActiveBlock::Duplicates);
+      app.dispatch(IoEvent::FindPlaylistDuplicates(playlist_id));
+    }
+    "bugreport" => {
+      let path = bugreport::write(app).map_err(|e| anyhow!("Couldn't write bug report: {}", e))?;
+      app.show_toast(format!("Bug report written to {}", path.display()));
+    }
+    "colors" => {
+      app.push_navigation_stack(RouteId::ColorStory, ActiveBlock::ColorStory);
+    }
+    "seek" => {
+      let position_ms = parse_position(argument)?;
+      app.song_progress_ms = u128::from(position_ms);
+      app.dispatch(IoEvent::Seek(position_ms));
+    }
+    "suspend" => {
+      app.suspend_requested = true;
+    }
+    "sleep" => {
+      let (duration, action) = match argument.split_once(' ') {
+        Some((duration, action)) => (duration, action.trim()),
+        None => (argument, ""),
+      };
+      let action = match action {
+        "" | "pause" => SleepAction::Pause,
+        "quit" => SleepAction::Quit,
+        _ => return Err(anyhow!("Usage: :sleep <30m|1h|off> [pause|quit]")),
+      };
+      match duration {
+        "" => return Err(anyhow!("Usage: :sleep <30m|1h|off> [pause|quit]")),
+        "off" | "cancel" => app.cancel_sleep_timer(),
+        _ => app.start_sleep_timer(parse_duration(duration)?, action),
+      }
+    }
+    "vol" => {
+      let volume = argument
+        .parse::<u8>()
+        .ok()
+        .filter(|volume| *volume <= 100)
+        .ok_or_else(|| anyhow!("Volume must be between 0 and 100, is \"{}\"", argument))?;
+      app.set_volume(volume);
+    }
+    _ => return Err(anyhow!("Unknown command: {}", command)),
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_position() {
+    assert_eq!(parse_position("90").unwrap(), 90_000);
+    assert_eq!(parse_position("1:30").unwrap(), 90_000);
+    assert_eq!(parse_position("1:02:03").unwrap(), 3_723_000);
+    assert!(parse_position("").is_err());
+    assert!(parse_position("1:x").is_err());
+    assert!(parse_position("1:2:3:4").is_err());
+  }
+
+  #[test]
+  fn test_parse_duration() {
+    assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(30 * 60));
+    assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(60 * 60));
+    assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45 * 60));
+    assert!(parse_duration("0m").is_err());
+    assert!(parse_duration("m").is_err());
+    assert!(parse_duration("soon").is_err());
+  }
+
+  #[test]
+  fn test_completion_candidates() {
+    let devices = vec!["Kitchen".to_string(), "Laptop".to_string()];
+    let playlists = vec!["Focus".to_string(), "Running".to_string()];
+    assert_eq!(
+      completion_candidates("q", &devices, &playlists),
+      (String::new(), vec!["q".to_string(), "quit".to_string()])
+    );
+    assert_eq!(
+      completion_candidates("device k", &devices, &playlists),
+      ("device ".to_string(), vec!["Kitchen".to_string()])
+    );
+    assert_eq!(
+      completion_candidates("add ru", &devices, &playlists),
+      ("add ".to_string(), vec!["Running".to_string()])
+    );
+    assert_eq!(
+      completion_candidates("vol 4", &devices, &playlists).1,
+      Vec::<String>::new()
+    );
+  }
+
+  #[test]
+  fn test_quit_and_unknown_commands() {
+    let mut app = App::default();
+    assert!(execute("bogus", &mut app).is_err());
+    assert!(execute("vol 400", &mut app).is_err());
+    // Without a playlist there is nothing to import into or dedupe
+    assert!(execute("import tracks.txt", &mut app).is_err());
+    assert!(execute("dedupe", &mut app).is_err());
+    assert!(execute("sleep", &mut app).is_err());
+    assert!(execute("sleep 30m later", &mut app).is_err());
+    execute("sleep 30m quit", &mut app).unwrap();
+    assert!(app.sleep_timer.is_some());
+    execute("sleep off", &mut app).unwrap();
+    assert!(app.sleep_timer.is_none());
+    execute("q", &mut app).unwrap();
+    assert!(app.should_quit);
+  }
+}
@@ -29,9 +29,51 @@ pub fn low_event(key: Key) -> bool {
   matches!(key, Key::Char('L'))
 }
 
-pub fn on_down_press_handler<T>(selection_data: &[T], selection_index: Option<usize>) -> usize {
+/// Keys a numeric count prefix moves further, e.g. `5j`. Pages load one at a time, so the page
+/// keys don't take a count
+pub fn is_count_motion(key: Key) -> bool {
+  down_event(key) || up_event(key)
+}
+
+/// Blocks listing rows, where digits are a count for the next motion rather than keys of their
+/// own
+pub fn is_list_block(block: ActiveBlock) -> bool {
+  matches!(
+    block,
+    ActiveBlock::AlbumList
+      | ActiveBlock::AlbumTracks
+      | ActiveBlock::ArtistBlock
+      | ActiveBlock::Artists
+      | ActiveBlock::Categories
+      | ActiveBlock::CategoryPlaylists
+      | ActiveBlock::Duplicates
+      | ActiveBlock::EpisodeTable
+      | ActiveBlock::FeaturedPlaylists
+      | ActiveBlock::Library
+      | ActiveBlock::LogStream
+      | ActiveBlock::MadeForYou
+      | ActiveBlock::MyPlaylists
+      | ActiveBlock::NewReleases
+      | ActiveBlock::Podcasts
+      | ActiveBlock::RecentlyPlayed
+      | ActiveBlock::SearchResultBlock
+      | ActiveBlock::SelectDevice
+      | ActiveBlock::TrackTable
+  )
+}
+
+/// The row `count` rows down: one wraps around from the last row to the first, a longer jump
+/// stops at the last row
+pub fn on_down_press_handler<T>(
+  selection_data: &[T],
+  selection_index: Option<usize>,
+  count: usize,
+) -> usize {
   match selection_index {
     Some(selection_index) => {
+      if count > 1 && !selection_data.is_empty() {
+        return (selection_index + count).min(selection_data.len() - 1);
+      }
       if !selection_data.is_empty() {
         let next_index = selection_index + 1;
         if next_index > selection_data.len() - 1 {
@@ -46,9 +88,18 @@ pub fn on_down_press_handler<T>(selection_data: &[T], selection_index: Option<us
   }
 }
 
-pub fn on_up_press_handler<T>(selection_data: &[T], selection_index: Option<usize>) -> usize {
+/// The row `count` rows up: one wraps around from the first row to the last, a longer jump stops
+/// at the first row
+pub fn on_up_press_handler<T>(
+  selection_data: &[T],
+  selection_index: Option<usize>,
+  count: usize,
+) -> usize {
   match selection_index {
     Some(selection_index) => {
+      if count > 1 && !selection_data.is_empty() {
+        return selection_index.saturating_sub(count);
+      }
       if !selection_data.is_empty() {
         if selection_index > 0 {
           return selection_index - 1;
@@ -93,6 +144,33 @@ pub fn handle_right_event(app: &mut App) {
       RouteId::Podcasts => {
         app.set_current_route_state(Some(ActiveBlock::Podcasts), Some(ActiveBlock::Podcasts));
       }
+      RouteId::MadeForYou => {
+        app.set_current_route_state(Some(ActiveBlock::MadeForYou), Some(ActiveBlock::MadeForYou));
+      }
+      RouteId::NewReleases => {
+        app.set_current_route_state(
+          Some(ActiveBlock::NewReleases),
+          Some(ActiveBlock::NewReleases),
+        );
+      }
+      RouteId::FeaturedPlaylists => {
+        app.set_current_route_state(
+          Some(ActiveBlock::FeaturedPlaylists),
+          Some(ActiveBlock::FeaturedPlaylists),
+        );
+      }
+      RouteId::Categories => {
+        app.set_current_route_state(Some(ActiveBlock::Categories), Some(ActiveBlock::Categories));
+      }
+      RouteId::Profile => {
+        app.set_current_route_state(Some(ActiveBlock::Profile), Some(ActiveBlock::Profile));
+      }
+      RouteId::CategoryPlaylists => {
+        app.set_current_route_state(
+          Some(ActiveBlock::CategoryPlaylists),
+          Some(ActiveBlock::CategoryPlaylists),
+        );
+      }
       RouteId::Recommendations => {
         app.set_current_route_state(Some(ActiveBlock::TrackTable), Some(ActiveBlock::TrackTable));
       }
@@ -114,6 +192,9 @@ pub fn handle_right_event(app: &mut App) {
           Some(ActiveBlock::RecentlyPlayed),
         );
       }
+      RouteId::Duplicates => {
+        app.set_current_route_state(Some(ActiveBlock::Duplicates), Some(ActiveBlock::Duplicates));
+      }
       RouteId::Search => {
         app.set_current_route_state(
           Some(ActiveBlock::SearchResultBlock),
@@ -131,6 +212,7 @@ pub fn handle_right_event(app: &mut App) {
       RouteId::Analysis => {}
       RouteId::BasicView => {}
       RouteId::LogStream => {}
+      RouteId::ColorStory => {}
       RouteId::Error => {}
       RouteId::Dialog => {}
     },
@@ -152,28 +234,47 @@ mod tests {
     let data = vec!["Choice 1", "Choice 2", "Choice 3"];
 
     let index = 0;
-    let next_index = on_down_press_handler(&data, Some(index));
+    let next_index = on_down_press_handler(&data, Some(index), 1);
 
     assert_eq!(next_index, 1);
 
     // Selection wrap if on last item
     let index = data.len() - 1;
-    let next_index = on_down_press_handler(&data, Some(index));
+    let next_index = on_down_press_handler(&data, Some(index), 1);
     assert_eq!(next_index, 0);
   }
 
+  #[test]
+  fn test_count_jumps_without_wrapping() {
+    let data = vec!["Choice 1", "Choice 2", "Choice 3", "Choice 4"];
+
+    assert_eq!(on_down_press_handler(&data, Some(0), 2), 2);
+    assert_eq!(on_down_press_handler(&data, Some(2), 5), 3);
+    assert_eq!(on_up_press_handler(&data, Some(3), 2), 1);
+    assert_eq!(on_up_press_handler(&data, Some(1), 5), 0);
+  }
+
+  #[test]
+  fn test_is_count_motion() {
+    let app = App::default();
+    assert!(is_count_motion(Key::Char('j')));
+    assert!(is_count_motion(Key::Up));
+    assert!(!is_count_motion(app.user_config.keys.next_page));
+    assert!(!is_count_motion(Key::Enter));
+  }
+
   #[test]
   fn test_on_up_press_handler() {
     let data = vec!["Choice 1", "Choice 2", "Choice 3"];
 
     let index = data.len() - 1;
-    let next_index = on_up_press_handler(&data, Some(index));
+    let next_index = on_up_press_handler(&data, Some(index), 1);
 
     assert_eq!(next_index, index - 1);
 
     // Selection wrap if on first item
     let index = 0;
-    let next_index = on_up_press_handler(&data, Some(index));
+    let next_index = on_up_press_handler(&data, Some(index), 1);
     assert_eq!(next_index, data.len() - 1);
   }
 }
@@ -1,21 +1,19 @@
-use super::super::app::{ActiveBlock, App, DialogContext};
+use super::super::app::{App, Dialog};
 use crate::event::Key;
+use crate::network::IoEvent;
+use crate::open_in;
+use crate::share::ShareFormat;
 
 pub fn handler(key: Key, app: &mut App) {
   match key {
     Key::Enter => {
-      if let Some(route) = app.pop_navigation_stack() {
-        if app.confirm {
-          if let ActiveBlock::Dialog(d) = route.active_block {
-            match d {
-              DialogContext::PlaylistWindow => handle_playlist_dialog(app),
-              DialogContext::PlaylistSearch => handle_playlist_search_dialog(app),
-            }
-          }
-        }
+      app.pop_navigation_stack();
+      if let Some(dialog) = app.dialog.take() {
+        answer_dialog(app, dialog);
       }
     }
     Key::Esc => {
+      app.dialog = None;
       app.pop_navigation_stack();
     }
     Key::Right => app.confirm = !app.confirm,
@@ -24,10 +22,179 @@ pub fn handler(key: Key, app: &mut App) {
   }
 }
 
-fn handle_playlist_dialog(app: &mut App) {
-  app.user_unfollow_playlist()
+fn answer_dialog(app: &mut App, dialog: Dialog) {
+  match dialog {
+    Dialog::ResumeEpisode {
+      uri, position_ms, ..
+    } => {
+      let position = if app.confirm { Some(position_ms) } else { None };
+      app.dispatch(IoEvent::PlayEpisode(uri, position));
+    }
+    _ if !app.confirm => {}
+    Dialog::UnfollowPlaylist { id, .. } => app.dispatch(IoEvent::UserUnfollowPlaylist(id)),
+    Dialog::UnfollowArtist { id, name } => app.unfollow_artist(id, &name),
+    Dialog::RemoveSavedAlbum { id, .. } => app.dispatch(IoEvent::CurrentUserSavedAlbumDelete(id)),
+    Dialog::RemoveFromPlaylist {
+      playlist_id,
+      track_uris,
+      ..
+    } => {
+      app.track_table.visual_anchor = None;
+      app.dispatch(IoEvent::RemoveTracksFromPlaylist(playlist_id, track_uris));
+    }
+    Dialog::RemoveDuplicates {
+      playlist_id,
+      duplicates,
+      ..
+    } => app.dispatch(IoEvent::RemovePlaylistDuplicates(playlist_id, duplicates)),
+  }
+}
+
+// The add-to-playlist dialog takes text, so it gets every key before the global bindings
+pub fn playlist_picker_handler(key: Key, app: &mut App) {
+  let match_count = app.playlist_picker_matches().len();
+  let picker = match app.playlist_picker.as_mut() {
+    Some(picker) => picker,
+    None => return,
+  };
+
+  match key {
+    Key::Esc => close_playlist_picker(app),
+    Key::Enter => {
+      let selected_index = picker.selected_index;
+      let selected = app
+        .playlist_picker_matches()
+        .get(selected_index)
+        .map(|playlist| playlist.id.to_string());
+      if let (Some(playlist_id), Some(picker)) = (selected, app.playlist_picker.take()) {
+        app.track_table.visual_anchor = None;
+        app.dispatch(IoEvent::AddTracksToPlaylist(playlist_id, picker.track_uris));
+      }
+      close_playlist_picker(app);
+    }
+    Key::Down | Key::Ctrl('n') if match_count > 0 => {
+      picker.selected_index = (picker.selected_index + 1) % match_count;
+    }
+    Key::Up | Key::Ctrl('p') if match_count > 0 => {
+      picker.selected_index = (picker.selected_index + match_count - 1) % match_count;
+    }
+    Key::Backspace => {
+      picker.filter.pop();
+      picker.selected_index = 0;
+    }
+    Key::Ctrl('u') => {
+      picker.filter.clear();
+      picker.selected_index = 0;
+    }
+    Key::Char(c) => {
+      picker.filter.push(c);
+      picker.selected_index = 0;
+    }
+    _ => {}
+  }
+}
+
+fn close_playlist_picker(app: &mut App) {
+  app.playlist_picker = None;
+  app.pop_navigation_stack();
 }
 
-fn handle_playlist_search_dialog(app: &mut App) {
-  app.user_unfollow_playlist_search_result()
+// The "open in" menu lists few entries, so like the add-to-playlist dialog it takes every key
+pub fn open_in_handler(key: Key, app: &mut App) {
+  let menu = match app.open_in_menu.as_mut() {
+    Some(menu) => menu,
+    None => return,
+  };
+  let count = menu.targets.len();
+
+  match key {
+    Key::Esc | Key::Char('q') => close_open_in_menu(app),
+    Key::Enter => {
+      let target = menu.targets.get(menu.selected_index).cloned();
+      let in_app = menu.in_app;
+      close_open_in_menu(app);
+      if let Some(target) = target {
+        match open_in::open(&target, in_app) {
+          Ok(()) => app.add_log_message(format!("Opened {}", target.uri)),
+          Err(e) => app.handle_error(e),
+        }
+      }
+    }
+    // Switch between the Spotify app and the browser
+    Key::Tab => menu.in_app = !menu.in_app,
+    Key::Down | Key::Char('j') | Key::Ctrl('n') => {
+      menu.selected_index = (menu.selected_index + 1) % count.max(1);
+    }
+    Key::Up | Key::Char('k') | Key::Ctrl('p') => {
+      menu.selected_index = (menu.selected_index + count.max(1) - 1) % count.max(1);
+    }
+    _ => {}
+  }
+}
+
+fn close_open_in_menu(app: &mut App) {
+  app.open_in_menu = None;
+  app.pop_navigation_stack();
+}
+
+pub fn share_handler(key: Key, app: &mut App) {
+  let menu = match app.share_menu.as_mut() {
+    Some(menu) => menu,
+    None => return,
+  };
+  let count = ShareFormat::ALL.len();
+
+  match key {
+    Key::Esc | Key::Char('q') => close_share_menu(app),
+    Key::Enter => {
+      let format = ShareFormat::ALL[menu.selected_index];
+      let text = format.format(&menu.item);
+      close_share_menu(app);
+      app.copy_to_clipboard(text, format.label());
+    }
+    Key::Down | Key::Char('j') | Key::Ctrl('n') => {
+      menu.selected_index = (menu.selected_index + 1) % count;
+    }
+    Key::Up | Key::Char('k') | Key::Ctrl('p') => {
+      menu.selected_index = (menu.selected_index + count - 1) % count;
+    }
+    _ => {}
+  }
+}
+
+fn close_share_menu(app: &mut App) {
+  app.share_menu = None;
+  app.pop_navigation_stack();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{app::RouteId, user_config::UserConfig};
+  use std::time::SystemTime;
+
+  #[test]
+  fn destructive_dialogs_act_only_on_ok() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App::new(tx, UserConfig::new(), SystemTime::now());
+    let dialog = Dialog::RemoveSavedAlbum {
+      id: "4aawyAB9vmqN3uQ7FjRGTy".to_string(),
+      name: "Global Warming".to_string(),
+    };
+
+    // Cancel is highlighted to begin with
+    app.open_dialog(dialog.clone());
+    handler(Key::Enter, &mut app);
+    assert!(rx.try_recv().is_err());
+    assert!(app.dialog.is_none());
+
+    app.open_dialog(dialog);
+    handler(Key::Left, &mut app);
+    handler(Key::Enter, &mut app);
+    assert!(matches!(
+      rx.try_recv(),
+      Ok((IoEvent::CurrentUserSavedAlbumDelete(id), _)) if id == "4aawyAB9vmqN3uQ7FjRGTy"
+    ));
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+  }
 }
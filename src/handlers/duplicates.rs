@@ -0,0 +1,67 @@
+use super::{
+  super::app::{App, Dialog},
+  common_key_events,
+};
+use crate::event::Key;
+
+pub fn handler(key: Key, app: &mut App) {
+  match key {
+    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
+    k if common_key_events::down_event(k) => {
+      if let Some(review) = &mut app.playlist_duplicates {
+        if let Some(duplicates) = &review.duplicates {
+          review.selected_index = common_key_events::on_down_press_handler(
+            duplicates,
+            Some(review.selected_index),
+            app.motion_count,
+          );
+        }
+      }
+    }
+    k if common_key_events::up_event(k) => {
+      if let Some(review) = &mut app.playlist_duplicates {
+        if let Some(duplicates) = &review.duplicates {
+          review.selected_index = common_key_events::on_up_press_handler(
+            duplicates,
+            Some(review.selected_index),
+            app.motion_count,
+          );
+        }
+      }
+    }
+    k if common_key_events::high_event(k) => {
+      if let Some(review) = &mut app.playlist_duplicates {
+        review.selected_index = common_key_events::on_high_press_handler();
+      }
+    }
+    k if common_key_events::low_event(k) => {
+      if let Some(review) = &mut app.playlist_duplicates {
+        if let Some(duplicates) = review.duplicates.as_ref().filter(|d| !d.is_empty()) {
+          review.selected_index = common_key_events::on_low_press_handler(duplicates);
+        }
+      }
+    }
+    _ if key == app.user_config.keys.remove_from_playlist => open_remove_duplicates_dialog(app),
+    _ => {}
+  }
+}
+
+pub fn open_remove_duplicates_dialog(app: &mut App) {
+  // Every duplicate the scan found, keeping the first copy of each track
+  let dialog = match &app.playlist_duplicates {
+    Some(review) => match review.duplicates.as_deref() {
+      None | Some([]) => return,
+      Some(duplicates) => Dialog::RemoveDuplicates {
+        playlist_id: review.playlist_id.clone(),
+        duplicates: duplicates.to_vec(),
+        subject: format!(
+          "{} duplicates from {}",
+          duplicates.len(),
+          review.playlist_name
+        ),
+      },
+    },
+    None => return,
+  };
+  app.open_dialog(dialog);
+}
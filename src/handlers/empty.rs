@@ -20,6 +20,12 @@ pub fn handler(key: Key, app: &mut App) {
       | ActiveBlock::AlbumTracks
       | ActiveBlock::Artists
       | ActiveBlock::Podcasts
+      | ActiveBlock::MadeForYou
+      | ActiveBlock::NewReleases
+      | ActiveBlock::FeaturedPlaylists
+      | ActiveBlock::Categories
+      | ActiveBlock::CategoryPlaylists
+      | ActiveBlock::Profile
       | ActiveBlock::EpisodeTable
       | ActiveBlock::Home
       | ActiveBlock::MyPlaylists
@@ -42,6 +48,12 @@ pub fn handler(key: Key, app: &mut App) {
       | ActiveBlock::AlbumTracks
       | ActiveBlock::Artists
       | ActiveBlock::Podcasts
+      | ActiveBlock::MadeForYou
+      | ActiveBlock::NewReleases
+      | ActiveBlock::FeaturedPlaylists
+      | ActiveBlock::Categories
+      | ActiveBlock::CategoryPlaylists
+      | ActiveBlock::Profile
       | ActiveBlock::EpisodeTable
       | ActiveBlock::Home
       | ActiveBlock::RecentlyPlayed
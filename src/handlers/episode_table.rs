@@ -1,5 +1,5 @@
 use super::{
-  super::app::{App, EpisodeTableContext},
+  super::app::{App, Dialog, EpisodeTableContext},
   common_key_events,
 };
 use crate::app::ActiveBlock;
@@ -11,15 +11,21 @@ pub fn handler(key: Key, app: &mut App) {
     k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
     k if common_key_events::down_event(k) => {
       if let Some(episodes) = &mut app.library.show_episodes.get_results(None) {
-        let next_index =
-          common_key_events::on_down_press_handler(&episodes.items, Some(app.episode_list_index));
+        let next_index = common_key_events::on_down_press_handler(
+          &episodes.items,
+          Some(app.episode_list_index),
+          app.motion_count,
+        );
         app.episode_list_index = next_index;
       }
     }
     k if common_key_events::up_event(k) => {
       if let Some(episodes) = &mut app.library.show_episodes.get_results(None) {
-        let next_index =
-          common_key_events::on_up_press_handler(&episodes.items, Some(app.episode_list_index));
+        let next_index = common_key_events::on_up_press_handler(
+          &episodes.items,
+          Some(app.episode_list_index),
+          app.motion_count,
+        );
         app.episode_list_index = next_index;
       }
     }
@@ -50,7 +56,6 @@ pub fn handler(key: Key, app: &mut App) {
     k if k == app.user_config.keys.previous_page => handle_prev_event(app),
     Key::Char('S') => toggle_sort_by_date(app),
     Key::Char('s') => handle_follow_event(app),
-    Key::Char('D') => handle_unfollow_event(app),
     Key::Ctrl('e') => jump_to_end(app),
     Key::Ctrl('a') => jump_to_start(app),
     _ => {}
@@ -65,13 +70,29 @@ fn jump_to_end(app: &mut App) {
 }
 
 fn on_enter(app: &mut App) {
-  if let Some(episodes) = app.library.show_episodes.get_results(None) {
-    let episode_uris = episodes
-      .items
-      .iter()
-      .map(|episode| format!("spotify:track:{}", episode.id.to_string()).to_owned())
-      .collect::<Vec<String>>();
-    app.dispatch(IoEvent::StartPlayback(None, None));
+  let episode = app
+    .library
+    .show_episodes
+    .get_results(None)
+    .and_then(|episodes| episodes.items.get(app.episode_list_index))
+    .cloned();
+
+  if let Some(episode) = episode {
+    let id_str = episode.id.to_string();
+    let episode_uri = if id_str.starts_with("spotify:episode:") {
+      id_str.clone()
+    } else {
+      format!("spotify:episode:{}", id_str)
+    };
+
+    match app.episode_positions.get(&id_str) {
+      Some(position_ms) => app.open_dialog(Dialog::ResumeEpisode {
+        uri: episode_uri,
+        position_ms,
+        name: episode.name,
+      }),
+      None => app.dispatch(IoEvent::PlayEpisode(episode_uri, None)),
+    }
   }
 }
 
@@ -100,7 +121,7 @@ fn handle_follow_event(app: &mut App) {
   app.user_follow_show(ActiveBlock::EpisodeTable);
 }
 
-fn handle_unfollow_event(app: &mut App) {
+pub fn handle_unfollow_event(app: &mut App) {
   app.user_unfollow_show(ActiveBlock::EpisodeTable);
 }
 
@@ -0,0 +1,89 @@
+use super::common_key_events;
+use crate::{
+  app::{ActiveBlock, App, RouteId, TrackTableContext},
+  event::Key,
+  network::IoEvent,
+  open_in,
+};
+
+pub fn handler(key: Key, app: &mut App) {
+  let playlists = match &app.featured_playlists {
+    Some(featured) if !featured.playlists.items.is_empty() => &featured.playlists.items,
+    _ => {
+      if common_key_events::left_event(key) {
+        common_key_events::handle_left_event(app);
+      }
+      return;
+    }
+  };
+  match key {
+    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
+    k if common_key_events::down_event(k) => {
+      let next_index = common_key_events::on_down_press_handler(
+        playlists,
+        Some(app.featured_playlists_index),
+        app.motion_count,
+      );
+      app.featured_playlists_index = next_index;
+    }
+    k if common_key_events::up_event(k) => {
+      let next_index = common_key_events::on_up_press_handler(
+        playlists,
+        Some(app.featured_playlists_index),
+        app.motion_count,
+      );
+      app.featured_playlists_index = next_index;
+    }
+    k if common_key_events::high_event(k) => {
+      app.featured_playlists_index = common_key_events::on_high_press_handler();
+    }
+    k if common_key_events::middle_event(k) => {
+      app.featured_playlists_index = common_key_events::on_middle_press_handler(playlists);
+    }
+    k if common_key_events::low_event(k) => {
+      app.featured_playlists_index = common_key_events::on_low_press_handler(playlists);
+    }
+    Key::Enter => {
+      if let Some(playlist_id) = selected_playlist_id(app) {
+        app.track_table.context = Some(TrackTableContext::FeaturedPlaylists);
+        app.dispatch(IoEvent::GetFeaturedPlaylistTracks(playlist_id));
+        app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+      }
+    }
+    // Play the whole playlist
+    Key::Char('e') => {
+      if let Some(playlist_id) = selected_playlist_id(app) {
+        let playlist_uri = open_in::uri("playlist", &playlist_id);
+        app.dispatch(IoEvent::StartPlayback(Some(playlist_uri), None));
+      }
+    }
+    _ => {}
+  }
+}
+
+fn selected_playlist_id(app: &App) -> Option<String> {
+  app
+    .selected_featured_playlist()
+    .map(|playlist| playlist.id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_keys_before_playlists_load() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::FeaturedPlaylists, ActiveBlock::FeaturedPlaylists);
+
+    handler(Key::Down, &mut app);
+    handler(Key::Enter, &mut app);
+    assert_eq!(app.featured_playlists_index, 0);
+    assert_eq!(app.get_current_route().id, RouteId::FeaturedPlaylists);
+
+    handler(Key::Left, &mut app);
+    let current_route = app.get_current_route();
+    assert_eq!(current_route.active_block, ActiveBlock::Empty);
+    assert_eq!(current_route.hovered_block, ActiveBlock::Library);
+  }
+}
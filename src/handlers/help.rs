@@ -0,0 +1,60 @@
+//! Scrolling the help overlay, which takes every key while it is open
+
+use super::common_key_events;
+use crate::app::App;
+use crate::event::Key;
+use crate::ui::help::max_scroll;
+use crate::ui::util::help_rect;
+
+pub fn toggle(app: &mut App) {
+  app.help_scroll = match app.help_scroll {
+    Some(_) => None,
+    None => Some(0),
+  };
+}
+
+/// Moves through the help a row or a page at a time, and closes it on the help key, the back key
+/// or Esc
+pub fn handler(key: Key, app: &mut App) {
+  let scroll = match app.help_scroll {
+    Some(scroll) => scroll,
+    None => return,
+  };
+  let max = max_scroll(app);
+  let page = help_rect(app.size).height.saturating_sub(2).max(1);
+  let keys = &app.user_config.keys;
+  app.help_scroll = match key {
+    k if k == keys.help || k == keys.back || k == Key::Esc => None,
+    k if common_key_events::down_event(k) => Some(scroll.saturating_add(1).min(max)),
+    k if common_key_events::up_event(k) => Some(scroll.saturating_sub(1)),
+    k if k == keys.next_page || k == Key::PageDown => Some(scroll.saturating_add(page).min(max)),
+    k if k == keys.previous_page || k == Key::PageUp => Some(scroll.saturating_sub(page)),
+    k if k == keys.jump_to_start || k == Key::Home => Some(0),
+    k if k == keys.jump_to_end || k == Key::End => Some(max),
+    _ => Some(scroll),
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ratatui::layout::Rect;
+
+  #[test]
+  fn scrolls_within_the_help() {
+    let mut app = App::default();
+    app.size = Rect::new(0, 0, 80, 24);
+    toggle(&mut app);
+    handler(Key::Char('k'), &mut app);
+    assert_eq!(app.help_scroll, Some(0));
+    handler(Key::Char('j'), &mut app);
+    assert_eq!(app.help_scroll, Some(1));
+    handler(app.user_config.keys.jump_to_end, &mut app);
+    assert_eq!(app.help_scroll, Some(max_scroll(&app)));
+    handler(Key::Char('j'), &mut app);
+    assert_eq!(app.help_scroll, Some(max_scroll(&app)));
+
+    handler(Key::Esc, &mut app);
+    assert_eq!(app.help_scroll, None);
+  }
+}
@@ -1,14 +1,56 @@
-extern crate unicode_width;
-
-use super::super::app::{ActiveBlock, App, RouteId};
+use super::super::app::{ActiveBlock, App, InputPurpose, RouteId, SearchResultBlock};
 use crate::event::Key;
-use crate::focus_manager::ComponentId;
 use crate::network::IoEvent;
-use std::convert::TryInto;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::open_in;
+use crate::search_filter;
+use crate::search_history::{HistoryRecall, ReverseSearch};
+use crate::ui::util::display_width;
+use rspotify::model::enums::SearchType;
+use std::time::Instant;
+use unicode_segmentation::UnicodeSegmentation;
 
 // Handle event when the search input block is active
 pub fn handler(key: Key, app: &mut App) {
+  let previous_input = app.input.clone();
+  handle_key(key, app);
+  finish_edit(app, &previous_input);
+}
+
+/// Insert pasted text at the cursor in one go. Line breaks and tabs become spaces, so pasting a
+/// line with its ending doesn't submit it.
+pub fn paste_handler(text: &str, app: &mut App) {
+  let previous_input = app.input.clone();
+  let pasted = text
+    .trim_end_matches(['\r', '\n'])
+    .replace("\r\n", " ")
+    .chars()
+    .map(|c| if c.is_whitespace() { ' ' } else { c })
+    .filter(|c| !c.is_control())
+    .collect::<Vec<char>>();
+  let index = app.input_idx.min(app.input.len());
+  app.input_idx = index + pasted.len();
+  app.input.splice(index..index, pasted);
+  finish_edit(app, &previous_input);
+}
+
+fn finish_edit(app: &mut App, previous_input: &[char]) {
+  update_cursor_position(app);
+
+  // Search once typing pauses, unless the key left the search box
+  let searching = app.input_purpose == InputPurpose::Search
+    && app.get_current_route().active_block == ActiveBlock::Input;
+  if !searching {
+    app.pending_search = None;
+  } else if app.input != previous_input && app.user_config.behavior.search_delay_milliseconds > 0 {
+    app.pending_search = Some(Instant::now());
+  }
+}
+
+fn handle_key(key: Key, app: &mut App) {
+  if app.input_purpose == InputPurpose::Search && history_handler(key, app) {
+    return;
+  }
+
   match key {
     Key::Ctrl('k') => {
       app.input.drain(app.input_idx..app.input.len());
@@ -16,15 +58,13 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Ctrl('u') => {
       app.input.drain(..app.input_idx);
       app.input_idx = 0;
-      app.input_cursor_position = 0;
     }
     Key::Ctrl('l') => {
       app.input = vec![];
       app.input_idx = 0;
-      app.input_cursor_position = 0;
     }
     Key::Ctrl('w') => {
-      if app.input_cursor_position == 0 {
+      if app.input_idx == 0 {
         return;
       }
       let word_end = match app.input[..app.input_idx].iter().rposition(|&x| x != ' ') {
@@ -35,84 +75,182 @@ pub fn handler(key: Key, app: &mut App) {
         Some(index) => index + 1,
         None => 0,
       };
-      let deleted: String = app.input[word_start..app.input_idx].iter().collect();
-      let deleted_len: u16 = UnicodeWidthStr::width(deleted.as_str()).try_into().unwrap();
       app.input.drain(word_start..app.input_idx);
       app.input_idx = word_start;
-      app.input_cursor_position -= deleted_len;
     }
     Key::End | Key::Ctrl('e') => {
       app.input_idx = app.input.len();
-      let input_string: String = app.input.iter().collect();
-      app.input_cursor_position = UnicodeWidthStr::width(input_string.as_str())
-        .try_into()
-        .unwrap();
     }
     Key::Home | Key::Ctrl('a') => {
       app.input_idx = 0;
-      app.input_cursor_position = 0;
     }
     Key::Left | Key::Ctrl('b') => {
-      if !app.input.is_empty() && app.input_idx > 0 {
-        let last_c = app.input[app.input_idx - 1];
-        app.input_idx -= 1;
-        app.input_cursor_position -= compute_character_width(last_c);
-      }
+      app.input_idx = previous_grapheme_start(&app.input, app.input_idx);
     }
     Key::Right | Key::Ctrl('f') => {
-      if app.input_idx < app.input.len() {
-        let next_c = app.input[app.input_idx];
-        app.input_idx += 1;
-        app.input_cursor_position += compute_character_width(next_c);
-      }
+      app.input_idx = next_grapheme_start(&app.input, app.input_idx);
     }
     Key::Esc => {
-      app.clear_all_focus();
-      app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
+      if app.input_purpose == InputPurpose::Search {
+        app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
+      } else {
+        finish_playlist_name_input(app);
+      }
     }
     Key::Enter => {
       let input_str: String = app.input.iter().collect();
 
-      process_input(app, input_str);
+      match app.input_purpose.clone() {
+        InputPurpose::Search => process_input(app, input_str),
+        InputPurpose::NewPlaylistName => {
+          let name = input_str.trim().to_string();
+          if !name.is_empty() {
+            app.dispatch(IoEvent::CreatePlaylist(name));
+          }
+          finish_playlist_name_input(app);
+        }
+        InputPurpose::RenamePlaylist(playlist_id) => {
+          let name = input_str.trim().to_string();
+          if !name.is_empty() {
+            app.dispatch(IoEvent::RenamePlaylist(playlist_id, name));
+          }
+          finish_playlist_name_input(app);
+        }
+      }
     }
     Key::Char(c) => {
       app.input.insert(app.input_idx, c);
       app.input_idx += 1;
-      app.input_cursor_position += compute_character_width(c);
     }
+    // Deleting takes a whole grapheme, so no half of an accented letter or emoji is left behind
     Key::Backspace | Key::Ctrl('h') => {
-      if !app.input.is_empty() && app.input_idx > 0 {
-        let last_c = app.input.remove(app.input_idx - 1);
-        app.input_idx -= 1;
-        app.input_cursor_position -= compute_character_width(last_c);
-      }
+      let start = previous_grapheme_start(&app.input, app.input_idx);
+      app.input.drain(start..app.input_idx);
+      app.input_idx = start;
     }
     Key::Delete | Key::Ctrl('d') => {
-      if !app.input.is_empty() && app.input_idx < app.input.len() {
-        app.input.remove(app.input_idx);
-      }
+      let end = next_grapheme_start(&app.input, app.input_idx);
+      app.input.drain(app.input_idx..end);
     }
     _ => {}
   }
 }
 
+// Up/Down step through earlier searches and Ctrl-R searches them. Returns whether the key was
+// used for that.
+fn history_handler(key: Key, app: &mut App) -> bool {
+  let entries = app.search_history.entries();
+  if let Some(search) = app.reverse_search.as_mut() {
+    match key {
+      Key::Ctrl('r') => {
+        // Look further back, staying on the current match when there is nothing older
+        let before = search.matched.unwrap_or(entries.len());
+        if let Some(index) = app.search_history.find(&search.query, before) {
+          search.matched = Some(index);
+        }
+      }
+      Key::Char(c) => {
+        search.query.push(c);
+        let before = search.matched.map_or(entries.len(), |index| index + 1);
+        search.matched = app.search_history.find(&search.query, before);
+      }
+      Key::Backspace | Key::Ctrl('h') => {
+        search.query.pop();
+        search.matched = if search.query.is_empty() {
+          None
+        } else {
+          app.search_history.find(&search.query, entries.len())
+        };
+      }
+      Key::Esc | Key::Ctrl('g') => {
+        let draft = search.draft.clone();
+        app.reverse_search = None;
+        set_input(app, &draft);
+        return true;
+      }
+      // Anything else keeps the match in the search box, and Enter searches for it
+      _ => {
+        app.reverse_search = None;
+        return false;
+      }
+    }
+    if let Some(entry) = search.matched.map(|index| entries[index].clone()) {
+      set_input(app, &entry);
+    }
+    return true;
+  }
+
+  match key {
+    Key::Ctrl('r') => {
+      app.search_history_recall = None;
+      app.reverse_search = Some(ReverseSearch {
+        query: String::new(),
+        matched: None,
+        draft: app.input.iter().collect(),
+      });
+    }
+    Key::Up => {
+      let recall = match app.search_history_recall.take() {
+        Some(recall) => HistoryRecall {
+          index: recall.index.saturating_sub(1),
+          ..recall
+        },
+        None if !entries.is_empty() => HistoryRecall {
+          index: entries.len() - 1,
+          draft: app.input.iter().collect(),
+        },
+        None => return true,
+      };
+      let entry = entries[recall.index].clone();
+      set_input(app, &entry);
+      app.search_history_recall = Some(recall);
+    }
+    Key::Down => match app.search_history_recall.take() {
+      Some(recall) if recall.index + 1 < entries.len() => {
+        let entry = entries[recall.index + 1].clone();
+        set_input(app, &entry);
+        app.search_history_recall = Some(HistoryRecall {
+          index: recall.index + 1,
+          ..recall
+        });
+      }
+      // Past the newest search, back to what was being typed
+      Some(recall) => set_input(app, &recall.draft),
+      None => {}
+    },
+    _ => {
+      app.search_history_recall = None;
+      return false;
+    }
+  }
+  true
+}
+
+fn set_input(app: &mut App, text: &str) {
+  app.input = text.chars().collect();
+  app.input_idx = app.input.len();
+}
+
 fn process_input(app: &mut App, input: String) {
   // Don't do anything if there is no input
   if input.is_empty() {
     return;
   }
+  app.search_history.record(&input);
 
   // On searching for a track, clear the playlist selection
   app.selected_playlist_index = Some(0);
 
-  if attempt_process_uri(app, &input, "https://open.spotify.com/", "/")
-    || attempt_process_uri(app, &input, "spotify:", ":")
-  {
+  // A pasted link opens what it points to instead of searching for it
+  if open_link(app, &input) {
+    app.input = vec![];
+    app.input_idx = 0;
+    app.input_cursor_position = 0;
     return;
   }
 
   // Default fallback behavior: treat the input as a raw search phrase.
-  app.dispatch(IoEvent::GetSearchResults(input));
+  app.dispatch(IoEvent::GetSearchResults(input.clone()));
   
   // Clear the input field after search
   app.input = vec![];
@@ -126,12 +264,35 @@ fn process_input(app: &mut App, input: String) {
   // This ensures keyboard input goes to search results, not the input field
   app.set_current_route_state(Some(ActiveBlock::SearchResultBlock), Some(ActiveBlock::SearchResultBlock));
   
-  // Focus on Songs search result panel for quick navigation
-  app.search_results.selected_block = super::super::app::SearchResultBlock::SongSearch;
-  app.search_results.hovered_block = super::super::app::SearchResultBlock::SongSearch;
-  
-  // Use focus manager to track this properly
-  app.enter_component(ComponentId::SearchResults(super::super::app::SearchResultBlock::SongSearch));
+  // Focus on Songs search result panel for quick navigation, or the first kind of result an
+  // `@type` prefix or field filter narrowed the search to
+  let block = match search_filter::parse(&input).types.first() {
+    Some(SearchType::Artist) => SearchResultBlock::ArtistSearch,
+    Some(SearchType::Album) => SearchResultBlock::AlbumSearch,
+    Some(SearchType::Playlist) => SearchResultBlock::PlaylistSearch,
+    Some(SearchType::Show) => SearchResultBlock::ShowSearch,
+    _ => SearchResultBlock::SongSearch,
+  };
+  app.search_results.selected_block = block;
+  app.search_results.hovered_block = block;
+}
+
+/// Show what a Spotify link or URI points to: an album, artist, playlist or show, or the album of
+/// a track. Returns false when `link` isn't one that can be shown.
+pub fn open_link(app: &mut App, link: &str) -> bool {
+  match open_in::link_uri(link) {
+    Some(uri) => attempt_process_uri(app, &uri, "spotify:", ":"),
+    None => false,
+  }
+}
+
+// Give the input box back to search and return to the playlists
+fn finish_playlist_name_input(app: &mut App) {
+  app.input_purpose = InputPurpose::Search;
+  app.input = vec![];
+  app.input_idx = 0;
+  app.input_cursor_position = 0;
+  app.set_current_route_state(Some(ActiveBlock::MyPlaylists), Some(ActiveBlock::MyPlaylists));
 }
 
 fn spotify_resource_id(base: &str, uri: &str, sep: &str, resource_type: &str) -> (String, bool) {
@@ -151,42 +312,74 @@ fn attempt_process_uri(app: &mut App, input: &str, base: &str, sep: &str) -> boo
   let (album_id, matched) = spotify_resource_id(base, input, sep, "album");
   if matched {
     app.dispatch(IoEvent::GetAlbum(album_id));
+    app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
     return true;
   }
 
   let (artist_id, matched) = spotify_resource_id(base, input, sep, "artist");
   if matched {
     app.get_artist(artist_id, "".to_string());
-    app.push_navigation_stack(RouteId::Artist, ActiveBlock::ArtistBlock);
+    app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
     return true;
   }
 
   let (track_id, matched) = spotify_resource_id(base, input, sep, "track");
   if matched {
     app.dispatch(IoEvent::GetAlbumForTrack(track_id));
+    app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
     return true;
   }
 
   let (playlist_id, matched) = spotify_resource_id(base, input, sep, "playlist");
   if matched {
     app.dispatch(IoEvent::GetPlaylistTracks(playlist_id.to_string(), 0));
+    app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
     return true;
   }
 
   let (show_id, matched) = spotify_resource_id(base, input, sep, "show");
   if matched {
     app.dispatch(IoEvent::GetShow(show_id));
+    app.push_loading_route(RouteId::PodcastEpisodes, ActiveBlock::EpisodeTable);
     return true;
   }
 
   false
 }
 
-fn compute_character_width(character: char) -> u16 {
-  UnicodeWidthChar::width(character)
-    .unwrap()
-    .try_into()
-    .unwrap()
+// The cursor goes after the cells taken by the text before it, which for wide characters isn't
+// the number of characters
+fn update_cursor_position(app: &mut App) {
+  app.input_idx = app.input_idx.min(app.input.len());
+  let before_cursor: String = app.input[..app.input_idx].iter().collect();
+  app.input_cursor_position = display_width(&before_cursor);
+}
+
+// Indices into the input where graphemes start, followed by its length
+fn grapheme_starts(input: &[char]) -> Vec<usize> {
+  let text: String = input.iter().collect();
+  let mut starts = vec![0];
+  let mut index = 0;
+  for grapheme in text.graphemes(true) {
+    index += grapheme.chars().count();
+    starts.push(index);
+  }
+  starts
+}
+
+fn previous_grapheme_start(input: &[char], index: usize) -> usize {
+  grapheme_starts(input)
+    .into_iter()
+    .rev()
+    .find(|&start| start < index)
+    .unwrap_or(0)
+}
+
+fn next_grapheme_start(input: &[char], index: usize) -> usize {
+  grapheme_starts(input)
+    .into_iter()
+    .find(|&start| start > index)
+    .unwrap_or(input.len())
 }
 
 #[cfg(test)]
@@ -198,10 +391,51 @@ mod tests {
   }
 
   #[test]
-  fn test_compute_character_width_with_multiple_characters() {
-    assert_eq!(1, compute_character_width('a'));
-    assert_eq!(1, compute_character_width('ß'));
-    assert_eq!(1, compute_character_width('ç'));
+  fn test_display_width_with_multiple_characters() {
+    assert_eq!(1, display_width("a"));
+    assert_eq!(1, display_width("ß"));
+    assert_eq!(1, display_width("ç"));
+    assert_eq!(2, display_width("日"));
+    // Family: man, woman, girl joined into one emoji
+    assert_eq!(2, display_width("👨\u{200d}👩\u{200d}👧"));
+  }
+
+  #[test]
+  fn test_paste_inserts_at_the_cursor() {
+    let mut app = App::default();
+    app.input = str_to_vec_char("play  now");
+    app.input_idx = 5;
+
+    paste_handler("日本\tsongs\r\n", &mut app);
+    assert_eq!(app.input, str_to_vec_char("play 日本 songs now"));
+    assert_eq!(app.input_idx, 13);
+    assert_eq!(app.input_cursor_position, 15);
+    // Still typing, the line ending didn't search
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::Empty);
+  }
+
+  #[test]
+  fn test_input_handler_moves_by_grapheme() {
+    let mut app = App::default();
+    for c in "日本e\u{301}".chars() {
+      handler(Key::Char(c), &mut app);
+    }
+    assert_eq!(app.input_idx, 4);
+    assert_eq!(app.input_cursor_position, 5);
+
+    // The accented e is two characters but one cell
+    handler(Key::Left, &mut app);
+    assert_eq!(app.input_idx, 2);
+    assert_eq!(app.input_cursor_position, 4);
+    handler(Key::Left, &mut app);
+    assert_eq!(app.input_cursor_position, 2);
+
+    handler(Key::Delete, &mut app);
+    assert_eq!(app.input, str_to_vec_char("日e\u{301}"));
+    handler(Key::End, &mut app);
+    handler(Key::Backspace, &mut app);
+    assert_eq!(app.input, str_to_vec_char("日"));
+    assert_eq!(app.input_cursor_position, 2);
   }
 
   #[test]
@@ -312,6 +546,61 @@ mod tests {
     assert_eq!(app.input, str_to_vec_char("My text"));
   }
 
+  #[test]
+  fn test_input_handler_waits_to_search() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
+
+    handler(Key::Char('a'), &mut app);
+    assert!(app.pending_search.is_some());
+
+    handler(Key::Enter, &mut app);
+    assert!(app.pending_search.is_none());
+    assert_eq!(app.get_current_route().id, RouteId::Search);
+
+    app.user_config.behavior.search_delay_milliseconds = 0;
+    app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
+    handler(Key::Char('a'), &mut app);
+    assert!(app.pending_search.is_none());
+  }
+
+  #[test]
+  fn test_input_handler_search_history() {
+    let mut app = App::default();
+    app.search_history.record("bowie");
+    app.search_history.record("eno");
+    app.input = str_to_vec_char("kr");
+
+    handler(Key::Up, &mut app);
+    handler(Key::Up, &mut app);
+    assert_eq!(app.input, str_to_vec_char("bowie"));
+    assert_eq!(app.input_cursor_position, 5);
+    handler(Key::Down, &mut app);
+    handler(Key::Down, &mut app);
+    assert_eq!(app.input, str_to_vec_char("kr"));
+
+    handler(Key::Ctrl('r'), &mut app);
+    handler(Key::Char('w'), &mut app);
+    assert_eq!(app.input, str_to_vec_char("bowie"));
+    handler(Key::Esc, &mut app);
+    assert_eq!(app.input, str_to_vec_char("kr"));
+    assert!(app.reverse_search.is_none());
+  }
+
+  #[test]
+  fn test_input_handler_enter_pasted_link() {
+    let mut app = App::default();
+
+    app.input =
+      str_to_vec_char("https://open.spotify.com/intl-fr/show/3aNsrV6lkzmcU1w8u8kA7N?si=1");
+    handler(Key::Enter, &mut app);
+
+    let route = app.get_current_route();
+    assert_eq!(route.id, RouteId::PodcastEpisodes);
+    assert!(route.loading);
+    assert!(app.input.is_empty());
+  }
+
   #[test]
   fn test_input_handler_backspace() {
     let mut app = App::default();
@@ -1,9 +1,10 @@
 use super::{
-  super::app::{ActiveBlock, App, RouteId, LIBRARY_OPTIONS},
+  super::app::{ActiveBlock, App, LibraryOption, RouteId, LIBRARY_OPTIONS},
   common_key_events,
 };
 use crate::event::Key;
 use crate::network::IoEvent;
+use std::time::Instant;
 
 pub fn handler(key: Key, app: &mut App) {
   match key {
@@ -12,12 +13,16 @@ pub fn handler(key: Key, app: &mut App) {
       let next_index = common_key_events::on_down_press_handler(
         &LIBRARY_OPTIONS,
         Some(app.library.selected_index),
+        app.motion_count,
       );
       app.library.selected_index = next_index;
     }
     k if common_key_events::up_event(k) => {
-      let next_index =
-        common_key_events::on_up_press_handler(&LIBRARY_OPTIONS, Some(app.library.selected_index));
+      let next_index = common_key_events::on_up_press_handler(
+        &LIBRARY_OPTIONS,
+        Some(app.library.selected_index),
+        app.motion_count,
+      );
       app.library.selected_index = next_index;
     }
     k if common_key_events::high_event(k) => {
@@ -32,47 +37,64 @@ pub fn handler(key: Key, app: &mut App) {
       let next_index = common_key_events::on_low_press_handler(&LIBRARY_OPTIONS);
       app.library.selected_index = next_index
     }
-    // `library` should probably be an array of structs with enums rather than just using indexes
-    // like this
-    Key::Enter => match app.library.selected_index {
-      // Recently Played,
-      0 => {
-        app.dispatch(IoEvent::GetRecentlyPlayed);
-        app.push_navigation_stack(RouteId::RecentlyPlayed, ActiveBlock::RecentlyPlayed);
-      }
-      // Liked Songs,
-      1 => {
-        app.dispatch(IoEvent::GetCurrentSavedTracks(None));
-        app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
-      }
-      // Albums,
-      2 => {
-        app.dispatch(IoEvent::GetCurrentUserSavedAlbums(None));
-        app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
-      }
-      //  Artists,
-      3 => {
-        app.dispatch(IoEvent::GetFollowedArtists(None));
-        app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
-      }
-      // Podcasts,
-      4 => {
-        app.dispatch(IoEvent::GetCurrentUserSavedShows(None));
-        app.push_navigation_stack(RouteId::Podcasts, ActiveBlock::Podcasts);
-      }
-      // Top Tracks,
-      5 => {
-        app.dispatch(IoEvent::GetTopTracks);
-        app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
-      }
-      // Top Artists,
-      6 => {
-        app.dispatch(IoEvent::GetTopArtists);
-        app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
-      }
-      // This is required because Rust can't tell if this pattern in exhaustive
-      _ => {}
-    },
+    Key::Enter => open_option(app, app.library.selected_index),
     _ => (),
   };
 }
+
+/// Open the library option at `index` in `LIBRARY_OPTIONS`, loading what it shows
+pub fn open_option(app: &mut App, index: usize) {
+  match LibraryOption::from_index(index) {
+    Some(LibraryOption::RecentlyPlayed) => {
+      app.dispatch(IoEvent::GetRecentlyPlayed);
+      app.instant_since_last_recently_played_poll = Instant::now();
+      app.push_navigation_stack(RouteId::RecentlyPlayed, ActiveBlock::RecentlyPlayed);
+    }
+    Some(LibraryOption::LikedSongs) => {
+      app.dispatch(IoEvent::GetCurrentSavedTracks(None));
+      app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    }
+    Some(LibraryOption::Albums) => {
+      app.dispatch(IoEvent::GetCurrentUserSavedAlbums(None));
+      app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    }
+    Some(LibraryOption::Artists) => {
+      app.dispatch(IoEvent::GetFollowedArtists(None));
+      app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
+    }
+    Some(LibraryOption::Podcasts) => {
+      app.dispatch(IoEvent::GetCurrentUserSavedShows(None));
+      app.push_navigation_stack(RouteId::Podcasts, ActiveBlock::Podcasts);
+    }
+    Some(LibraryOption::TopTracks) => {
+      app.dispatch(IoEvent::GetTopTracks);
+      app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    }
+    Some(LibraryOption::TopArtists) => {
+      app.dispatch(IoEvent::GetTopArtists);
+      app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
+    }
+    Some(LibraryOption::MadeForYou) => {
+      app.dispatch(IoEvent::GetMadeForYouPlaylists);
+      app.push_loading_route(RouteId::MadeForYou, ActiveBlock::MadeForYou);
+    }
+    Some(LibraryOption::NewReleases) => {
+      app.dispatch(IoEvent::GetNewReleases(None));
+      app.push_loading_route(RouteId::NewReleases, ActiveBlock::NewReleases);
+    }
+    Some(LibraryOption::FeaturedPlaylists) => {
+      app.dispatch(IoEvent::GetFeaturedPlaylists);
+      app.push_loading_route(RouteId::FeaturedPlaylists, ActiveBlock::FeaturedPlaylists);
+    }
+    Some(LibraryOption::Categories) => {
+      app.dispatch(IoEvent::GetCategories);
+      app.push_loading_route(RouteId::Categories, ActiveBlock::Categories);
+    }
+    Some(LibraryOption::Profile) => {
+      // Fetched again, so the follower count is current
+      app.dispatch(IoEvent::GetUser);
+      app.push_loading_route(RouteId::Profile, ActiveBlock::Profile);
+    }
+    None => {}
+  }
+}
@@ -5,77 +5,89 @@ use super::{
 use crate::event::Key;
 
 pub fn handler(key: Key, app: &mut App) {
+  let shown = app.shown_log_messages().len();
   match key {
-    k if common_key_events::down_event(k) => {
-      if !app.log_messages.is_empty() {
-        let new_index = if app.log_stream_selected_index < app.log_messages.len() - 1 {
-          app.log_stream_selected_index + 1
-        } else {
-          app.log_stream_selected_index
-        };
-        app.log_stream_selected_index = new_index;
-        
-        // Update scroll offset to keep selection visible
-        update_scroll_offset(app);
-      }
+    k if common_key_events::down_event(k) && shown > 0 => {
+      app.log_stream_selected_index =
+        (app.log_stream_selected_index + app.motion_count).min(shown - 1);
+
+      // Update scroll offset to keep selection visible
+      update_scroll_offset(app);
     }
-    k if common_key_events::up_event(k) => {
-      if !app.log_messages.is_empty() && app.log_stream_selected_index > 0 {
-        app.log_stream_selected_index -= 1;
-        
-        // Update scroll offset to keep selection visible
-        update_scroll_offset(app);
-      }
+    k if common_key_events::up_event(k) && shown > 0 && app.log_stream_selected_index > 0 => {
+      app.log_stream_selected_index = app
+        .log_stream_selected_index
+        .saturating_sub(app.motion_count);
+
+      // Update scroll offset to keep selection visible
+      update_scroll_offset(app);
     }
-    k if common_key_events::high_event(k) => {
-      // Jump to top
-      if !app.log_messages.is_empty() {
-        app.log_stream_selected_index = 0;
-        app.log_stream_scroll_offset = 0;
-      }
+    // Jump to top
+    k if common_key_events::high_event(k) && shown > 0 => {
+      app.log_stream_selected_index = 0;
+      app.log_stream_scroll_offset = 0;
     }
-    k if common_key_events::low_event(k) => {
-      // Jump to bottom
-      if !app.log_messages.is_empty() {
-        app.log_stream_selected_index = app.log_messages.len() - 1;
-        update_scroll_offset(app);
-      }
+    // Jump to bottom
+    k if common_key_events::low_event(k) && shown > 0 => {
+      app.log_stream_selected_index = shown - 1;
+      update_scroll_offset(app);
     }
-    Key::PageUp => {
-      // Page up
-      if !app.log_messages.is_empty() {
-        let page_size = 10; // Adjust based on visible height if needed
-        app.log_stream_selected_index = app.log_stream_selected_index.saturating_sub(page_size);
-        update_scroll_offset(app);
-      }
+    Key::PageUp if shown > 0 => {
+      let page_size = 10; // Adjust based on visible height if needed
+      app.log_stream_selected_index = app.log_stream_selected_index.saturating_sub(page_size);
+      update_scroll_offset(app);
     }
-    Key::PageDown => {
-      // Page down
-      if !app.log_messages.is_empty() {
-        let page_size = 10; // Adjust based on visible height if needed
-        let max_index = app.log_messages.len() - 1;
-        app.log_stream_selected_index = std::cmp::min(
-          app.log_stream_selected_index + page_size,
-          max_index
-        );
-        update_scroll_offset(app);
-      }
+    Key::PageDown if shown > 0 => {
+      let page_size = 10; // Adjust based on visible height if needed
+      let max_index = shown - 1;
+      app.log_stream_selected_index =
+        std::cmp::min(app.log_stream_selected_index + page_size, max_index);
+      update_scroll_offset(app);
     }
+    Key::Char('f') => app.cycle_log_stream_level(),
+    k if k == app.user_config.keys.search => app.log_stream_search_input = true,
+    k if k == app.user_config.keys.copy_now_playing => app.copy_selected_log_message(),
     _ => {}
   }
 }
 
+/// Typing into the log stream's search, which filters the stream as it changes
+pub fn search_handler(key: Key, app: &mut App) {
+  match key {
+    Key::Enter => {
+      app.log_stream_search_input = false;
+      return;
+    }
+    Key::Esc => {
+      app.log_stream_search_input = false;
+      app.log_stream_search.clear();
+    }
+    Key::Backspace => {
+      // Like the command line, backspacing over an empty search closes it
+      if app.log_stream_search.pop().is_none() {
+        app.log_stream_search_input = false;
+        return;
+      }
+    }
+    Key::Ctrl('u') => app.log_stream_search.clear(),
+    Key::Char(c) => app.log_stream_search.push(c),
+    _ => return,
+  }
+  app.scroll_log_stream_to_end();
+}
+
 fn update_scroll_offset(app: &mut App) {
   // Assume a reasonable visible height for now (could be passed as parameter)
   let visible_height = 10; // This should ideally be calculated from layout
-  
-  if app.log_messages.is_empty() {
+
+  let shown = app.shown_log_messages().len();
+  if shown == 0 {
     return;
   }
-  
+
   let selected = app.log_stream_selected_index;
   let scroll_offset = app.log_stream_scroll_offset;
-  
+
   // If selection is above visible area, scroll up
   if selected < scroll_offset {
     app.log_stream_scroll_offset = selected;
@@ -84,10 +96,46 @@ fn update_scroll_offset(app: &mut App) {
   else if selected >= scroll_offset + visible_height {
     app.log_stream_scroll_offset = selected.saturating_sub(visible_height - 1);
   }
-  
+
   // Ensure scroll offset doesn't exceed bounds
-  let max_scroll = app.log_messages.len().saturating_sub(visible_height);
+  let max_scroll = shown.saturating_sub(visible_height);
   if app.log_stream_scroll_offset > max_scroll {
     app.log_stream_scroll_offset = max_scroll;
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::LogLevel;
+
+  #[test]
+  fn search_and_level_filter_the_stream() {
+    let mut app = App::default();
+    app.add_log_message("Playing Teardrop".to_string());
+    app.log(LogLevel::Warn, "Failed to fetch album art".to_string());
+    app.log(LogLevel::Debug, "Found 2 devices".to_string());
+    // Debug messages are hidden to begin with
+    assert_eq!(app.shown_log_messages().len(), 2);
+
+    handler(Key::Char('f'), &mut app);
+    assert_eq!(app.shown_log_messages().len(), 1);
+    assert_eq!(app.log_stream_selected_index, 0);
+
+    handler(Key::Char('f'), &mut app);
+    handler(Key::Char('f'), &mut app);
+    assert_eq!(app.log_stream_level, LogLevel::Debug);
+    assert_eq!(app.shown_log_messages().len(), 3);
+
+    handler(Key::Char('/'), &mut app);
+    assert!(app.log_stream_search_input);
+    for c in "DEVICES".chars() {
+      search_handler(Key::Char(c), &mut app);
+    }
+    search_handler(Key::Enter, &mut app);
+    assert!(!app.log_stream_search_input);
+    let shown = app.shown_log_messages();
+    assert_eq!(shown.len(), 1);
+    assert!(shown[0].text.ends_with("DEBUG Found 2 devices"));
+  }
+}
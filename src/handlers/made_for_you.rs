@@ -0,0 +1,68 @@
+use super::common_key_events;
+use crate::{
+  app::{ActiveBlock, App, RouteId, TrackTableContext},
+  event::Key,
+  network::IoEvent,
+};
+
+pub fn handler(key: Key, app: &mut App) {
+  let playlists = &app.library.made_for_you_playlists;
+  match key {
+    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
+    k if common_key_events::down_event(k) && !playlists.is_empty() => {
+      let next_index = common_key_events::on_down_press_handler(
+        playlists,
+        Some(app.made_for_you_index),
+        app.motion_count,
+      );
+      app.made_for_you_index = next_index;
+    }
+    k if common_key_events::up_event(k) && !playlists.is_empty() => {
+      let next_index = common_key_events::on_up_press_handler(
+        playlists,
+        Some(app.made_for_you_index),
+        app.motion_count,
+      );
+      app.made_for_you_index = next_index;
+    }
+    k if common_key_events::high_event(k) && !playlists.is_empty() => {
+      app.made_for_you_index = common_key_events::on_high_press_handler();
+    }
+    k if common_key_events::middle_event(k) && !playlists.is_empty() => {
+      app.made_for_you_index = common_key_events::on_middle_press_handler(playlists);
+    }
+    k if common_key_events::low_event(k) && !playlists.is_empty() => {
+      app.made_for_you_index = common_key_events::on_low_press_handler(playlists);
+    }
+    Key::Enter => {
+      if let Some(playlist) = playlists.get(app.made_for_you_index) {
+        let playlist_id = playlist.id.to_string();
+        app.track_table.context = Some(TrackTableContext::MadeForYou);
+        app.dispatch(IoEvent::GetMadeForYouPlaylistTracks(playlist_id));
+        app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+      }
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_keys_before_playlists_load() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::MadeForYou, ActiveBlock::MadeForYou);
+
+    handler(Key::Down, &mut app);
+    handler(Key::Enter, &mut app);
+    assert_eq!(app.made_for_you_index, 0);
+    assert_eq!(app.get_current_route().id, RouteId::MadeForYou);
+
+    handler(Key::Left, &mut app);
+    let current_route = app.get_current_route();
+    assert_eq!(current_route.active_block, ActiveBlock::Empty);
+    assert_eq!(current_route.hovered_block, ActiveBlock::Library);
+  }
+}
@@ -4,31 +4,92 @@ mod analysis;
 mod artist;
 mod artists;
 mod basic_view;
+mod breadcrumb;
+mod categories;
+mod category_playlists;
+mod command;
 mod common_key_events;
 mod dialog;
+mod duplicates;
 mod empty;
 mod episode_table;
+mod featured_playlists;
+mod help;
 mod home;
 mod input;
 mod library;
 mod log_stream;
+mod made_for_you;
+mod new_releases;
 mod playbar;
 mod playlist;
 mod podcasts;
+mod profile;
 mod recently_played;
 mod search_results;
 mod select_device;
 mod track_table;
 
-use super::app::{ActiveBlock, App, ArtistBlock, RouteId, SearchResultBlock};
+use super::app::{ActiveBlock, App, ArtistBlock, RouteId, SearchResultBlock, TopTimeRange};
 use crate::event::Key;
-use crate::focus_manager::ComponentId;
+use crate::jump_list;
 use crate::network::IoEvent;
+use crate::workspace;
 use rspotify::model::{context::CurrentPlaybackContext, PlayableItem};
 use crate::network::PlayingItem;
 use std::time::Instant;
 
-pub use input::handler as input_handler;
+pub use breadcrumb::{click as breadcrumb_click, handler as breadcrumb_handler};
+pub use command::handler as command_handler;
+pub use dialog::{open_in_handler, playlist_picker_handler, share_handler};
+pub use help::handler as help_handler;
+pub use input::{handler as input_handler, open_link, paste_handler as input_paste_handler};
+pub use library::open_option as open_library_option;
+pub use log_stream::search_handler as log_stream_search_handler;
+pub use playlist::filter_handler as playlist_filter_handler;
+
+// Upper bound for count prefixes so a stray run of digits can't stall the UI
+const MAX_COUNT: usize = 999;
+
+/// Handle a key press with vim style count prefixes: in list blocks, digits typed before `j`/`k`
+/// move that many rows in one step, any other key discards the count.
+pub fn handle_app_with_count(key: Key, app: &mut App) {
+  // The top views use 1/2/3 to pick their time range instead
+  if app.pending_count.is_none()
+    && app.is_top_view_active()
+    && TopTimeRange::from_key(key).is_some()
+  {
+    handle_app(key, app);
+    return;
+  }
+  if let Key::Char(digit @ '0'..='9') = key {
+    // A leading zero isn't a count, and only lists take one
+    if (digit != '0' || app.pending_count.is_some())
+      && common_key_events::is_list_block(app.get_current_route().active_block)
+    {
+      let value = digit.to_digit(10).unwrap_or(0) as usize;
+      let count = app.pending_count.unwrap_or(0) * 10 + value;
+      app.pending_count = Some(count.min(MAX_COUNT));
+      return;
+    }
+  }
+
+  let count = app.pending_count.take().unwrap_or(1);
+  if common_key_events::is_count_motion(key) {
+    app.motion_count = count;
+  }
+  handle_app(key, app);
+  app.motion_count = 1;
+}
+
+fn is_device_picker_active(app: &App) -> bool {
+  app.get_current_route().active_block == ActiveBlock::SelectDevice
+}
+
+// The log stream searches and copies its own lines
+fn is_log_stream_active(app: &App) -> bool {
+  app.get_current_route().active_block == ActiveBlock::LogStream
+}
 
 pub fn handle_app(key: Key, app: &mut App) {
   // First handle any global event and then move to block event
@@ -39,17 +100,14 @@ pub fn handle_app(key: Key, app: &mut App) {
     // Component entry shortcuts - enter components directly for internal navigation
     Key::Char('L') | Key::Char('l') => {
       app.clear_navigation_stack();
-      app.enter_component(ComponentId::Library);
       app.set_current_route_state(Some(ActiveBlock::Library), Some(ActiveBlock::Library));
     }
     Key::Char('P') | Key::Char('p') => {
       app.clear_navigation_stack();
-      app.enter_component(ComponentId::MyPlaylists);
       app.set_current_route_state(Some(ActiveBlock::MyPlaylists), Some(ActiveBlock::MyPlaylists));
     }
     Key::Char('S') | Key::Char('s') => {
       app.clear_navigation_stack();
-      app.enter_component(ComponentId::SearchInput);
       app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
       // Clear any existing search results focus to avoid dual focus
       app.search_results.selected_block = SearchResultBlock::Empty;
@@ -58,13 +116,28 @@ pub fn handle_app(key: Key, app: &mut App) {
     Key::Char('D') => {
       app.set_current_route_state(Some(ActiveBlock::SelectDevice), Some(ActiveBlock::SelectDevice));
     }
-    Key::Char('O') => {
+    Key::Ctrl('l') => {
       app.push_navigation_stack(RouteId::LogStream, ActiveBlock::LogStream);
     }
-    Key::Ctrl('l') => {
+    Key::Alt(c @ '1'..='9') => workspace::switch(app, c as usize - '1' as usize),
+    _ if key == app.user_config.keys.log_stream => {
       app.push_navigation_stack(RouteId::LogStream, ActiveBlock::LogStream);
     }
-    Key::Char('F') | Key::Char('f') => {
+    _ if key == app.user_config.keys.debug_overlay => {
+      app.show_debug_overlay = !app.show_debug_overlay;
+    }
+    _ if key == app.user_config.keys.help => help::toggle(app),
+    _ if key == app.user_config.keys.jump_back => jump_list::back(app),
+    _ if key == app.user_config.keys.jump_forward => jump_list::forward(app),
+    _ if key == app.user_config.keys.breadcrumb => breadcrumb::start(app),
+    _ if key == app.user_config.keys.split_view => {
+      app.split_view = !app.split_view;
+      if app.split_view && app.split_view_routes().is_none() {
+        app.show_toast("Split view on, open something to see it side by side".to_string());
+      }
+    }
+    _ if key == app.user_config.keys.switch_pane => app.switch_split_pane(),
+    _ if key == app.user_config.keys.toggle_idle_mode => {
       // Toggle fullscreen/idle mode
       app.is_idle_mode = !app.is_idle_mode;
       // Reset idle timer to prevent automatic idle mode from interfering
@@ -84,23 +157,24 @@ pub fn handle_app(key: Key, app: &mut App) {
         app.add_log_message("Exited fullscreen album art mode".to_string());
       }
     }
-    Key::Char('v') | Key::Char('V') => {
-      // Change visual mode in idle mode
-      if app.is_idle_mode {
-        use crate::app::IdleAnimation;
-        // Update the last interaction time to prevent auto-exit from idle mode
-        app.last_user_interaction = Instant::now();
-        app.idle_animation = match app.idle_animation {
-          IdleAnimation::SpinningRecord => {
-            app.add_log_message("Switched to coin-flip animation".to_string());
-            IdleAnimation::CoinFlip
-          }
-          IdleAnimation::CoinFlip => {
-            app.add_log_message("Switched to spinning record animation".to_string());
-            IdleAnimation::SpinningRecord
-          }
-        };
-      }
+    _ if key == app.user_config.keys.switch_idle_animation && app.is_idle_mode => {
+      use crate::app::IdleAnimation;
+      // Update the last interaction time to prevent auto-exit from idle mode
+      app.last_user_interaction = Instant::now();
+      app.idle_animation = match app.idle_animation {
+        IdleAnimation::SpinningRecord => {
+          app.add_log_message("Switched to coin-flip animation".to_string());
+          IdleAnimation::CoinFlip
+        }
+        IdleAnimation::CoinFlip => {
+          app.add_log_message("Switched to split art and track layout".to_string());
+          IdleAnimation::Split
+        }
+        IdleAnimation::Split => {
+          app.add_log_message("Switched to spinning record animation".to_string());
+          IdleAnimation::SpinningRecord
+        }
+      };
     }
     _ if key == app.user_config.keys.jump_to_album => {
       handle_jump_to_album(app);
@@ -118,12 +192,22 @@ pub fn handle_app(key: Key, app: &mut App) {
       // Then fetch the devices
       app.dispatch(IoEvent::GetDevices);
     }
-    _ if key == app.user_config.keys.decrease_volume => {
+    // The device picker changes the volume of the highlighted device instead
+    _ if key == app.user_config.keys.decrease_volume && !is_device_picker_active(app) => {
       app.decrease_volume();
     }
-    _ if key == app.user_config.keys.increase_volume => {
+    _ if key == app.user_config.keys.increase_volume && !is_device_picker_active(app) => {
       app.increase_volume();
     }
+    _ if key == app.user_config.keys.mute => {
+      app.toggle_mute();
+    }
+    _ if key == app.user_config.keys.loop_start => {
+      app.mark_loop_start();
+    }
+    _ if key == app.user_config.keys.loop_end => {
+      app.mark_loop_end();
+    }
     // Press space to toggle playback
     _ if key == app.user_config.keys.toggle_playback => {
       app.toggle_playback();
@@ -147,28 +231,91 @@ pub fn handle_app(key: Key, app: &mut App) {
     _ if key == app.user_config.keys.repeat => {
       app.repeat();
     }
-    _ if key == app.user_config.keys.search => {
+    _ if key == app.user_config.keys.search && !is_log_stream_active(app) => {
       app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
       // Clear any existing search results focus to avoid dual focus
       app.search_results.selected_block = SearchResultBlock::Empty;
       app.search_results.hovered_block = SearchResultBlock::Empty;
     }
-    _ if key == app.user_config.keys.copy_song_url => {
-      app.copy_song_url();
+    _ if key == app.user_config.keys.like_current_track => {
+      app.toggle_like_current_track();
+    }
+    _ if key == app.user_config.keys.follow_current_artist => {
+      app.toggle_follow_current_artist();
+    }
+    _ if key == app.user_config.keys.open_in => {
+      app.open_open_in_menu();
     }
-    _ if key == app.user_config.keys.copy_album_url => {
-      app.copy_album_url();
+    _ if key == app.user_config.keys.share => {
+      app.open_share_menu();
+    }
+    _ if key == app.user_config.keys.copy_now_playing && !is_log_stream_active(app) => {
+      app.copy_now_playing();
     }
     _ if key == app.user_config.keys.audio_analysis => {
       app.get_audio_analysis();
     }
+    _ if key == app.user_config.keys.audio_features => {
+      app.toggle_audio_features();
+    }
+    _ if key == app.user_config.keys.sleep_timer => {
+      app.cycle_sleep_timer();
+    }
     _ if key == app.user_config.keys.basic_view => {
       app.push_navigation_stack(RouteId::BasicView, ActiveBlock::BasicView);
     }
+    _ if key == app.user_config.keys.command_line => {
+      app.command_line = Some(Default::default());
+    }
     _ => handle_block_events(key, app),
   }
 }
 
+/// Run an action reached through a chord. Chord-only actions go straight to the focused block
+/// so global keys can't shadow them; any other action returns its bound key to be handled as a
+/// normal key press.
+pub fn handle_chord_action(action: &str, app: &mut App) -> Option<Key> {
+  match action {
+    "top" => {
+      handle_block_top(app);
+      None
+    }
+    "delete" => {
+      handle_block_delete(app);
+      None
+    }
+    _ => app
+      .user_config
+      .keys
+      .bindings()
+      .into_iter()
+      .find(|(name, _)| *name == action)
+      .map(|(_, key)| key),
+  }
+}
+
+// The `top` chord goes through each block's own `H` handling
+fn handle_block_top(app: &mut App) {
+  handle_block_events(Key::Char('H'), app);
+}
+
+// The `delete` chord: delete, unfollow or remove the selected item of the focused block. `D` on
+// its own opens the devices, so this is the only way to it
+fn handle_block_delete(app: &mut App) {
+  match app.get_current_route().active_block {
+    ActiveBlock::AlbumList => app.current_user_saved_album_delete(ActiveBlock::AlbumList),
+    ActiveBlock::ArtistBlock => artist::handle_delete_event(app),
+    ActiveBlock::Artists => app.user_unfollow_artists(ActiveBlock::AlbumList),
+    ActiveBlock::Duplicates => duplicates::open_remove_duplicates_dialog(app),
+    ActiveBlock::EpisodeTable => episode_table::handle_unfollow_event(app),
+    ActiveBlock::MyPlaylists => playlist::handle_delete_event(app),
+    ActiveBlock::Podcasts => app.user_unfollow_show(ActiveBlock::Podcasts),
+    ActiveBlock::SearchResultBlock => search_results::handle_delete_event(app),
+    ActiveBlock::TrackTable => track_table::open_remove_from_playlist_dialog(app),
+    _ => {}
+  }
+}
+
 // Handle event for the current active block
 fn handle_block_events(key: Key, app: &mut App) {
   let current_route = app.get_current_route();
@@ -221,6 +368,24 @@ fn handle_block_events(key: Key, app: &mut App) {
     ActiveBlock::Podcasts => {
       podcasts::handler(key, app);
     }
+    ActiveBlock::MadeForYou => {
+      made_for_you::handler(key, app);
+    }
+    ActiveBlock::NewReleases => {
+      new_releases::handler(key, app);
+    }
+    ActiveBlock::FeaturedPlaylists => {
+      featured_playlists::handler(key, app);
+    }
+    ActiveBlock::Categories => {
+      categories::handler(key, app);
+    }
+    ActiveBlock::CategoryPlaylists => {
+      category_playlists::handler(key, app);
+    }
+    ActiveBlock::Profile => {
+      profile::handler(key, app);
+    }
     ActiveBlock::PlayBar => {
       playbar::handler(key, app);
     }
@@ -230,6 +395,12 @@ fn handle_block_events(key: Key, app: &mut App) {
     ActiveBlock::LogStream => {
       log_stream::handler(key, app);
     }
+    ActiveBlock::Duplicates => {
+      duplicates::handler(key, app);
+    }
+    ActiveBlock::ColorStory => {
+      // Only there to look at, Esc goes back
+    }
     ActiveBlock::Error => {
       // Error screen no longer exists, do nothing
     }
@@ -242,8 +413,6 @@ fn handle_block_events(key: Key, app: &mut App) {
 fn handle_escape(app: &mut App) {
   match app.get_current_route().active_block {
     ActiveBlock::SearchResultBlock => {
-      // Clear search results focus using centralized system
-      app.clear_all_focus();
       app.search_results.selected_block = SearchResultBlock::Empty;
       app.search_results.hovered_block = SearchResultBlock::Empty;
     }
@@ -251,23 +420,22 @@ fn handle_escape(app: &mut App) {
       if let Some(artist) = &mut app.artist {
         artist.artist_selected_block = ArtistBlock::Empty;
       }
-      app.clear_focus();
+    }
+    // Leave visual mode before leaving the table
+    ActiveBlock::TrackTable if app.track_table.visual_anchor.is_some() => {
+      app.track_table.visual_anchor = None;
     }
     ActiveBlock::Dialog(_) => {
       app.pop_navigation_stack();
     }
     // These are global views that have no active/inactive distinction
-    ActiveBlock::SelectDevice => {
-      app.pop_navigation_stack();
-    }
-    ActiveBlock::Analysis => {
-      app.clear_focus();
-    }
-    ActiveBlock::LogStream => {
+    ActiveBlock::SelectDevice
+    | ActiveBlock::Analysis
+    | ActiveBlock::LogStream
+    | ActiveBlock::ColorStory => {
       app.pop_navigation_stack();
     }
     _ => {
-      app.clear_all_focus();
       app.set_current_route_state(Some(ActiveBlock::Empty), None);
     }
   }
@@ -318,7 +486,7 @@ fn handle_jump_to_artist_album(app: &mut App) {
           let artist_id = artist.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string());
           if !artist_id.is_empty() {
             app.get_artist(artist_id, artist.name.clone());
-            app.push_navigation_stack(RouteId::Artist, ActiveBlock::ArtistBlock);
+            app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
           }
         }
       }
@@ -328,3 +496,111 @@ fn handle_jump_to_artist_album(app: &mut App) {
     }
   };
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::{Dialog, DialogContext, LIBRARY_OPTIONS};
+  use crate::event::{ChordEvent, ChordState};
+
+  #[test]
+  fn test_count_prefix_repeats_motion() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Library), Some(ActiveBlock::Library));
+
+    handle_app_with_count(Key::Char('3'), &mut app);
+    assert_eq!(app.pending_count, Some(3));
+    handle_app_with_count(Key::Char('j'), &mut app);
+    assert_eq!(app.library.selected_index, 3);
+    assert_eq!(app.pending_count, None);
+
+    // Counts larger than the list still land inside it
+    handle_app_with_count(Key::Char('1'), &mut app);
+    handle_app_with_count(Key::Char('0'), &mut app);
+    handle_app_with_count(Key::Char('k'), &mut app);
+    assert_eq!(app.library.selected_index, 0);
+    assert_eq!(app.motion_count, 1);
+    handle_app_with_count(Key::Char('2'), &mut app);
+    handle_app_with_count(Key::Char('0'), &mut app);
+    handle_app_with_count(Key::Char('j'), &mut app);
+    assert_eq!(app.library.selected_index, LIBRARY_OPTIONS.len() - 1);
+  }
+
+  #[test]
+  fn test_digits_outside_lists_are_not_a_count() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::PlayBar), Some(ActiveBlock::PlayBar));
+    handle_app_with_count(Key::Char('5'), &mut app);
+    assert_eq!(app.pending_count, None);
+  }
+
+  #[test]
+  fn test_leading_zero_is_not_a_count() {
+    let mut app = App::default();
+    handle_app_with_count(Key::Char('0'), &mut app);
+    assert_eq!(app.pending_count, None);
+  }
+
+  #[test]
+  fn test_digits_switch_top_time_range() {
+    let mut app = App::default();
+    app.showing_top_artists = true;
+    app.set_current_route_state(Some(ActiveBlock::Artists), Some(ActiveBlock::Artists));
+
+    handle_app_with_count(Key::Char('1'), &mut app);
+    assert_eq!(app.pending_count, None);
+    assert_eq!(app.top_time_range, TopTimeRange::FourWeeks);
+    handle_app_with_count(Key::Char('3'), &mut app);
+    assert_eq!(app.top_time_range, TopTimeRange::AllTime);
+
+    // Followed artists keep using digits as counts
+    app.showing_top_artists = false;
+    handle_app_with_count(Key::Char('2'), &mut app);
+    assert_eq!(app.pending_count, Some(2));
+    assert_eq!(app.top_time_range, TopTimeRange::AllTime);
+  }
+
+  #[test]
+  fn test_default_delete_chord_opens_dialog() {
+    let mut app = App::default();
+    app.playlists = serde_json::from_value(serde_json::json!({
+      "href": "",
+      "items": [{
+        "collaborative": false,
+        "external_urls": {},
+        "href": "",
+        "id": "37i9dQZF1DXcBWIGoYBM5M",
+        "images": [],
+        "name": "Today's Top Hits",
+        "owner": { "external_urls": {}, "href": "", "id": "spotify" },
+        "snapshot_id": "",
+        "tracks": { "href": "", "total": 50 },
+      }],
+      "limit": 50,
+      "offset": 0,
+      "total": 1,
+    }))
+    .unwrap();
+    app.selected_playlist_index = Some(0);
+    app.set_current_route_state(
+      Some(ActiveBlock::MyPlaylists),
+      Some(ActiveBlock::MyPlaylists),
+    );
+
+    let mut chord_state = ChordState::new();
+    let chords = app.user_config.chords.clone();
+    let mut action = None;
+    for key in [app.user_config.keys.leader, Key::Char('d')] {
+      if let ChordEvent::Action(name) = chord_state.handle_key(key, &chords) {
+        action = Some(name);
+      }
+    }
+    handle_chord_action(&action.unwrap(), &mut app);
+
+    assert!(matches!(app.dialog, Some(Dialog::UnfollowPlaylist { .. })));
+    assert_eq!(
+      app.get_current_route().active_block,
+      ActiveBlock::Dialog(DialogContext::Confirm)
+    );
+  }
+}
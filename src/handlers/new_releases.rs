@@ -0,0 +1,97 @@
+use super::common_key_events;
+use crate::{
+  app::{ActiveBlock, App, RouteId, TrackTableContext},
+  event::Key,
+  network::IoEvent,
+  open_in,
+};
+
+pub fn handler(key: Key, app: &mut App) {
+  let albums = match app.new_releases.get_results(None) {
+    Some(page) if !page.items.is_empty() => &page.items,
+    _ => {
+      if common_key_events::left_event(key) {
+        common_key_events::handle_left_event(app);
+      }
+      return;
+    }
+  };
+  match key {
+    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
+    k if common_key_events::down_event(k) => {
+      let next_index = common_key_events::on_down_press_handler(
+        albums,
+        Some(app.new_releases_index),
+        app.motion_count,
+      );
+      app.new_releases_index = next_index;
+    }
+    k if common_key_events::up_event(k) => {
+      let next_index = common_key_events::on_up_press_handler(
+        albums,
+        Some(app.new_releases_index),
+        app.motion_count,
+      );
+      app.new_releases_index = next_index;
+    }
+    k if common_key_events::high_event(k) => {
+      app.new_releases_index = common_key_events::on_high_press_handler();
+    }
+    k if common_key_events::middle_event(k) => {
+      app.new_releases_index = common_key_events::on_middle_press_handler(albums);
+    }
+    k if common_key_events::low_event(k) => {
+      app.new_releases_index = common_key_events::on_low_press_handler(albums);
+    }
+    // Open the album's tracks
+    Key::Enter => {
+      if let Some(album_id) = selected_album_id(app) {
+        app.track_table.context = Some(TrackTableContext::AlbumSearch);
+        app.dispatch(IoEvent::GetAlbum(album_id));
+        app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+      }
+    }
+    // Play the whole album
+    Key::Char('e') => {
+      if let Some(album_id) = selected_album_id(app) {
+        let album_uri = open_in::uri("album", &album_id);
+        app.dispatch(IoEvent::StartPlayback(Some(album_uri), None));
+      }
+    }
+    Key::Char('w') => {
+      if let Some(album_id) = selected_album_id(app) {
+        app.dispatch(IoEvent::ToggleSaveAlbum(album_id));
+      }
+    }
+    k if k == app.user_config.keys.next_page => app.get_new_releases_next(),
+    k if k == app.user_config.keys.previous_page => app.get_new_releases_previous(),
+    _ => {}
+  }
+}
+
+fn selected_album_id(app: &App) -> Option<String> {
+  let album = app.selected_new_release()?;
+  album.id.as_ref().map(|id| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_keys_before_releases_load() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::NewReleases, ActiveBlock::NewReleases);
+
+    handler(Key::Down, &mut app);
+    handler(Key::Enter, &mut app);
+    handler(app.user_config.keys.next_page, &mut app);
+    assert_eq!(app.new_releases_index, 0);
+    assert_eq!(app.get_current_route().id, RouteId::NewReleases);
+
+    handler(Key::Left, &mut app);
+    let current_route = app.get_current_route();
+    assert_eq!(current_route.active_block, ActiveBlock::Empty);
+    assert_eq!(current_route.hovered_block, ActiveBlock::Library);
+  }
+}
@@ -1,10 +1,11 @@
 use super::{
-  super::app::{App, DialogContext, TrackTableContext},
+  super::app::{App, Dialog, InputPurpose, TrackTableContext},
   common_key_events,
 };
 use crate::app::{ActiveBlock, RouteId};
 use crate::event::Key;
 use crate::network::IoEvent;
+use crate::ui::util::display_width;
 
 pub fn handler(key: Key, app: &mut App) {
   match key {
@@ -13,8 +14,11 @@ pub fn handler(key: Key, app: &mut App) {
       match &app.playlists {
         Some(p) => {
           if let Some(selected_playlist_index) = app.selected_playlist_index {
-            let next_index =
-              common_key_events::on_down_press_handler(&p.items, Some(selected_playlist_index));
+            let next_index = common_key_events::on_down_press_handler(
+              &p.items,
+              Some(selected_playlist_index),
+              app.motion_count,
+            );
             app.selected_playlist_index = Some(next_index);
           }
         }
@@ -24,8 +28,11 @@ pub fn handler(key: Key, app: &mut App) {
     k if common_key_events::up_event(k) => {
       match &app.playlists {
         Some(p) => {
-          let next_index =
-            common_key_events::on_up_press_handler(&p.items, app.selected_playlist_index);
+          let next_index = common_key_events::on_up_press_handler(
+            &p.items,
+            app.selected_playlist_index,
+            app.motion_count,
+          );
           app.selected_playlist_index = Some(next_index);
         }
         None => {}
@@ -58,36 +65,142 @@ pub fn handler(key: Key, app: &mut App) {
         None => {}
       };
     }
-    Key::Enter => {
-      if let (Some(playlists), Some(selected_playlist_index)) =
-        (&app.playlists, &app.selected_playlist_index)
-      {
-        app.active_playlist_index = Some(selected_playlist_index.to_owned());
-        app.track_table.context = Some(TrackTableContext::MyPlaylists);
-        app.playlist_offset = 0;
-        if let Some(selected_playlist) = playlists.items.get(selected_playlist_index.to_owned()) {
-          let playlist_id = selected_playlist.id.to_owned();
-          app.dispatch(IoEvent::GetPlaylistTracks(playlist_id.to_string(), app.playlist_offset));
-          // Navigate to the track table view to show the playlist tracks
-          app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    Key::Enter => open_selected_playlist(app),
+    _ if key == app.user_config.keys.sort_playlists => app.cycle_playlist_sort(),
+    _ if key == app.user_config.keys.pin_playlist => app.toggle_pinned_playlist(),
+    // Narrow the list by typing, see `filter_handler`
+    Key::Char('f') if app.playlists.is_some() => app.playlist_filter = Some(String::new()),
+    // Ask for a name in the input box, then create the playlist
+    Key::Char('N') => {
+      app.input_purpose = InputPurpose::NewPlaylistName;
+      app.input = vec![];
+      app.input_idx = 0;
+      app.input_cursor_position = 0;
+      app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
+    }
+    // Edit the name of the selected playlist in the input box, starting from the current one
+    Key::Char('R') => {
+      let selected = app
+        .playlists
+        .as_ref()
+        .zip(app.selected_playlist_index)
+        .and_then(|(playlists, index)| playlists.items.get(index))
+        .map(|playlist| {
+          let owned = app
+            .user
+            .as_ref()
+            .map(|user| user.id == playlist.owner.id)
+            .unwrap_or(false);
+          (playlist.id.to_string(), playlist.name.clone(), owned)
+        });
+      match selected {
+        Some((playlist_id, name, true)) => {
+          app.input_purpose = InputPurpose::RenamePlaylist(playlist_id);
+          app.input = name.chars().collect();
+          app.input_idx = app.input.len();
+          app.input_cursor_position = display_width(&name);
+          app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
         }
-      };
+        Some((_, name, false)) => {
+          app.show_toast(format!("Only the owner can rename {}", name));
+        }
+        None => {}
+      }
+    }
+    _ => {}
+  }
+}
+
+// Delete, unfollow or remove the selected item, for the delete chord
+pub fn handle_delete_event(app: &mut App) {
+  if let (Some(playlists), Some(selected_index)) = (&app.playlists, app.selected_playlist_index) {
+    let selected_playlist = &playlists.items[selected_index];
+    let dialog = Dialog::UnfollowPlaylist {
+      id: selected_playlist.id.to_string(),
+      name: selected_playlist.name.clone(),
+    };
+    app.open_dialog(dialog);
+  }
+}
+
+fn open_selected_playlist(app: &mut App) {
+  if let (Some(playlists), Some(selected_playlist_index)) =
+    (&app.playlists, &app.selected_playlist_index)
+  {
+    let selected_playlist_index = *selected_playlist_index;
+    if let Some(selected_playlist) = playlists.items.get(selected_playlist_index) {
+      let playlist_id = selected_playlist.id.to_owned();
+      // Navigate to the track table view to show the playlist tracks, before it changes over so
+      // the jump list notes what it showed
+      app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+      app.active_playlist_index = Some(selected_playlist_index);
+      app.track_table.context = Some(TrackTableContext::MyPlaylists);
+      app.playlist_offset = 0;
+      app.dispatch(IoEvent::GetPlaylistTracks(
+        playlist_id.to_string(),
+        app.playlist_offset,
+      ));
     }
-    Key::Char('D') => {
-      if let (Some(playlists), Some(selected_index)) = (&app.playlists, app.selected_playlist_index)
-      {
-        let selected_playlist = &playlists.items[selected_index].name;
-        app.dialog = Some(selected_playlist.clone());
-        app.confirm = false;
+  };
+}
+
+/// Handle a key press while the sidebar filter is open. Typing narrows the playlists fuzzily and
+/// keeps the best match selected, Enter opens the selected one.
+pub fn filter_handler(key: Key, app: &mut App) {
+  let filter = match app.playlist_filter.as_mut() {
+    Some(filter) => filter,
+    None => return,
+  };
 
-        app.push_navigation_stack(
-          RouteId::Dialog,
-          ActiveBlock::Dialog(DialogContext::PlaylistWindow),
-        );
+  match key {
+    Key::Esc => {
+      app.playlist_filter = None;
+      return;
+    }
+    Key::Enter => {
+      app.playlist_filter = None;
+      open_selected_playlist(app);
+      return;
+    }
+    Key::Down | Key::Ctrl('n') => {
+      move_filter_selection(app, 1);
+      return;
+    }
+    Key::Up | Key::Ctrl('p') => {
+      move_filter_selection(app, -1);
+      return;
+    }
+    Key::Backspace => {
+      // Like the command line, backspacing over an empty filter closes it
+      if filter.pop().is_none() {
+        app.playlist_filter = None;
+        return;
       }
     }
-    _ => {}
+    Key::Ctrl('u') => filter.clear(),
+    Key::Char(c) => filter.push(c),
+    _ => return,
+  }
+
+  if let Some(&best) = app.playlist_filter_matches().first() {
+    app.selected_playlist_index = Some(best);
+  }
+}
+
+/// Step the selection through the filtered playlists, wrapping around at either end
+fn move_filter_selection(app: &mut App, step: isize) {
+  let matches = app.playlist_filter_matches();
+  if matches.is_empty() {
+    return;
   }
+  let position = app
+    .selected_playlist_index
+    .and_then(|selected| matches.iter().position(|&index| index == selected));
+  let next = match position {
+    Some(position) => (position as isize + step).rem_euclid(matches.len() as isize) as usize,
+    None => 0,
+  };
+  app.selected_playlist_index = Some(matches[next]);
 }
 
 #[cfg(test)]
@@ -0,0 +1,28 @@
+use super::common_key_events;
+use crate::{app::App, event::Key};
+
+pub fn handler(key: Key, app: &mut App) {
+  if common_key_events::left_event(key) {
+    common_key_events::handle_left_event(app);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::{ActiveBlock, RouteId};
+
+  #[test]
+  fn on_left_press() {
+    let mut app = App::default();
+    app.push_loading_route(RouteId::Profile, ActiveBlock::Profile);
+
+    handler(Key::Down, &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::Profile);
+
+    handler(Key::Left, &mut app);
+    let current_route = app.get_current_route();
+    assert_eq!(current_route.active_block, ActiveBlock::Empty);
+    assert_eq!(current_route.hovered_block, ActiveBlock::Library);
+  }
+}
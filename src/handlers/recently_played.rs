@@ -1,5 +1,5 @@
 use super::{super::app::App, common_key_events};
-use crate::{app::RecommendationsContext, event::Key, network::IoEvent};
+use crate::{app::RecommendationsContext, event::Key, network::IoEvent, open_in};
 
 pub fn handler(key: Key, app: &mut App) {
   match key {
@@ -9,6 +9,7 @@ pub fn handler(key: Key, app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &recently_played_result.items,
           Some(app.recently_played.index),
+          app.motion_count,
         );
         app.recently_played.index = next_index;
       }
@@ -18,6 +19,7 @@ pub fn handler(key: Key, app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &recently_played_result.items,
           Some(app.recently_played.index),
+          app.motion_count,
         );
         app.recently_played.index = next_index;
       }
@@ -52,19 +54,9 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Enter => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         if let Some(selected_item) = recently_played_result.items.get(app.recently_played.index) {
-          // Play the specific track
-          let track_uri = selected_item.track.id.as_ref().map(|id| {
-            let id_str = id.to_string();
-            if id_str.starts_with("spotify:track:") {
-              id_str
-            } else {
-              format!("spotify:track:{}", id_str)
-            }
-          });
-          
-          if let Some(uri) = track_uri {
-            // Start playback with just the track URI (no context)
-            app.dispatch(IoEvent::StartPlayback(Some(uri.clone()), None));
+          // Play the specific track, with just the track URI (no context)
+          if let Some(uri) = open_in::track_uri(&selected_item.track) {
+            app.dispatch(IoEvent::StartPlayback(Some(uri), None));
           }
         }
       };
@@ -86,15 +78,9 @@ pub fn handler(key: Key, app: &mut App) {
     _ if key == app.user_config.keys.add_item_to_queue => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         if let Some(history) = recently_played_result.items.get(app.recently_played.index) {
-          let track_uri = history.track.id.as_ref().map(|id| {
-            let id_str = id.to_string();
-            if id_str.starts_with("spotify:track:") {
-              id_str
-            } else {
-              format!("spotify:track:{}", id_str)
-            }
-          }).unwrap_or_else(|| "".to_string());
-          app.dispatch(IoEvent::AddItemToQueue(track_uri))
+          if let Some(uri) = open_in::track_uri(&history.track) {
+            app.dispatch(IoEvent::AddItemToQueue(uri))
+          }
         }
       };
     }
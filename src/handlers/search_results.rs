@@ -1,12 +1,12 @@
 use super::{
   super::app::{
-    ActiveBlock, App, DialogContext, RecommendationsContext, RouteId, SearchResultBlock,
-    TrackTableContext,
+    ActiveBlock, App, Dialog, RecommendationsContext, RouteId, SearchResultBlock, TrackTableContext,
   },
   common_key_events,
 };
 use crate::event::Key;
 use crate::network::IoEvent;
+use crate::open_in;
 
 fn handle_down_press_on_selected_block(app: &mut App) {
   // Start selecting within the selected block
@@ -16,6 +16,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &result.items,
           app.search_results.selected_album_index,
+          app.motion_count,
         );
         app.search_results.selected_album_index = Some(next_index);
       }
@@ -25,6 +26,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &result.items,
           app.search_results.selected_tracks_index,
+          app.motion_count,
         );
         app.search_results.selected_tracks_index = Some(next_index);
       }
@@ -34,6 +36,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &result.items,
           app.search_results.selected_artists_index,
+          app.motion_count,
         );
         app.search_results.selected_artists_index = Some(next_index);
       }
@@ -43,6 +46,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &result.items,
           app.search_results.selected_playlists_index,
+          app.motion_count,
         );
         app.search_results.selected_playlists_index = Some(next_index);
       }
@@ -52,6 +56,7 @@ fn handle_down_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_down_press_handler(
           &result.items,
           app.search_results.selected_shows_index,
+          app.motion_count,
         );
         app.search_results.selected_shows_index = Some(next_index);
       }
@@ -89,6 +94,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &result.items,
           app.search_results.selected_album_index,
+          app.motion_count,
         );
         app.search_results.selected_album_index = Some(next_index);
       }
@@ -98,6 +104,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &result.items,
           app.search_results.selected_tracks_index,
+          app.motion_count,
         );
         app.search_results.selected_tracks_index = Some(next_index);
       }
@@ -107,6 +114,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &result.items,
           app.search_results.selected_artists_index,
+          app.motion_count,
         );
         app.search_results.selected_artists_index = Some(next_index);
       }
@@ -116,6 +124,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &result.items,
           app.search_results.selected_playlists_index,
+          app.motion_count,
         );
         app.search_results.selected_playlists_index = Some(next_index);
       }
@@ -125,6 +134,7 @@ fn handle_up_press_on_selected_block(app: &mut App) {
         let next_index = common_key_events::on_up_press_handler(
           &result.items,
           app.search_results.selected_shows_index,
+          app.motion_count,
         );
         app.search_results.selected_shows_index = Some(next_index);
       }
@@ -269,8 +279,7 @@ fn handle_add_item_to_queue(app: &mut App) {
         app.search_results.selected_tracks_index,
         &app.search_results.tracks,
       ) {
-        if let Some(track) = tracks.items.get(index) {
-          let uri = format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
+        if let Some(uri) = tracks.items.get(index).and_then(open_in::track_uri) {
           app.dispatch(IoEvent::AddItemToQueue(uri));
         }
       }
@@ -297,23 +306,35 @@ fn handle_enter_event_on_selected_block(app: &mut App) {
       }
     }
     SearchResultBlock::SongSearch => {
-      let index = app.search_results.selected_tracks_index;
-      let tracks = app.search_results.tracks.clone();
-      let track_uris = tracks.map(|tracks| {
-        tracks
-          .items
-          .into_iter()
-          .map(|track| format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string())))
-          .collect::<Vec<String>>()
-      });
-      app.dispatch(IoEvent::StartPlayback(None, None));
+      let (index, tracks) = match (
+        app.search_results.selected_tracks_index,
+        &app.search_results.tracks,
+      ) {
+        (Some(index), Some(tracks)) => (index, tracks),
+        _ => return,
+      };
+      // Play the whole result list so next/previous move through it. Rows without an ID can't
+      // be played, so they are left out and the offset counts only the playable rows before ours
+      if !matches!(tracks.items.get(index), Some(track) if track.id.is_some()) {
+        return;
+      }
+      let offset = tracks.items[..index]
+        .iter()
+        .filter(|track| track.id.is_some())
+        .count();
+      let track_uris = tracks
+        .items
+        .iter()
+        .filter_map(open_in::track_uri)
+        .collect::<Vec<String>>();
+      app.dispatch(IoEvent::PlayTrackList(track_uris, offset));
     }
     SearchResultBlock::ArtistSearch => {
       if let Some(index) = &app.search_results.selected_artists_index {
         if let Some(result) = app.search_results.artists.clone() {
           if let Some(artist) = result.items.get(index.to_owned()) {
             app.get_artist(artist.id.to_string(), artist.name.clone());
-            app.push_navigation_stack(RouteId::Artist, ActiveBlock::ArtistBlock);
+            app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
           };
         };
       };
@@ -328,6 +349,7 @@ fn handle_enter_event_on_selected_block(app: &mut App) {
           app.track_table.context = Some(TrackTableContext::PlaylistSearch);
           let playlist_id = playlist.id.to_owned();
           app.dispatch(IoEvent::GetPlaylistTracks(playlist_id.to_string(), app.playlist_offset));
+          app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
         };
       }
     }
@@ -491,6 +513,8 @@ pub fn handler(key: Key, app: &mut App) {
         handle_low_press_on_selected_block(app)
       }
     }
+    k if k == app.user_config.keys.next_page => app.change_search_results_page(true),
+    k if k == app.user_config.keys.previous_page => app.change_search_results_page(false),
     // Handle pressing enter when block is selected to start playing track
     Key::Enter => match app.search_results.selected_block {
       SearchResultBlock::Empty => handle_enter_event_on_hovered_block(app),
@@ -512,33 +536,35 @@ pub fn handler(key: Key, app: &mut App) {
       SearchResultBlock::ShowSearch => app.user_follow_show(ActiveBlock::SearchResultBlock),
       SearchResultBlock::Empty => {}
     },
-    Key::Char('D') => match app.search_results.selected_block {
-      SearchResultBlock::AlbumSearch => {
-        app.current_user_saved_album_delete(ActiveBlock::SearchResultBlock)
-      }
-      SearchResultBlock::SongSearch => {}
-      SearchResultBlock::ArtistSearch => app.user_unfollow_artists(ActiveBlock::SearchResultBlock),
-      SearchResultBlock::PlaylistSearch => {
-        if let (Some(playlists), Some(selected_index)) = (
-          &app.search_results.playlists,
-          app.search_results.selected_playlists_index,
-        ) {
-          let selected_playlist = &playlists.items[selected_index].name;
-          app.dialog = Some(selected_playlist.clone());
-          app.confirm = false;
-
-          app.push_navigation_stack(
-            RouteId::Dialog,
-            ActiveBlock::Dialog(DialogContext::PlaylistSearch),
-          );
-        }
-      }
-      SearchResultBlock::ShowSearch => app.user_unfollow_show(ActiveBlock::SearchResultBlock),
-      SearchResultBlock::Empty => {}
-    },
     Key::Char('r') => handle_recommended_tracks(app),
     _ if key == app.user_config.keys.add_item_to_queue => handle_add_item_to_queue(app),
     // Add `s` to "see more" on each option
     _ => {}
   }
 }
+
+// Delete, unfollow or remove the selected item, for the delete chord
+pub fn handle_delete_event(app: &mut App) {
+  match app.search_results.selected_block {
+    SearchResultBlock::AlbumSearch => {
+      app.current_user_saved_album_delete(ActiveBlock::SearchResultBlock)
+    }
+    SearchResultBlock::SongSearch => {}
+    SearchResultBlock::ArtistSearch => app.user_unfollow_artists(ActiveBlock::SearchResultBlock),
+    SearchResultBlock::PlaylistSearch => {
+      if let (Some(playlists), Some(selected_index)) = (
+        &app.search_results.playlists,
+        app.search_results.selected_playlists_index,
+      ) {
+        let selected_playlist = &playlists.items[selected_index];
+        let dialog = Dialog::UnfollowPlaylist {
+          id: selected_playlist.id.to_string(),
+          name: selected_playlist.name.clone(),
+        };
+        app.open_dialog(dialog);
+      }
+    }
+    SearchResultBlock::ShowSearch => app.user_unfollow_show(ActiveBlock::SearchResultBlock),
+    SearchResultBlock::Empty => {}
+  }
+}
@@ -4,6 +4,7 @@ use super::{
 };
 use crate::event::Key;
 use crate::network::IoEvent;
+use std::cmp::min;
 
 pub fn handler(key: Key, app: &mut App) {
   match key {
@@ -11,8 +12,11 @@ pub fn handler(key: Key, app: &mut App) {
       match &app.devices {
         Some(p) => {
           if let Some(selected_device_index) = app.selected_device_index {
-            let next_index =
-              common_key_events::on_down_press_handler(&p.devices, Some(selected_device_index));
+            let next_index = common_key_events::on_down_press_handler(
+              &p.devices,
+              Some(selected_device_index),
+              app.motion_count,
+            );
             app.selected_device_index = Some(next_index);
           }
         }
@@ -23,8 +27,11 @@ pub fn handler(key: Key, app: &mut App) {
       match &app.devices {
         Some(p) => {
           if let Some(selected_device_index) = app.selected_device_index {
-            let next_index =
-              common_key_events::on_up_press_handler(&p.devices, Some(selected_device_index));
+            let next_index = common_key_events::on_up_press_handler(
+              &p.devices,
+              Some(selected_device_index),
+              app.motion_count,
+            );
             app.selected_device_index = Some(next_index);
           }
         }
@@ -67,10 +74,54 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Enter => {
       if let (Some(devices), Some(index)) = (&app.devices, app.selected_device_index) {
         if let Some(device) = &devices.devices.get(index) {
-          app.dispatch(IoEvent::TransferPlaybackToDevice(device.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string())));
+          app.dispatch(IoEvent::TransferPlaybackToDevice(
+            device
+              .id
+              .as_ref()
+              .map(|id| id.to_string())
+              .unwrap_or_else(|| "".to_string()),
+          ));
         }
       };
     }
+    k if k == app.user_config.keys.toggle_streaming => app.toggle_streaming(),
+    k if k == app.user_config.keys.decrease_volume => change_device_volume(app, false),
+    k if k == app.user_config.keys.increase_volume => change_device_volume(app, true),
     _ => {}
   }
 }
+
+/// Step the highlighted device's volume by `volume_increment`, showing it in the list right away
+fn change_device_volume(app: &mut App, increase: bool) {
+  let increment = u32::from(app.user_config.behavior.volume_increment);
+  let is_free_tier = app.is_free_tier();
+  let device = match (&mut app.devices, app.selected_device_index) {
+    (Some(devices), Some(index)) => match devices.devices.get_mut(index) {
+      Some(device) => device,
+      None => return,
+    },
+    _ => return,
+  };
+  if device.is_restricted {
+    let message = format!("{} doesn't let its volume be changed", device.name);
+    app.show_toast(message);
+    return;
+  }
+  let device_id = match &device.id {
+    Some(id) => id.to_string(),
+    None => return,
+  };
+  let current_volume = device.volume_percent.unwrap_or(50);
+  let next_volume = if increase {
+    min(current_volume + increment, 100)
+  } else {
+    current_volume.saturating_sub(increment)
+  };
+  if next_volume == current_volume {
+    return;
+  }
+  if !is_free_tier {
+    device.volume_percent = Some(next_volume);
+  }
+  app.dispatch(IoEvent::SetDeviceVolume(device_id, next_volume as u8));
+}
@@ -1,9 +1,10 @@
 use super::{
-  super::app::{App, RecommendationsContext, TrackTable, TrackTableContext},
+  super::app::{App, Dialog, RecommendationsContext, TopTimeRange, TrackTable, TrackTableContext},
   common_key_events,
 };
 use crate::event::Key;
-use crate::network::IoEvent;
+use crate::network::{IoEvent, PlaybackOffset};
+use crate::open_in;
 use rand::{thread_rng, Rng};
 use serde_json::from_value;
 
@@ -14,6 +15,7 @@ pub fn handler(key: Key, app: &mut App) {
       let next_index = common_key_events::on_down_press_handler(
         &app.track_table.tracks,
         Some(app.track_table.selected_index),
+        app.motion_count,
       );
       app.track_table.selected_index = next_index;
     }
@@ -21,6 +23,7 @@ pub fn handler(key: Key, app: &mut App) {
       let next_index = common_key_events::on_up_press_handler(
         &app.track_table.tracks,
         Some(app.track_table.selected_index),
+        app.motion_count,
       );
       app.track_table.selected_index = next_index;
     }
@@ -65,6 +68,11 @@ pub fn handler(key: Key, app: &mut App) {
           }
           TrackTableContext::AlbumSearch => {}
           TrackTableContext::PlaylistSearch => {}
+          TrackTableContext::TopTracks => {}
+          // The whole playlist is loaded at once
+          TrackTableContext::MadeForYou
+          | TrackTableContext::FeaturedPlaylists
+          | TrackTableContext::CategoryPlaylists => {}
         },
         None => {}
       };
@@ -94,11 +102,24 @@ pub fn handler(key: Key, app: &mut App) {
           }
           TrackTableContext::AlbumSearch => {}
           TrackTableContext::PlaylistSearch => {}
+          TrackTableContext::TopTracks => {}
+          // The whole playlist is loaded at once
+          TrackTableContext::MadeForYou
+          | TrackTableContext::FeaturedPlaylists
+          | TrackTableContext::CategoryPlaylists => {}
         },
         None => {}
       };
     }
+    // Mark a range of tracks, like vim's linewise visual mode
+    k if k == app.user_config.keys.visual_mode => {
+      app.track_table.visual_anchor = match app.track_table.visual_anchor {
+        Some(_) => None,
+        None => Some(app.track_table.selected_index),
+      };
+    }
     Key::Char('s') => handle_save_track_event(app),
+    k if k == app.user_config.keys.save_track => handle_save_track_event(app),
     Key::Char('S') => play_random_song(app),
     k if k == app.user_config.keys.jump_to_end => jump_to_end(app),
     k if k == app.user_config.keys.jump_to_start => jump_to_start(app),
@@ -106,7 +127,30 @@ pub fn handler(key: Key, app: &mut App) {
     Key::Char('r') => {
       handle_recommended_tracks(app);
     }
-    _ if key == app.user_config.keys.add_item_to_queue => on_queue(app),
+    _ if key == app.user_config.keys.add_item_to_queue => {
+      if app.track_table.visual_anchor.is_some() {
+        let uris = selected_track_uris(app);
+        app.track_table.visual_anchor = None;
+        app.dispatch(IoEvent::AddItemsToQueue(uris));
+      } else {
+        on_queue(app);
+      }
+    }
+    _ if key == app.user_config.keys.play_next => on_play_next(app),
+    k if app.track_table.context == Some(TrackTableContext::TopTracks)
+      && TopTimeRange::from_key(k).is_some() =>
+    {
+      if let Some(time_range) = TopTimeRange::from_key(k) {
+        app.top_time_range = time_range;
+        app.dispatch(IoEvent::GetTopTracks);
+      }
+    }
+    _ if key == app.user_config.keys.add_to_playlist => {
+      let uris = selected_track_uris(app);
+      app.open_playlist_picker(uris);
+    }
+    // `D` comes from the `delete` chord
+    _ if key == app.user_config.keys.remove_from_playlist => open_remove_from_playlist_dialog(app),
     _ => {}
   }
 }
@@ -135,22 +179,21 @@ fn play_random_song(app: &mut App) {
           app.dispatch(IoEvent::StartPlayback(context_uri, None));
         }
       }
-      TrackTableContext::RecommendedTracks => {}
+      TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists => {
+        if let Some(playlist) = app.viewed_playlist() {
+          let context_uri = open_in::uri("playlist", &playlist.id.to_string());
+          app.dispatch(IoEvent::StartPlayback(Some(context_uri), None));
+        }
+      }
+      TrackTableContext::RecommendedTracks | TrackTableContext::TopTracks => {}
       TrackTableContext::SavedTracks => {
         if let Some(saved_tracks) = &app.library.saved_tracks.get_results(None) {
           if !saved_tracks.items.is_empty() {
             let rand_idx = thread_rng().gen_range(0..saved_tracks.items.len());
             if let Some(saved_track) = saved_tracks.items.get(rand_idx) {
-              let track_uri = saved_track.track.id.as_ref().map(|id| {
-                let id_str = id.to_string();
-                if id_str.starts_with("spotify:track:") {
-                  id_str
-                } else {
-                  format!("spotify:track:{}", id_str)
-                }
-              });
-              
-              if let Some(uri) = track_uri {
+              if let Some(uri) = open_in::track_uri(&saved_track.track) {
                 app.dispatch(IoEvent::StartPlayback(Some(uri), None));
               }
             }
@@ -187,14 +230,49 @@ fn play_random_song(app: &mut App) {
   };
 }
 
-fn handle_save_track_event(app: &mut App) {
-  let (selected_index, tracks) = (&app.track_table.selected_index, &app.track_table.tracks);
-  if let Some(track) = tracks.get(*selected_index) {
-    if let Some(id) = &track.id {
-      let id = id.to_string();
-      app.dispatch(IoEvent::ToggleSaveTrack(id));
-    };
+/// URIs of the marked tracks in visual mode, or of the track under the cursor
+pub fn selected_track_uris(app: &App) -> Vec<String> {
+  app
+    .track_table
+    .selected_tracks()
+    .iter()
+    .filter_map(open_in::track_uri)
+    .collect()
+}
+
+pub fn open_remove_from_playlist_dialog(app: &mut App) {
+  let playlist = match app.viewed_playlist() {
+    Some(playlist) => playlist,
+    None => return,
+  };
+  if !app.can_edit_playlist(playlist) {
+    let message = format!("Only the owner can remove tracks from {}", playlist.name);
+    app.show_toast(message);
+    return;
+  }
+
+  let playlist_id = playlist.id.to_string();
+
+  // The marked tracks, or the one under the cursor
+  let subject = match app.track_table.selected_tracks() {
+    [] => return,
+    [track] => track.name.clone(),
+    tracks => format!("{} tracks", tracks.len()),
   };
+  let track_uris = selected_track_uris(app);
+  app.open_dialog(Dialog::RemoveFromPlaylist {
+    playlist_id,
+    track_uris,
+    subject,
+  });
+}
+
+fn handle_save_track_event(app: &mut App) {
+  let track_ids = selected_track_uris(app);
+  app.track_table.visual_anchor = None;
+  if !track_ids.is_empty() {
+    app.dispatch(IoEvent::ToggleSaveTracks(track_ids));
+  }
 }
 
 fn handle_recommended_tracks(app: &mut App) {
@@ -232,6 +310,10 @@ fn jump_to_end(app: &mut App) {
       TrackTableContext::SavedTracks => {}
       TrackTableContext::AlbumSearch => {}
       TrackTableContext::PlaylistSearch => {}
+      TrackTableContext::TopTracks => {}
+      TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists => {}
     },
     None => {}
   }
@@ -262,50 +344,63 @@ fn on_enter(app: &mut App) {
             _ => None,
           };
 
-          // For playlists, we need to pass the track URI as offset to play specific track
-          let track_uri = tracks.get(selected_index).and_then(|track| {
-            track.id.as_ref().map(|id| {
-              let id_str = id.to_string();
-              if id_str.starts_with("spotify:track:") {
-                id_str
-              } else {
-                format!("spotify:track:{}", id_str)
-              }
-            })
-          });
-          app.dispatch(IoEvent::StartPlayback(context_uri, track_uri));
+          // By position, as a URI would miss local and relinked tracks
+          let offset = PlaybackOffset::Position(app.track_table.context_position(selected_index));
+          app.dispatch(IoEvent::StartPlayback(context_uri, Some(offset)));
         };
       }
+      TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists => {
+        let context_uri = app
+          .viewed_playlist()
+          .map(|playlist| open_in::uri("playlist", &playlist.id.to_string()));
+        if selected_index < tracks.len() {
+          let offset = PlaybackOffset::Position(app.track_table.context_position(selected_index));
+          app.dispatch(IoEvent::StartPlayback(context_uri, Some(offset)));
+        }
+      }
       TrackTableContext::RecommendedTracks => {
         app.dispatch(IoEvent::StartPlayback(None, None));
       }
+      TrackTableContext::TopTracks => {
+        if let Some(uri) = tracks.get(selected_index).and_then(open_in::track_uri) {
+          app.dispatch(IoEvent::StartPlayback(Some(uri), None));
+        }
+      }
       TrackTableContext::SavedTracks => {
-        // Extract saved tracks data to avoid borrow issues
-        let saved_track_data = app.library.saved_tracks.get_results(None)
-          .and_then(|saved_tracks| {
-            saved_tracks.items.get(selected_index).map(|saved_track| {
-              saved_track.track.id.clone()
-            })
-          });
-        
-        if let Some(track_id) = saved_track_data {
-          // Play the specific track
-          let track_uri = track_id.map(|id| {
-            let id_str = id.to_string();
-            if id_str.starts_with("spotify:track:") {
-              id_str
-            } else {
-              format!("spotify:track:{}", id_str)
+        let track_uri = app
+          .library
+          .saved_tracks
+          .get_results(None)
+          .and_then(|saved_tracks| saved_tracks.items.get(selected_index))
+          .and_then(|saved_track| open_in::track_uri(&saved_track.track));
+        if let Some(uri) = track_uri {
+          // Start playback with just the track URI (no context)
+          app.dispatch(IoEvent::StartPlayback(Some(uri), None));
+        }
+      }
+      TrackTableContext::AlbumSearch => {
+        // Play the album from the selected track on, so next/previous follow the album order
+        if let Some(track) = tracks.get(selected_index) {
+          let track_uri = open_in::track_uri(track);
+          let offset = PlaybackOffset::Position(app.track_table.context_position(selected_index));
+          let album_uri = track
+            .album
+            .id
+            .as_ref()
+            .map(|id| open_in::uri("album", &id.to_string()));
+
+          match (album_uri, track_uri) {
+            (Some(album_uri), _) => {
+              app.dispatch(IoEvent::StartPlayback(Some(album_uri), Some(offset)))
             }
-          });
-          
-          if let Some(uri) = track_uri {
-            // Start playback with just the track URI (no context)
-            app.dispatch(IoEvent::StartPlayback(Some(uri.clone()), None));
+            // Without an album to play, the track alone is better than nothing
+            (None, Some(track_uri)) => app.dispatch(IoEvent::StartPlayback(Some(track_uri), None)),
+            (None, None) => {}
           }
-        }
+        };
       }
-      TrackTableContext::AlbumSearch => {}
       TrackTableContext::PlaylistSearch => {
         if let Some(_track) = tracks.get(selected_index) {
           let context_uri = match (
@@ -319,7 +414,8 @@ fn on_enter(app: &mut App) {
             _ => None,
           };
 
-          app.dispatch(IoEvent::StartPlayback(context_uri, None));
+          let offset = PlaybackOffset::Position(app.track_table.context_position(selected_index));
+          app.dispatch(IoEvent::StartPlayback(context_uri, Some(offset)));
         };
       }
     },
@@ -332,32 +428,29 @@ fn on_queue(app: &mut App) {
     context,
     selected_index,
     tracks,
+    ..
   } = &app.track_table;
   match &context {
     Some(context) => match context {
-      TrackTableContext::MyPlaylists => {
-        if let Some(track) = tracks.get(*selected_index) {
-          let uri = format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
+      TrackTableContext::MyPlaylists
+      | TrackTableContext::TopTracks
+      | TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists => {
+        if let Some(uri) = tracks.get(*selected_index).and_then(open_in::track_uri) {
           app.dispatch(IoEvent::AddItemToQueue(uri));
         };
       }
       TrackTableContext::RecommendedTracks => {
-        if let Some(full_track) = app.recommended_tracks.get(app.track_table.selected_index) {
-          let uri = format!("spotify:track:{}", full_track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
+        let track = app.recommended_tracks.get(app.track_table.selected_index);
+        if let Some(uri) = track.and_then(open_in::track_uri) {
           app.dispatch(IoEvent::AddItemToQueue(uri));
         }
       }
       TrackTableContext::SavedTracks => {
         if let Some(page) = app.library.saved_tracks.get_results(None) {
-          if let Some(saved_track) = page.items.get(app.track_table.selected_index) {
-            let uri = saved_track.track.id.as_ref().map(|id| {
-              let id_str = id.to_string();
-              if id_str.starts_with("spotify:track:") {
-                id_str
-              } else {
-                format!("spotify:track:{}", id_str)
-              }
-            }).unwrap_or_else(|| "".to_string());
+          let saved_track = page.items.get(app.track_table.selected_index);
+          if let Some(uri) = saved_track.and_then(|saved| open_in::track_uri(&saved.track)) {
             app.dispatch(IoEvent::AddItemToQueue(uri));
           }
         }
@@ -369,8 +462,7 @@ fn on_queue(app: &mut App) {
           tracks,
           ..
         } = &app.track_table;
-        if let Some(track) = tracks.get(*selected_index) {
-          let uri = format!("spotify:track:{}", track.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()));
+        if let Some(uri) = tracks.get(*selected_index).and_then(open_in::track_uri) {
           app.dispatch(IoEvent::AddItemToQueue(uri));
         };
       }
@@ -379,6 +471,54 @@ fn on_queue(app: &mut App) {
   };
 }
 
+fn on_play_next(app: &mut App) {
+  let track_uri = app
+    .track_table
+    .tracks
+    .get(app.track_table.selected_index)
+    .and_then(open_in::track_uri);
+
+  // Only playlists can be matched against the playing context
+  let playlist_id = match &app.track_table.context {
+    Some(TrackTableContext::MyPlaylists) => match (&app.selected_playlist_index, &app.playlists) {
+      (Some(selected_playlist_index), Some(playlists)) => playlists
+        .items
+        .get(*selected_playlist_index)
+        .map(|playlist| playlist.id.to_string()),
+      _ => None,
+    },
+    Some(TrackTableContext::PlaylistSearch) => match (
+      &app.search_results.selected_playlists_index,
+      &app.search_results.playlists,
+    ) {
+      (Some(selected_playlist_index), Some(playlist_result)) => playlist_result
+        .items
+        .get(*selected_playlist_index)
+        .map(|playlist| playlist.id.to_string()),
+      _ => None,
+    },
+    Some(
+      TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists,
+    ) => app
+      .viewed_playlist()
+      .map(|playlist| playlist.id.to_string()),
+    _ => None,
+  };
+  let context_uri = playlist_id.map(|id_str| {
+    if id_str.starts_with("spotify:playlist:") {
+      id_str
+    } else {
+      format!("spotify:playlist:{}", id_str)
+    }
+  });
+
+  if let Some(uri) = track_uri {
+    app.play_next(context_uri, uri);
+  }
+}
+
 fn jump_to_start(app: &mut App) {
   match &app.track_table.context {
     Some(context) => match context {
@@ -397,6 +537,10 @@ fn jump_to_start(app: &mut App) {
       TrackTableContext::SavedTracks => {}
       TrackTableContext::AlbumSearch => {}
       TrackTableContext::PlaylistSearch => {}
+      TrackTableContext::TopTracks => {}
+      TrackTableContext::MadeForYou
+      | TrackTableContext::FeaturedPlaylists
+      | TrackTableContext::CategoryPlaylists => {}
     },
     None => {}
   }
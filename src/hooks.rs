@@ -0,0 +1,147 @@
+//! Shell commands run on playback events, set with `behavior.on_track_change`, `on_pause` and
+//! `on_resume`. They get what is playing in `SPOTUI_*` environment variables.
+
+use anyhow::{anyhow, Result};
+use rspotify::model::{CurrentPlaybackContext, PlayableItem};
+use std::{
+  process::{Command, Stdio},
+  thread,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaybackEvent {
+  TrackChange,
+  Pause,
+  Resume,
+}
+
+impl PlaybackEvent {
+  pub fn name(self) -> &'static str {
+    match self {
+      PlaybackEvent::TrackChange => "track_change",
+      PlaybackEvent::Pause => "pause",
+      PlaybackEvent::Resume => "resume",
+    }
+  }
+}
+
+/// The playing item's ID and whether it is playing, as far as hooks care
+pub type PlaybackState = (Option<String>, bool);
+
+/// What happened between two looks at playback. The first look counts as a track change when
+/// something is playing, so hooks start out in step.
+pub fn events(previous: Option<&PlaybackState>, current: &PlaybackState) -> Vec<PlaybackEvent> {
+  let (previous_id, was_playing) = match previous {
+    Some((id, is_playing)) => (id.as_ref(), *is_playing),
+    None => (None, false),
+  };
+  let (current_id, is_playing) = (current.0.as_ref(), current.1);
+
+  let mut events = vec![];
+  if current_id.is_some() && current_id != previous_id {
+    events.push(PlaybackEvent::TrackChange);
+  }
+  match (was_playing, is_playing) {
+    (true, false) => events.push(PlaybackEvent::Pause),
+    (false, true) if previous.is_some() => events.push(PlaybackEvent::Resume),
+    _ => {}
+  }
+  events
+}
+
+/// What the hook gets to know about the playing track or episode
+pub fn environment(
+  event: PlaybackEvent,
+  context: Option<&CurrentPlaybackContext>,
+) -> Vec<(String, String)> {
+  let mut env = vec![("EVENT", event.name().to_string())];
+  if let Some(context) = context {
+    env.push(("IS_PLAYING", context.is_playing.to_string()));
+    env.push(("DEVICE", context.device.name.clone()));
+  }
+  match context.and_then(|context| context.item.as_ref()) {
+    Some(PlayableItem::Track(track)) => {
+      let artists = track
+        .artists
+        .iter()
+        .map(|artist| artist.name.as_str())
+        .collect::<Vec<_>>();
+      env.push(("TITLE", track.name.clone()));
+      env.push(("ARTIST", artists.join(", ")));
+      env.push(("ALBUM", track.album.name.clone()));
+      env.push(("DURATION_MS", track.duration.num_milliseconds().to_string()));
+      if let Some(id) = &track.id {
+        env.push(("TRACK_ID", id.to_string()));
+      }
+      if let Some(image) = track.album.images.first() {
+        env.push(("ART_URL", image.url.clone()));
+      }
+    }
+    Some(PlayableItem::Episode(episode)) => {
+      env.push(("TITLE", episode.name.clone()));
+      env.push(("ARTIST", episode.show.publisher.clone()));
+      env.push(("ALBUM", episode.show.name.clone()));
+      env.push((
+        "DURATION_MS",
+        episode.duration.num_milliseconds().to_string(),
+      ));
+      env.push(("TRACK_ID", episode.id.to_string()));
+      if let Some(image) = episode.images.first() {
+        env.push(("ART_URL", image.url.clone()));
+      }
+    }
+    None => {}
+  }
+  env
+    .into_iter()
+    .map(|(name, value)| (format!("SPOTUI_{}", name), value))
+    .collect()
+}
+
+/// Start the command in the background. It is waited on from a thread of its own, so a slow hook
+/// never holds up the UI.
+pub fn run(command: &str, env: Vec<(String, String)>) -> Result<()> {
+  let mut shell = if cfg!(windows) {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C");
+    shell
+  } else {
+    let mut shell = Command::new("sh");
+    shell.arg("-c");
+    shell
+  };
+  let mut child = shell
+    .arg(command)
+    .envs(env)
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .map_err(|e| anyhow!("Couldn't run hook \"{}\": {}", command, e))?;
+  thread::spawn(move || child.wait());
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use PlaybackEvent::*;
+
+  #[test]
+  fn events_test() {
+    let playing = |id: &str| (Some(id.to_string()), true);
+    let paused = |id: &str| (Some(id.to_string()), false);
+
+    assert_eq!(events(None, &playing("a")), [TrackChange]);
+    assert!(events(None, &(None, false)).is_empty());
+    assert!(events(Some(&playing("a")), &playing("a")).is_empty());
+    assert_eq!(events(Some(&playing("a")), &playing("b")), [TrackChange]);
+    assert_eq!(events(Some(&playing("a")), &paused("a")), [Pause]);
+    assert_eq!(events(Some(&paused("a")), &playing("a")), [Resume]);
+    assert_eq!(
+      events(Some(&paused("a")), &playing("b")),
+      [TrackChange, Resume]
+    );
+    assert_eq!(events(Some(&playing("a")), &(None, false)), [Pause]);
+  }
+}
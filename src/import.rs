@@ -0,0 +1,271 @@
+use crate::network::PLAYLIST_ITEMS_PER_REQUEST;
+use anyhow::{anyhow, Result};
+use futures::stream::TryStreamExt;
+use rspotify::{
+  clients::{BaseClient, OAuthClient},
+  model::{enums::SearchType, PlayableId, PlaylistId, SearchResult, SimplifiedPlaylist, TrackId},
+  AuthCodeSpotify,
+};
+use std::time::Duration;
+
+/// One track to import, as read from a line of the import file
+#[derive(Debug, PartialEq)]
+pub enum ImportEntry {
+  /// A `spotify:track:` URI, also what open.spotify.com track URLs are turned into
+  Uri(String),
+  /// An `artist,title` pair, resolved with a track search
+  Search { artist: String, title: String },
+}
+
+impl ImportEntry {
+  fn describe(&self) -> String {
+    match self {
+      ImportEntry::Uri(uri) => uri.clone(),
+      ImportEntry::Search { artist, title } => format!("{} - {}", artist, title),
+    }
+  }
+}
+
+/// What an import did: how many tracks went into the playlist and what couldn't be matched
+pub struct ImportSummary {
+  pub added: usize,
+  pub misses: Vec<String>,
+}
+
+impl ImportSummary {
+  pub fn describe(&self, playlist_name: &str) -> String {
+    format!(
+      "Added {} tracks to {}, {} not found",
+      self.added,
+      playlist_name,
+      self.misses.len()
+    )
+  }
+}
+
+/// Read an import file: one Spotify track URL or URI per line, or CSV `artist,title` lines with
+/// an optional header. Blank lines and `#` comments are skipped; lines that are none of these are
+/// returned as misses.
+pub fn parse(contents: &str) -> (Vec<ImportEntry>, Vec<String>) {
+  let mut entries = vec![];
+  let mut misses = vec![];
+  let mut seen_content = false;
+
+  for (index, line) in contents.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let is_first = !seen_content;
+    seen_content = true;
+
+    if let Some(uri) = track_uri(line) {
+      entries.push(ImportEntry::Uri(uri));
+      continue;
+    }
+    match csv_fields(line).as_slice() {
+      [artist, title, ..] if !artist.is_empty() && !title.is_empty() => {
+        let is_header =
+          artist.eq_ignore_ascii_case("artist") && title.eq_ignore_ascii_case("title");
+        if !(is_first && is_header) {
+          entries.push(ImportEntry::Search {
+            artist: artist.clone(),
+            title: title.clone(),
+          });
+        }
+      }
+      _ => misses.push(format!("line {}: {}", index + 1, line)),
+    }
+  }
+  (entries, misses)
+}
+
+/// The track URI for a `spotify:track:` URI or an open.spotify.com track URL
+fn track_uri(text: &str) -> Option<String> {
+  let id = match text.strip_prefix("spotify:track:") {
+    Some(id) => id,
+    None => {
+      let (_, path) = text.split_once("open.spotify.com/")?;
+      let path = path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+      // Localized links look like /intl-de/track/<id>
+      let mut segments = path.split('/').skip_while(|segment| *segment != "track");
+      segments.next()?;
+      segments.next()?
+    }
+  };
+  if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+    Some(format!("spotify:track:{}", id))
+  } else {
+    None
+  }
+}
+
+/// Split a CSV line, allowing fields in double quotes with `""` for a literal quote
+fn csv_fields(line: &str) -> Vec<String> {
+  let mut fields = vec![];
+  let mut field = String::new();
+  let mut in_quotes = false;
+  let mut chars = line.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if in_quotes && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      }
+      '"' => in_quotes = !in_quotes,
+      ',' if !in_quotes => fields.push(std::mem::take(&mut field).trim().to_string()),
+      c => field.push(c),
+    }
+  }
+  fields.push(field.trim().to_string());
+  fields
+}
+
+/// Find one of the user's playlists by name, exact match first, or by ID/URI
+pub async fn find_playlist(
+  spotify: &AuthCodeSpotify,
+  name_or_id: &str,
+) -> Result<SimplifiedPlaylist> {
+  let playlists = spotify
+    .current_user_playlists()
+    .try_collect::<Vec<SimplifiedPlaylist>>()
+    .await
+    .map_err(|e| anyhow!("Failed to load playlists: {}", e))?;
+  let wanted = name_or_id.to_lowercase();
+  let by_id = PlaylistId::from_id_or_uri(name_or_id).ok();
+
+  playlists
+    .iter()
+    .find(|playlist| playlist.name.to_lowercase() == wanted)
+    .or_else(|| {
+      playlists
+        .iter()
+        .find(|playlist| Some(&playlist.id) == by_id.as_ref())
+    })
+    .or_else(|| {
+      playlists
+        .iter()
+        .find(|playlist| playlist.name.to_lowercase().contains(&wanted))
+    })
+    .cloned()
+    .ok_or_else(|| anyhow!("No playlist matching \"{}\"", name_or_id))
+}
+
+/// Resolve `entries` to tracks, searching for the artist/title ones, and add them to the playlist.
+/// An import can run to hundreds of requests, so each one gets `request_timeout` rather than the
+/// whole import, and `progress` hears how many entries have been looked up out of how many.
+pub async fn import_tracks(
+  spotify: &AuthCodeSpotify,
+  playlist_id: PlaylistId<'static>,
+  entries: Vec<ImportEntry>,
+  request_timeout: Duration,
+  mut progress: impl FnMut(usize, usize),
+) -> Result<ImportSummary> {
+  let mut ids: Vec<TrackId<'static>> = vec![];
+  let mut misses = vec![];
+  let timed_out = || {
+    anyhow!(
+      "Spotify request timed out after {}s",
+      request_timeout.as_secs()
+    )
+  };
+
+  let total = entries.len();
+  for (index, entry) in entries.into_iter().enumerate() {
+    let found = match &entry {
+      ImportEntry::Uri(uri) => TrackId::from_uri(uri).ok().map(|id| id.into_static()),
+      ImportEntry::Search { artist, title } => {
+        let query = format!("track:{} artist:{}", title, artist);
+        let search = spotify.search(&query, SearchType::Track, None, None, Some(1), None);
+        match tokio::time::timeout(request_timeout, search)
+          .await
+          .map_err(|_| timed_out())?
+        {
+          Ok(SearchResult::Tracks(page)) => {
+            page.items.into_iter().next().and_then(|track| track.id)
+          }
+          Ok(_) => None,
+          Err(e) => return Err(anyhow!("Failed to search for {}: {}", entry.describe(), e)),
+        }
+      }
+    };
+    match found {
+      Some(id) => ids.push(id),
+      None => misses.push(entry.describe()),
+    }
+    progress(index + 1, total);
+  }
+
+  for chunk in ids.chunks(PLAYLIST_ITEMS_PER_REQUEST) {
+    let add = spotify.playlist_add_items(
+      playlist_id.clone(),
+      chunk.iter().cloned().map(PlayableId::Track),
+      None,
+    );
+    tokio::time::timeout(request_timeout, add)
+      .await
+      .map_err(|_| timed_out())?
+      .map_err(|e| anyhow!("Failed to add tracks to playlist: {}", e))?;
+  }
+
+  Ok(ImportSummary {
+    added: ids.len(),
+    misses,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_track_uri() {
+    assert_eq!(
+      track_uri("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC?si=abc"),
+      Some("spotify:track:4uLU6hMCjMI75M1A2tKUQC".to_string())
+    );
+    assert_eq!(
+      track_uri("https://open.spotify.com/intl-de/track/4uLU6hMCjMI75M1A2tKUQC"),
+      Some("spotify:track:4uLU6hMCjMI75M1A2tKUQC".to_string())
+    );
+    assert_eq!(
+      track_uri("spotify:track:4uLU6hMCjMI75M1A2tKUQC"),
+      Some("spotify:track:4uLU6hMCjMI75M1A2tKUQC".to_string())
+    );
+    assert_eq!(
+      track_uri("https://open.spotify.com/album/1DFixLWuPkv3KT3TnV35m3"),
+      None
+    );
+  }
+
+  #[test]
+  fn test_parse() {
+    let contents = "artist,title\n\
+      # favourites\n\
+      Rick Astley,Never Gonna Give You Up\n\
+      \"Crosby, Stills & Nash\",\"Suite: Judy Blue Eyes\"\n\
+      \n\
+      spotify:track:4uLU6hMCjMI75M1A2tKUQC\n\
+      just some words";
+    let (entries, misses) = parse(contents);
+    assert_eq!(
+      entries,
+      vec![
+        ImportEntry::Search {
+          artist: "Rick Astley".to_string(),
+          title: "Never Gonna Give You Up".to_string(),
+        },
+        ImportEntry::Search {
+          artist: "Crosby, Stills & Nash".to_string(),
+          title: "Suite: Judy Blue Eyes".to_string(),
+        },
+        ImportEntry::Uri("spotify:track:4uLU6hMCjMI75M1A2tKUQC".to_string()),
+      ]
+    );
+    assert_eq!(misses, vec!["line 7: just some words".to_string()]);
+  }
+}
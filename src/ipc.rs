@@ -0,0 +1,254 @@
+//! A unix socket the running app takes one-line commands on, so `spt control` and status bar
+//! modules get an answer straight away without authenticating again. Each connection sends a
+//! command and gets one line back: `ok`, `error: …` or, for `status`, a JSON object.
+
+use crate::app::App;
+use crate::network::IoEvent;
+use rspotify::model::{CurrentPlaybackContext, PlayableItem};
+use serde_json::json;
+use std::{
+  path::PathBuf,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+pub const COMMANDS: [&str; 6] = ["toggle", "next", "previous", "like", "status", "play"];
+
+// Whether this process made the socket, and so should remove it on the way out
+static SERVING: AtomicBool = AtomicBool::new(false);
+
+/// Per user, in the runtime directory where there is one
+pub fn socket_path() -> Option<PathBuf> {
+  match dirs::runtime_dir() {
+    Some(dir) => Some(dir.join("spotui.sock")),
+    None => {
+      let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+      Some(std::env::temp_dir().join(format!("spotui-{}.sock", user)))
+    }
+  }
+}
+
+/// Carry out a command for the running app and say how it went
+pub fn run(app: &mut App, command: &str) -> String {
+  let (name, argument) = match command.trim().split_once(' ') {
+    Some((name, argument)) => (name, argument.trim()),
+    None => (command.trim(), ""),
+  };
+  match name {
+    "toggle" => app.toggle_playback(),
+    "next" => app.dispatch(IoEvent::NextTrack),
+    "previous" => app.previous_track(),
+    "like" => app.toggle_like_current_track(),
+    "status" => {
+      let liked = app
+        .playing_track_id()
+        .map(|track_id| app.liked_song_ids_set.contains(&track_id));
+      return status(app.current_playback_context.as_ref(), liked).to_string();
+    }
+    "play" => match argument.split(':').nth(1) {
+      Some("track") => app.dispatch(IoEvent::PlayTrackList(vec![argument.to_string()], 0)),
+      Some("episode") => app.dispatch(IoEvent::PlayEpisode(argument.to_string(), None)),
+      Some("playlist" | "album" | "artist" | "show") => {
+        app.dispatch(IoEvent::StartPlayback(Some(argument.to_string()), None))
+      }
+      Some(kind) => return format!("error: can't play a {}", kind),
+      None => return "error: play needs a spotify: URI".to_string(),
+    },
+    _ => {
+      return format!(
+        "error: unknown command \"{}\", expected one of {}",
+        name,
+        COMMANDS.join(", ")
+      )
+    }
+  }
+  "ok".to_string()
+}
+
+/// What is playing, for `status`. `liked` is unknown for episodes.
+pub fn status(context: Option<&CurrentPlaybackContext>, liked: Option<bool>) -> serde_json::Value {
+  let context = match context {
+    Some(context) => context,
+    None => return json!({ "state": "stopped" }),
+  };
+  let state = if context.is_playing {
+    "playing"
+  } else {
+    "paused"
+  };
+  let progress_ms = context.progress.map(|progress| progress.num_milliseconds());
+  match &context.item {
+    Some(PlayableItem::Track(track)) => json!({
+      "state": state,
+      "title": track.name,
+      "artist": track
+        .artists
+        .iter()
+        .map(|artist| artist.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", "),
+      "album": track.album.name,
+      "progress_ms": progress_ms,
+      "duration_ms": track.duration.num_milliseconds(),
+      "liked": liked,
+    }),
+    Some(PlayableItem::Episode(episode)) => json!({
+      "state": state,
+      "title": episode.name,
+      "artist": episode.show.publisher,
+      "album": episode.show.name,
+      "progress_ms": progress_ms,
+      "duration_ms": episode.duration.num_milliseconds(),
+      "liked": null,
+    }),
+    None => json!({ "state": state }),
+  }
+}
+
+#[cfg(unix)]
+pub use self::unix::{send, serve};
+
+#[cfg(unix)]
+mod unix {
+  use super::{run, socket_path, SERVING};
+  use crate::app::{App, LogLevel};
+  use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+  };
+  use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt},
+    net::UnixListener,
+    sync::Mutex,
+  };
+
+  // Longest `send` waits for the running app to answer
+  const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+  // Longest the app waits for a connection to send its command, so one that never does doesn't
+  // hold a task forever
+  const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+
+  /// Take commands until the app exits. Only the first instance listens, later ones say so in
+  /// the log stream.
+  pub async fn serve(app: Arc<Mutex<App>>) {
+    let path = match socket_path() {
+      Some(path) => path,
+      None => return,
+    };
+    if path.exists() {
+      if UnixStream::connect(&path).is_ok() {
+        app.lock().await.add_log_message(
+          "Another spotui is taking commands, so this one doesn't listen for them".to_string(),
+        );
+        return;
+      }
+      // Left behind by an instance that didn't exit cleanly
+      let _ = std::fs::remove_file(&path);
+    }
+    let listener = match UnixListener::bind(&path) {
+      Ok(listener) => listener,
+      Err(e) => {
+        app.lock().await.log(
+          LogLevel::Warn,
+          format!("Couldn't listen on {}: {}", path.display(), e),
+        );
+        return;
+      }
+    };
+    SERVING.store(true, Ordering::SeqCst);
+
+    loop {
+      let stream = match listener.accept().await {
+        Ok((stream, _)) => stream,
+        Err(_) => continue,
+      };
+      let app = Arc::clone(&app);
+      tokio::spawn(async move {
+        let (reader, mut writer) = stream.into_split();
+        let mut command = String::new();
+        let mut reader = tokio::io::BufReader::new(reader);
+        match tokio::time::timeout(COMMAND_TIMEOUT, reader.read_line(&mut command)).await {
+          Ok(Ok(_)) => {}
+          _ => return,
+        }
+        let reply = run(&mut *app.lock().await, &command);
+        let _ = writer.write_all(format!("{}\n", reply).as_bytes()).await;
+      });
+    }
+  }
+
+  /// Send a command to the running app, `None` when there isn't one
+  pub fn send(command: &str) -> Option<String> {
+    let mut stream = UnixStream::connect(socket_path()?).ok()?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT)).ok()?;
+    writeln!(stream, "{}", command).ok()?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).ok()?;
+    Some(reply.trim_end().to_string())
+  }
+}
+
+// Named pipes aren't supported yet, so elsewhere the app doesn't listen and `spt control` and
+// `spt module` refuse to run, see `check_supported`
+#[cfg(not(unix))]
+pub async fn serve(_app: std::sync::Arc<tokio::sync::Mutex<App>>) {}
+
+#[cfg(not(unix))]
+pub fn send(_command: &str) -> Option<String> {
+  None
+}
+
+/// `spt control` and `spt module` talk to the running app over a unix socket, which only unix
+/// platforms have
+pub fn check_supported(subcommand: &str) -> anyhow::Result<()> {
+  if cfg!(unix) {
+    Ok(())
+  } else {
+    Err(anyhow::anyhow!(
+      "spt {} needs a unix socket to reach the running app, which this platform doesn't have",
+      subcommand
+    ))
+  }
+}
+
+/// Remove the socket if this process made it
+pub fn cleanup() {
+  if SERVING.load(Ordering::SeqCst) {
+    if let Some(path) = socket_path() {
+      let _ = std::fs::remove_file(path);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn run_dispatches_commands() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut app = App::new(
+      tx,
+      crate::user_config::UserConfig::new(),
+      std::time::SystemTime::now(),
+    );
+
+    assert_eq!(run(&mut app, "next\n"), "ok");
+    assert!(matches!(rx.try_recv(), Ok((IoEvent::NextTrack, _))));
+
+    assert_eq!(run(&mut app, "play spotify:album:abc"), "ok");
+    assert!(matches!(
+      rx.try_recv(),
+      Ok((IoEvent::StartPlayback(Some(uri), None), _)) if uri == "spotify:album:abc"
+    ));
+
+    assert!(run(&mut app, "play abc").starts_with("error"));
+    assert_eq!(
+      run(&mut app, "play spotify:user:abc"),
+      "error: can't play a user"
+    );
+    assert!(run(&mut app, "dance").starts_with("error"));
+    assert_eq!(run(&mut app, "status"), r#"{"state":"stopped"}"#);
+  }
+}
@@ -0,0 +1,144 @@
+//! The views gone to, to step back and forth through like vim's jump list, whatever the navigation
+//! stack has done since. Each jump keeps the selected track, so stepping back to a track table
+//! finds the same row.
+
+use crate::app::App;
+use crate::location::Location;
+
+// Older jumps are dropped once there are this many
+const MAX_JUMPS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jump {
+  pub location: Location,
+  pub track_index: usize,
+}
+
+impl Jump {
+  /// The view on top as a jump, if it can be opened again
+  pub fn current(app: &App) -> Option<Jump> {
+    Location::current(app).map(|location| Jump {
+      location,
+      track_index: app.track_table.selected_index,
+    })
+  }
+}
+
+#[derive(Default)]
+pub struct JumpList {
+  jumps: Vec<Jump>,
+  // The jump being shown, or `jumps.len()` when that is a view not gone to through the list
+  position: usize,
+  // Set while a jump is opened, so going to it isn't noted as a new jump
+  moving: bool,
+}
+
+impl JumpList {
+  /// Note the view being left for another one, dropping any jumps forward of where the list was
+  pub fn record(&mut self, jump: Jump) {
+    if self.moving {
+      return;
+    }
+    self.jumps.truncate(self.position + 1);
+    match self.jumps.last_mut() {
+      Some(last) if last.location == jump.location => *last = jump,
+      _ => self.jumps.push(jump),
+    }
+    if self.jumps.len() > MAX_JUMPS {
+      self.jumps.remove(0);
+    }
+    self.position = self.jumps.len();
+  }
+
+  /// The jump before the one being shown, which is kept with its selection to come forward to
+  pub fn back(&mut self, current: Option<Jump>) -> Option<Jump> {
+    self.update_current(current);
+    self.position = self.position.checked_sub(1)?;
+    self.jumps.get(self.position).cloned()
+  }
+
+  /// The jump after the one being shown
+  pub fn forward(&mut self, current: Option<Jump>) -> Option<Jump> {
+    if self.position + 1 >= self.jumps.len() {
+      return None;
+    }
+    self.update_current(current);
+    self.position += 1;
+    self.jumps.get(self.position).cloned()
+  }
+
+  // Keep the view being shown at `position` with its latest selection
+  fn update_current(&mut self, current: Option<Jump>) {
+    let current = match current {
+      Some(current) => current,
+      None => return,
+    };
+    match self.jumps.get_mut(self.position) {
+      Some(jump) if jump.location == current.location => *jump = current,
+      Some(_) => {}
+      None => {
+        match self.jumps.last_mut() {
+          Some(last) if last.location == current.location => *last = current,
+          _ => self.jumps.push(current),
+        }
+        self.position = self.jumps.len() - 1;
+      }
+    }
+  }
+}
+
+/// Go back to the view before, with the selection it was left with
+pub fn back(app: &mut App) {
+  let current = Jump::current(app);
+  match app.jump_list.back(current) {
+    Some(jump) => open(app, jump),
+    None => app.show_toast("At the start of the jump list".to_string()),
+  }
+}
+
+/// Go forward again to a view gone back from
+pub fn forward(app: &mut App) {
+  let current = Jump::current(app);
+  match app.jump_list.forward(current) {
+    Some(jump) => open(app, jump),
+    None => app.show_toast("At the end of the jump list".to_string()),
+  }
+}
+
+fn open(app: &mut App, jump: Jump) {
+  app.jump_list.moving = true;
+  jump.location.open(app, jump.track_index);
+  app.jump_list.moving = false;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn jump(index: usize) -> Jump {
+    Jump {
+      location: Location::Library(index),
+      track_index: 0,
+    }
+  }
+
+  #[test]
+  fn back_and_forward_keep_the_view_left() {
+    let mut jumps = JumpList::default();
+    jumps.record(jump(0));
+    jumps.record(jump(1));
+    assert_eq!(jumps.back(Some(jump(2))), Some(jump(1)));
+    assert_eq!(jumps.back(Some(jump(1))), Some(jump(0)));
+    assert_eq!(jumps.back(Some(jump(0))), None);
+    assert_eq!(jumps.forward(Some(jump(0))), Some(jump(1)));
+    assert_eq!(jumps.forward(Some(jump(1))), Some(jump(2)));
+    assert_eq!(jumps.forward(Some(jump(2))), None);
+
+    // Going somewhere new from part way back drops the jumps forward of there
+    jumps.back(Some(jump(2)));
+    jumps.record(jump(1));
+    assert_eq!(jumps.forward(Some(jump(3))), None);
+    assert_eq!(jumps.back(Some(jump(3))), Some(jump(1)));
+    assert_eq!(jumps.back(Some(jump(1))), Some(jump(0)));
+  }
+}
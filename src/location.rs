@@ -0,0 +1,143 @@
+//! Views that can be opened again from a few IDs, for the session saved on quitting and the jump
+//! list. The rest depend on how they were reached, such as search results.
+
+use crate::app::{ActiveBlock, App, LibraryOption, RouteId, TrackTableContext};
+use crate::handlers;
+use crate::network::IoEvent;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Location {
+  // One of `LIBRARY_OPTIONS`, by its index
+  Library(usize),
+  // One of the user's playlists and the page of it shown
+  Playlist { id: String, offset: u32 },
+  Album(String),
+  Artist { id: String, name: String },
+}
+
+impl Location {
+  fn library(option: LibraryOption) -> Location {
+    Location::Library(option as usize)
+  }
+
+  /// Where the view on top is, if it can be opened again
+  pub fn current(app: &App) -> Option<Location> {
+    match app.get_current_route().id {
+      RouteId::RecentlyPlayed => Some(Location::library(LibraryOption::RecentlyPlayed)),
+      RouteId::AlbumList => Some(Location::library(LibraryOption::Albums)),
+      RouteId::Artists if app.showing_top_artists => {
+        Some(Location::library(LibraryOption::TopArtists))
+      }
+      RouteId::Artists => Some(Location::library(LibraryOption::Artists)),
+      RouteId::Podcasts => Some(Location::library(LibraryOption::Podcasts)),
+      RouteId::MadeForYou => Some(Location::library(LibraryOption::MadeForYou)),
+      RouteId::NewReleases => Some(Location::library(LibraryOption::NewReleases)),
+      RouteId::FeaturedPlaylists => Some(Location::library(LibraryOption::FeaturedPlaylists)),
+      RouteId::Categories => Some(Location::library(LibraryOption::Categories)),
+      RouteId::Profile => Some(Location::library(LibraryOption::Profile)),
+      RouteId::Artist => app.artist.as_ref().map(|artist| Location::Artist {
+        id: artist.artist_id.clone(),
+        name: artist.artist_name.clone(),
+      }),
+      RouteId::TrackTable => match app.track_table.context {
+        Some(TrackTableContext::SavedTracks) => Some(Location::library(LibraryOption::LikedSongs)),
+        Some(TrackTableContext::TopTracks) => Some(Location::library(LibraryOption::TopTracks)),
+        Some(TrackTableContext::MyPlaylists) => {
+          let playlists = app.playlists.as_ref()?;
+          let playlist = playlists.items.get(app.active_playlist_index?)?;
+          Some(Location::Playlist {
+            id: playlist.id.to_string(),
+            offset: app.playlist_offset,
+          })
+        }
+        Some(TrackTableContext::AlbumSearch) => app
+          .selected_album_full
+          .as_ref()
+          .map(|selected| Location::Album(selected.album.id.to_string())),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /// Load the view again and go to it, selecting `track_index` once its tracks are in if it is a
+  /// track table
+  pub fn open(&self, app: &mut App, track_index: usize) {
+    match self {
+      Location::Library(index) => handlers::open_library_option(app, *index),
+      Location::Playlist { id, offset } => {
+        app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+        app.track_table.context = Some(TrackTableContext::MyPlaylists);
+        app.playlist_offset = *offset;
+        app.dispatch(IoEvent::GetPlaylistTracks(id.clone(), *offset));
+        app
+          .pending_selection
+          .get_or_insert_with(PendingSelection::default)
+          .active_playlist_id = Some(id.clone());
+      }
+      Location::Album(id) => {
+        app.push_loading_route(RouteId::TrackTable, ActiveBlock::TrackTable);
+        app.dispatch(IoEvent::GetAlbum(id.clone()));
+      }
+      Location::Artist { id, name } => {
+        app.push_loading_route(RouteId::Artist, ActiveBlock::ArtistBlock);
+        app.get_artist(id.clone(), name.clone());
+      }
+    }
+    if app.get_current_route().id == RouteId::TrackTable {
+      app
+        .pending_selection
+        .get_or_insert_with(PendingSelection::default)
+        .track_index = Some(track_index);
+    }
+  }
+}
+
+/// Selections waiting for what they point into to load
+#[derive(Default, Debug, PartialEq)]
+pub struct PendingSelection {
+  // The playlist to select in the sidebar
+  pub playlist_id: Option<String>,
+  // The playlist whose tracks were opened again
+  pub active_playlist_id: Option<String>,
+  pub track_index: Option<usize>,
+}
+
+/// Select the waiting playlists once the playlists are in, and the track once the reopened track
+/// table has loaded, unless it has been left by then
+pub fn apply_loaded(app: &mut App) {
+  let mut pending = match app.pending_selection.take() {
+    Some(pending) => pending,
+    None => return,
+  };
+
+  if let Some(playlists) = &app.playlists {
+    let position = |id: &str| {
+      playlists
+        .items
+        .iter()
+        .position(|playlist| playlist.id.to_string() == id)
+    };
+    if let Some(id) = pending.playlist_id.take() {
+      app.selected_playlist_index = position(&id).or(app.selected_playlist_index);
+    }
+    if let Some(id) = pending.active_playlist_id.take() {
+      app.active_playlist_index = position(&id);
+    }
+  }
+
+  if let Some(track_index) = pending.track_index {
+    let route = app.get_current_route();
+    if route.id != RouteId::TrackTable {
+      pending.track_index = None;
+    } else if !route.loading && !app.track_table.tracks.is_empty() {
+      app.track_table.selected_index = track_index.min(app.track_table.tracks.len() - 1);
+      pending.track_index = None;
+    }
+  }
+
+  if pending != PendingSelection::default() {
+    app.pending_selection = Some(pending);
+  }
+}
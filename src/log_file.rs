@@ -0,0 +1,167 @@
+//! The log file, in the state directory unless `behavior.log_file` or `--log-file` put it
+//! elsewhere. Once it grows past `behavior.log_file_max_kb` it is moved aside to `<name>.1`, the
+//! older ones moving up a number, so a long session never fills the disk.
+
+use anyhow::{anyhow, Result};
+use std::{
+  fs::{self, File, OpenOptions},
+  io::{self, Write},
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+const LOG_DIR: &str = "spotify-tui";
+const FILE_NAME: &str = "spotui.log";
+
+// How many rotated logs are kept besides the one being written
+const ROTATED_FILES: usize = 3;
+
+// Empty until `init`, so nothing is written for commands that exit straight away
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+pub struct LogFile {
+  path: PathBuf,
+  // 0 never rotates
+  max_bytes: u64,
+  file: File,
+  size: u64,
+}
+
+impl LogFile {
+  pub fn open(path: PathBuf, max_bytes: u64) -> Result<Self> {
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)
+        .map_err(|e| anyhow!("Couldn't create log directory {}: {}", dir.display(), e))?;
+    }
+    let file = open_append(&path)
+      .map_err(|e| anyhow!("Couldn't open log file {}: {}", path.display(), e))?;
+    let size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    Ok(Self {
+      path,
+      max_bytes,
+      file,
+      size,
+    })
+  }
+
+  /// Append one or more whole lines, rotating first if they would take the file past its size
+  pub fn append(&mut self, lines: &[u8]) -> io::Result<()> {
+    let length = lines.len() as u64;
+    if self.max_bytes > 0 && self.size > 0 && self.size + length > self.max_bytes {
+      self.rotate()?;
+    }
+    self.file.write_all(lines)?;
+    self.size += length;
+    Ok(())
+  }
+
+  fn rotate(&mut self) -> io::Result<()> {
+    let _ = fs::remove_file(rotated_path(&self.path, ROTATED_FILES));
+    for number in (1..ROTATED_FILES).rev() {
+      let _ = fs::rename(
+        rotated_path(&self.path, number),
+        rotated_path(&self.path, number + 1),
+      );
+    }
+    fs::rename(&self.path, rotated_path(&self.path, 1))?;
+    self.file = open_append(&self.path)?;
+    self.size = 0;
+    Ok(())
+  }
+}
+
+/// `spotify-tui/spotui.log` in the state directory, which only Linux has, or else the cache
+/// directory
+pub fn default_path() -> Option<PathBuf> {
+  dirs::state_dir()
+    .or_else(dirs::cache_dir)
+    .map(|dir| dir.join(LOG_DIR).join(FILE_NAME))
+}
+
+/// Start writing the log to `path`
+pub fn init(path: PathBuf, max_bytes: u64) -> Result<()> {
+  let log_file = LogFile::open(path, max_bytes)?;
+  if let Ok(mut current) = LOG_FILE.lock() {
+    *current = Some(log_file);
+  }
+  Ok(())
+}
+
+/// Where the log is being written, if anywhere
+pub fn path() -> Option<PathBuf> {
+  LOG_FILE
+    .lock()
+    .ok()?
+    .as_ref()
+    .map(|log_file| log_file.path.clone())
+}
+
+/// Append `text` as it is, for a panic, where going through tracing isn't safe. Gives up rather
+/// than waiting if the log is busy, as the panic may have happened while writing to it. Returns
+/// where it was written
+pub fn write_directly(text: &str) -> Option<PathBuf> {
+  let mut current = LOG_FILE.try_lock().ok()?;
+  let log_file = current.as_mut()?;
+  log_file.append(text.as_bytes()).ok()?;
+  Some(log_file.path.clone())
+}
+
+/// The log file for the tracing subscriber to write events to, dropping them before `init`
+pub struct Writer;
+
+impl Write for Writer {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    if let Ok(mut current) = LOG_FILE.lock() {
+      if let Some(log_file) = current.as_mut() {
+        log_file.append(buf)?;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+fn rotated_path(path: &Path, number: usize) -> PathBuf {
+  let mut name = path.file_name().unwrap_or_default().to_os_string();
+  name.push(format!(".{}", number));
+  path.with_file_name(name)
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+  OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rotates_past_max_size() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("spotui-log-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("spotui.log");
+    let mut log_file = LogFile::open(path.clone(), 20)?;
+
+    // Lines of 10 bytes with the newline, so two fit in a file
+    for number in 1..=9 {
+      log_file.append(format!("line {}   \n", number).as_bytes())?;
+    }
+
+    assert_eq!(fs::read_to_string(&path)?, "line 9   \n");
+    assert_eq!(
+      fs::read_to_string(rotated_path(&path, 1))?,
+      "line 7   \nline 8   \n"
+    );
+    assert_eq!(
+      fs::read_to_string(rotated_path(&path, 3))?,
+      "line 3   \nline 4   \n"
+    );
+    assert!(!rotated_path(&path, 4).exists());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+  }
+}
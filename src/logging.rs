@@ -0,0 +1,112 @@
+//! Internal logging through `tracing`. Events go to the log file, as verbose as `SPOTUI_LOG`
+//! says in `RUST_LOG` syntax, and warnings and errors also show in the log stream.
+
+use crate::app::LogLevel;
+use crate::log_file;
+use anyhow::{anyhow, Result};
+use std::{
+  fmt::{self, Write},
+  sync::{mpsc::Sender, Mutex},
+};
+use tracing::{
+  field::{Field, Visit},
+  Event, Level, Subscriber,
+};
+use tracing_subscriber::{fmt as tracing_fmt, layer::Context, prelude::*, EnvFilter, Layer};
+
+/// Where the log stream's own messages are logged from, so they aren't sent back to it
+pub const LOG_STREAM_TARGET: &str = "spt::log_stream";
+
+const FILTER_ENV: &str = "SPOTUI_LOG";
+
+// spotui's info and up, only warnings from the libraries it uses
+const DEFAULT_FILTER: &str = "warn,spt=info";
+
+/// Start logging, sending warnings and errors to the log stream through `log_stream`
+pub fn init(log_stream: Sender<(LogLevel, String)>) -> Result<()> {
+  let filter =
+    EnvFilter::try_from_env(FILTER_ENV).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+  tracing_subscriber::registry()
+    .with(filter)
+    .with(
+      tracing_fmt::layer()
+        .with_ansi(false)
+        .with_writer(|| log_file::Writer),
+    )
+    .with(LogStreamLayer::new(log_stream))
+    .try_init()
+    .map_err(|e| anyhow!("Couldn't start logging: {}", e))
+}
+
+/// Passes warnings and errors on to the log stream
+pub struct LogStreamLayer {
+  sender: Mutex<Sender<(LogLevel, String)>>,
+}
+
+impl LogStreamLayer {
+  pub fn new(sender: Sender<(LogLevel, String)>) -> Self {
+    Self {
+      sender: Mutex::new(sender),
+    }
+  }
+}
+
+impl<S: Subscriber> Layer<S> for LogStreamLayer {
+  fn on_event(&self, event: &Event<'_>, _context: Context<'_, S>) {
+    let metadata = event.metadata();
+    let level = match *metadata.level() {
+      Level::ERROR => LogLevel::Error,
+      Level::WARN => LogLevel::Warn,
+      _ => return,
+    };
+    if metadata.target() == LOG_STREAM_TARGET {
+      return;
+    }
+    let mut message = MessageVisitor::default();
+    event.record(&mut message);
+    if let Ok(sender) = self.sender.lock() {
+      let _ = sender.send((level, message.0));
+    }
+  }
+}
+
+/// An event's message followed by its other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    if field.name() == "message" {
+      let fields = std::mem::take(&mut self.0);
+      let _ = write!(self.0, "{:?}{}", value, fields);
+    } else {
+      let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::mpsc;
+
+  #[test]
+  fn layer_passes_on_warnings_and_errors() {
+    let (tx, rx) = mpsc::channel();
+    let subscriber = tracing_subscriber::registry().with(LogStreamLayer::new(tx));
+    tracing::subscriber::with_default(subscriber, || {
+      tracing::info!("Got 3 playlists");
+      tracing::warn!(status = 404, "Failed to get queue");
+      tracing::error!(target: LOG_STREAM_TARGET, "Already in the log stream");
+      tracing::error!("Invalid track ID {}", "abc");
+    });
+
+    assert_eq!(
+      rx.try_iter().collect::<Vec<_>>(),
+      [
+        (LogLevel::Warn, "Failed to get queue status=404".to_string()),
+        (LogLevel::Error, "Invalid track ID abc".to_string()),
+      ]
+    );
+  }
+}
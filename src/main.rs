@@ -1,44 +1,73 @@
 mod app;
 mod album_art;
 mod banner;
+mod bugreport;
 // mod cli;  // TODO: Re-enable after fixing clap compatibility
 mod config;
+mod debug_stats;
+mod duplicates;
+mod episode_positions;
 mod event;
 mod focus_manager;
+mod fuzzy;
 mod handlers;
+mod hooks;
+mod import;
+mod ipc;
+mod jump_list;
+mod location;
+mod log_file;
+mod logging;
 mod network;  // Temporary minimal network module
+mod now_playing;
+mod open_in;
+mod palette;
+mod paths;
+mod playlist_sort;
 mod redirect_uri;
+mod search_filter;
+mod search_history;
+mod session;
+mod share;
+mod spotifyd;
+mod status_bar;
+#[cfg(feature = "streaming")]
+mod streaming;
+mod template;
 mod ui;
 mod user_config;
+mod workspace;
 
 use crate::app::RouteId;
-use crate::event::Key;
+use crate::event::{ChordEvent, ChordState, Key};
 use anyhow::{anyhow, Result};
-use app::{ActiveBlock, App};
+use app::{ActiveBlock, App, DialogContext, LogLevel};
 use backtrace::Backtrace;
 use banner::BANNER;
 use clap::{Arg, Command};
 use config::ClientConfig;
 use crossterm::{
   cursor::MoveTo,
-  event::{DisableMouseCapture, EnableMouseCapture},
+  event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
   execute,
-  style::Print,
   terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
   },
   ExecutableCommand,
 };
-use network::{IoEvent, Network};
+use network::{DispatchedEvent, IoEvent, Network};
 // use redirect_uri::redirect_uri_web_server;  // TODO: Fix redirect_uri module
 use rspotify::{
   AuthCodeSpotify, Credentials, OAuth,
   prelude::*,
 };
+use session::Session;
 use webbrowser;
 use std::{
   cmp::{max, min},
+  collections::VecDeque,
   io::{self, stdout},
+  mem,
   panic::{self, PanicInfo},
   path::PathBuf,
   sync::Arc,
@@ -52,6 +81,9 @@ use ratatui::{
 };
 use user_config::{UserConfig, UserConfigPaths};
 
+// Longest quitting waits for the network thread to finish the requests it has left
+const QUIT_TIMEOUT: Duration = Duration::from_secs(10);
+
 fn get_scopes() -> std::collections::HashSet<String> {
   [
     "playlist-read-collaborative",
@@ -69,6 +101,9 @@ fn get_scopes() -> std::collections::HashSet<String> {
     "user-read-private",
     "user-read-recently-played",
     "user-top-read",
+    // The built-in device logs in to Spotify Connect with the same token
+    #[cfg(feature = "streaming")]
+    "streaming",
   ].iter().map(|s| s.to_string()).collect()
 }
 
@@ -255,35 +290,107 @@ fn determine_optimal_tick_rate(app: &App, user_config: &UserConfig) -> u64 {
 fn close_application() -> Result<()> {
   disable_raw_mode()?;
   let mut stdout = io::stdout();
-  execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+  execute!(
+    stdout,
+    LeaveAlternateScreen,
+    DisableMouseCapture,
+    DisableBracketedPaste
+  )?;
+  Ok(())
+}
+
+// Hand the terminal back to the shell and stop, the way a program left to SIGTSTP would, then set
+// it up again once continued
+#[cfg(unix)]
+fn suspend<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+  terminal.show_cursor()?;
+  close_application()?;
+  // Raising SIGTSTP would come straight back to our own handler, and SIGSTOP can't be caught
+  unsafe {
+    libc::raise(libc::SIGSTOP);
+  }
+  resume(terminal)
+}
+
+// Set the terminal up again after being stopped, and draw everything afresh over whatever the
+// shell left on it
+fn resume<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+  execute!(
+    io::stdout(),
+    EnterAlternateScreen,
+    EnableMouseCapture,
+    EnableBracketedPaste
+  )?;
+  enable_raw_mode()?;
+  terminal.hide_cursor()?;
+  terminal.clear()?;
   Ok(())
 }
 
+// Listen for SIGTSTP sent from outside, e.g. `kill -TSTP`, and SIGCONT, passing them on to the UI
+// loop. Ctrl-Z doesn't send SIGTSTP in raw mode, it arrives as a key
+#[cfg(unix)]
+fn handle_job_control_signals(app: &Arc<Mutex<App>>, redraw_notifier: event::RedrawNotifier) {
+  use tokio::signal::unix::{signal, SignalKind};
+
+  let (mut stop, mut cont) = match (
+    signal(SignalKind::from_raw(libc::SIGTSTP)),
+    signal(SignalKind::from_raw(libc::SIGCONT)),
+  ) {
+    (Ok(stop), Ok(cont)) => (stop, cont),
+    _ => return,
+  };
+  let app = Arc::clone(app);
+  tokio::spawn(async move {
+    loop {
+      tokio::select! {
+        Some(()) = stop.recv() => {
+          app.lock().await.suspend_requested = true;
+        }
+        Some(()) = cont.recv() => {
+          app.lock().await.resume_requested = true;
+        }
+        else => break,
+      }
+      redraw_notifier.notify();
+    }
+  });
+}
+
+// Put the terminal back however spotui was built, so a crash doesn't leave it unusable, then say
+// what happened in a line or two with the backtrace in the log file
 fn panic_hook(info: &PanicInfo<'_>) {
-  if cfg!(debug_assertions) {
-    let location = info.location().unwrap();
-
-    let msg = match info.payload().downcast_ref::<&'static str>() {
-      Some(s) => *s,
-      None => match info.payload().downcast_ref::<String>() {
-        Some(s) => &s[..],
-        None => "Box<Any>",
-      },
-    };
+  let _ = disable_raw_mode();
+  let _ = execute!(
+    io::stdout(),
+    LeaveAlternateScreen,
+    DisableMouseCapture,
+    DisableBracketedPaste
+  );
 
-    let stacktrace: String = format!("{:?}", Backtrace::new()).replace('\n', "\n\r");
-
-    disable_raw_mode().unwrap();
-    execute!(
-      io::stdout(),
-      LeaveAlternateScreen,
-      Print(format!(
-        "thread '<unnamed>' panicked at '{}', {}\n\r{}",
-        msg, location, stacktrace
-      )),
-      DisableMouseCapture
-    )
-    .unwrap();
+  let msg = match info.payload().downcast_ref::<&'static str>() {
+    Some(s) => *s,
+    None => match info.payload().downcast_ref::<String>() {
+      Some(s) => &s[..],
+      None => "Box<Any>",
+    },
+  };
+  let location = info
+    .location()
+    .map_or_else(String::new, |location| format!(" at {}", location));
+  let thread = std::thread::current();
+  let error = format!(
+    "thread '{}' panicked{}: {}",
+    thread.name().unwrap_or("<unnamed>"),
+    location,
+    msg
+  );
+  let stacktrace = format!("{:?}", Backtrace::new());
+
+  eprintln!("spotui crashed: {}", error);
+  match log_file::write_directly(&format!("{}\n{}\n", error, stacktrace)) {
+    Some(path) => eprintln!("The backtrace is in {}", path.display()),
+    None => eprintln!("{}", stacktrace),
   }
 }
 
@@ -321,6 +428,13 @@ of the app. Beware that this comes at a CPU cost!",
         .help("Specify configuration file path.")
         .takes_value(true),
     )
+    .arg(
+      Arg::new("log-file")
+        .long("log-file")
+        .help("Write the log to this file instead of the state directory.")
+        .takes_value(true)
+        .value_name("PATH"),
+    )
     .arg(
       Arg::new("completions")
         .long("completions")
@@ -329,6 +443,79 @@ of the app. Beware that this comes at a CPU cost!",
         .possible_values(&["bash", "zsh", "fish", "power-shell", "elvish"])
         .value_name("SHELL"),
     )
+    .subcommand(
+      Command::new("calibrate-art")
+        .about("Checks the terminal's cell aspect ratio so album art is drawn square"),
+    )
+    .subcommand(
+      Command::new("check-config")
+        .about("Reports every problem in config.yml and client.yml instead of starting"),
+    )
+    .subcommand(
+      Command::new("control")
+        .about("Controls playback, through the running app when there is one")
+        .arg(
+          Arg::new("command")
+            .required(true)
+            .possible_values(["toggle", "next", "previous", "like", "status"])
+            .help("What to do; status prints what is playing as JSON"),
+        ),
+    )
+    .subcommand(
+      Command::new("import")
+        .about("Adds the tracks listed in a file to one of your playlists")
+        .arg(
+          Arg::new("file")
+            .required(true)
+            .help("Spotify track URLs/URIs, or CSV artist,title lines, one track per line"),
+        )
+        .arg(
+          Arg::new("playlist")
+            .short('p')
+            .long("playlist")
+            .required(true)
+            .takes_value(true)
+            .help("Name or ID of the playlist to add the tracks to"),
+        ),
+    )
+    .subcommand(
+      Command::new("module")
+        .about("Prints what is playing for a waybar or polybar module, from the running app")
+        .arg(
+          Arg::new("bar")
+            .required(true)
+            .possible_values(status_bar::BARS)
+            .help("waybar prints JSON with a playing/paused/stopped class, polybar plain text"),
+        )
+        .arg(
+          Arg::new("follow")
+            .short('f')
+            .long("follow")
+            .help("Keep running, printing a new line whenever it changes"),
+        )
+        .arg(
+          Arg::new("interval")
+            .long("interval")
+            .takes_value(true)
+            .default_value("2")
+            .help("Seconds between checks with --follow"),
+        ),
+    )
+    .subcommand(
+      Command::new("open")
+        .about("Starts the app on what a Spotify link points to, or plays it with --play")
+        .arg(
+          Arg::new("link")
+            .required(true)
+            .help("An open.spotify.com URL or spotify: URI"),
+        )
+        .arg(
+          Arg::new("play")
+            .short('p')
+            .long("play")
+            .help("Play it on the active device instead of starting the app"),
+        ),
+    )
     // Control spotify from the command line
     // TODO: Re-enable CLI commands after fixing clap compatibility
     // .subcommand(cli::playback_subcommand())
@@ -354,12 +541,20 @@ of the app. Beware that this comes at a CPU cost!",
   //   return Ok(());
   // }
 
+  // Calibration only talks to the terminal
+  if matches.subcommand_matches("calibrate-art").is_some() {
+    return ui::cell_aspect::calibrate();
+  }
+
   let mut user_config = UserConfig::new();
   if let Some(config_file_path) = matches.get_one::<String>("config") {
     let config_file_path = PathBuf::from(config_file_path);
     let path = UserConfigPaths { config_file_path };
     user_config.path_to_config.replace(path);
   }
+  if matches.subcommand_matches("check-config").is_some() {
+    return check_config(&mut user_config);
+  }
   user_config.load_config()?;
 
   if let Some(tick_rate) = matches
@@ -373,6 +568,75 @@ of the app. Beware that this comes at a CPU cost!",
     }
   }
 
+  // Catch a bad link before authenticating or taking over the terminal
+  let open_link = match matches.subcommand_matches("open") {
+    Some(open_matches) => {
+      let link = open_matches
+        .get_one::<String>("link")
+        .ok_or_else(|| anyhow!("No link to open"))?;
+      let uri =
+        open_in::link_uri(link).ok_or_else(|| anyhow!("Not a Spotify link or URI: {}", link))?;
+      Some((uri, open_matches.is_present("play")))
+    }
+    None => None,
+  };
+
+  if let Some(module_matches) = matches.subcommand_matches("module") {
+    ipc::check_supported("module")?;
+    let bar = module_matches
+      .get_one::<String>("bar")
+      .ok_or_else(|| anyhow!("No bar to print for"))?;
+    let interval = module_matches
+      .get_one::<String>("interval")
+      .and_then(|interval| interval.parse().ok())
+      .ok_or_else(|| anyhow!("--interval should be a number of seconds"))?;
+    return status_bar::run(
+      bar,
+      module_matches.is_present("follow"),
+      Duration::from_secs(interval),
+      &user_config.behavior,
+    );
+  }
+
+  // The running app answers straight away, without authenticating again
+  let control_command = matches
+    .subcommand_matches("control")
+    .and_then(|control_matches| control_matches.get_one::<String>("command"))
+    .cloned();
+  if control_command.is_some() {
+    ipc::check_supported("control")?;
+  }
+  let ipc_command = match (&control_command, &open_link) {
+    (Some(command), _) => Some(command.clone()),
+    (None, Some((uri, true))) => Some(format!("play {}", uri)),
+    _ => None,
+  };
+  if let Some(reply) = ipc_command.as_deref().and_then(ipc::send) {
+    return match reply.strip_prefix("error: ") {
+      Some(error) => Err(anyhow!("{}", error)),
+      None => {
+        println!("{}", reply);
+        Ok(())
+      }
+    };
+  }
+
+  let log_path = match matches.get_one::<String>("log-file") {
+    Some(log_path) => Some(PathBuf::from(log_path)),
+    None if !user_config.behavior.log_file.is_empty() => {
+      Some(paths::expand_home(&user_config.behavior.log_file))
+    }
+    None => log_file::default_path(),
+  };
+  if let Some(log_path) = log_path {
+    log_file::init(log_path, user_config.behavior.log_file_max_kb * 1024)?;
+  }
+  let (log_stream_tx, log_stream_rx) = std::sync::mpsc::channel();
+  logging::init(log_stream_tx)?;
+  for warning in &user_config.warnings {
+    tracing::warn!("{}", warning);
+  }
+
   let mut client_config = ClientConfig::new();
   client_config.load_config()?;
 
@@ -381,7 +645,20 @@ of the app. Beware that this comes at a CPU cost!",
   // Start authorization with spotify
   match create_spotify_client(&client_config).await {
     Ok(spotify) => {
-      let (sync_io_tx, sync_io_rx) = std::sync::mpsc::channel::<IoEvent>();
+      if let Some(import_matches) = matches.subcommand_matches("import") {
+        let request_timeout = Duration::from_secs(user_config.behavior.network_timeout_seconds);
+        return import_from_cli(&spotify, import_matches, request_timeout).await;
+      }
+      if let Some(command) = &control_command {
+        return control_from_cli(&spotify, command).await;
+      }
+      if let Some((uri, true)) = &open_link {
+        open_in::play(&spotify, uri).await?;
+        println!("Playing {}", uri);
+        return Ok(());
+      }
+
+      let (sync_io_tx, sync_io_rx) = std::sync::mpsc::channel::<DispatchedEvent>();
 
       // Get token expiry from the authenticated client
       let token_expiry = if let Ok(token_guard) = spotify.token.lock().await {
@@ -408,28 +685,104 @@ of the app. Beware that this comes at a CPU cost!",
       // Add startup log message
       {
         let mut app_lock = app.lock().await;
+        app_lock.traced_log_messages = Some(log_stream_rx);
         app_lock.add_log_message("Spotify TUI started - checking current device...".to_string());
         app_lock.add_log_message("Tip: Press 'd' to select a playback device".to_string());
+        if let Some((uri, _)) = &open_link {
+          if !handlers::open_link(&mut app_lock, uri) {
+            return Err(anyhow!("{} can't be shown in the app, try --play", uri));
+          }
+        } else if user_config.behavior.save_state_on_quit {
+          Session::load().restore(&mut app_lock);
+        }
       }
 
+      let spotifyd = if user_config.behavior.spotifyd_path.is_empty() {
+        None
+      } else {
+        let behavior = &user_config.behavior;
+        let mut app_lock = app.lock().await;
+        match spotifyd::Spotifyd::spawn(
+          &behavior.spotifyd_path,
+          &behavior.spotifyd_config_path,
+          &behavior.spotifyd_device_name,
+          &app,
+        ) {
+          Ok(spotifyd) => {
+            app_lock.add_log_message("Started spotifyd".to_string());
+            app_lock.awaited_device = Some(behavior.spotifyd_device_name.clone());
+            Some(spotifyd)
+          }
+          Err(e) => {
+            app_lock.handle_error(e);
+            None
+          }
+        }
+      };
+
       // Check current playback context on startup
-      if let Err(_) = sync_io_tx.send(IoEvent::GetCurrentPlayback) {
+      if let Err(_) = sync_io_tx.send((IoEvent::GetCurrentPlayback, None)) {
         // Failed to dispatch initial playback check
       }
 
+      let events = event::Events::new(user_config.behavior.tick_rate_milliseconds);
+      let redraw_notifier = events.redraw_notifier();
+      let request_timeout = Duration::from_secs(user_config.behavior.network_timeout_seconds);
+      let audio_analysis_disk_cache = user_config.behavior.audio_analysis_disk_cache;
+      let finish_changes_on_quit = user_config.behavior.finish_changes_on_quit;
+      let pause_on_quit = user_config.behavior.pause_on_quit;
+
+      if cfg!(feature = "streaming") && user_config.behavior.streaming {
+        let _ = sync_io_tx.send((IoEvent::StartStreaming, None));
+      }
+
       // Start network handler in background thread  
       let app_clone = Arc::clone(&app);
+      let network_stats = Arc::clone(&app.lock().await.network_stats);
       let spotify_clone = spotify.clone();
-      std::thread::spawn(move || {
+      let (network_done_tx, network_done_rx) = tokio::sync::oneshot::channel();
+      let network_thread = std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-          let mut network = Network::new(spotify_clone, client_config, &app_clone);
-          start_tokio(sync_io_rx, &mut network).await;
+          let mut network =
+            Network::new(spotify_clone, client_config, &app_clone, redraw_notifier);
+          network.request_timeout = request_timeout;
+          network.stats = network_stats;
+          if audio_analysis_disk_cache {
+            network.analysis_cache.use_disk_cache("audio-analysis");
+            network.features_cache.use_disk_cache("audio-features");
+          }
+          start_tokio(sync_io_rx, &mut network, finish_changes_on_quit).await;
         });
+        let _ = network_done_tx.send(());
       });
 
       // Launch the UI
-      start_ui(user_config, &app).await?;
+      tokio::spawn(ipc::serve(Arc::clone(&app)));
+
+      let result = start_ui(user_config, &app, events).await;
+
+      // Let the network thread finish what it is doing rather than exiting under it, giving up
+      // on it after a while
+      let pause_playback = pause_on_quit
+        && app
+          .lock()
+          .await
+          .current_playback_context
+          .as_ref()
+          .is_some_and(|context| context.is_playing);
+      let _ = sync_io_tx.send((IoEvent::Shutdown { pause_playback }, None));
+      match tokio::time::timeout(QUIT_TIMEOUT, network_done_rx).await {
+        Ok(_) => {
+          let _ = network_thread.join();
+        }
+        Err(_) => eprintln!("Gave up waiting for Spotify requests to finish"),
+      }
+      ipc::cleanup();
+      if let Some(spotifyd) = spotifyd {
+        spotifyd.stop();
+      }
+      result?;
     }
     Err(e) => {
       println!("\nSpotify authentication failed: {}", e);
@@ -440,16 +793,230 @@ of the app. Beware that this comes at a CPU cost!",
   Ok(())
 }
 
-async fn start_tokio(io_rx: std::sync::mpsc::Receiver<IoEvent>, network: &mut Network) {
-  while let Ok(io_event) = io_rx.recv() {
-    network.handle_network_event(io_event).await;
+// Checks a config file's contents, returning a message per problem
+type ConfigCheck = fn(&str) -> Vec<String>;
+
+/// `spt check-config`: print every problem in both config files and fail if there were any
+fn check_config(user_config: &mut UserConfig) -> Result<()> {
+  if user_config.path_to_config.is_none() {
+    user_config.get_or_build_paths()?;
+  }
+  let user_config_path = user_config
+    .path_to_config
+    .as_ref()
+    .map(|paths| paths.config_file_path.clone())
+    .ok_or_else(|| anyhow!("No config.yml path"))?;
+  let client_config_path = ClientConfig::new().get_or_build_paths()?.config_file_path;
+
+  let checks: [(PathBuf, ConfigCheck); 2] = [
+    (user_config_path, UserConfig::check_config),
+    (client_config_path, ClientConfig::check_config),
+  ];
+  let mut problem_count = 0;
+  for (path, check) in checks {
+    if !path.exists() {
+      println!("{}: not found, defaults are used", path.display());
+      continue;
+    }
+    let problems = check(&std::fs::read_to_string(&path)?);
+    if problems.is_empty() {
+      println!("{}: ok", path.display());
+    }
+    for problem in &problems {
+      println!("{}: {}", path.display(), problem);
+    }
+    problem_count += problems.len();
+  }
+
+  if problem_count > 0 {
+    Err(anyhow!("Found {} problem(s)", problem_count))
+  } else {
+    Ok(())
+  }
+}
+
+/// `spt control` without a running app: do the same through the API
+async fn control_from_cli(spotify: &AuthCodeSpotify, command: &str) -> Result<()> {
+  use rspotify::model::PlayableItem;
+
+  let context = spotify.current_playback(None, None::<&[_]>).await?;
+  let track_id = match context.as_ref().and_then(|context| context.item.as_ref()) {
+    Some(PlayableItem::Track(track)) => track.id.clone(),
+    _ => None,
+  };
+  let liked = match &track_id {
+    Some(track_id) => spotify
+      .current_user_saved_tracks_contains([track_id.clone()])
+      .await?
+      .first()
+      .copied(),
+    None => None,
+  };
+
+  match command {
+    "toggle" => match &context {
+      Some(context) if context.is_playing => spotify.pause_playback(None).await?,
+      _ => spotify.resume_playback(None, None).await?,
+    },
+    "next" => spotify.next_track(None).await?,
+    "previous" => spotify.previous_track(None).await?,
+    "like" => {
+      let track_id = track_id.ok_or_else(|| anyhow!("No track is playing"))?;
+      if liked == Some(true) {
+        spotify.current_user_saved_tracks_delete([track_id]).await?;
+      } else {
+        spotify.current_user_saved_tracks_add([track_id]).await?;
+      }
+    }
+    "status" => {
+      println!("{}", ipc::status(context.as_ref(), liked));
+      return Ok(());
+    }
+    _ => return Err(anyhow!("Unknown command \"{}\"", command)),
+  }
+  println!("ok");
+  Ok(())
+}
+
+/// `spt import`: add the tracks in a file to a playlist and list what couldn't be found
+async fn import_from_cli(
+  spotify: &AuthCodeSpotify,
+  matches: &clap::ArgMatches,
+  request_timeout: Duration,
+) -> Result<()> {
+  let file = matches
+    .get_one::<String>("file")
+    .ok_or_else(|| anyhow!("No file to import"))?;
+  let playlist_name = matches
+    .get_one::<String>("playlist")
+    .ok_or_else(|| anyhow!("No playlist to import into"))?;
+
+  let contents = std::fs::read_to_string(paths::expand_home(file))
+    .map_err(|e| anyhow!("Couldn't read {}: {}", file, e))?;
+  let (entries, unreadable) = import::parse(&contents);
+  let playlist = import::find_playlist(spotify, playlist_name).await?;
+  let mut summary =
+    import::import_tracks(spotify, playlist.id, entries, request_timeout, |_, _| {}).await?;
+  summary.misses.extend(unreadable);
+
+  for miss in &summary.misses {
+    println!("Not found: {}", miss);
+  }
+  println!("{}", summary.describe(&playlist.name));
+  Ok(())
+}
+
+/// Handle events until `IoEvent::Shutdown`, after which only the requests already running and,
+/// with `finish_changes`, the changes still waiting are seen through
+async fn start_tokio(
+  io_rx: std::sync::mpsc::Receiver<DispatchedEvent>,
+  network: &mut Network,
+  finish_changes: bool,
+) {
+  // Forward events onto an async channel so new ones can be looked at while a request is running
+  let (async_io_tx, mut async_io_rx) = tokio::sync::mpsc::unbounded_channel::<DispatchedEvent>();
+  let stats = Arc::clone(&network.stats);
+  let forwarded_stats = Arc::clone(&stats);
+  std::thread::spawn(move || {
+    while let Ok(io_event) = io_rx.recv() {
+      forwarded_stats.event_received();
+      if async_io_tx.send(io_event).is_err() {
+        break;
+      }
+    }
+  });
+
+  let mut queued: VecDeque<DispatchedEvent> = VecDeque::new();
+  let mut shutdown: Option<bool> = None;
+  loop {
+    let (io_event, workspace) = match queued.pop_front() {
+      Some(dispatched) => dispatched,
+      None if shutdown.is_some() => break,
+      None => match async_io_rx.recv().await {
+        Some(dispatched) => dispatched,
+        None => break,
+      },
+    };
+    stats.events_taken(1);
+    if let IoEvent::Shutdown { pause_playback } = io_event {
+      shutdown = Some(pause_playback);
+      continue;
+    }
+    // Once quitting, only changes to the library are worth sending
+    if shutdown.is_some() && !(finish_changes && io_event.is_change()) {
+      continue;
+    }
+
+    let cancellable = io_event.is_superseded_by_newer();
+    let kind = mem::discriminant(&io_event);
+    let in_flight = network.handle_network_event((io_event, workspace));
+    tokio::pin!(in_flight);
+    loop {
+      tokio::select! {
+        _ = &mut in_flight => break,
+        Some(next_event) = async_io_rx.recv() => {
+          if let (IoEvent::Shutdown { pause_playback }, _) = next_event {
+            stats.events_taken(1);
+            shutdown = Some(pause_playback);
+            // Nobody is left to see what a load brings back
+            if cancellable {
+              break;
+            }
+            continue;
+          }
+          // Each workspace waits on its own loads, so only a newer one from the same workspace
+          // supersedes a request
+          let superseded = |(event, from): &DispatchedEvent| {
+            mem::discriminant(event) == kind && *from == workspace
+          };
+          if cancellable && superseded(&next_event) {
+            // Drop the stale request, only the newest of its kind is worth finishing
+            let before = queued.len();
+            queued.retain(|queued_event| !superseded(queued_event));
+            stats.events_taken(before - queued.len());
+            queued.push_back(next_event);
+            break;
+          }
+          queued.push_back(next_event);
+        }
+      }
+    }
+  }
+
+  if shutdown == Some(true) {
+    network
+      .handle_network_event((IoEvent::PausePlayback, None))
+      .await;
+  }
+}
+
+fn handle_key_press(key: Key, app: &mut App) {
+  if key == app.user_config.keys.back {
+    // Go back through navigation stack when not in search input mode
+    // NOTE: Unlike before, we do NOT exit the app - only Ctrl-C should do that
+    let _pop_result = match app.pop_navigation_stack() {
+      Some(ref x) if x.id == RouteId::Search => app.pop_navigation_stack(),
+      Some(x) => Some(x),
+      None => None,
+    };
+  } else {
+    handlers::handle_app_with_count(key, app);
   }
 }
 
-async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()> {
+async fn start_ui(
+  user_config: UserConfig,
+  app: &Arc<Mutex<App>>,
+  events: event::Events,
+) -> Result<()> {
   // Terminal initialization
   let mut stdout = stdout();
-  execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+  execute!(
+    stdout,
+    EnterAlternateScreen,
+    EnableMouseCapture,
+    EnableBracketedPaste
+  )?;
   enable_raw_mode()?;
 
   let mut backend = CrosstermBackend::new(stdout);
@@ -461,11 +1028,20 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
   let mut terminal = Terminal::new(backend)?;
   terminal.hide_cursor()?;
 
-  let events = event::Events::new(user_config.behavior.tick_rate_milliseconds);
+  // Measured here rather than when loading the config, so commands that don't draw skip it
+  {
+    let mut app = app.lock().await;
+    let behavior = &mut app.user_config.behavior;
+    behavior.art_aspect_ratio = behavior.art_aspect_ratio.resolve();
+  }
+
+  #[cfg(unix)]
+  handle_job_control_signals(app, events.redraw_notifier());
 
   // play music on, if not send them to the device selection view
 
   let mut is_first_render = true;
+  let mut chord_state = ChordState::new();
 
   loop {
     let mut app = app.lock().await;
@@ -502,9 +1078,14 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
     let current_active_block = current_route.active_block.clone();
     
     // Wrap terminal draw in error handling to prevent freezing
+    let draw_started = Instant::now();
+    // Only set again if the breadcrumb is drawn this frame
+    app.breadcrumb_area.set(None);
     if let Err(e) = terminal.draw(|mut f| {
-      // Check for idle mode first
-      if app.is_idle_mode {
+      // Too small for anything but the mini layout, then check for idle mode
+      if ui::util::is_mini_layout(&app) {
+        ui::draw_mini_layout(&mut f, &app);
+      } else if app.is_idle_mode {
         ui::draw_idle_mode(&mut f, &app);
       } else {
         match current_active_block {
@@ -520,33 +1101,74 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
           ActiveBlock::LogStream => {
             ui::draw_log_stream_full_screen(&mut f, &app);
           }
+          ActiveBlock::ColorStory => {
+            ui::draw_color_story(&mut f, &app);
+          }
           _ => {
             ui::draw_main_layout(&mut f, &app);
           }
         }
       }
+      ui::draw_error_banner(&mut f, &app);
+      ui::draw_command_line(&mut f, &app);
+      ui::help::draw(&mut f, &app);
+      if app.show_debug_overlay {
+        ui::draw_debug_overlay(&mut f, &app);
+      }
+      ui::color::downsample_buffer(f.buffer_mut(), app.user_config.behavior.color_support);
     }) {
       // Log the error but continue running
-      app.add_log_message(format!("Terminal draw error: {}", e));
+      app.log(LogLevel::Warn, format!("Terminal draw error: {}", e));
     }
+    app
+      .frame_stats
+      .record(draw_started.elapsed(), Instant::now());
 
-    if current_active_block == ActiveBlock::Input {
+    let picker_open = current_active_block == ActiveBlock::Dialog(DialogContext::AddToPlaylist);
+    // The mini layout draws neither the search input nor the picker, only the command line
+    let typing = current_active_block == ActiveBlock::Input || picker_open;
+    if app.command_line.is_some() || (typing && !ui::util::is_mini_layout(&app)) {
       terminal.show_cursor()?;
     } else {
       terminal.hide_cursor()?;
     }
 
-    let cursor_offset = if app.size.height > ui::util::SMALL_TERMINAL_HEIGHT {
+    let cursor_offset = if ui::util::is_tall_layout(&app) {
       2
     } else {
       1
     };
 
-    // Put the cursor back inside the input box only if Input is active
-    if app.get_current_route().active_block == ActiveBlock::Input {
+    // The command line sits on the last row, after the `:`
+    if let Some(command_line) = &app.command_line {
+      terminal.backend_mut().execute(MoveTo(
+        1 + ui::util::display_width(&command_line.input),
+        app.size.height.saturating_sub(1),
+      ))?;
+    } else if let Some(picker) = app.playlist_picker.as_ref().filter(|_| picker_open) {
+      let picker_rect = ui::util::playlist_picker_rect(app.size);
+      terminal.backend_mut().execute(MoveTo(
+        picker_rect.x + 1 + ui::util::display_width(&picker.filter),
+        picker_rect.y + 1,
+      ))?;
+    } else if app.get_current_route().active_block == ActiveBlock::Input
+      && ui::util::is_ultra_small_layout(&app)
+    {
+      let popup = ui::util::search_popup_rect(app.size);
+      terminal.backend_mut().execute(MoveTo(
+        popup.x + 1 + app.input_cursor_position,
+        popup.y + 1,
+      ))?;
+    } else if app.get_current_route().active_block == ActiveBlock::Input {
+      // Put the cursor back inside the input box only if Input is active, which is below the
+      // playbar when that is at the top
+      let playbar_offset = match app.user_config.behavior.playbar_position {
+        ui::util::PlaybarPosition::Top => ui::util::playbar_height(app.size),
+        ui::util::PlaybarPosition::Bottom => 0,
+      };
       terminal.backend_mut().execute(MoveTo(
         cursor_offset + app.input_cursor_position,
-        cursor_offset,
+        cursor_offset + playbar_offset,
       ))?;
     }
 
@@ -562,9 +1184,10 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
     match events.next()? {
       event::Event::Input(key) => {
         // Check if this key should preserve idle mode
-        let preserve_idle_mode = app.is_idle_mode && matches!(key, Key::Char('v') | Key::Char('V'));
+        let preserve_idle_mode =
+          app.is_idle_mode && key == app.user_config.keys.switch_idle_animation;
         
-        // Reset idle timer on any user input (but don't exit idle mode for 'v')
+        // Reset idle timer on any user input (but don't exit idle mode when switching animation)
         if preserve_idle_mode {
           // Just reset the timer without exiting idle mode
           app.last_user_interaction = Instant::now();
@@ -581,23 +1204,75 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
 
         // To avoid swallowing global key presses make a special
         // case for the input handler
-        if current_active_block == ActiveBlock::Input {
+        if key == Key::Ctrl('z') {
+          app.suspend_requested = true;
+        } else if app.command_line.is_some() {
+          handlers::command_handler(key, &mut app);
+        } else if app.playlist_filter.is_some() {
+          handlers::playlist_filter_handler(key, &mut app);
+        } else if app.log_stream_search_input {
+          handlers::log_stream_search_handler(key, &mut app);
+        } else if app.picking_breadcrumb {
+          handlers::breadcrumb_handler(key, &mut app);
+        } else if app.help_scroll.is_some() {
+          handlers::help_handler(key, &mut app);
+        } else if current_active_block == ActiveBlock::Input {
           handlers::input_handler(key, &mut app);
-        } else if key == app.user_config.keys.back {
-          if app.get_current_route().active_block != ActiveBlock::Input {
-            // Go back through navigation stack when not in search input mode
-            // NOTE: Unlike before, we do NOT exit the app - only Ctrl-C should do that
-            let _pop_result = match app.pop_navigation_stack() {
-              Some(ref x) if x.id == RouteId::Search => app.pop_navigation_stack(),
-              Some(x) => Some(x),
-              None => None,
-            };
-            // Removed: if pop_result.is_none() { break; } - no longer exit on 'q'
+        } else if current_active_block == ActiveBlock::Dialog(DialogContext::AddToPlaylist) {
+          handlers::playlist_picker_handler(key, &mut app);
+        } else if current_active_block == ActiveBlock::Dialog(DialogContext::OpenIn) {
+          handlers::open_in_handler(key, &mut app);
+        } else if current_active_block == ActiveBlock::Dialog(DialogContext::Share) {
+          handlers::share_handler(key, &mut app);
+        } else {
+          match chord_state.handle_key(key, &app.user_config.chords) {
+            ChordEvent::Keys(keys) => {
+              for key in keys {
+                handle_key_press(key, &mut app);
+              }
+            }
+            ChordEvent::Action(action) => {
+              if let Some(key) = handlers::handle_chord_action(&action, &mut app) {
+                handle_key_press(key, &mut app);
+              }
+            }
           }
+        }
+
+        if app.should_quit {
+          break;
+        }
+      }
+      event::Event::Paste(text) => {
+        app.reset_idle_timer();
+        let current_active_block = app.get_current_route().active_block;
+
+        // Paste only ever goes into text, never setting off bindings. Fields other than the
+        // input box take it typed in a character at a time, as they did before
+        let type_into: Option<fn(Key, &mut App)> = if app.command_line.is_some() {
+          Some(handlers::command_handler)
+        } else if app.playlist_filter.is_some() {
+          Some(handlers::playlist_filter_handler)
+        } else if app.log_stream_search_input {
+          Some(handlers::log_stream_search_handler)
+        } else if current_active_block == ActiveBlock::Input {
+          handlers::input_paste_handler(&text, &mut app);
+          None
+        } else if current_active_block == ActiveBlock::Dialog(DialogContext::AddToPlaylist) {
+          Some(handlers::playlist_picker_handler)
         } else {
-          handlers::handle_app(key, &mut app);
+          None
+        };
+        if let Some(handler) = type_into {
+          for c in text.chars().filter(|c| !c.is_control()) {
+            handler(Key::Char(c), &mut app);
+          }
         }
       }
+      event::Event::Click(column, row) => {
+        app.reset_idle_timer();
+        handlers::breadcrumb_click(column, row, &mut app);
+      }
       event::Event::Resize(width, height) => {
         // Update size immediately to prevent blocking
         app.size = Rect::new(0, 0, width, height);
@@ -606,7 +1281,14 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
         // Don't do any complex operations here that could block
         // The size change will be handled in the next render loop iteration
       }
+      // Nothing to do, the loop draws the new state on its way round
+      event::Event::Redraw => {}
       event::Event::Tick => {
+        let chord_timeout = Duration::from_millis(app.user_config.behavior.chord_timeout_milliseconds);
+        for key in chord_state.flush_expired(chord_timeout) {
+          handle_key_press(key, &mut app);
+        }
+
         app.update_on_tick();
         // Check if we should enter idle mode
         let idle_timeout = app.user_config.behavior.idle_timeout_seconds;
@@ -614,6 +1296,16 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
       }
     }
 
+    if std::mem::take(&mut app.suspend_requested) {
+      #[cfg(unix)]
+      suspend(&mut terminal)?;
+      #[cfg(not(unix))]
+      app.show_toast("Suspending isn't supported on this platform".to_string());
+    }
+    if std::mem::take(&mut app.resume_requested) {
+      resume(&mut terminal)?;
+    }
+
     // Delay spotify request until first render, will have the effect of improving
     // startup speed
     if is_first_render {
@@ -629,5 +1321,18 @@ async fn start_ui(user_config: UserConfig, app: &Arc<Mutex<App>>) -> Result<()>
   terminal.show_cursor()?;
   close_application()?;
 
+  // Episode positions and search history are always kept, only the session is optional
+  if let Err(e) = app.lock().await.episode_positions.save() {
+    eprintln!("Failed to save episode positions: {}", e);
+  }
+  if let Err(e) = app.lock().await.search_history.save() {
+    eprintln!("Failed to save search history: {}", e);
+  }
+  if user_config.behavior.save_state_on_quit {
+    if let Err(e) = Session::capture(&*app.lock().await).save() {
+      eprintln!("Failed to save the session: {}", e);
+    }
+  }
+
   Ok(())
 }
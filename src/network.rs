@@ -1,9 +1,15 @@
 use crate::app::{
-  ActiveBlock, AlbumTableContext, App, Artist, ArtistBlock, EpisodeTableContext, RouteId,
-  ScrollableResultPages, SelectedAlbum, SelectedFullAlbum, SelectedFullShow, SelectedShow,
-  TrackTableContext,
+  App, Artist, ArtistBlock, EpisodeTableContext, LogLevel, RouteId, ScrollableResultPages,
+  SearchResult, SelectedFullAlbum, SelectedFullShow, TopTimeRange, TrackTableContext,
 };
 use crate::config::ClientConfig;
+use crate::debug_stats::{self, NetworkStats};
+use crate::duplicates::{self, Duplicate, ScannedTrack};
+use crate::event::RedrawNotifier;
+use crate::import;
+use crate::paths;
+use crate::search_filter;
+use crate::workspace::ResponseGuard;
 use anyhow::Result;
 use rspotify::{
   AuthCodeSpotify,
@@ -18,17 +24,21 @@ use rspotify::{
     show::SimplifiedEpisode,
     PlayableItem,
     CurrentPlaybackContext,
-    enums::{Country, RepeatState as SpotifyRepeatState, SearchType, AdditionalType},
+    audio::{AudioAnalysis, AudioFeatures},
+    enums::{Country, RepeatState as SpotifyRepeatState, SearchType, AdditionalType, TimeRange},
   },
 };
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json;
 use std::{
+  collections::{HashMap, HashSet},
+  fs,
+  path::{Path, PathBuf},
   sync::Arc,
   time::{Duration, Instant, SystemTime},
-  fs::OpenOptions,
-  io::Write,
 };
 use tokio::sync::Mutex;
+use tracing::{debug, warn, Instrument};
 use futures::stream::TryStreamExt;
 use chrono::{Duration as ChronoDuration};
 
@@ -37,9 +47,19 @@ pub enum IoEvent {
   GetPlaylists,
   GetUser,
   GetCurrentPlayback,
+  GetUpNext,
   UpdateSearchLimits(u32, u32),
   RefreshAuthentication,
   GetPlaylistTracks(String, u32),
+  GetMadeForYouPlaylists,
+  GetMadeForYouPlaylistTracks(String),
+  GetFeaturedPlaylists,
+  GetFeaturedPlaylistTracks(String),
+  GetCategories,
+  GetCategoryPlaylists(String),
+  GetCategoryPlaylistTracks(String),
+  GetNewReleases(Option<u32>),
+  ToggleSaveAlbum(String),
   GetAlbumTracks(String),
   GetArtist(String),
   GetArtistAlbums(String),
@@ -47,7 +67,11 @@ pub enum IoEvent {
   GetEpisodes(String),
   GetRecommendations(String, String, String, String, String),
   GetSearchResults(String),
-  StartPlayback(Option<String>, Option<String>),
+  GetSearchResultsPage(SearchType, u32),
+  StartPlayback(Option<String>, Option<PlaybackOffset>),
+  PlayTrackList(Vec<String>, usize),
+  // Play recommendations seeded by these track IDs once the context has run out
+  StartAutoplay(Vec<String>),
   PausePlayback,
   NextTrack,
   PreviousTrack,
@@ -57,12 +81,19 @@ pub enum IoEvent {
   VolumeUp,
   VolumeDown,
   SetVolume(u8),
+  // Set the volume of a device from the picker, which needn't be the active one
+  SetDeviceVolume(String, u8),
   TransferPlaybackToDevice(String),
   GetDevices,
+  // Start or stop spotui's own playback device, with the streaming feature
+  StartStreaming,
+  StopStreaming,
   ToggleSaveTrack(String),
   GetAudioAnalysis(String),
+  GetAudioFeatures(String),
   AddItemToQueue(String),
   CurrentUserSavedAlbumAdd(String),
+  CurrentUserSavedAlbumDelete(String),
   GetShowEpisodes(Box<SimplifiedShow>),
   GetAlbum(String),
   GetAlbumForTrack(String),
@@ -74,6 +105,411 @@ pub enum IoEvent {
   GetTopTracks,
   GetTopArtists,
   FetchAlbumArt(String),
+  // Save the cover at this URL to behavior.now_playing_art_file
+  WriteNowPlayingArt(String),
+  FetchProfileImage(String),
+  CheckFollowedArtists(Vec<String>),
+  UserFollowArtists(Vec<String>),
+  UserUnfollowArtists(Vec<String>),
+  CheckLikedTracks(Vec<String>),
+  CheckSavedAlbums(Vec<String>),
+  CheckSavedShows(Vec<String>),
+  PlayEpisode(String, Option<u32>),
+  SearchAndPlay(String),
+  AddItemsToQueue(Vec<String>),
+  ToggleSaveTracks(Vec<String>),
+  AddTracksToPlaylist(String, Vec<String>),
+  CreatePlaylist(String),
+  RenamePlaylist(String, String),
+  UserUnfollowPlaylist(String),
+  RemoveTracksFromPlaylist(String, Vec<String>),
+  ImportTracks(String, String),
+  FindPlaylistDuplicates(String),
+  RemovePlaylistDuplicates(String, Vec<Duplicate>),
+  // Sent last on quitting, for the event loop to finish up and stop, pausing playback first if
+  // asked to
+  Shutdown { pause_playback: bool },
+}
+
+impl IoEvent {
+  /// Whether a newer event of the same kind makes this one pointless because both load the same
+  /// view, e.g. a new search while the previous one is still running
+  pub fn is_superseded_by_newer(&self) -> bool {
+    matches!(
+      self,
+      IoEvent::GetSearchResults(_)
+        | IoEvent::GetPlaylistTracks(..)
+        | IoEvent::GetMadeForYouPlaylistTracks(_)
+        | IoEvent::GetFeaturedPlaylistTracks(_)
+        | IoEvent::GetCategoryPlaylists(_)
+        | IoEvent::GetCategoryPlaylistTracks(_)
+        | IoEvent::GetAlbumTracks(_)
+        | IoEvent::GetArtist(_)
+        | IoEvent::GetRecommendations(..)
+        | IoEvent::GetAudioAnalysis(_)
+        | IoEvent::GetAudioFeatures(_)
+        | IoEvent::FetchAlbumArt(_)
+        | IoEvent::GetCurrentSavedTracks(_)
+        | IoEvent::GetCurrentUserSavedAlbums(_)
+        | IoEvent::GetTopTracks
+        | IoEvent::GetTopArtists
+        | IoEvent::GetRecentlyPlayed
+    )
+  }
+
+  /// Whether this changes the user's library, playlists or queue, so quitting with it still
+  /// waiting would lose something they asked for
+  pub fn is_change(&self) -> bool {
+    matches!(
+      self,
+      IoEvent::ToggleSaveAlbum(_)
+        | IoEvent::ToggleSaveTrack(_)
+        | IoEvent::ToggleSaveTracks(_)
+        | IoEvent::CurrentUserSavedAlbumAdd(_)
+        | IoEvent::CurrentUserSavedAlbumDelete(_)
+        | IoEvent::UserFollowArtists(_)
+        | IoEvent::UserUnfollowArtists(_)
+        | IoEvent::AddItemToQueue(_)
+        | IoEvent::AddItemsToQueue(_)
+        | IoEvent::AddTracksToPlaylist(..)
+        | IoEvent::CreatePlaylist(_)
+        | IoEvent::RenamePlaylist(..)
+        | IoEvent::UserUnfollowPlaylist(_)
+        | IoEvent::RemoveTracksFromPlaylist(..)
+        | IoEvent::ImportTracks(..)
+        | IoEvent::RemovePlaylistDuplicates(..)
+    )
+  }
+
+  /// Jobs of many requests, which time out request by request and show how far they have got
+  /// rather than being cut off partway by the timeout for a whole event
+  pub fn is_long_running(&self) -> bool {
+    matches!(
+      self,
+      IoEvent::ImportTracks(..) | IoEvent::RemovePlaylistDuplicates(..)
+    )
+  }
+
+  /// Whether Spotify only does this for Premium accounts: everything that controls playback
+  pub fn requires_premium(&self) -> bool {
+    matches!(
+      self,
+      IoEvent::StartPlayback(..)
+        | IoEvent::PlayTrackList(..)
+        | IoEvent::StartAutoplay(_)
+        | IoEvent::PausePlayback
+        | IoEvent::NextTrack
+        | IoEvent::PreviousTrack
+        | IoEvent::Seek(_)
+        | IoEvent::Shuffle(_)
+        | IoEvent::Repeat(_)
+        | IoEvent::VolumeUp
+        | IoEvent::VolumeDown
+        | IoEvent::SetVolume(_)
+        | IoEvent::SetDeviceVolume(..)
+        | IoEvent::TransferPlaybackToDevice(_)
+        | IoEvent::StartStreaming
+        | IoEvent::AddItemToQueue(_)
+        | IoEvent::AddItemsToQueue(_)
+        | IoEvent::PlayEpisode(..)
+        | IoEvent::SearchAndPlay(_)
+    )
+  }
+
+  /// The route showing what this event loads, which stays in its loading state until it is done
+  pub fn loaded_route(&self) -> Option<RouteId> {
+    match self {
+      IoEvent::GetPlaylistTracks(..)
+      | IoEvent::GetMadeForYouPlaylistTracks(_)
+      | IoEvent::GetFeaturedPlaylistTracks(_)
+      | IoEvent::GetCategoryPlaylistTracks(_)
+      | IoEvent::GetAlbum(_)
+      | IoEvent::GetAlbumForTrack(_) => Some(RouteId::TrackTable),
+      IoEvent::GetMadeForYouPlaylists => Some(RouteId::MadeForYou),
+      IoEvent::GetFeaturedPlaylists => Some(RouteId::FeaturedPlaylists),
+      IoEvent::GetCategories => Some(RouteId::Categories),
+      IoEvent::GetCategoryPlaylists(_) => Some(RouteId::CategoryPlaylists),
+      IoEvent::GetUser => Some(RouteId::Profile),
+      IoEvent::GetNewReleases(_) => Some(RouteId::NewReleases),
+      IoEvent::GetShow(_) => Some(RouteId::PodcastEpisodes),
+      IoEvent::GetArtist(_) => Some(RouteId::Artist),
+      _ => None,
+    }
+  }
+}
+
+/// Whether a playback request failed because no device is active: a 404 whose body gives
+/// `NO_ACTIVE_DEVICE` as the reason. Other 404s, like an unknown track, don't count. Reads the body
+/// out of the error, which keeps its status for reporting.
+pub async fn is_no_active_device(error: &mut rspotify::ClientError) -> bool {
+  let response = match error {
+    rspotify::ClientError::Http(http) => match http.as_mut() {
+      rspotify::http::HttpError::StatusCode(response) => response,
+      _ => return false,
+    },
+    _ => return false,
+  };
+  if response.status().as_u16() != 404 {
+    return false;
+  }
+  let mut body = Vec::new();
+  while let Ok(Some(chunk)) = response.chunk().await {
+    body.extend_from_slice(&chunk);
+  }
+  serde_json::from_slice::<serde_json::Value>(&body)
+    .map(|body| body["error"]["reason"] == "NO_ACTIVE_DEVICE")
+    .unwrap_or(false)
+}
+
+/// The ID of the playing track or episode, to notice when playback moves on
+fn playing_item_id(context: &CurrentPlaybackContext) -> Option<String> {
+  match context.item.as_ref()? {
+    PlayableItem::Track(track) => track.id.as_ref().map(|id| id.to_string()),
+    PlayableItem::Episode(episode) => Some(episode.id.to_string()),
+  }
+}
+
+// Leeway for how far from a track's end playback can stop and still count as having played out
+const TRACK_END_SLACK_MS: i64 = 2_000;
+
+/// Whether playback stopped by itself because the context ran out, rather than being paused:
+/// the last track was playing out with nothing up next, and playback is now gone or stopped at
+/// the start or end of a track
+fn context_finished(
+  previous: &CurrentPlaybackContext,
+  current: Option<&CurrentPlaybackContext>,
+  since_previous: Duration,
+  nothing_up_next: bool,
+) -> bool {
+  let progress_ms = |context: &CurrentPlaybackContext| {
+    context
+      .progress
+      .map_or(0, |progress| progress.num_milliseconds())
+  };
+  let track_duration_ms = |context: &CurrentPlaybackContext| match &context.item {
+    Some(PlayableItem::Track(track)) => Some(track.duration.num_milliseconds()),
+    _ => None,
+  };
+
+  let played_out = match track_duration_ms(previous) {
+    Some(duration_ms) => {
+      progress_ms(previous) + since_previous.as_millis() as i64 >= duration_ms - TRACK_END_SLACK_MS
+    }
+    None => return false,
+  };
+  let stopped = match current {
+    None => true,
+    Some(current) => {
+      let progress = progress_ms(current);
+      !current.is_playing
+        && (progress < TRACK_END_SLACK_MS
+          || track_duration_ms(current)
+            .is_none_or(|duration_ms| progress >= duration_ms - TRACK_END_SLACK_MS))
+    }
+  };
+
+  previous.is_playing
+    && previous.repeat_state == SpotifyRepeatState::Off
+    && nothing_up_next
+    && played_out
+    && stopped
+}
+
+/// Add the saved ones of `ids` to `set` and drop the rest, given the answer of a contains check
+fn update_saved_set<T: std::fmt::Display>(set: &mut HashSet<String>, ids: &[T], saved: Vec<bool>) {
+  for (id, is_saved) in ids.iter().zip(saved) {
+    if is_saved {
+      set.insert(id.to_string());
+    } else {
+      set.remove(&id.to_string());
+    }
+  }
+}
+
+const APP_CACHE_DIR: &str = "spotify-tui";
+
+// Files a disk cache keeps before the least recently written are deleted
+const MAX_DISK_CACHE_FILES: usize = 200;
+
+/// The most items Spotify adds to or removes from a playlist in one request
+pub const PLAYLIST_ITEMS_PER_REQUEST: usize = 100;
+
+/// API answers about a track that never change, such as its audio analysis, kept for the most
+/// recently used tracks and optionally on disk so they outlive the session
+pub struct TrackCache<T> {
+  capacity: usize,
+  // Least recently used first
+  entries: Vec<(String, T)>,
+  dir: Option<PathBuf>,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> TrackCache<T> {
+  pub fn new(capacity: usize) -> Self {
+    TrackCache {
+      capacity,
+      entries: vec![],
+      dir: None,
+    }
+  }
+
+  /// Also keep entries as files in `name` under the cache directory
+  pub fn use_disk_cache(&mut self, name: &str) {
+    self.dir = dirs::cache_dir().map(|cache| cache.join(APP_CACHE_DIR).join(name));
+  }
+
+  pub fn get(&mut self, track_id: &str) -> Option<T> {
+    if let Some(index) = self.entries.iter().position(|(id, _)| id == track_id) {
+      let entry = self.entries.remove(index);
+      let value = entry.1.clone();
+      self.entries.push(entry);
+      return Some(value);
+    }
+    let value: T = self
+      .file_path(track_id)
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|contents| serde_json::from_str(&contents).ok())?;
+    self.remember(track_id, value.clone());
+    Some(value)
+  }
+
+  /// Remember `value`, failing only if it couldn't be written to disk
+  pub fn insert(&mut self, track_id: &str, value: T) -> Result<()> {
+    let saved = match (self.file_path(track_id), &self.dir) {
+      (Some(path), Some(dir)) => Self::save(dir, &path, &value),
+      _ => Ok(()),
+    };
+    self.remember(track_id, value);
+    saved
+  }
+
+  fn remember(&mut self, track_id: &str, value: T) {
+    self.entries.retain(|(id, _)| id != track_id);
+    self.entries.push((track_id.to_string(), value));
+    if self.entries.len() > self.capacity {
+      self.entries.remove(0);
+    }
+  }
+
+  /// Where a track's entry is kept on disk. Only plain IDs get one, so a key can't point
+  /// outside the directory.
+  fn file_path(&self, track_id: &str) -> Option<PathBuf> {
+    if track_id.is_empty() || !track_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+      return None;
+    }
+    Some(self.dir.as_ref()?.join(format!("{}.json", track_id)))
+  }
+
+  fn save(dir: &Path, path: &Path, value: &T) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(path, serde_json::to_string(value)?)?;
+
+    let mut files: Vec<_> = fs::read_dir(dir)?
+      .filter_map(|entry| {
+        let entry = entry.ok()?;
+        Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+      })
+      .collect();
+    if files.len() > MAX_DISK_CACHE_FILES {
+      files.sort();
+      for (_, path) in &files[..files.len() - MAX_DISK_CACHE_FILES] {
+        fs::remove_file(path)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+// Names of the playlists Spotify makes for each user, in the order Made For You lists them
+const MADE_FOR_YOU_NAMES: [&str; 5] = [
+  "Discover Weekly",
+  "Release Radar",
+  "Daily Mix",
+  "On Repeat",
+  "Repeat Rewind",
+];
+
+/// Where a playlist goes in Made For You, if it is one Spotify made for the user: owned by
+/// Spotify and named like one of them, the Daily Mixes being numbered
+fn made_for_you_rank(playlist: &SimplifiedPlaylist) -> Option<usize> {
+  let owner = playlist.owner.id.to_string();
+  if owner.trim_start_matches("spotify:user:") != "spotify" {
+    return None;
+  }
+  MADE_FOR_YOU_NAMES
+    .iter()
+    .position(|name| playlist.name.starts_with(name))
+}
+
+/// The check for which of the shown `search_type` search results are saved, for their ♥ markers
+fn search_results_saved_check(results: &SearchResult, search_type: SearchType) -> Option<IoEvent> {
+  match search_type {
+    SearchType::Track => {
+      let track_ids = results
+        .tracks
+        .iter()
+        .flat_map(|page| page.items.iter())
+        .filter_map(|track| track.id.as_ref().map(|id| id.to_string()))
+        .collect();
+      Some(IoEvent::CheckLikedTracks(track_ids))
+    }
+    SearchType::Artist => {
+      let artist_ids = results
+        .artists
+        .iter()
+        .flat_map(|page| page.items.iter())
+        .map(|artist| artist.id.to_string())
+        .collect();
+      Some(IoEvent::CheckFollowedArtists(artist_ids))
+    }
+    SearchType::Album => {
+      let album_ids = results
+        .albums
+        .iter()
+        .flat_map(|page| page.items.iter())
+        .filter_map(|album| album.id.as_ref().map(|id| id.to_string()))
+        .collect();
+      Some(IoEvent::CheckSavedAlbums(album_ids))
+    }
+    SearchType::Show => {
+      let show_ids = results
+        .shows
+        .iter()
+        .flat_map(|page| page.items.iter())
+        .map(|show| show.id.to_string())
+        .collect();
+      Some(IoEvent::CheckSavedShows(show_ids))
+    }
+    _ => None,
+  }
+}
+
+fn spotify_time_range(time_range: TopTimeRange) -> TimeRange {
+  match time_range {
+    TopTimeRange::FourWeeks => TimeRange::ShortTerm,
+    TopTimeRange::SixMonths => TimeRange::MediumTerm,
+    TopTimeRange::AllTime => TimeRange::LongTerm,
+  }
+}
+
+/// The track in an album or playlist to start playback at
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlaybackOffset {
+  // Misses local tracks and tracks Spotify relinked to another ID
+  Uri(String),
+  // The track's index in the album or playlist
+  Position(u32),
+}
+
+impl From<PlaybackOffset> for rspotify::model::Offset {
+  fn from(offset: PlaybackOffset) -> Self {
+    match offset {
+      PlaybackOffset::Uri(uri) => rspotify::model::Offset::Uri(uri),
+      // rspotify carries the index as the milliseconds of a duration
+      PlaybackOffset::Position(position) => {
+        rspotify::model::Offset::Position(ChronoDuration::milliseconds(i64::from(position)))
+      }
+    }
+  }
 }
 
 // Compatibility types
@@ -113,13 +549,24 @@ impl From<PlayableItem> for PlayingItem {
   }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RepeatState {
   Off,
   Track,
   Context,
 }
 
+impl RepeatState {
+  /// The state the repeat key moves on to: off, then the whole context, then the one track
+  pub fn next(&self) -> RepeatState {
+    match self {
+      RepeatState::Off => RepeatState::Context,
+      RepeatState::Context => RepeatState::Track,
+      RepeatState::Track => RepeatState::Off,
+    }
+  }
+}
+
 impl From<SpotifyRepeatState> for RepeatState {
   fn from(state: SpotifyRepeatState) -> Self {
     match state {
@@ -140,39 +587,117 @@ impl Into<SpotifyRepeatState> for RepeatState {
   }
 }
 
+/// An event and the workspace it was dispatched from, `None` for the few sent from outside the UI
+pub type DispatchedEvent = (IoEvent, Option<usize>);
+
+/// The shared app, locked on behalf of the event being handled so that what it brings back lands
+/// in the workspace it came from
+pub struct EventApp {
+  app: Arc<Mutex<App>>,
+  workspace: Option<usize>,
+}
+
+impl EventApp {
+  pub async fn lock(&self) -> ResponseGuard<'_> {
+    ResponseGuard::new(self.app.lock().await, self.workspace)
+  }
+
+  pub fn try_lock(&self) -> Option<ResponseGuard<'_>> {
+    let app = self.app.try_lock().ok()?;
+    Some(ResponseGuard::new(app, self.workspace))
+  }
+}
+
 pub struct Network {
   pub spotify: AuthCodeSpotify,
   pub client_config: ClientConfig,
-  pub app: Arc<Mutex<App>>,
+  pub app: EventApp,
   pub large_search_limit: u32,
   pub small_search_limit: u32,
+  /// Longest a single event may take before it is abandoned
+  pub request_timeout: Duration,
+  pub analysis_cache: TrackCache<AudioAnalysis>,
+  pub features_cache: TrackCache<AudioFeatures>,
+  // Whether the first device listing has had its chance to move playback to the saved device
+  restored_device: bool,
+  #[cfg(feature = "streaming")]
+  streaming_device: Option<crate::streaming::StreamingDevice>,
+  redraw_notifier: RedrawNotifier,
+  /// Queue depth, in-flight request and poll latency for the debug overlay
+  pub stats: Arc<NetworkStats>,
 }
 
 impl Network {
-  pub fn new(spotify: AuthCodeSpotify, client_config: ClientConfig, app: &Arc<Mutex<App>>) -> Self {
+  pub fn new(
+    spotify: AuthCodeSpotify,
+    client_config: ClientConfig,
+    app: &Arc<Mutex<App>>,
+    redraw_notifier: RedrawNotifier,
+  ) -> Self {
     Self {
       spotify,
       client_config,
-      app: Arc::clone(app),
+      app: EventApp {
+        app: Arc::clone(app),
+        workspace: None,
+      },
       large_search_limit: 20,
       small_search_limit: 4,
+      request_timeout: Duration::from_secs(30),
+      // Analyses run to hundreds of kilobytes each, features are a few numbers
+      analysis_cache: TrackCache::new(20),
+      features_cache: TrackCache::new(500),
+      restored_device: false,
+      #[cfg(feature = "streaming")]
+      streaming_device: None,
+      redraw_notifier,
+      stats: Arc::default(),
     }
   }
 
-  fn log_error(&self, message: &str) {
-    // Don't print to stdout - this interferes with TUI
-    
-    if let Ok(mut file) = OpenOptions::new()
-      .create(true)
-      .append(true)
-      .open("/tmp/spotify-tui-errors.log") 
-    {
-      let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S");
-      let _ = writeln!(file, "[{}] {}", timestamp, message);
+  pub async fn handle_network_event(&mut self, (io_event, workspace): DispatchedEvent) {
+    self.app.workspace = workspace;
+    let request_timeout = self.request_timeout;
+    let loaded_route = io_event.loaded_route();
+    let is_poll = matches!(io_event, IoEvent::GetCurrentPlayback);
+    let is_long_running = io_event.is_long_running();
+    let stats = Arc::clone(&self.stats);
+    let description = format!("{:?}", io_event);
+    let _running = stats.start_request(debug_stats::event_name(&description));
+    let started = Instant::now();
+    // Whatever a request logs is tagged with the event behind it
+    let span = tracing::debug_span!("request", event = %description);
+    let request = self.dispatch_network_event(io_event).instrument(span);
+    let timed_out = if is_long_running {
+      request.await;
+      false
+    } else {
+      tokio::time::timeout(request_timeout, request)
+        .await
+        .is_err()
+    };
+    if is_poll && !timed_out {
+      stats.record_poll(started.elapsed());
+    }
+    if timed_out {
+      let mut app = self.app.lock().await;
+      app.is_loading = false;
+      app.handle_error(anyhow::anyhow!(
+        "Spotify request timed out after {}s",
+        request_timeout.as_secs()
+      ));
+    }
+    // A failed load leaves the route as it was rather than loading forever
+    if let Some(route_id) = loaded_route {
+      let mut app = self.app.lock().await;
+      app.finish_loading_route(route_id, false);
     }
+
+    // Show whatever just loaded right away instead of on the next tick
+    self.redraw_notifier.notify();
   }
 
-  pub async fn handle_network_event(&mut self, io_event: IoEvent) {
+  async fn dispatch_network_event(&mut self, io_event: IoEvent) {
     match io_event {
       IoEvent::GetPlaylists => {
         self.get_playlists().await;
@@ -183,6 +708,9 @@ impl Network {
       IoEvent::GetCurrentPlayback => {
         self.get_current_playback().await;
       }
+      IoEvent::GetUpNext => {
+        self.get_up_next().await;
+      }
       IoEvent::UpdateSearchLimits(large, small) => {
         self.large_search_limit = large;
         self.small_search_limit = small;
@@ -190,12 +718,60 @@ impl Network {
       IoEvent::RefreshAuthentication => {
         self.refresh_authentication().await;
       }
-      IoEvent::GetPlaylistTracks(playlist_id, offset) => {
-        self.get_playlist_tracks(&playlist_id, offset).await;
+      // The whole playlist loads, whichever page of it the track table was on
+      IoEvent::GetPlaylistTracks(playlist_id, _) => {
+        self
+          .get_playlist_tracks(&playlist_id, TrackTableContext::MyPlaylists)
+          .await;
+      }
+      IoEvent::GetMadeForYouPlaylists => {
+        self.get_made_for_you_playlists().await;
+      }
+      IoEvent::GetMadeForYouPlaylistTracks(playlist_id) => {
+        self
+          .get_playlist_tracks(&playlist_id, TrackTableContext::MadeForYou)
+          .await;
+      }
+      IoEvent::GetFeaturedPlaylists => {
+        self.get_featured_playlists().await;
+      }
+      IoEvent::GetFeaturedPlaylistTracks(playlist_id) => {
+        self
+          .get_playlist_tracks(&playlist_id, TrackTableContext::FeaturedPlaylists)
+          .await;
+      }
+      IoEvent::GetCategories => {
+        self.get_categories().await;
+      }
+      IoEvent::GetCategoryPlaylists(category_id) => {
+        self.get_category_playlists(&category_id).await;
+      }
+      IoEvent::GetCategoryPlaylistTracks(playlist_id) => {
+        self
+          .get_playlist_tracks(&playlist_id, TrackTableContext::CategoryPlaylists)
+          .await;
+      }
+      IoEvent::GetNewReleases(offset) => {
+        self.get_new_releases(offset).await;
+      }
+      IoEvent::ToggleSaveAlbum(album_id) => {
+        self.toggle_save_album(album_id).await;
       }
       IoEvent::StartPlayback(context_uri, offset) => {
         self.start_playback(context_uri.as_deref(), offset).await;
       }
+      IoEvent::PlayTrackList(track_uris, offset) => {
+        self.play_track_list(track_uris, offset).await;
+      }
+      IoEvent::StartAutoplay(seed_track_ids) => {
+        self.start_autoplay(seed_track_ids).await;
+      }
+      IoEvent::StartStreaming => {
+        self.start_streaming().await;
+      }
+      IoEvent::StopStreaming => {
+        self.stop_streaming().await;
+      }
       IoEvent::PausePlayback => {
         self.pause_playback().await;
       }
@@ -215,30 +791,72 @@ impl Network {
         self.repeat(state).await;
       }
       IoEvent::SetVolume(volume) => {
-        self.set_volume(volume).await;
+        self.set_volume(volume, None).await;
+      }
+      IoEvent::SetDeviceVolume(device_id, volume) => {
+        self.set_volume(volume, Some(device_id)).await;
       }
       IoEvent::TransferPlaybackToDevice(device_id) => {
-        self.transfer_playback_to_device(device_id).await;
+        self.transfer_playback_to_device(device_id, true).await;
       }
       IoEvent::GetDevices => {
         self.get_devices().await;
       }
       IoEvent::ToggleSaveTrack(track_id) => {
-        // TODO: Implement toggle save track
-        self.log_error(&format!("TODO: ToggleSaveTrack: {}", track_id));
+        self.toggle_save_tracks(vec![track_id]).await;
+      }
+      IoEvent::ToggleSaveTracks(track_ids) => {
+        self.toggle_save_tracks(track_ids).await;
+      }
+      IoEvent::AddItemsToQueue(uris) => {
+        self.add_items_to_queue(uris).await;
+      }
+      IoEvent::AddTracksToPlaylist(playlist_id, track_uris) => {
+        self.add_tracks_to_playlist(playlist_id, track_uris).await;
+      }
+      IoEvent::CreatePlaylist(name) => {
+        self.create_playlist(name).await;
+      }
+      IoEvent::RenamePlaylist(playlist_id, name) => {
+        self.rename_playlist(playlist_id, name).await;
+      }
+      IoEvent::UserUnfollowPlaylist(playlist_id) => {
+        self.user_unfollow_playlist(playlist_id).await;
+      }
+      IoEvent::RemoveTracksFromPlaylist(playlist_id, track_uris) => {
+        self.remove_tracks_from_playlist(playlist_id, track_uris).await;
+      }
+      IoEvent::ImportTracks(path, playlist_id) => {
+        self.import_tracks(path, playlist_id).await;
+      }
+      IoEvent::FindPlaylistDuplicates(playlist_id) => {
+        self.find_playlist_duplicates(playlist_id).await;
+      }
+      IoEvent::RemovePlaylistDuplicates(playlist_id, duplicates) => {
+        self.remove_playlist_duplicates(playlist_id, duplicates).await;
       }
       IoEvent::AddItemToQueue(uri) => {
-        // TODO: Implement add to queue
-        self.log_error(&format!("TODO: AddItemToQueue: {}", uri));
+        self.add_item_to_queue(uri).await;
+      }
+      IoEvent::SearchAndPlay(query) => {
+        self.search_and_play(query).await;
       }
       IoEvent::CurrentUserSavedAlbumAdd(album_id) => {
         // TODO: Implement save album
         // TODO: Implement CurrentUserSavedAlbumAdd
       }
+      IoEvent::CurrentUserSavedAlbumDelete(album_id) => {
+        self.current_user_saved_album_delete(album_id).await;
+      }
+      // The event loop stops on this before it gets here
+      IoEvent::Shutdown { .. } => {}
       IoEvent::GetShowEpisodes(show) => {
         // TODO: Implement get show episodes
         // TODO: Implement GetShowEpisodes
       }
+      IoEvent::GetShow(show_id) => {
+        self.get_show(show_id).await;
+      }
       IoEvent::GetArtist(artist_id) => {
         self.get_artist(artist_id).await;
       }
@@ -246,12 +864,10 @@ impl Network {
         self.get_album_tracks(album_id).await;
       }
       IoEvent::GetAlbum(album_id) => {
-        // TODO: Implement get album
-        // TODO: Implement GetAlbum
+        self.get_album_tracks(album_id).await;
       }
       IoEvent::GetAlbumForTrack(track_id) => {
-        // TODO: Implement get album for track
-        // TODO: Implement GetAlbumForTrack
+        self.get_album_for_track(track_id).await;
       }
       IoEvent::GetRecentlyPlayed => {
         self.get_recently_played().await;
@@ -274,9 +890,58 @@ impl Network {
       IoEvent::GetTopArtists => {
         self.get_top_artists().await;
       }
+      IoEvent::WriteNowPlayingArt(url) => {
+        let path = {
+          let app = self.app.lock().await;
+          app.user_config.behavior.now_playing_art_file.clone()
+        };
+        if let Err(e) = crate::now_playing::write_art(&path, &url).await {
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            format!("Failed to write the now playing cover: {}", e),
+          );
+        }
+      }
       IoEvent::FetchAlbumArt(url) => {
         self.fetch_album_art(url).await;
       }
+      IoEvent::FetchProfileImage(url) => {
+        self.fetch_profile_image(url).await;
+      }
+      IoEvent::GetAudioAnalysis(track_id) => {
+        self.get_audio_analysis(track_id).await;
+      }
+      IoEvent::GetAudioFeatures(track_id) => {
+        self.get_audio_features(track_id).await;
+      }
+      IoEvent::CheckFollowedArtists(artist_ids) => {
+        self.check_followed_artists(artist_ids).await;
+      }
+      IoEvent::UserFollowArtists(artist_ids) => {
+        self.user_follow_artists(artist_ids, true).await;
+      }
+      IoEvent::UserUnfollowArtists(artist_ids) => {
+        self.user_follow_artists(artist_ids, false).await;
+      }
+      IoEvent::GetSearchResults(query) => {
+        self.get_search_results(query).await;
+      }
+      IoEvent::GetSearchResultsPage(search_type, offset) => {
+        self.get_search_results_page(search_type, offset).await;
+      }
+      IoEvent::CheckLikedTracks(track_ids) => {
+        self.check_liked_tracks(track_ids).await;
+      }
+      IoEvent::CheckSavedAlbums(album_ids) => {
+        self.check_saved_albums(album_ids).await;
+      }
+      IoEvent::CheckSavedShows(show_ids) => {
+        self.check_saved_shows(show_ids).await;
+      }
+      IoEvent::PlayEpisode(episode_uri, position_ms) => {
+        self.play_episode(episode_uri, position_ms).await;
+      }
       // Add more handlers as needed
       _ => {
         // Unhandled network event
@@ -285,7 +950,7 @@ impl Network {
   }
 
   async fn get_playlists(&mut self) {
-    self.log_error("DEBUG: Starting get_playlists");
+    debug!("Starting get_playlists");
     use futures::StreamExt;
     
     let mut stream = self.spotify.current_user_playlists();
@@ -302,10 +967,11 @@ impl Network {
           }
         }
         Err(e) => {
-          let error_msg = format!("DETAILED ERROR getting playlists: {:?}", e);
-          let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-          self.log_error(&error_msg);
-          self.log_error(&type_msg);
+          debug!(
+            error = ?e,
+            error_type = std::any::type_name_of_val(&e),
+            "Failed to get playlists"
+          );
           let mut app = self.app.lock().await;
           app.handle_error(anyhow::anyhow!("Failed to load playlists: {}", e));
           return;
@@ -313,7 +979,7 @@ impl Network {
       }
     }
     
-    self.log_error(&format!("SUCCESS: Got {} playlists", playlists.len()));
+    debug!("Got {} playlists", playlists.len());
     
     // Store playlists in app state
     let mut app = self.app.lock().await;
@@ -327,7 +993,13 @@ impl Network {
       previous: None,
       href: String::new(),
     };
+    app.playlist_spotify_order = page
+      .items
+      .iter()
+      .map(|playlist| playlist.id.to_string())
+      .collect();
     app.playlists = Some(page);
+    app.sort_playlists();
     // Set loading to false after playlists are loaded
     app.is_loading = false;
   }
@@ -336,9 +1008,18 @@ impl Network {
     match self.spotify.me().await {
       Ok(user) => {
         let mut app = self.app.lock().await;
-        // Note: user_country field may need to be added to App struct
-        // app.user_country = user.country;
-        // User info received - logged via app.add_log_message
+        let image_url = user
+          .images
+          .iter()
+          .flatten()
+          .min_by_key(|image| image.width.unwrap_or(1000))
+          .map(|image| image.url.clone());
+        // Country, product and the rest are only filled in with the user-read-private scope
+        app.user = Some(user);
+        match image_url {
+          Some(url) => app.dispatch(IoEvent::FetchProfileImage(url)),
+          None => app.profile_image = None,
+        }
       }
       Err(e) => {
         // Error handled via app.handle_error
@@ -349,15 +1030,66 @@ impl Network {
   }
 
   async fn get_current_playback(&mut self) {
-    // Try to get the full playback context which includes device information
-    match self.spotify.current_playback(None, None::<&[_]>).await {
+    // Try to get the full playback context which includes device information. Without asking
+    // for episodes too, Spotify leaves out whatever podcast is playing, so it can't be seeked
+    let additional_types = [AdditionalType::Track, AdditionalType::Episode];
+    match self
+      .spotify
+      .current_playback(None, Some(&additional_types))
+      .await
+    {
       Ok(Some(context)) => {
         let mut app = self.app.lock().await;
         
         // Don't log playback status on every poll to avoid spam
         
+        let previous = app.current_playback_context.as_ref();
+        let track_changed = previous.map(playing_item_id) != Some(playing_item_id(&context));
+        let autoplay = app.user_config.behavior.autoplay
+          && previous.is_some_and(|previous| {
+            context_finished(
+              previous,
+              Some(&context),
+              app.instant_since_last_current_playback_poll.elapsed(),
+              app.up_next.is_none(),
+            )
+          });
+        let shuffle_changed = previous.map(|previous| previous.shuffle_state)
+          != Some(context.shuffle_state);
+        // So following the playing artist knows whether they are followed already
+        let first_artist_id = match &context.item {
+          Some(PlayableItem::Track(track)) => track
+            .artists
+            .first()
+            .and_then(|artist| artist.id.as_ref())
+            .map(|id| id.to_string()),
+          _ => None,
+        };
+
         // Store the playback context  
         app.current_playback_context = Some(context);
+        // The queue moves on with the track, and shuffling reorders it
+        if track_changed || shuffle_changed {
+          app.dispatch(IoEvent::GetUpNext);
+        }
+        match first_artist_id {
+          Some(artist_id) if track_changed => {
+            app.dispatch(IoEvent::CheckFollowedArtists(vec![artist_id]));
+          }
+          _ => {}
+        }
+        if autoplay {
+          app.start_autoplay();
+        }
+        if track_changed {
+          app.remember_played_track();
+          app.fetch_audio_features();
+          if app.get_current_route().id == RouteId::Analysis
+            || app.user_config.behavior.beat_accents
+          {
+            app.load_audio_analysis();
+          }
+        }
         
         // Update album art for the current track
         app.update_album_art();
@@ -368,7 +1100,23 @@ impl Network {
       }
       Ok(None) => {
         let mut app = self.app.lock().await;
+        let autoplay = app.user_config.behavior.autoplay
+          && app
+            .current_playback_context
+            .as_ref()
+            .is_some_and(|previous| {
+              context_finished(
+                previous,
+                None,
+                app.instant_since_last_current_playback_poll.elapsed(),
+                app.up_next.is_none(),
+              )
+            });
+        if autoplay {
+          app.start_autoplay();
+        }
         app.current_playback_context = None;
+        app.up_next = None;
         
         // Reset polling state
         app.is_fetching_current_playback = false;
@@ -385,10 +1133,25 @@ impl Network {
     }
   }
 
-  async fn get_playlist_tracks(&mut self, playlist_id: &str, offset: u32) {
+  async fn get_up_next(&mut self) {
+    match self.spotify.current_user_queue().await {
+      Ok(queue) => {
+        let mut app = self.app.lock().await;
+        app.up_next = queue.queue.into_iter().next();
+      }
+      Err(e) => {
+        // Polled along with playback, so only log it
+        warn!("Failed to get queue: {:?}", e);
+        let mut app = self.app.lock().await;
+        app.up_next = None;
+      }
+    }
+  }
+
+  async fn get_playlist_tracks(&mut self, playlist_id: &str, context: TrackTableContext) {
     use rspotify::model::PlaylistId;
     
-    self.log_error(&format!("DEBUG: get_playlist_tracks called with ID: '{}'", playlist_id));
+    debug!("get_playlist_tracks called with ID: '{}'", playlist_id);
     
     // Extract just the ID from the Spotify URI (e.g., "spotify:playlist:ID" -> "ID")
     let id_part = if playlist_id.starts_with("spotify:playlist:") {
@@ -400,8 +1163,10 @@ impl Network {
     let playlist_id = match PlaylistId::from_id(id_part) {
       Ok(id) => id,
       Err(e) => {
-        let error_msg = format!("ERROR: Invalid playlist ID '{}' (extracted: '{}'): {:?}", playlist_id, id_part, e);
-        self.log_error(&error_msg);
+        warn!(
+          "Invalid playlist ID '{}' (extracted: '{}'): {:?}",
+          playlist_id, id_part, e
+        );
         return;
       }
     };
@@ -412,15 +1177,21 @@ impl Network {
       playlist_items.push(item);
     }
     
-    self.log_error(&format!("SUCCESS: Got {} playlist items", playlist_items.len()));
+    debug!("Got {} playlist items", playlist_items.len());
     
     // Convert PlaylistItems to FullTracks (only tracks, not episodes)
     let mut tracks = Vec::new();
-    for item in playlist_items {
+    let mut added_at = HashMap::new();
+    let mut positions = Vec::new();
+    for (position, item) in playlist_items.into_iter().enumerate() {
       if let Some(track) = item.track {
         match track {
           PlayableItem::Track(full_track) => {
+            if let (Some(id), Some(date)) = (&full_track.id, item.added_at) {
+              added_at.entry(id.to_string()).or_insert(date);
+            }
             tracks.push(full_track);
+            positions.push(position as u32);
           }
           PlayableItem::Episode(_) => {
             // Skip episodes for now since track_table expects only tracks
@@ -429,20 +1200,183 @@ impl Network {
       }
     }
     
-    self.log_error(&format!("SUCCESS: Extracted {} tracks from playlist", tracks.len()));
+    debug!("Extracted {} tracks from playlist", tracks.len());
     
     let mut app = self.app.lock().await;
     // Store playlist tracks in app.track_table for display in right panel
     app.track_table.tracks = tracks;
-    app.track_table.context = Some(TrackTableContext::MyPlaylists);
+    app.track_table.added_at = added_at;
+    app.track_table.context_positions = positions;
+    app.track_table.context = Some(context);
     app.track_table.selected_index = 0;
+    app.track_table.visual_anchor = None;
+    app.finish_loading_route(RouteId::TrackTable, true);
+  }
+
+  /// Load a page of new releases, starting the list over without an offset
+  async fn get_new_releases(&mut self, offset: Option<u32>) {
+    use rspotify::model::Market;
+
+    let result = self
+      .spotify
+      .new_releases_manual(
+        Some(Market::FromToken),
+        Some(self.large_search_limit),
+        offset,
+      )
+      .await;
+
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(page) => {
+        let album_ids = page
+          .items
+          .iter()
+          .filter_map(|album| album.id.as_ref().map(|id| id.to_string()))
+          .collect();
+        if offset.is_none() {
+          app.new_releases = ScrollableResultPages::new();
+        }
+        app.new_releases.add_pages(page);
+        app.new_releases_index = 0;
+        app.dispatch(IoEvent::CheckSavedAlbums(album_ids));
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!("Failed to load new releases: {}", e));
+      }
+    }
+  }
+
+  async fn get_featured_playlists(&mut self) {
+    use rspotify::model::Market;
+
+    let result = self
+      .spotify
+      .featured_playlists(None, Some(Market::FromToken), None, Some(50), None)
+      .await;
+
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(featured) => {
+        app.featured_playlists = Some(featured);
+        app.featured_playlists_index = 0;
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!("Failed to load featured playlists: {}", e));
+      }
+    }
+  }
+
+  /// There are only a few dozen categories, so load them all rather than a page at a time
+  async fn get_categories(&mut self) {
+    use rspotify::model::Market;
+
+    let mut categories = vec![];
+    let mut offset = 0;
+    loop {
+      let result = self
+        .spotify
+        .categories_manual(None, Some(Market::FromToken), Some(50), Some(offset))
+        .await;
+      match result {
+        Ok(page) => {
+          offset += page.items.len() as u32;
+          let is_last = page.next.is_none() || page.items.is_empty();
+          categories.extend(page.items);
+          if is_last {
+            break;
+          }
+        }
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.handle_error(anyhow::anyhow!("Failed to load categories: {}", e));
+          return;
+        }
+      }
+    }
+
+    let mut app = self.app.lock().await;
+    app.categories = categories;
+    app.categories_index = 0;
+  }
+
+  async fn get_category_playlists(&mut self, category_id: &str) {
+    use rspotify::model::Market;
+
+    let result = self
+      .spotify
+      .category_playlists_manual(category_id, Some(Market::FromToken), Some(50), None)
+      .await;
+
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(page) => {
+        app.category_playlists = page.items;
+        app.category_playlists_index = 0;
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!(
+          "Failed to load the category's playlists: {}",
+          e
+        ));
+      }
+    }
+  }
+
+  /// Load the tracks of the playlist open in the track table again, after they changed
+  async fn reload_viewed_playlist_tracks(&mut self, playlist_id: &str) {
+    let context = self.app.lock().await.track_table.context.clone();
+    let context = context.unwrap_or(TrackTableContext::MyPlaylists);
+    self.get_playlist_tracks(playlist_id, context).await;
+  }
+
+  /// Spotify has no endpoint for the playlists it makes for the user, so look for its own
+  /// playlists with their names, among the user's playlists and in a search for each name
+  async fn get_made_for_you_playlists(&mut self) {
+    use rspotify::model::SearchResult;
+
+    let spotify = &self.spotify;
+    let searches = MADE_FOR_YOU_NAMES
+      .iter()
+      .map(|name| spotify.search(name, SearchType::Playlist, None, None, Some(50), None));
+    let results = futures::future::join_all(searches).await;
+
+    let mut app = self.app.lock().await;
+    let mut playlists = app
+      .playlists
+      .iter()
+      .flat_map(|page| page.items.iter().cloned())
+      .collect::<Vec<SimplifiedPlaylist>>();
+    let mut error = None;
+    for result in results {
+      match result {
+        Ok(SearchResult::Playlists(page)) => playlists.extend(page.items),
+        Ok(_) => {}
+        Err(e) => error = Some(e),
+      }
+    }
+    let mut seen = HashSet::new();
+    playlists.retain(|playlist| {
+      made_for_you_rank(playlist).is_some() && seen.insert(playlist.id.to_string())
+    });
+    playlists.sort_by_key(|playlist| (made_for_you_rank(playlist), playlist.name.clone()));
+
+    // The search results alone are still worth showing when one of the searches failed
+    if let Some(e) = error {
+      app.handle_error(anyhow::anyhow!(
+        "Failed to look for Made For You playlists: {}",
+        e
+      ));
+    }
+    app.library.made_for_you_playlists = playlists;
+    app.made_for_you_index = 0;
   }
 
   async fn get_album_tracks(&mut self, album_id: String) {
     use rspotify::model::AlbumId;
     use futures::TryStreamExt;
     
-    self.log_error(&format!("DEBUG: get_album_tracks called with ID: '{}'", album_id));
+    debug!("get_album_tracks called with ID: '{}'", album_id);
     
     // Extract just the ID from the Spotify URI if present
     let id_part = if album_id.starts_with("spotify:album:") {
@@ -454,8 +1388,10 @@ impl Network {
     let album_id = match AlbumId::from_id(id_part) {
       Ok(id) => id,
       Err(e) => {
-        let error_msg = format!("ERROR: Invalid album ID '{}' (extracted: '{}'): {:?}", album_id, id_part, e);
-        self.log_error(&error_msg);
+        debug!(
+          "Invalid album ID '{}' (extracted: '{}'): {:?}",
+          album_id, id_part, e
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Invalid album ID: {}", e));
         return;
@@ -465,12 +1401,11 @@ impl Network {
     // Get the album details first to get album name and other info
     let album = match self.spotify.album(album_id.clone(), None).await {
       Ok(album) => {
-        self.log_error(&format!("SUCCESS: Got album: {}", album.name));
+        debug!("Got album: {}", album.name);
         album
       }
       Err(e) => {
-        let error_msg = format!("ERROR getting album details: {:?}", e);
-        self.log_error(&error_msg);
+        debug!("Failed to get album details: {:?}", e);
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to get album: {}", e));
         return;
@@ -518,13 +1453,16 @@ impl Network {
       });
     }
     
-    self.log_error(&format!("SUCCESS: Got {} tracks from album", tracks.len()));
+    debug!("Got {} tracks from album", tracks.len());
     
     let mut app = self.app.lock().await;
     // Store album tracks in app.track_table for display
     app.track_table.tracks = tracks;
+    app.track_table.added_at.clear();
+    app.track_table.context_positions.clear();
     app.track_table.context = Some(TrackTableContext::AlbumSearch);
     app.track_table.selected_index = 0;
+    app.track_table.visual_anchor = None;
     
     // Store the album URI for playback
     app.selected_album_full = Some(SelectedFullAlbum {
@@ -533,135 +1471,249 @@ impl Network {
     });
   }
 
-  async fn start_playback(&mut self, context_uri: Option<&str>, offset_uri: Option<String>) {
-    self.log_error(&format!("DEBUG: start_playback called with context_uri: {:?}, offset_uri: {:?}", context_uri, offset_uri));
-    
-    // Add to log stream for visibility
-    {
-      let mut app = self.app.lock().await;
-      app.add_log_message(format!("Starting playback - Context: {:?}, Offset: {:?}", context_uri, offset_uri));
-    }
-    
-    // Log detailed information
-    if let Some(uri) = context_uri {
-      self.log_error(&format!("DEBUG: Context URI type: {}", 
-        if uri.contains("playlist") { "playlist" } 
-        else if uri.contains("album") { "album" } 
-        else if uri.contains("track") { "track" } 
-        else { "unknown" }
-      ));
-    }
-    if let Some(ref offset) = offset_uri {
-      self.log_error(&format!("DEBUG: Offset URI: {}", offset));
+  async fn get_audio_analysis(&mut self, track_id: String) {
+    use rspotify::model::{Id, TrackId};
+
+    let result = match TrackId::from_id_or_uri(&track_id) {
+      Ok(id) => match self.analysis_cache.get(id.id()) {
+        Some(analysis) => Ok(analysis),
+        None => {
+          let key = id.id().to_string();
+          let result = self.spotify.track_analysis(id).await;
+          if let Ok(analysis) = &result {
+            if let Err(e) = self.analysis_cache.insert(&key, analysis.clone()) {
+              let mut app = self.app.lock().await;
+              app.log(
+                LogLevel::Warn,
+                format!("Failed to cache audio analysis: {}", e),
+              );
+            }
+          }
+          result.map_err(anyhow::Error::from)
+        }
+      },
+      Err(e) => Err(anyhow::anyhow!("Invalid track ID {}: {}", track_id, e)),
+    };
+
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(analysis) => app.audio_analysis = Some(analysis),
+      // Beat accents load analyses in the background, those failing shouldn't interrupt
+      Err(e) if app.get_current_route().id != RouteId::Analysis => {
+        app.log(
+          LogLevel::Warn,
+          format!("Failed to get audio analysis: {}", e),
+        );
+      }
+      Err(e) => app.handle_error(anyhow::anyhow!("Failed to get audio analysis: {}", e)),
     }
-    
-    let result = if let Some(uri) = context_uri {
-      self.log_error(&format!("DEBUG: Starting playback with context URI: {}", uri));
-      
-      // Parse the URI to get the appropriate ID and call the right API
-      if uri.starts_with("spotify:playlist:") {
-        let playlist_id = &uri[17..]; // Remove "spotify:playlist:" prefix
-        match rspotify::model::PlaylistId::from_id(playlist_id) {
-          Ok(id) => {
-            // Convert to PlayContextId 
-            use rspotify::model::PlayContextId;
-            let context = PlayContextId::Playlist(id);
-            
-            // For playlists, if we have an offset_uri (track URI), use it
-            let offset = offset_uri.as_ref().map(|uri| {
-              self.log_error(&format!("DEBUG: Using track URI offset: {}", uri));
-              rspotify::model::Offset::Uri(uri.clone())
-            });
-            
-            // Get current device ID from app state
-            let device_id = {
-              let app = self.app.lock().await;
-              app.current_playback_context.as_ref()
-                .and_then(|ctx| ctx.device.id.as_ref())
-                .map(|id| id.to_string())
-            };
-            
-            self.spotify.start_context_playback(context, device_id.as_deref(), offset, None).await
-          }
-          Err(e) => {
-            self.log_error(&format!("ERROR: Invalid playlist ID in URI '{}': {:?}", uri, e));
-            return;
+  }
+
+  async fn get_audio_features(&mut self, track_id: String) {
+    use rspotify::model::{Id, TrackId};
+
+    let result = match TrackId::from_id_or_uri(&track_id) {
+      Ok(id) => match self.features_cache.get(id.id()) {
+        Some(features) => Ok(features),
+        None => {
+          let key = id.id().to_string();
+          let result = self.spotify.track_features(id).await;
+          if let Ok(features) = &result {
+            if let Err(e) = self.features_cache.insert(&key, features.clone()) {
+              let mut app = self.app.lock().await;
+              app.log(
+                LogLevel::Warn,
+                format!("Failed to cache audio features: {}", e),
+              );
+            }
           }
+          result.map_err(anyhow::Error::from)
         }
-      } else if uri.starts_with("spotify:album:") {
-        let album_id = &uri[14..]; // Remove "spotify:album:" prefix
-        match rspotify::model::AlbumId::from_id(album_id) {
-          Ok(id) => {
-            use rspotify::model::PlayContextId;
-            let context = PlayContextId::Album(id);
-            // For albums, if we have an offset_uri (track URI), use it
-            let offset = offset_uri.as_ref().map(|uri| {
-              self.log_error(&format!("DEBUG: Using track URI offset for album: {}", uri));
-              rspotify::model::Offset::Uri(uri.clone())
-            });
-            
-            // Get current device ID from app state
-            let device_id = {
-              let app = self.app.lock().await;
-              app.current_playback_context.as_ref()
-                .and_then(|ctx| ctx.device.id.as_ref())
-                .map(|id| id.to_string())
-            };
-            
-            self.spotify.start_context_playback(context, device_id.as_deref(), offset, None).await
-          }
-          Err(e) => {
-            self.log_error(&format!("ERROR: Invalid album ID in URI '{}': {:?}", uri, e));
-            return;
-          }
+      },
+      Err(e) => Err(anyhow::anyhow!("Invalid track ID {}: {}", track_id, e)),
+    };
+
+    let mut app = self.app.lock().await;
+    // Cached even when missing, Spotify has none for some tracks and asking again won't help
+    let features = match result {
+      Ok(features) => Some(features),
+      Err(e) => {
+        app.log(
+          LogLevel::Warn,
+          format!("Failed to get audio features: {}", e),
+        );
+        None
+      }
+    };
+    app.audio_features.insert(track_id, features);
+  }
+
+  /// Show the album of a track with the track selected
+  async fn get_album_for_track(&mut self, track_id: String) {
+    use rspotify::model::TrackId;
+
+    let track = match TrackId::from_id_or_uri(&track_id) {
+      Ok(id) => self.spotify.track(id, None).await,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid track ID {}: {}", track_id, e));
+        return;
+      }
+    };
+    let track = match track {
+      Ok(track) => track,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to get track: {}", e));
+        return;
+      }
+    };
+    let album_id = match &track.album.id {
+      Some(album_id) => album_id.to_string(),
+      None => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("\"{}\" has no album to show", track.name));
+        return;
+      }
+    };
+
+    self.get_album_tracks(album_id).await;
+
+    let mut app = self.app.lock().await;
+    if let Some(index) = app
+      .track_table
+      .tracks
+      .iter()
+      .position(|album_track| album_track.id == track.id)
+    {
+      app.track_table.selected_index = index;
+      if let Some(selected_album) = app.selected_album_full.as_mut() {
+        selected_album.selected_index = index;
+      }
+    }
+  }
+
+  /// Show the episodes of a show
+  async fn get_show(&mut self, show_id: String) {
+    use rspotify::model::ShowId;
+
+    let show = match ShowId::from_id_or_uri(&show_id) {
+      Ok(id) => self.spotify.get_a_show(id, None).await,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid show ID {}: {}", show_id, e));
+        return;
+      }
+    };
+
+    let mut app = self.app.lock().await;
+    match show {
+      Ok(show) => {
+        app.library.show_episodes = ScrollableResultPages::new();
+        app.library.show_episodes.add_pages(show.episodes.clone());
+        app.episode_list_index = 0;
+        app.episode_table_context = EpisodeTableContext::Full;
+        app.selected_show_full = Some(SelectedFullShow { show });
+        app.finish_loading_route(RouteId::PodcastEpisodes, true);
+      }
+      Err(e) => app.handle_error(anyhow::anyhow!("Failed to get show: {}", e)),
+    }
+  }
+
+  async fn start_playback(&mut self, context_uri: Option<&str>, offset: Option<PlaybackOffset>) {
+    debug!(
+      "start_playback called with context_uri: {:?}, offset: {:?}",
+      context_uri, offset
+    );
+    
+    // Add to log stream for visibility
+    {
+      let mut app = self.app.lock().await;
+      app.log(
+        LogLevel::Debug,
+        format!(
+          "Starting playback - Context: {:?}, Offset: {:?}",
+          context_uri, offset
+        ),
+      );
+    }
+    
+    // Log detailed information
+    if let Some(uri) = context_uri {
+      debug!(
+        "Context URI type: {}",
+        if uri.contains("playlist") {
+          "playlist"
+        } else if uri.contains("album") {
+          "album"
+        } else if uri.contains("track") {
+          "track"
+        } else {
+          "unknown"
         }
-      } else if uri.starts_with("spotify:track:") {
-        let track_id = &uri[14..]; // Remove "spotify:track:" prefix
-        match rspotify::model::TrackId::from_id(track_id) {
-          Ok(id) => {
-            // For individual tracks, use start_uris_playback
-            use rspotify::model::PlayableId;
-            let track_ids = vec![PlayableId::Track(id)];
-            // Get current device ID from app state
-            let device_id = {
-              let app = self.app.lock().await;
-              app.current_playback_context.as_ref()
-                .and_then(|ctx| ctx.device.id.as_ref())
-                .map(|id| id.to_string())
-            };
-            
-            self.spotify.start_uris_playback(track_ids, device_id.as_deref(), None, None).await
+      );
+    }
+    if let Some(ref offset) = offset {
+      debug!("Offset: {:?}", offset);
+    }
+    
+    // Get current device ID from app state
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+    let request = self.request_playback(context_uri, offset.as_ref(), device_id.as_deref());
+    let mut result = match request.await {
+      Some(result) => result,
+      None => return,
+    };
+
+    // With nothing playing anywhere and a single device there's no doubt where to play, so wake
+    // that device up and try once more instead of making the user pick it first
+    let no_active_device = match &mut result {
+      Err(e) => is_no_active_device(e).await,
+      Ok(_) => false,
+    };
+    if no_active_device {
+      if let Some((device_id, device_name)) = self.sole_device().await {
+        match self.spotify.transfer_playback(&device_id, Some(false)).await {
+          Ok(_) => {
+            {
+              let mut app = self.app.lock().await;
+              app.add_log_message(format!(
+                "No active device, transferred playback to {} and retrying",
+                device_name
+              ));
+              app.dispatch(IoEvent::GetCurrentPlayback);
+            }
+            let request = self.request_playback(context_uri, offset.as_ref(), Some(&device_id));
+            if let Some(retried) = request.await {
+              result = retried;
+            }
           }
           Err(e) => {
-            self.log_error(&format!("ERROR: Invalid track ID in URI '{}': {:?}", uri, e));
-            return;
+            let mut app = self.app.lock().await;
+            app.log(
+              LogLevel::Warn,
+              format!(
+                "No active device, couldn't transfer playback to {}: {}",
+                device_name, e
+              ),
+            );
           }
         }
-      } else {
-        self.log_error(&format!("ERROR: Unsupported URI format: {}", uri));
-        return;
       }
-    } else {
-      // Resume current playback
-      self.log_error("DEBUG: Resuming current playback");
-      // Get current device ID from app state
-      let device_id = {
-        let app = self.app.lock().await;
-        app.current_playback_context.as_ref()
-          .and_then(|ctx| ctx.device.id.as_ref())
-          .map(|id| id.to_string())
-      };
-      
-      self.spotify.resume_playback(device_id.as_deref(), None).await
-    };
-    
+    }
+
     match result {
       Ok(_) => {
-        self.log_error("SUCCESS: Started playback");
+        debug!("Started playback");
         let mut app = self.app.lock().await;
         app.add_log_message("Playback started".to_string());
         // Update the playback state when resuming
-        if context_uri.is_none() && offset_uri.is_none() {
+        if context_uri.is_none() && offset.is_none() {
           // This was a resume operation, update the state
           if let Some(ref mut context) = app.current_playback_context {
             context.is_playing = true;
@@ -671,8 +1723,8 @@ impl Network {
         }
       }
       Err(e) => {
-        let error_msg = format!("ERROR: Failed to start playback: {:?}", e);
-        self.log_error(&error_msg);
+        let error_msg = format!("{:?}", e);
+        debug!("Failed to start playback: {}", error_msg);
         
         // Extract and format detailed error information
         let error_str = format!("{:?}", e);
@@ -693,11 +1745,14 @@ impl Network {
           let mut app = self.app.lock().await;
           
           // For now, add a simple error message since HTTP errors don't include body
-          app.add_log_message(format!("ERROR: Playback failed - {}", status));
-          app.add_log_message("Check that a Spotify device is active and try again".to_string());
-          
+          app.log(LogLevel::Error, format!("Playback failed - {}", status));
+          app.log(
+            LogLevel::Warn,
+            "Check that a Spotify device is active and try again".to_string(),
+          );
+
           // Log the full error for debugging
-          self.log_error(&format!("Full HTTP error: {}", error_str));
+          debug!("Full HTTP error: {}", error_str);
         }
         // Try to extract and format the error response body if it exists
         else if let Some(start) = error_str.find("ApiError(") {
@@ -705,9 +1760,9 @@ impl Network {
             let api_error = &error_str[start+9..end];
             
             // Log the error in parts for better readability
-            self.log_error("=== SPOTIFY API ERROR ===");
+            debug!("=== SPOTIFY API ERROR ===");
             let api_status = if error_str.contains("status: 400") { "400 Bad Request" } else if error_str.contains("status: 403") { "403 Forbidden" } else { "Unknown" };
-            self.log_error(&format!("Status: {}", api_status));
+            debug!("Status: {}", api_status);
             
             // Try to extract JSON body
             if let Some(body_start) = api_error.find("body: Some(\"") {
@@ -716,10 +1771,10 @@ impl Network {
                 // Unescape the JSON string
                 let unescaped_body = body.replace("\\\"", "\"").replace("\\n", "\n");
                 
-                self.log_error("Response body:");
+                debug!("Response body:");
                 // Split into multiple lines for readability
                 for line in unescaped_body.lines() {
-                  self.log_error(&format!("  {}", line));
+                  debug!("  {}", line);
                 }
                 
                 // Try to parse and pretty print JSON
@@ -731,114 +1786,470 @@ impl Network {
                       "=== SPOTIFY API ERROR ({}) ===\n{}\n==========================================",
                       api_status, pretty_json
                     );
-                    app.add_log_message(error_message);
+                    app.log(LogLevel::Debug, error_message);
                   }
                 }
               }
             }
             
-            self.log_error("=========================");
+            debug!("=========================");
           }
         }
         
         // Check if it's a 400 error
         if error_msg.contains("status: 400") {
-          self.log_error("BAD REQUEST: The request format is incorrect");
+          debug!("BAD REQUEST: The request format is incorrect");
           let mut app = self.app.lock().await;
           if !error_str.contains("body: Some") {
-            app.add_log_message(format!("Bad Request (400): {}", error_str));
+            app.log(LogLevel::Warn, format!("Bad Request (400): {}", error_str));
           }
         }
         // Check if it's a 403 error which usually means Premium required or no active device
         else if error_msg.contains("status: 403") {
           let user_error = "Playback failed: Spotify Premium subscription required. Please upgrade to Premium and ensure you have an active device (open Spotify and start playing music on any device).";
-          self.log_error("PREMIUM REQUIRED: Playback control needs Spotify Premium");
+          debug!("PREMIUM REQUIRED: Playback control needs Spotify Premium");
           
           // Add to log stream and show in UI
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for playback control".to_string());
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for playback control".to_string(),
+          );
           app.handle_error(anyhow::anyhow!("{}", user_error));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Playback error: {}", e));
+          app.log(LogLevel::Warn, format!("Playback error: {}", e));
           app.handle_error(anyhow::anyhow!("Failed to start playback: {}", e));
         }
       }
     }
   }
 
-  async fn pause_playback(&mut self) {
-    // Get current device ID from app state
+  /// Send the request behind `start_playback` to the device, `None` when the URI can't be played
+  async fn request_playback(
+    &self,
+    context_uri: Option<&str>,
+    offset: Option<&PlaybackOffset>,
+    device_id: Option<&str>,
+  ) -> Option<rspotify::ClientResult<()>> {
+    let result = if let Some(uri) = context_uri {
+      debug!("Starting playback with context URI: {}", uri);
+      
+      // Parse the URI to get the appropriate ID and call the right API
+      if let Some(playlist_id) = uri.strip_prefix("spotify:playlist:") {
+        match rspotify::model::PlaylistId::from_id(playlist_id) {
+          Ok(id) => {
+            // Convert to PlayContextId 
+            use rspotify::model::PlayContextId;
+            let context = PlayContextId::Playlist(id);
+            
+            // For playlists, start at the chosen track if there is one
+            let offset = offset.cloned().map(rspotify::model::Offset::from);
+            self.spotify.start_context_playback(context, device_id, offset, None).await
+          }
+          Err(e) => {
+            warn!("Invalid playlist ID in URI '{}': {:?}", uri, e);
+            return None;
+          }
+        }
+      } else if let Some(album_id) = uri.strip_prefix("spotify:album:") {
+        match rspotify::model::AlbumId::from_id(album_id) {
+          Ok(id) => {
+            use rspotify::model::PlayContextId;
+            let context = PlayContextId::Album(id);
+            // For albums, start at the chosen track if there is one
+            let offset = offset.cloned().map(rspotify::model::Offset::from);
+            self.spotify.start_context_playback(context, device_id, offset, None).await
+          }
+          Err(e) => {
+            warn!("Invalid album ID in URI '{}': {:?}", uri, e);
+            return None;
+          }
+        }
+      } else if let Some(artist_id) = uri.strip_prefix("spotify:artist:") {
+        match rspotify::model::ArtistId::from_id(artist_id) {
+          Ok(id) => {
+            // Spotify picks the tracks for an artist, so there is no track to start at
+            use rspotify::model::PlayContextId;
+            let context = PlayContextId::Artist(id);
+            self
+              .spotify
+              .start_context_playback(context, device_id, None, None)
+              .await
+          }
+          Err(e) => {
+            warn!("Invalid artist ID in URI '{}': {:?}", uri, e);
+            return None;
+          }
+        }
+      } else if let Some(show_id) = uri.strip_prefix("spotify:show:") {
+        match rspotify::model::ShowId::from_id(show_id) {
+          Ok(id) => {
+            use rspotify::model::PlayContextId;
+            let context = PlayContextId::Show(id);
+            let offset = offset.cloned().map(rspotify::model::Offset::from);
+            self
+              .spotify
+              .start_context_playback(context, device_id, offset, None)
+              .await
+          }
+          Err(e) => {
+            warn!("Invalid show ID in URI '{}': {:?}", uri, e);
+            return None;
+          }
+        }
+      } else if let Some(track_id) = uri.strip_prefix("spotify:track:") {
+        match rspotify::model::TrackId::from_id(track_id) {
+          Ok(id) => {
+            // For individual tracks, use start_uris_playback
+            use rspotify::model::PlayableId;
+            let track_ids = vec![PlayableId::Track(id)];
+            self.spotify.start_uris_playback(track_ids, device_id, None, None).await
+          }
+          Err(e) => {
+            warn!("Invalid track ID in URI '{}': {:?}", uri, e);
+            return None;
+          }
+        }
+      } else {
+        warn!("Unsupported URI format: {}", uri);
+        return None;
+      }
+    } else {
+      // Resume current playback
+      debug!("Resuming current playback");
+      self.spotify.resume_playback(device_id, None).await
+    };
+    Some(result)
+  }
+
+  /// The user's only device, when they have exactly one
+  async fn sole_device(&self) -> Option<(String, String)> {
+    let devices = self.spotify.device().await.ok()?;
+    match devices.as_slice() {
+      [device] => device.id.clone().map(|id| (id, device.name.clone())),
+      _ => None,
+    }
+  }
+
+  /// Play the top track result for a search, as used by `:play <query>`
+  async fn search_and_play(&mut self, query: String) {
+    use rspotify::model::{PlayableId, SearchResult};
+
+    let track = match self
+      .spotify
+      .search(&query, SearchType::Track, None, None, Some(1), None)
+      .await
+    {
+      Ok(SearchResult::Tracks(page)) => page.items.into_iter().next(),
+      Ok(_) => None,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to search for \"{}\": {}", query, e));
+        return;
+      }
+    };
+
+    let found = track.and_then(|track| track.id.map(|id| (id, track.name)));
+    let (track_id, track_name) = match found {
+      Some(found) => found,
+      None => {
+        let mut app = self.app.lock().await;
+        app.show_toast(format!("No tracks found for \"{}\"", query));
+        return;
+      }
+    };
+
     let device_id = {
       let app = self.app.lock().await;
       app.current_playback_context.as_ref()
         .and_then(|ctx| ctx.device.id.as_ref())
         .map(|id| id.to_string())
     };
-    
-    match self.spotify.pause_playback(device_id.as_deref()).await {
+
+    match self
+      .spotify
+      .start_uris_playback(
+        vec![PlayableId::Track(track_id)],
+        device_id.as_deref(),
+        None,
+        None,
+      )
+      .await
+    {
       Ok(_) => {
         let mut app = self.app.lock().await;
-        app.add_log_message("Playback paused".to_string());
-        // Update the playback state locally
-        if let Some(ref mut context) = app.current_playback_context {
-          context.is_playing = false;
-        }
-        // Schedule a playback state refresh
+        app.add_log_message(format!("Playing {}", track_name));
         app.dispatch(IoEvent::GetCurrentPlayback);
-      },
+      }
       Err(e) => {
-        let error_msg = format!("{:?}", e);
-        self.log_error(&format!("Pause error: {}", error_msg));
-        
-        // For 403 errors, don't show the premium error immediately
-        // It might be a temporary issue with the device
-        if error_msg.contains("status: 403") {
-          let mut app = self.app.lock().await;
-          // Just log it without showing an error dialog
-          app.add_log_message("Failed to pause - try again or check device".to_string());
-          // Update the state anyway to keep UI in sync
-          if let Some(ref mut context) = app.current_playback_context {
-            context.is_playing = false;
-          }
-        } else if error_msg.contains("status: 404") {
-          let mut app = self.app.lock().await;
-          app.add_log_message("No active device found for pause".to_string());
-        } else {
-          let mut app = self.app.lock().await;
-          app.add_log_message(format!("Pause error: {}", e));
-          // Don't show error dialog for pause failures
-        }
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to start playback: {}", e));
       }
     }
   }
 
-  async fn next_track(&mut self) {
-    // Get current device ID from app state
+  /// Play a list of track URIs without a context, such as search results, starting at `offset`
+  /// Play tracks recommended from the seeds, like Spotify's autoplay does on its own devices
+  async fn start_autoplay(&mut self, seed_track_ids: Vec<String>) {
+    use rspotify::model::{ArtistId, Id, Market, RecommendationsAttribute, TrackId};
+
+    let seeds = seed_track_ids
+      .iter()
+      .filter_map(|id| TrackId::from_id_or_uri(id).ok())
+      .collect::<Vec<TrackId>>();
+    let result = self
+      .spotify
+      .recommendations(
+        Vec::<RecommendationsAttribute>::new(),
+        None::<Vec<ArtistId>>,
+        None::<Vec<&str>>,
+        Some(seeds),
+        Some(Market::FromToken),
+        Some(20),
+      )
+      .await;
+
+    let track_uris = match result {
+      Ok(recommendations) => recommendations
+        .tracks
+        .into_iter()
+        .filter_map(|track| track.id.map(|id| id.uri()))
+        .collect::<Vec<String>>(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.show_toast(format!("Autoplay couldn't get recommendations: {}", e));
+        return;
+      }
+    };
+    if track_uris.is_empty() {
+      let mut app = self.app.lock().await;
+      app.show_toast("Autoplay found nothing to play".to_string());
+      return;
+    }
+
+    {
+      let mut app = self.app.lock().await;
+      app.show_toast(format!(
+        "Autoplay: {} tracks like the ones you just played",
+        track_uris.len()
+      ));
+    }
+    self.play_track_list(track_uris, 0).await;
+  }
+
+  async fn play_track_list(&mut self, track_uris: Vec<String>, offset: usize) {
+    use rspotify::model::{Offset, PlayableId, TrackId};
+
+    let start = match track_uris.get(offset) {
+      Some(uri) => Offset::Uri(uri.clone()),
+      None => return,
+    };
+    let tracks = track_uris
+      .iter()
+      .filter_map(|uri| TrackId::from_id_or_uri(uri).ok())
+      .map(PlayableId::Track)
+      .collect::<Vec<PlayableId>>();
+
     let device_id = {
       let app = self.app.lock().await;
       app.current_playback_context.as_ref()
         .and_then(|ctx| ctx.device.id.as_ref())
         .map(|id| id.to_string())
     };
-    
-    match self.spotify.next_track(device_id.as_deref()).await {
+
+    match self
+      .spotify
+      .start_uris_playback(tracks, device_id.as_deref(), Some(start), None)
+      .await
+    {
       Ok(_) => {
         let mut app = self.app.lock().await;
-        app.add_log_message("Skipped to next track".to_string());
-        // Skipped to next - already logged
-      },
+        app.add_log_message(format!(
+          "Playing track {} of {} from the list",
+          offset + 1,
+          track_uris.len()
+        ));
+        app.dispatch(IoEvent::GetCurrentPlayback);
+      }
       Err(e) => {
-        let error_msg = format!("{:?}", e);
-        if error_msg.contains("status: 403") {
-          let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for next track control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for playback controls"));
-        } else {
-          let mut app = self.app.lock().await;
-          app.add_log_message(format!("Next track error: {}", e));
-          app.handle_error(anyhow::anyhow!("Error skipping to next track: {}", e));
-        }
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to start playback: {}", e));
+      }
+    }
+  }
+
+  async fn add_item_to_queue(&mut self, item_uri: String) {
+    use rspotify::model::{EpisodeId, PlayableId, TrackId};
+
+    let item = if item_uri.starts_with("spotify:episode:") {
+      EpisodeId::from_uri(&item_uri).map(PlayableId::Episode)
+    } else {
+      TrackId::from_id_or_uri(&item_uri).map(PlayableId::Track)
+    };
+    let item = match item {
+      Ok(item) => item,
+      Err(e) => {
+        debug!("Invalid queue item URI '{}': {:?}", item_uri, e);
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Can't add to queue: {}", item_uri));
+        return;
+      }
+    };
+
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+
+    match self.spotify.add_item_to_queue(item, device_id.as_deref()).await {
+      Ok(()) => {
+        let mut app = self.app.lock().await;
+        app.add_log_message(format!("Added to queue: {}", item_uri));
+        app.dispatch(IoEvent::GetUpNext);
+      }
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to add to queue: {}", e));
+      }
+    }
+  }
+
+  async fn play_episode(&mut self, episode_uri: String, position_ms: Option<u32>) {
+    use rspotify::model::{EpisodeId, PlayableId};
+
+    let episode_id = match EpisodeId::from_id_or_uri(&episode_uri) {
+      Ok(id) => id,
+      Err(e) => {
+        debug!("Invalid episode URI '{}': {:?}", episode_uri, e);
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid episode: {}", episode_uri));
+        return;
+      }
+    };
+
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+    let position = position_ms.map(|ms| ChronoDuration::milliseconds(i64::from(ms)));
+
+    match self
+      .spotify
+      .start_uris_playback(
+        vec![PlayableId::Episode(episode_id)],
+        device_id.as_deref(),
+        None,
+        position,
+      )
+      .await
+    {
+      Ok(_) => {
+        let mut app = self.app.lock().await;
+        match position_ms {
+          Some(ms) => app.add_log_message(format!(
+            "Resumed episode at {}",
+            crate::ui::util::millis_to_minutes(u128::from(ms))
+          )),
+          None => app.add_log_message("Playing episode".to_string()),
+        }
+        app.dispatch(IoEvent::GetCurrentPlayback);
+      }
+      Err(e) => {
+        debug!("Failed to play episode: {:?}", e);
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to play episode: {}", e));
+      }
+    }
+  }
+
+  async fn pause_playback(&mut self) {
+    // Get current device ID from app state
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+    
+    match self.spotify.pause_playback(device_id.as_deref()).await {
+      Ok(_) => {
+        let mut app = self.app.lock().await;
+        app.add_log_message("Playback paused".to_string());
+        // Update the playback state locally
+        if let Some(ref mut context) = app.current_playback_context {
+          context.is_playing = false;
+        }
+        // Schedule a playback state refresh
+        app.dispatch(IoEvent::GetCurrentPlayback);
+      },
+      Err(e) => {
+        let error_msg = format!("{:?}", e);
+        debug!("Pause error: {}", error_msg);
+        
+        // For 403 errors, don't show the premium error immediately
+        // It might be a temporary issue with the device
+        if error_msg.contains("status: 403") {
+          let mut app = self.app.lock().await;
+          // Just log it without showing an error dialog
+          app.log(
+            LogLevel::Warn,
+            "Failed to pause - try again or check device".to_string(),
+          );
+          // Update the state anyway to keep UI in sync
+          if let Some(ref mut context) = app.current_playback_context {
+            context.is_playing = false;
+          }
+        } else if error_msg.contains("status: 404") {
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            "No active device found for pause".to_string(),
+          );
+        } else {
+          let mut app = self.app.lock().await;
+          app.log(LogLevel::Warn, format!("Pause error: {}", e));
+          // Don't show error dialog for pause failures
+        }
+      }
+    }
+  }
+
+  async fn next_track(&mut self) {
+    // Get current device ID from app state
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+    
+    match self.spotify.next_track(device_id.as_deref()).await {
+      Ok(_) => {
+        let mut app = self.app.lock().await;
+        app.add_log_message("Skipped to next track".to_string());
+        // Skipped to next - already logged
+      },
+      Err(e) => {
+        let error_msg = format!("{:?}", e);
+        if error_msg.contains("status: 403") {
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for next track control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for playback controls"
+          ));
+        } else {
+          let mut app = self.app.lock().await;
+          app.log(LogLevel::Warn, format!("Next track error: {}", e));
+          app.handle_error(anyhow::anyhow!("Error skipping to next track: {}", e));
+        }
       }
     }
   }
@@ -862,11 +2273,16 @@ impl Network {
         let error_msg = format!("{:?}", e);
         if error_msg.contains("status: 403") {
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for previous track control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for playback controls"));
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for previous track control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for playback controls"
+          ));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Previous track error: {}", e));
+          app.log(LogLevel::Warn, format!("Previous track error: {}", e));
           app.handle_error(anyhow::anyhow!("Error skipping to previous track: {}", e));
         }
       }
@@ -886,17 +2302,25 @@ impl Network {
     match self.spotify.seek_track(duration, device_id.as_deref()).await {
       Ok(_) => {
         let mut app = self.app.lock().await;
-        app.add_log_message(format!("Seeked to position: {}ms", position_ms));
+        app.log(
+          LogLevel::Debug,
+          format!("Seeked to position: {}ms", position_ms),
+        );
       }
       Err(e) => {
         let error_msg = format!("{:?}", e);
         if error_msg.contains("status: 403") {
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for seek control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for playback controls"));
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for seek control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for playback controls"
+          ));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Seek error: {}", e));
+          app.log(LogLevel::Warn, format!("Seek error: {}", e));
           app.handle_error(anyhow::anyhow!("Error seeking to position: {}", e));
         }
       }
@@ -916,16 +2340,27 @@ impl Network {
       Ok(_) => {
         let mut app = self.app.lock().await;
         app.add_log_message(format!("Set shuffle to: {}", state));
+        // Shuffling reorders what's up next
+        app.dispatch(IoEvent::GetUpNext);
       }
       Err(e) => {
+        // Undo the state the playbar already shows
+        if let Some(context) = &mut self.app.lock().await.current_playback_context {
+          context.shuffle_state = !state;
+        }
         let error_msg = format!("{:?}", e);
         if error_msg.contains("status: 403") {
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for shuffle control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for playback controls"));
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for shuffle control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for playback controls"
+          ));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Shuffle error: {}", e));
+          app.log(LogLevel::Warn, format!("Shuffle error: {}", e));
           app.handle_error(anyhow::anyhow!("Error setting shuffle: {}", e));
         }
       }
@@ -948,54 +2383,74 @@ impl Network {
         app.add_log_message(format!("Set repeat to: {:?}", spotify_state));
       }
       Err(e) => {
+        // The playbar already shows the new state, so fetch the real one back
+        self.app.lock().await.dispatch(IoEvent::GetCurrentPlayback);
         let error_msg = format!("{:?}", e);
         if error_msg.contains("status: 403") {
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for repeat control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for playback controls"));
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for repeat control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for playback controls"
+          ));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Repeat error: {}", e));
+          app.log(LogLevel::Warn, format!("Repeat error: {}", e));
           app.handle_error(anyhow::anyhow!("Error setting repeat mode: {}", e));
         }
       }
     }
   }
 
-  async fn set_volume(&mut self, volume: u8) {
-    match self.spotify.volume(volume, None).await {
+  /// Set the volume of the given device, or the active one without an ID
+  async fn set_volume(&mut self, volume: u8, device_id: Option<String>) {
+    match self.spotify.volume(volume, device_id.as_deref()).await {
       Ok(_) => {
         let mut app = self.app.lock().await;
         app.add_log_message(format!("Set volume to: {}%", volume));
       }
       Err(e) => {
+        // The playbar or picker already shows the new volume, so fetch the real one back
+        let refresh = match device_id {
+          Some(_) => IoEvent::GetDevices,
+          None => IoEvent::GetCurrentPlayback,
+        };
+        self.app.lock().await.dispatch(refresh);
         let error_msg = format!("{:?}", e);
         if error_msg.contains("status: 403") {
           let mut app = self.app.lock().await;
-          app.add_log_message("Spotify Premium required for volume control".to_string());
-          app.handle_error(anyhow::anyhow!("Spotify Premium required for volume control"));
+          app.log(
+            LogLevel::Warn,
+            "Spotify Premium required for volume control".to_string(),
+          );
+          app.handle_error(anyhow::anyhow!(
+            "Spotify Premium required for volume control"
+          ));
         } else {
           let mut app = self.app.lock().await;
-          app.add_log_message(format!("Volume error: {}", e));
+          app.log(LogLevel::Warn, format!("Volume error: {}", e));
           app.handle_error(anyhow::anyhow!("Error setting volume: {}", e));
         }
       }
     }
   }
 
-  async fn transfer_playback_to_device(&mut self, device_id: String) {
-    self.log_error(&format!("DEBUG: Transferring playback to device: {}", device_id));
+  /// Move playback to the device, remembering it for next time. Without `play` the device only
+  /// becomes the active one, ready for whatever is played next.
+  async fn transfer_playback_to_device(&mut self, device_id: String, play: bool) {
+    debug!("Transferring playback to device: {}", device_id);
     
-    // Transfer playback with play=true to activate the device
-    match self.spotify.transfer_playback(&device_id, Some(true)).await {
+    match self.spotify.transfer_playback(&device_id, Some(play)).await {
           Ok(_) => {
-            self.log_error("SUCCESS: Playback transferred to device");
+            debug!("Playback transferred to device");
             
             // Save the device ID to config for future sessions
             if let Err(e) = self.client_config.set_device_id(device_id.clone()) {
-              self.log_error(&format!("Failed to save device ID to config: {}", e));
+              warn!("Failed to save device ID to config: {}", e);
             } else {
-              self.log_error("Device ID saved to config");
+              debug!("Device ID saved to config");
             }
             
             let mut app = self.app.lock().await;
@@ -1022,7 +2477,7 @@ impl Network {
             }
           }
       Err(e) => {
-        self.log_error(&format!("ERROR transferring playback: {:?}", e));
+        debug!("Failed to transfer playback: {:?}", e);
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to transfer playback: {}", e));
       }
@@ -1051,34 +2506,140 @@ impl Network {
         }
         
         let mut app = self.app.lock().await;
-        app.add_log_message(format!("Found {} devices", device_payload.devices.len()));
+        app.log(
+          LogLevel::Debug,
+          format!("Found {} devices", device_payload.devices.len()),
+        );
         app.devices = Some(device_payload);
         
         // Only set selected index if there are devices
         if !app.devices.as_ref().unwrap().devices.is_empty() {
           app.selected_device_index = Some(selected_index);
           
-          // If we found the saved device, activate it
-          if found_saved_device {
+          // Only on startup, and only when nothing is playing, so the saved device never takes
+          // over from one the user is listening on. Devices are polled every 30 seconds.
+          let nothing_playing = app
+            .current_playback_context
+            .as_ref()
+            .is_none_or(|context| !context.is_playing);
+          let restore = !self.restored_device && nothing_playing;
+          self.restored_device = true;
+
+          // A spotifyd spotui started is played on once it shows up, unless another device is
+          // already in use
+          let awaited_device = app.awaited_device.clone().and_then(|name| {
+            let devices = &app.devices.as_ref()?.devices;
+            let device = devices.iter().find(|device| device.name == name)?;
+            let any_active = devices.iter().any(|device| device.is_active);
+            Some((device.id.clone(), any_active))
+          });
+          if let Some((device_id, any_active)) = awaited_device {
+            app.awaited_device = None;
+            if let (Some(device_id), false, true) = (device_id, any_active, nothing_playing) {
+              app.add_log_message(format!("Playing on the new device: {}", device_id));
+              drop(app);
+              self
+                .transfer_playback_to_device(device_id.to_string(), false)
+                .await;
+              return;
+            }
+          }
+          if found_saved_device && restore {
             if let Some(saved_id) = saved_device_id {
               app.add_log_message(format!("Found saved device, activating: {}", saved_id));
               // Drop the lock before calling transfer_playback
               drop(app);
-              self.transfer_playback_to_device(saved_id).await;
+              self.transfer_playback_to_device(saved_id, false).await;
             }
-          } else {
-            app.add_log_message("No saved device found or device not available".to_string());
+          } else if !found_saved_device {
+            app.log(
+              LogLevel::Debug,
+              "No saved device found or device not available".to_string(),
+            );
           }
         }
       }
       Err(e) => {
         let mut app = self.app.lock().await;
-        app.add_log_message(format!("Error fetching devices: {}", e));
+        app.log(LogLevel::Warn, format!("Error fetching devices: {}", e));
         // Error already logged
       }
     }
   }
 
+  #[cfg(feature = "streaming")]
+  async fn start_streaming(&mut self) {
+    if self.streaming_device.is_some() {
+      return;
+    }
+    let (name, backend) = {
+      let app = self.app.lock().await;
+      let behavior = &app.user_config.behavior;
+      (
+        behavior.streaming_device_name.clone(),
+        behavior.streaming_audio_backend.clone(),
+      )
+    };
+    let access_token = match self.spotify.token.lock().await {
+      Ok(token) => token.as_ref().map(|token| token.access_token.clone()),
+      Err(_) => None,
+    };
+    let access_token = match access_token {
+      Some(access_token) => access_token,
+      None => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!(
+          "Can't start {} without being logged in",
+          name
+        ));
+        return;
+      }
+    };
+    let cache_dir = dirs::cache_dir().map(|cache| cache.join(APP_CACHE_DIR).join("streaming"));
+
+    match crate::streaming::StreamingDevice::start(&name, &backend, &access_token, cache_dir).await
+    {
+      Ok(device) => {
+        self.streaming_device = Some(device);
+        let mut app = self.app.lock().await;
+        app.streaming_device = Some(name.clone());
+        app.show_toast(format!("{} is ready to play on", name));
+        // So it shows up in the device picker
+        app.dispatch(IoEvent::GetDevices);
+      }
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Couldn't start the built-in device: {}", e));
+      }
+    }
+  }
+
+  #[cfg(not(feature = "streaming"))]
+  async fn start_streaming(&mut self) {
+    let mut app = self.app.lock().await;
+    app.show_toast("This build has no built-in device, see the streaming feature".to_string());
+  }
+
+  #[cfg(feature = "streaming")]
+  async fn stop_streaming(&mut self) {
+    let device = match self.streaming_device.take() {
+      Some(device) => device,
+      None => return,
+    };
+    let name = device.name().to_string();
+    let result = device.stop();
+    let mut app = self.app.lock().await;
+    app.streaming_device = None;
+    match result {
+      Ok(()) => app.show_toast(format!("Stopped {}", name)),
+      Err(e) => app.handle_error(anyhow::anyhow!("Couldn't stop {}: {}", name, e)),
+    }
+    app.dispatch(IoEvent::GetDevices);
+  }
+
+  #[cfg(not(feature = "streaming"))]
+  async fn stop_streaming(&mut self) {}
+
   async fn refresh_authentication(&mut self) {
     // Refreshing authentication token
     
@@ -1102,15 +2663,15 @@ impl Network {
               Ok(token_json) => {
                 match std::fs::write(&config_paths.token_cache_path, token_json) {
                   Ok(_) => {
-                    self.log_error("Successfully updated token cache");
+                    debug!("Successfully updated token cache");
                   }
                   Err(e) => {
-                    self.log_error(&format!("Failed to write token cache file: {}", e));
+                    warn!("Failed to write token cache file: {}", e);
                   }
                 }
               }
               Err(e) => {
-                self.log_error(&format!("Failed to serialize token: {}", e));
+                warn!("Failed to serialize token: {}", e);
               }
             }
           }
@@ -1129,13 +2690,13 @@ impl Network {
       Err(e) => {
         // Error refreshing token - handled below
         let mut app = self.app.lock().await;
-        app.handle_error(anyhow::anyhow!("Authentication failed: {}", e));
+        app.handle_fatal_error(anyhow::anyhow!("Authentication failed: {}", e));
       }
     }
   }
 
   async fn get_current_saved_tracks(&mut self, offset: Option<u32>) {
-    self.log_error("DEBUG: Starting get_current_saved_tracks");
+    debug!("Starting get_current_saved_tracks");
     use futures::{StreamExt, TryStreamExt};
     
     // Create a stream starting from the offset
@@ -1147,13 +2708,22 @@ impl Network {
     
     match tracks {
       Ok(saved_tracks) => {
-        self.log_error(&format!("SUCCESS: Got {} saved tracks", saved_tracks.len()));
+        debug!("Got {} saved tracks", saved_tracks.len());
         let mut app = self.app.lock().await;
         
         // Set the tracks in the track table for display
         app.track_table.tracks = saved_tracks.iter().map(|saved_track| {
           saved_track.track.clone()
         }).collect();
+        app.track_table.added_at = saved_tracks
+          .iter()
+          .filter_map(|saved_track| {
+            let id = saved_track.track.id.as_ref()?;
+            Some((id.to_string(), saved_track.added_at))
+          })
+          .collect();
+        app.track_table.context_positions.clear();
+        app.track_table.visual_anchor = None;
         
         // Create a Page<SavedTrack> to store in library.saved_tracks
         let page = Page {
@@ -1177,10 +2747,11 @@ impl Network {
         app.add_log_message(format!("Loaded {} liked songs", track_count));
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting saved tracks: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get saved tracks"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load saved tracks: {}", e));
       }
@@ -1188,7 +2759,7 @@ impl Network {
   }
 
   async fn get_current_user_saved_albums(&mut self, offset: Option<u32>) {
-    self.log_error("DEBUG: Starting get_current_user_saved_albums");
+    debug!("Starting get_current_user_saved_albums");
     use futures::{StreamExt, TryStreamExt};
     
     let stream = self.spotify.current_user_saved_albums(None);
@@ -1197,7 +2768,7 @@ impl Network {
     
     match albums {
       Ok(saved_albums) => {
-        self.log_error(&format!("SUCCESS: Got {} saved albums", saved_albums.len()));
+        debug!("Got {} saved albums", saved_albums.len());
         let mut app = self.app.lock().await;
         
         // Create a Page-like structure for the UI
@@ -1219,10 +2790,11 @@ impl Network {
         app.add_log_message(format!("Loaded {} saved albums", album_count));
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting saved albums: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get saved albums"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load saved albums: {}", e));
       }
@@ -1230,10 +2802,10 @@ impl Network {
   }
 
   async fn get_followed_artists(&mut self, after: Option<String>) {
-    self.log_error("DEBUG: Starting get_followed_artists");
+    debug!("Starting get_followed_artists");
     match self.spotify.current_user_followed_artists(after.as_deref(), Some(50)).await {
       Ok(cursor_page) => {
-        self.log_error(&format!("SUCCESS: Got {} followed artists", cursor_page.items.len()));
+        debug!("Got {} followed artists", cursor_page.items.len());
         let mut app = self.app.lock().await;
         
         // Store the artists - saved_artists expects a CursorBasedPage
@@ -1241,14 +2813,19 @@ impl Network {
         
         // Also populate the artists vec for the UI
         app.artists = cursor_page.items.clone();
+        app.showing_top_artists = false;
+        for artist in &cursor_page.items {
+          app.followed_artist_ids_set.insert(artist.id.to_string());
+        }
         
         app.add_log_message(format!("Loaded {} followed artists", cursor_page.items.len()));
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting followed artists: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get followed artists"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load followed artists: {}", e));
       }
@@ -1256,25 +2833,27 @@ impl Network {
   }
 
   async fn get_recently_played(&mut self) {
-    self.log_error("DEBUG: Starting get_recently_played");
+    debug!("Starting get_recently_played");
     
     // Get the last 50 recently played tracks
     match self.spotify.current_user_recently_played(Some(50), None).await {
       Ok(history) => {
-        self.log_error(&format!("SUCCESS: Got {} recently played tracks", history.items.len()));
+        debug!("Got {} recently played tracks", history.items.len());
         let mut app = self.app.lock().await;
-        
-        // Store recently played in the app state
-        app.recently_played.result = Some(history);
-        
-        let track_count = app.recently_played.result.as_ref().map(|h| h.items.len()).unwrap_or(0);
-        app.add_log_message(format!("Loaded {} recently played tracks", track_count));
+        let first_load = app.recently_played.result.is_none();
+        let track_count = app.merge_recently_played(history);
+        if first_load {
+          app.add_log_message(format!("Loaded {} recently played tracks", track_count));
+        } else if track_count > 0 {
+          app.add_log_message(format!("{} new recently played tracks", track_count));
+        }
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting recently played: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get recently played"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load recently played tracks: {}", e));
       }
@@ -1282,36 +2861,40 @@ impl Network {
   }
 
   async fn get_current_user_saved_shows(&mut self, _offset: Option<u32>) {
-    self.log_error("DEBUG: Starting get_current_user_saved_shows");
+    debug!("Starting get_current_user_saved_shows");
     let mut app = self.app.lock().await;
-    app.add_log_message("Podcasts feature requires additional work - the API returns a different Show type than expected".to_string());
+    app.log(LogLevel::Warn, "Podcasts feature requires additional work - the API returns a different Show type than expected".to_string());
     // TODO: The get_saved_show API returns Show, but the UI expects SimplifiedShow
     // This would require converting between the types or updating the UI
   }
 
   async fn get_top_tracks(&mut self) {
-    self.log_error("DEBUG: Starting get_top_tracks");
-    use rspotify::model::enums::TimeRange;
-    
-    // Get medium term (6 months) by default
-    match self.spotify.current_user_top_tracks_manual(Some(TimeRange::MediumTerm), Some(50), Some(0)).await {
+    debug!("Starting get_top_tracks");
+    let time_range = self.app.lock().await.top_time_range;
+
+    match self.spotify.current_user_top_tracks_manual(Some(spotify_time_range(time_range)), Some(50), Some(0)).await {
       Ok(page) => {
-        self.log_error(&format!("SUCCESS: Got {} top tracks", page.items.len()));
+        debug!("Got {} top tracks", page.items.len());
         let mut app = self.app.lock().await;
         
         // Set the tracks directly to the track table
         app.track_table.tracks = page.items.clone();
+        app.track_table.added_at.clear();
+        app.track_table.context_positions.clear();
+        app.track_table.visual_anchor = None;
         
         // Set context so the UI knows we're showing top tracks
-        app.track_table.context = Some(TrackTableContext::SavedTracks); // Using SavedTracks context for now
+        app.track_table.context = Some(TrackTableContext::TopTracks);
+        app.track_table.selected_index = 0;
         
-        app.add_log_message(format!("Loaded {} top tracks (last 6 months)", page.items.len()));
+        app.add_log_message(format!("Loaded {} top tracks ({})", page.items.len(), time_range.label()));
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting top tracks: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get top tracks"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load top tracks: {}", e));
       }
@@ -1319,25 +2902,31 @@ impl Network {
   }
 
   async fn get_top_artists(&mut self) {
-    self.log_error("DEBUG: Starting get_top_artists");
-    use rspotify::model::enums::TimeRange;
-    
-    // Get medium term (6 months) by default
-    match self.spotify.current_user_top_artists_manual(Some(TimeRange::MediumTerm), Some(50), Some(0)).await {
+    debug!("Starting get_top_artists");
+    let time_range = self.app.lock().await.top_time_range;
+
+    match self.spotify.current_user_top_artists_manual(Some(spotify_time_range(time_range)), Some(50), Some(0)).await {
       Ok(page) => {
-        self.log_error(&format!("SUCCESS: Got {} top artists", page.items.len()));
+        debug!("Got {} top artists", page.items.len());
         let mut app = self.app.lock().await;
         
         // Set the artists directly
         app.artists = page.items.clone();
+        app.showing_top_artists = true;
+        app.artists_list_index = 0;
         
-        app.add_log_message(format!("Loaded {} top artists (last 6 months)", page.items.len()));
+        app.add_log_message(format!("Loaded {} top artists ({})", page.items.len(), time_range.label()));
+        drop(app);
+
+        let artist_ids = page.items.iter().map(|artist| artist.id.to_string()).collect();
+        self.check_followed_artists(artist_ids).await;
       }
       Err(e) => {
-        let error_msg = format!("DETAILED ERROR getting top artists: {:?}", e);
-        let type_msg = format!("Error type: {}", std::any::type_name_of_val(&e));
-        self.log_error(&error_msg);
-        self.log_error(&type_msg);
+        debug!(
+          error = ?e,
+          error_type = std::any::type_name_of_val(&e),
+          "Failed to get top artists"
+        );
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load top artists: {}", e));
       }
@@ -1345,7 +2934,7 @@ impl Network {
   }
 
   async fn get_artist(&mut self, artist_id: String) {
-    self.log_error(&format!("DEBUG: Starting get_artist for ID: {}", artist_id));
+    debug!("Starting get_artist for ID: {}", artist_id);
     use rspotify::model::ArtistId;
     use futures::{StreamExt, TryStreamExt};
     
@@ -1360,7 +2949,7 @@ impl Network {
     let artist_id = match ArtistId::from_id(&artist_id_str) {
       Ok(id) => id,
       Err(e) => {
-        self.log_error(&format!("ERROR parsing artist ID: {:?}", e));
+        debug!("Failed to parse artist ID: {:?}", e);
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Invalid artist ID: {}", e));
         return;
@@ -1369,16 +2958,16 @@ impl Network {
     
     match self.spotify.artist(artist_id.clone()).await {
       Ok(full_artist) => {
-        self.log_error(&format!("SUCCESS: Got artist: {}", full_artist.name));
+        debug!("Got artist: {}", full_artist.name);
         
         // Get the artist's top tracks
         let top_tracks = match self.spotify.artist_top_tracks(artist_id.clone(), None).await {
           Ok(tracks) => {
-            self.log_error(&format!("Got {} top tracks for artist", tracks.len()));
+            debug!("Got {} top tracks for artist", tracks.len());
             tracks
           }
           Err(e) => {
-            self.log_error(&format!("ERROR getting artist top tracks: {:?}", e));
+            warn!("Failed to get artist top tracks: {:?}", e);
             vec![]
           }
         };
@@ -1389,7 +2978,7 @@ impl Network {
         
         let albums = match albums_result {
           Ok(items) => {
-            self.log_error(&format!("Got {} albums for artist", items.len()));
+            debug!("Got {} albums for artist", items.len());
             let total = items.len() as u32; // Capture length before move
             Page {
               href: String::new(),
@@ -1402,7 +2991,7 @@ impl Network {
             }
           }
           Err(e) => {
-            self.log_error(&format!("ERROR getting artist albums: {:?}", e));
+            warn!("Failed to get artist albums: {:?}", e);
             Page {
               href: String::new(),
               items: vec![],
@@ -1418,19 +3007,23 @@ impl Network {
         // Get related artists
         let related_artists = match self.spotify.artist_related_artists(artist_id).await {
           Ok(artists) => {
-            self.log_error(&format!("Got {} related artists", artists.len()));
+            debug!("Got {} related artists", artists.len());
             artists
           }
           Err(e) => {
-            self.log_error(&format!("ERROR getting related artists: {:?}", e));
+            debug!("Failed to get related artists: {:?}", e);
             vec![]
           }
         };
         
+        let mut followed_check_ids = vec![full_artist.id.to_string()];
+        followed_check_ids.extend(related_artists.iter().map(|artist| artist.id.to_string()));
+
         let mut app = self.app.lock().await;
         
         // Create the Artist struct
         let artist_data = Artist {
+          artist_id: full_artist.id.to_string(),
           artist_name: full_artist.name.clone(),
           albums,
           related_artists,
@@ -1443,39 +3036,1046 @@ impl Network {
         };
         
         app.artist = Some(artist_data);
+        app.finish_loading_route(RouteId::Artist, true);
         app.add_log_message(format!("Loaded artist: {}", full_artist.name));
+        drop(app);
+
+        self.check_followed_artists(followed_check_ids).await;
       }
       Err(e) => {
-        self.log_error(&format!("ERROR getting artist: {:?}", e));
+        debug!("Failed to get artist: {:?}", e);
         let mut app = self.app.lock().await;
         app.handle_error(anyhow::anyhow!("Failed to load artist: {}", e));
       }
     }
   }
 
-  async fn fetch_album_art(&mut self, url: String) {
+  async fn get_search_results(&mut self, query: String) {
+    let filtered = search_filter::parse(&query);
+    if filtered.text.is_empty() {
+      return;
+    }
+    if filtered.types.is_empty() {
+      let mut app = self.app.lock().await;
+      app.show_toast("Those filters don't apply to that result type".to_string());
+      return;
+    }
+
+    let spotify = &self.spotify;
+    let filtered = &filtered;
+    // A search narrowed to one type has room for more of its results
+    let limit = match filtered.types.len() {
+      1 => self.large_search_limit,
+      _ => self.small_search_limit,
+    };
+    let search = move |search_type| async move {
+      if !filtered.types.contains(&search_type) {
+        return None;
+      }
+      let result = spotify
+        .search(&filtered.text, search_type, None, None, Some(limit), None)
+        .await;
+      Some(result)
+    };
+    let (tracks, artists, albums, playlists, shows) = tokio::join!(
+      search(SearchType::Track),
+      search(SearchType::Artist),
+      search(SearchType::Album),
+      search(SearchType::Playlist),
+      search(SearchType::Show),
+    );
+
     let mut app = self.app.lock().await;
-    
-    // Get idle mode state before borrowing manager
-    let is_idle = app.is_idle_mode;
-    
-    if let Some(manager) = &mut app.album_art_manager {
-      // Use different sizes based on idle mode
-      // For idle mode, fetch larger size for better quality when scaling
-      // For normal mode, also fetch larger size since we're scaling it up in the playbar
-      let size = if is_idle { 256 } else { 64 };
-      
-      match manager.get_album_art(&url, size).await {
-        Ok(art) => {
-          app.current_album_art = Some(art);
-          app.add_log_message(format!("Successfully fetched album art ({}x{}) from: {}", size, size, url));
+    // Types a narrowed search left out show nothing rather than the previous results
+    app.search_results.clear(filtered.text.clone(), limit);
+    let results = [tracks, artists, albums, playlists, shows];
+    let mut error = None;
+    for result in results.into_iter().flatten() {
+      match result {
+        Ok(result) => app.search_results.add_page(result),
+        Err(e) => error = Some(e),
+      }
+    }
+    if let Some(e) = error {
+      app.handle_error(anyhow::anyhow!("Failed to search for \"{}\": {}", query, e));
+    }
+
+    // Show the results now and fill in the ♥ markers as the checks come back
+    for search_type in &filtered.types {
+      if let Some(check) = search_results_saved_check(&app.search_results, *search_type) {
+        app.dispatch(check);
+      }
+    }
+  }
+
+  async fn get_search_results_page(&mut self, search_type: SearchType, offset: u32) {
+    let (query, limit) = {
+      let app = self.app.lock().await;
+      (
+        app.search_results.query.clone(),
+        app.search_results.page_limit,
+      )
+    };
+    let result = self
+      .spotify
+      .search(&query, search_type, None, None, Some(limit), Some(offset))
+      .await;
+
+    let mut app = self.app.lock().await;
+    // A new search came back meanwhile, this page belongs to the old one
+    if app.search_results.query != query {
+      return;
+    }
+    match result {
+      Ok(result) => {
+        app.search_results.add_page(result);
+        if let Some(check) = search_results_saved_check(&app.search_results, search_type) {
+          app.dispatch(check);
+        }
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!("Failed to load more results: {}", e));
+      }
+    }
+  }
+
+  /// Refresh `liked_song_ids_set` for tracks shown outside the liked songs page
+  async fn check_liked_tracks(&mut self, track_ids: Vec<String>) {
+    use rspotify::model::TrackId;
+
+    let ids: Vec<TrackId<'static>> = track_ids
+      .iter()
+      .filter_map(|id| TrackId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+
+    // The saved tracks endpoints accept at most 50 ids per request
+    for chunk in ids.chunks(50) {
+      match self
+        .spotify
+        .current_user_saved_tracks_contains(chunk.iter().cloned())
+        .await
+      {
+        Ok(saved) => {
+          let mut app = self.app.lock().await;
+          update_saved_set(&mut app.liked_song_ids_set, chunk, saved);
         }
         Err(e) => {
-          app.add_log_message(format!("Failed to fetch album art: {}", e));
-          // Use placeholder art on failure
-          app.current_album_art = Some(crate::album_art::AlbumArtManager::get_placeholder_art(size));
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            format!("Failed to check liked tracks: {}", e),
+          );
+          return;
+        }
+      }
+    }
+  }
+
+  /// Refresh `saved_album_ids_set` for albums shown outside the saved albums page
+  async fn check_saved_albums(&mut self, album_ids: Vec<String>) {
+    use rspotify::model::AlbumId;
+
+    let ids: Vec<AlbumId<'static>> = album_ids
+      .iter()
+      .filter_map(|id| AlbumId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+
+    // The saved albums check accepts at most 20 ids per request
+    for chunk in ids.chunks(20) {
+      match self
+        .spotify
+        .current_user_saved_albums_contains(chunk.iter().cloned())
+        .await
+      {
+        Ok(saved) => {
+          let mut app = self.app.lock().await;
+          update_saved_set(&mut app.saved_album_ids_set, chunk, saved);
+        }
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            format!("Failed to check saved albums: {}", e),
+          );
+          return;
+        }
+      }
+    }
+  }
+
+  /// Refresh `saved_show_ids_set` for shows shown outside the podcasts page
+  async fn check_saved_shows(&mut self, show_ids: Vec<String>) {
+    use rspotify::model::ShowId;
+
+    let ids: Vec<ShowId<'static>> = show_ids
+      .iter()
+      .filter_map(|id| ShowId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+
+    // The saved shows check accepts at most 50 ids per request
+    for chunk in ids.chunks(50) {
+      match self.spotify.check_users_saved_shows(chunk.iter().cloned()).await {
+        Ok(saved) => {
+          let mut app = self.app.lock().await;
+          update_saved_set(&mut app.saved_show_ids_set, chunk, saved);
+        }
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            format!("Failed to check saved shows: {}", e),
+          );
+          return;
         }
       }
     }
   }
-}
\ No newline at end of file
+
+  /// Follow or unfollow artists. The caller has already updated `followed_artist_ids_set`, so a
+  /// failure checks their state again.
+  async fn user_follow_artists(&mut self, artist_ids: Vec<String>, follow: bool) {
+    use rspotify::model::ArtistId;
+
+    let ids: Vec<ArtistId<'static>> = artist_ids
+      .iter()
+      .filter_map(|id| ArtistId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+
+    // The follow endpoints accept at most 50 ids per request
+    for chunk in ids.chunks(50) {
+      let result = if follow {
+        self
+          .spotify
+          .user_follow_artists(chunk.iter().cloned())
+          .await
+      } else {
+        self
+          .spotify
+          .user_unfollow_artists(chunk.iter().cloned())
+          .await
+      };
+      if let Err(e) = result {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!(
+          "Failed to {} artists: {}",
+          if follow { "follow" } else { "unfollow" },
+          e
+        ));
+        app.dispatch(IoEvent::CheckFollowedArtists(artist_ids));
+        return;
+      }
+    }
+
+    let mut app = self.app.lock().await;
+    if !follow && !app.showing_top_artists {
+      // Drop them from the followed artists list in the library
+      let unfollowed = ids.iter().map(|id| id.to_string()).collect::<Vec<String>>();
+      app
+        .artists
+        .retain(|artist| !unfollowed.contains(&artist.id.to_string()));
+      app.artists_list_index = app
+        .artists_list_index
+        .min(app.artists.len().saturating_sub(1));
+    }
+    app.add_log_message(format!(
+      "{} {} artist{}",
+      if follow { "Followed" } else { "Unfollowed" },
+      ids.len(),
+      if ids.len() == 1 { "" } else { "s" }
+    ));
+  }
+
+  /// Refresh `followed_artist_ids_set` for artists shown outside the followed-artists page
+  async fn check_followed_artists(&mut self, artist_ids: Vec<String>) {
+    use rspotify::model::ArtistId;
+
+    let ids: Vec<ArtistId<'static>> = artist_ids
+      .iter()
+      .filter_map(|id| ArtistId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+
+    // The follow check endpoint accepts at most 50 ids per request
+    for chunk in ids.chunks(50) {
+      match self
+        .spotify
+        .user_artist_check_follow(chunk.iter().cloned())
+        .await
+      {
+        Ok(follows) => {
+          let mut app = self.app.lock().await;
+          for (id, is_followed) in chunk.iter().zip(follows) {
+            if is_followed {
+              app.followed_artist_ids_set.insert(id.to_string());
+            } else {
+              app.followed_artist_ids_set.remove(&id.to_string());
+            }
+          }
+        }
+        Err(e) => {
+          debug!("Failed to check followed artists: {:?}", e);
+          let mut app = self.app.lock().await;
+          app.log(
+            LogLevel::Warn,
+            format!("Failed to check followed artists: {}", e),
+          );
+          return;
+        }
+      }
+    }
+  }
+
+  /// Like every track, or unlike them all when they are all liked already
+  async fn toggle_save_tracks(&mut self, track_ids: Vec<String>) {
+    use rspotify::model::TrackId;
+
+    let ids: Vec<TrackId<'static>> = track_ids
+      .iter()
+      .filter_map(|id| TrackId::from_id_or_uri(id).ok().map(|id| id.into_static()))
+      .collect();
+    if ids.is_empty() {
+      return;
+    }
+
+    // The saved tracks endpoints accept at most 50 ids per request
+    let mut unsaved = vec![];
+    for chunk in ids.chunks(50) {
+      match self
+        .spotify
+        .current_user_saved_tracks_contains(chunk.iter().cloned())
+        .await
+      {
+        Ok(saved) => unsaved.extend(
+          chunk
+            .iter()
+            .zip(saved)
+            .filter(|(_, is_saved)| !is_saved)
+            .map(|(id, _)| id.clone()),
+        ),
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.handle_error(anyhow::anyhow!("Failed to check liked tracks: {}", e));
+          app.dispatch(IoEvent::CheckLikedTracks(track_ids));
+          return;
+        }
+      }
+    }
+
+    let (changed, like) = if unsaved.is_empty() {
+      (ids, false)
+    } else {
+      (unsaved, true)
+    };
+    for chunk in changed.chunks(50) {
+      let result = if like {
+        self
+          .spotify
+          .current_user_saved_tracks_add(chunk.iter().cloned())
+          .await
+      } else {
+        self
+          .spotify
+          .current_user_saved_tracks_delete(chunk.iter().cloned())
+          .await
+      };
+      if let Err(e) = result {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to update liked tracks: {}", e));
+        // Undo optimistic ♥ changes
+        app.dispatch(IoEvent::CheckLikedTracks(track_ids));
+        return;
+      }
+    }
+
+    let mut app = self.app.lock().await;
+    for id in &changed {
+      if like {
+        app.liked_song_ids_set.insert(id.to_string());
+      } else {
+        app.liked_song_ids_set.remove(&id.to_string());
+      }
+    }
+    app.add_log_message(format!(
+      "{} {} track{}",
+      if like { "Liked" } else { "Unliked" },
+      changed.len(),
+      if changed.len() == 1 { "" } else { "s" }
+    ));
+  }
+
+  /// Save an album to the library, or remove it if it is saved already
+  async fn toggle_save_album(&mut self, album_id: String) {
+    use rspotify::model::AlbumId;
+
+    let id = match AlbumId::from_id_or_uri(&album_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid album ID {}: {}", album_id, e));
+        return;
+      }
+    };
+    let saved = match self
+      .spotify
+      .current_user_saved_albums_contains([id.clone()])
+      .await
+    {
+      Ok(saved) => saved.first().copied().unwrap_or(false),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to check saved albums: {}", e));
+        return;
+      }
+    };
+    let result = if saved {
+      self
+        .spotify
+        .current_user_saved_albums_delete([id.clone()])
+        .await
+    } else {
+      self
+        .spotify
+        .current_user_saved_albums_add([id.clone()])
+        .await
+    };
+
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(()) => {
+        update_saved_set(&mut app.saved_album_ids_set, &[id], vec![!saved]);
+        let message = if saved {
+          "Removed the album from your library"
+        } else {
+          "Saved the album to your library"
+        };
+        app.show_toast(message.to_string());
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!("Failed to update saved albums: {}", e));
+      }
+    }
+  }
+
+  /// Remove an album from the library, and from the saved albums list if it is loaded
+  async fn current_user_saved_album_delete(&mut self, album_id: String) {
+    use rspotify::model::AlbumId;
+
+    let id = match AlbumId::from_id_or_uri(&album_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid album ID {}: {}", album_id, e));
+        return;
+      }
+    };
+
+    let result = self
+      .spotify
+      .current_user_saved_albums_delete([id.clone()])
+      .await;
+    let mut app = self.app.lock().await;
+    match result {
+      Ok(()) => {
+        update_saved_set(
+          &mut app.saved_album_ids_set,
+          std::slice::from_ref(&id),
+          vec![false],
+        );
+        let remaining = app.library.saved_albums.get_mut_results(None).map(|page| {
+          page.items.retain(|saved| saved.album.id != id);
+          page.items.len()
+        });
+        if let Some(remaining) = remaining {
+          app.album_list_index = app.album_list_index.min(remaining.saturating_sub(1));
+        }
+        app.show_toast("Removed the album from your library".to_string());
+      }
+      Err(e) => {
+        app.handle_error(anyhow::anyhow!("Failed to remove saved album: {}", e));
+      }
+    }
+  }
+
+  /// Spotify's queue only takes one item per request, so queue them in order
+  async fn add_items_to_queue(&mut self, item_uris: Vec<String>) {
+    use rspotify::model::{PlayableId, TrackId};
+
+    let device_id = {
+      let app = self.app.lock().await;
+      app.current_playback_context.as_ref()
+        .and_then(|ctx| ctx.device.id.as_ref())
+        .map(|id| id.to_string())
+    };
+
+    let mut queued = 0;
+    for uri in &item_uris {
+      let id = match TrackId::from_id_or_uri(uri) {
+        Ok(id) => id,
+        Err(e) => {
+          warn!("Invalid queue item URI '{}': {:?}", uri, e);
+          continue;
+        }
+      };
+      match self
+        .spotify
+        .add_item_to_queue(PlayableId::Track(id), device_id.as_deref())
+        .await
+      {
+        Ok(()) => queued += 1,
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.handle_error(anyhow::anyhow!(
+            "Failed to add to queue after {} of {} tracks: {}",
+            queued,
+            item_uris.len(),
+            e
+          ));
+          return;
+        }
+      }
+    }
+
+    let mut app = self.app.lock().await;
+    app.add_log_message(format!("Added {} tracks to queue", queued));
+    app.dispatch(IoEvent::GetUpNext);
+  }
+
+  async fn add_tracks_to_playlist(&mut self, playlist_id: String, track_uris: Vec<String>) {
+    use rspotify::model::{PlayableId, PlaylistId, TrackId};
+
+    let playlist_id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+    let ids: Vec<TrackId<'static>> = track_uris
+      .iter()
+      .filter_map(|uri| TrackId::from_id_or_uri(uri).ok().map(|id| id.into_static()))
+      .collect();
+
+    for chunk in ids.chunks(PLAYLIST_ITEMS_PER_REQUEST) {
+      if let Err(e) = self
+        .spotify
+        .playlist_add_items(
+          playlist_id.clone(),
+          chunk.iter().cloned().map(PlayableId::Track),
+          None,
+        )
+        .await
+      {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to add tracks to playlist: {}", e));
+        return;
+      }
+    }
+
+    let mut app = self.app.lock().await;
+    let playlist_name = app
+      .playlists
+      .as_ref()
+      .and_then(|playlists| {
+        playlists
+          .items
+          .iter()
+          .find(|playlist| playlist.id == playlist_id)
+          .map(|playlist| playlist.name.clone())
+      })
+      .unwrap_or_else(|| "playlist".to_string());
+    app.add_log_message(format!("Added {} tracks to {}", ids.len(), playlist_name));
+  }
+
+  async fn remove_tracks_from_playlist(&mut self, playlist_id: String, track_uris: Vec<String>) {
+    use rspotify::model::{PlayableId, PlaylistId, TrackId};
+
+    let playlist_id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+    let ids: Vec<TrackId<'static>> = track_uris
+      .iter()
+      .filter_map(|uri| TrackId::from_id_or_uri(uri).ok().map(|id| id.into_static()))
+      .collect();
+
+    for chunk in ids.chunks(PLAYLIST_ITEMS_PER_REQUEST) {
+      if let Err(e) = self
+        .spotify
+        .playlist_remove_all_occurrences_of_items(
+          playlist_id.clone(),
+          chunk.iter().cloned().map(PlayableId::Track),
+          None,
+        )
+        .await
+      {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to remove tracks from playlist: {}", e));
+        return;
+      }
+    }
+
+    // Update the open track table in place so the cursor stays where it was
+    let mut app = self.app.lock().await;
+    let removed_ids = ids.iter().map(|id| id.to_string()).collect::<Vec<String>>();
+    let showing_playlist = app
+      .viewed_playlist()
+      .map(|playlist| playlist.id == playlist_id)
+      .unwrap_or(false);
+    if showing_playlist {
+      app.track_table.remove_tracks(&removed_ids);
+    }
+
+    let mut playlist_name = "playlist".to_string();
+    if let Some(playlist) = app
+      .playlists
+      .as_mut()
+      .and_then(|playlists| playlists.items.iter_mut().find(|playlist| playlist.id == playlist_id))
+    {
+      playlist.tracks.total = playlist.tracks.total.saturating_sub(ids.len() as u32);
+      playlist_name = playlist.name.clone();
+    }
+    app.add_log_message(format!("Removed {} tracks from {}", ids.len(), playlist_name));
+  }
+
+  async fn import_tracks(&mut self, path: String, playlist_id: String) {
+    use rspotify::model::PlaylistId;
+
+    let playlist_id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+    let contents = match std::fs::read_to_string(paths::expand_home(&path)) {
+      Ok(contents) => contents,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Couldn't read {}: {}", path, e));
+        return;
+      }
+    };
+    let (entries, unreadable) = import::parse(&contents);
+
+    let app = &self.app;
+    let redraw_notifier = &self.redraw_notifier;
+    let result = import::import_tracks(
+      &self.spotify,
+      playlist_id.clone(),
+      entries,
+      self.request_timeout,
+      |done, total| {
+        // Skipped while the UI holds the app, the next entry shows it instead
+        if let Some(mut app) = app.try_lock() {
+          let message = format!("Importing: {} of {} tracks looked up", done, total);
+          app.toast = Some((message, Instant::now()));
+          redraw_notifier.notify();
+        }
+      },
+    )
+    .await;
+    let mut summary = match result {
+      Ok(summary) => summary,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(e);
+        return;
+      }
+    };
+    summary.misses.extend(unreadable);
+
+    let showing_playlist = {
+      let mut app = self.app.lock().await;
+      let mut playlist_name = "playlist".to_string();
+      if let Some(playlist) = app.playlists.as_mut().and_then(|playlists| {
+        playlists
+          .items
+          .iter_mut()
+          .find(|playlist| playlist.id == playlist_id)
+      }) {
+        playlist.tracks.total += summary.added as u32;
+        playlist_name = playlist.name.clone();
+      }
+      for miss in &summary.misses {
+        app.log(LogLevel::Warn, format!("Import: not found: {}", miss));
+      }
+      app.show_toast(summary.describe(&playlist_name));
+      app
+        .viewed_playlist()
+        .map(|playlist| playlist.id == playlist_id)
+        .unwrap_or(false)
+    };
+
+    // Show the imported tracks if the playlist is open
+    if showing_playlist && summary.added > 0 {
+      self
+        .reload_viewed_playlist_tracks(&playlist_id.to_string())
+        .await;
+    }
+  }
+
+  async fn find_playlist_duplicates(&mut self, playlist_id: String) {
+    use rspotify::model::PlaylistId;
+
+    let id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+
+    // Scan the whole playlist rather than the track table, which leaves out episodes and so
+    // doesn't know the positions removal needs
+    let items = match self
+      .spotify
+      .playlist_items(id, None, None)
+      .try_collect::<Vec<_>>()
+      .await
+    {
+      Ok(items) => items,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to load playlist tracks: {}", e));
+        return;
+      }
+    };
+    let tracks = items
+      .into_iter()
+      .enumerate()
+      .filter_map(|(position, item)| match item.track {
+        Some(PlayableItem::Track(track)) => Some(ScannedTrack {
+          position: position as u32,
+          id: track.id?.to_string(),
+          name: track.name,
+          artists: track
+            .artists
+            .iter()
+            .map(|artist| artist.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        }),
+        _ => None,
+      })
+      .collect::<Vec<ScannedTrack>>();
+    let found = duplicates::find(&tracks);
+
+    let mut app = self.app.lock().await;
+    if let Some(review) = app
+      .playlist_duplicates
+      .as_mut()
+      .filter(|review| review.playlist_id == playlist_id)
+    {
+      review.duplicates = Some(found);
+      review.selected_index = 0;
+    }
+  }
+
+  async fn remove_playlist_duplicates(&mut self, playlist_id: String, duplicates: Vec<Duplicate>) {
+    use rspotify::model::{ItemPositions, PlayableId, PlaylistId, TrackId};
+
+    let id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+    let mut entries = duplicates
+      .iter()
+      .filter_map(|duplicate| {
+        let track_id = TrackId::from_id_or_uri(&duplicate.id).ok()?.into_static();
+        Some((track_id, [duplicate.position]))
+      })
+      .collect::<Vec<_>>();
+    // Removing from the end first keeps the positions of the next batch valid
+    entries.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    for (index, chunk) in entries.chunks(PLAYLIST_ITEMS_PER_REQUEST).enumerate() {
+      let items = chunk.iter().map(|(track_id, positions)| ItemPositions {
+        id: PlayableId::Track(track_id.clone()),
+        positions,
+      });
+      // Each batch gets the request timeout, as there is none for the whole removal
+      let removal = tokio::time::timeout(
+        self.request_timeout,
+        self
+          .spotify
+          .playlist_remove_specific_occurrences_of_items(id.clone(), items, None),
+      )
+      .await;
+      let error = match removal {
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!(
+          "request timed out after {}s",
+          self.request_timeout.as_secs()
+        )),
+      };
+      let mut app = self.app.lock().await;
+      if let Some(error) = error {
+        app.handle_error(anyhow::anyhow!("Failed to remove duplicates: {}", error));
+        return;
+      }
+      let removed = entries.len().min((index + 1) * PLAYLIST_ITEMS_PER_REQUEST);
+      let message = format!("Removing duplicates: {} of {}", removed, entries.len());
+      app.toast = Some((message, Instant::now()));
+      drop(app);
+      self.redraw_notifier.notify();
+    }
+
+    let showing_playlist = {
+      let mut app = self.app.lock().await;
+      let mut playlist_name = "playlist".to_string();
+      if let Some(playlist) = app.playlists.as_mut().and_then(|playlists| {
+        playlists
+          .items
+          .iter_mut()
+          .find(|playlist| playlist.id == id)
+      }) {
+        playlist.tracks.total = playlist.tracks.total.saturating_sub(entries.len() as u32);
+        playlist_name = playlist.name.clone();
+      }
+      app.playlist_duplicates = None;
+      if app.get_current_route().id == RouteId::Duplicates {
+        app.pop_navigation_stack();
+      }
+      app.add_log_message(format!(
+        "Removed {} duplicates from {}",
+        entries.len(),
+        playlist_name
+      ));
+      app
+        .viewed_playlist()
+        .map(|playlist| playlist.id == id)
+        .unwrap_or(false)
+    };
+
+    // Positions in the track table are stale now
+    if showing_playlist {
+      self.reload_viewed_playlist_tracks(&playlist_id).await;
+    }
+  }
+
+  async fn create_playlist(&mut self, name: String) {
+    use rspotify::model::playlist::PlaylistTracksRef;
+
+    let known_user_id = self.app.lock().await.user.as_ref().map(|user| user.id.clone());
+    let user_id = match known_user_id {
+      Some(user_id) => user_id,
+      None => match self.spotify.me().await {
+        Ok(user) => user.id,
+        Err(e) => {
+          let mut app = self.app.lock().await;
+          app.handle_error(anyhow::anyhow!("Failed to create playlist: {}", e));
+          return;
+        }
+      },
+    };
+
+    match self
+      .spotify
+      .user_playlist_create(user_id, &name, Some(false), None, None)
+      .await
+    {
+      Ok(playlist) => {
+        let created = SimplifiedPlaylist {
+          collaborative: playlist.collaborative,
+          external_urls: playlist.external_urls,
+          href: playlist.href,
+          id: playlist.id,
+          images: playlist.images,
+          name: playlist.name,
+          owner: playlist.owner,
+          public: playlist.public,
+          snapshot_id: playlist.snapshot_id,
+          tracks: PlaylistTracksRef {
+            href: playlist.tracks.href,
+            total: playlist.tracks.total,
+          },
+        };
+        let mut app = self.app.lock().await;
+        app.add_log_message(format!("Created playlist {}", created.name));
+        // New playlists go first, like in the Spotify clients, with the selection following it
+        match app.playlists.as_mut() {
+          Some(playlists) => {
+            playlists.items.insert(0, created);
+            playlists.total += 1;
+          }
+          None => {
+            app.playlists = Some(Page {
+              items: vec![created],
+              limit: 50,
+              offset: 0,
+              total: 1,
+              next: None,
+              previous: None,
+              href: String::new(),
+            });
+          }
+        }
+        app.selected_playlist_index = Some(0);
+        app.sort_playlists();
+      }
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to create playlist: {}", e));
+      }
+    }
+  }
+
+  async fn rename_playlist(&mut self, playlist_id: String, name: String) {
+    use rspotify::model::PlaylistId;
+
+    let id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+
+    match self
+      .spotify
+      .playlist_change_detail(id.clone(), Some(&name), None, None, None)
+      .await
+    {
+      Ok(_) => {
+        let mut app = self.app.lock().await;
+        if let Some(playlist) = app
+          .playlists
+          .as_mut()
+          .and_then(|playlists| playlists.items.iter_mut().find(|playlist| playlist.id == id))
+        {
+          playlist.name = name.clone();
+        }
+        app.sort_playlists();
+        app.add_log_message(format!("Renamed playlist to {}", name));
+      }
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to rename playlist: {}", e));
+      }
+    }
+  }
+
+  // Spotify has no real playlist deletion, unfollowing your own playlist is what its clients do
+  async fn user_unfollow_playlist(&mut self, playlist_id: String) {
+    use rspotify::model::PlaylistId;
+
+    let id = match PlaylistId::from_id_or_uri(&playlist_id) {
+      Ok(id) => id.into_static(),
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Invalid playlist ID: {}", e));
+        return;
+      }
+    };
+
+    match self.spotify.playlist_unfollow(id.clone()).await {
+      Ok(_) => {
+        let mut app = self.app.lock().await;
+        let removed = app.playlists.as_mut().and_then(|playlists| {
+          let index = playlists.items.iter().position(|playlist| playlist.id == id)?;
+          playlists.total = playlists.total.saturating_sub(1);
+          Some((index, playlists.items.remove(index), playlists.items.len()))
+        });
+        match removed {
+          Some((index, playlist, remaining)) => {
+            // Keep the selection on the neighbouring playlist
+            app.selected_playlist_index = match app.selected_playlist_index {
+              _ if remaining == 0 => None,
+              Some(selected) if selected > index || selected >= remaining => Some(selected - 1),
+              selected => selected,
+            };
+            app.add_log_message(format!("Deleted playlist {}", playlist.name));
+          }
+          None => app.add_log_message("Unfollowed playlist".to_string()),
+        }
+      }
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        app.handle_error(anyhow::anyhow!("Failed to delete playlist: {}", e));
+      }
+    }
+  }
+
+  async fn fetch_album_art(&mut self, url: String) {
+    let mut app = self.app.lock().await;
+    
+    // Get idle mode state before borrowing manager
+    let is_idle = app.is_idle_mode;
+    
+    if let Some(manager) = &mut app.album_art_manager {
+      // Use different sizes based on idle mode
+      // For idle mode, fetch larger size for better quality when scaling
+      // For normal mode, also fetch larger size since we're scaling it up in the playbar
+      let size = if is_idle { 256 } else { 64 };
+      
+      match manager.get_album_art(&url, size).await {
+        Ok(art) => {
+          app.current_album_art = Some(art);
+          app.log(
+            LogLevel::Debug,
+            format!(
+              "Successfully fetched album art ({}x{}) from: {}",
+              size, size, url
+            ),
+          );
+        }
+        Err(e) => {
+          app.log(LogLevel::Warn, format!("Failed to fetch album art: {}", e));
+          // Use placeholder art on failure
+          app.current_album_art = Some(crate::album_art::AlbumArtManager::get_placeholder_art(size));
+        }
+      }
+    }
+  }
+
+  async fn fetch_profile_image(&mut self, url: String) {
+    let mut app = self.app.lock().await;
+    // Reborrowed so the manager and the image are borrowed as separate fields
+    let app = &mut *app;
+    if let Some(manager) = &mut app.album_art_manager {
+      match manager.get_album_art(&url, 32).await {
+        Ok(image) => app.profile_image = Some(image),
+        Err(e) => app.log(
+          LogLevel::Warn,
+          format!("Failed to fetch profile image: {}", e),
+        ),
+      }
+    }
+  }
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn track_cache_drops_least_recently_used() {
+    let mut cache = TrackCache::new(2);
+    cache.insert("a", 1).unwrap();
+    cache.insert("b", 2).unwrap();
+    assert_eq!(cache.get("a"), Some(1));
+
+    cache.insert("c", 3).unwrap();
+    assert_eq!(cache.get("b"), None);
+    assert_eq!(cache.get("a"), Some(1));
+    assert_eq!(cache.get("c"), Some(3));
+  }
+
+  #[test]
+  fn repeat_state_cycles() {
+    assert_eq!(RepeatState::Off.next(), RepeatState::Context);
+    assert_eq!(RepeatState::Context.next(), RepeatState::Track);
+    assert_eq!(RepeatState::Track.next(), RepeatState::Off);
+  }
+}
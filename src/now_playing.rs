@@ -0,0 +1,42 @@
+//! Files holding what is playing, kept up to date for stream overlays such as OBS text and image
+//! sources. Each is written to a temporary file first and renamed into place, so a source never
+//! reads one half written.
+
+use crate::paths::expand_home;
+use anyhow::{anyhow, Result};
+use std::{fs, path::Path, time::Duration};
+
+// Longest a cover download may take
+const ART_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn write_text(path: &str, text: &str) -> Result<()> {
+  write_atomically(&expand_home(path), text.as_bytes())
+}
+
+/// Download the cover image and put it at `path`
+pub async fn write_art(path: &str, url: &str) -> Result<()> {
+  let client = reqwest::Client::builder().timeout(ART_TIMEOUT).build()?;
+  let bytes = client
+    .get(url)
+    .send()
+    .await?
+    .error_for_status()?
+    .bytes()
+    .await?;
+  write_atomically(&expand_home(path), &bytes)
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+  let file_name = path
+    .file_name()
+    .ok_or_else(|| anyhow!("{} isn't a file path", path.display()))?;
+  let mut temporary_name = file_name.to_os_string();
+  temporary_name.push(".tmp");
+  let temporary_path = path.with_file_name(temporary_name);
+
+  fs::write(&temporary_path, contents)
+    .map_err(|e| anyhow!("Couldn't write {}: {}", temporary_path.display(), e))?;
+  fs::rename(&temporary_path, path)
+    .map_err(|e| anyhow!("Couldn't replace {}: {}", path.display(), e))?;
+  Ok(())
+}
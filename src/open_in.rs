@@ -0,0 +1,249 @@
+use crate::network;
+use anyhow::{anyhow, Result};
+use rspotify::{
+  clients::OAuthClient,
+  model::{
+    track::FullTrack, AlbumId, ArtistId, EpisodeId, PlayContextId, PlayableId, PlaylistId, ShowId,
+    TrackId,
+  },
+  AuthCodeSpotify,
+};
+use std::{
+  env,
+  path::PathBuf,
+  process::{Command, Stdio},
+};
+
+/// Something the "open in" menu can open: what the menu shows and its `spotify:` URI
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenTarget {
+  pub label: String,
+  pub uri: String,
+}
+
+impl OpenTarget {
+  pub fn new(kind: &str, name: &str, id: &str) -> OpenTarget {
+    let mut chars = kind.chars();
+    let title = chars
+      .next()
+      .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+      .unwrap_or_default();
+    OpenTarget {
+      label: format!("{}: {}", title, name),
+      uri: uri(kind, id),
+    }
+  }
+}
+
+/// The `spotify:` URI of a `kind` such as "track", `id` being either a bare ID or already a URI
+pub fn uri(kind: &str, id: &str) -> String {
+  let prefix = format!("spotify:{}:", kind);
+  if id.starts_with(&prefix) {
+    id.to_string()
+  } else {
+    format!("{}{}", prefix, id)
+  }
+}
+
+/// The `spotify:` URI of a track, if it has an ID (local files don't)
+pub fn track_uri(track: &FullTrack) -> Option<String> {
+  track.id.as_ref().map(|id| uri("track", &id.to_string()))
+}
+
+/// The "open in" menu for the playing item or the selected track
+#[derive(Clone, Default)]
+pub struct OpenInMenu {
+  pub targets: Vec<OpenTarget>,
+  pub selected_index: usize,
+  // Whether targets open in the Spotify app rather than the browser
+  pub in_app: bool,
+}
+
+/// A track, its album and its artists
+pub fn track_targets(track: &FullTrack) -> Vec<OpenTarget> {
+  let mut targets = vec![];
+  if let Some(id) = &track.id {
+    targets.push(OpenTarget::new("track", &track.name, &id.to_string()));
+  }
+  if let Some(id) = &track.album.id {
+    targets.push(OpenTarget::new("album", &track.album.name, &id.to_string()));
+  }
+  for artist in &track.artists {
+    if let Some(id) = &artist.id {
+      targets.push(OpenTarget::new("artist", &artist.name, &id.to_string()));
+    }
+  }
+  targets
+}
+
+/// The open.spotify.com page for a `spotify:` URI
+pub fn web_url(uri: &str) -> Option<String> {
+  match uri.split(':').collect::<Vec<&str>>().as_slice() {
+    ["spotify", kind, id] if !kind.is_empty() && !id.is_empty() => {
+      Some(format!("https://open.spotify.com/{}/{}", kind, id))
+    }
+    _ => None,
+  }
+}
+
+/// The `spotify:` URI of a pasted Spotify link: an open.spotify.com URL (with or without the
+/// scheme, a locale segment such as `intl-de` or query parameters) or a `spotify:` URI,
+/// including the old `spotify:user:<name>:playlist:<id>` form
+pub fn link_uri(input: &str) -> Option<String> {
+  let input = input.trim();
+  let parts: Vec<&str> = if let Some(uri) = input.strip_prefix("spotify:") {
+    uri.split(':').collect()
+  } else {
+    let url = input
+      .strip_prefix("https://")
+      .or_else(|| input.strip_prefix("http://"))
+      .unwrap_or(input);
+    let path = url
+      .strip_prefix("open.spotify.com/")
+      .or_else(|| url.strip_prefix("play.spotify.com/"))?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path
+      .split('/')
+      .filter(|part| !part.is_empty() && !part.starts_with("intl-"))
+      .collect()
+  };
+
+  let (kind, id) = match parts.as_slice() {
+    [kind, id] | ["user", _, kind, id] => (*kind, *id),
+    _ => return None,
+  };
+  let known_kind = ["album", "artist", "track", "playlist", "show", "episode"].contains(&kind);
+  if known_kind && !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+    Some(uri(kind, id))
+  } else {
+    None
+  }
+}
+
+/// Whether a Spotify desktop client is installed to handle `spotify:` URIs
+pub fn has_desktop_client() -> bool {
+  if cfg!(target_os = "macos") {
+    let home_apps = dirs::home_dir().map(|home| home.join("Applications/Spotify.app"));
+    PathBuf::from("/Applications/Spotify.app").exists()
+      || home_apps.map(|path| path.exists()).unwrap_or(false)
+  } else if cfg!(target_os = "windows") {
+    env::var_os("APPDATA")
+      .map(|app_data| PathBuf::from(app_data).join("Spotify/Spotify.exe").exists())
+      .unwrap_or(false)
+  } else {
+    env::var_os("PATH")
+      .map(|path| env::split_paths(&path).any(|dir| dir.join("spotify").is_file()))
+      .unwrap_or(false)
+  }
+}
+
+/// Open `target` in the Spotify app when `in_app`, otherwise on open.spotify.com in the browser
+pub fn open(target: &OpenTarget, in_app: bool) -> Result<()> {
+  if !in_app {
+    let url = web_url(&target.uri).ok_or_else(|| anyhow!("Can't open {}", target.uri))?;
+    return webbrowser::open(&url).map_err(|e| anyhow!("Failed to open {}: {}", url, e));
+  }
+
+  let mut command = if cfg!(target_os = "macos") {
+    Command::new("open")
+  } else if cfg!(target_os = "windows") {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start", ""]);
+    command
+  } else {
+    Command::new("xdg-open")
+  };
+  command
+    .arg(&target.uri)
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .map(|_| ())
+    .map_err(|e| anyhow!("Failed to open {} in Spotify: {}", target.uri, e))
+}
+
+/// Play a `spotify:` URI on the active device: tracks and episodes on their own, anything else
+/// as the playback context
+pub async fn play(spotify: &AuthCodeSpotify, uri: &str) -> Result<()> {
+  let result = match uri.split(':').nth(1).unwrap_or_default() {
+    "track" => {
+      let item = PlayableId::Track(TrackId::from_uri(uri)?);
+      spotify.start_uris_playback([item], None, None, None).await
+    }
+    "episode" => {
+      let item = PlayableId::Episode(EpisodeId::from_uri(uri)?);
+      spotify.start_uris_playback([item], None, None, None).await
+    }
+    kind => {
+      let context = match kind {
+        "album" => PlayContextId::Album(AlbumId::from_uri(uri)?),
+        "artist" => PlayContextId::Artist(ArtistId::from_uri(uri)?),
+        "playlist" => PlayContextId::Playlist(PlaylistId::from_uri(uri)?),
+        "show" => PlayContextId::Show(ShowId::from_uri(uri)?),
+        _ => return Err(anyhow!("Can't play {}", uri)),
+      };
+      spotify
+        .start_context_playback(context, None, None, None)
+        .await
+    }
+  };
+  match result {
+    Ok(()) => Ok(()),
+    Err(mut e) => {
+      if network::is_no_active_device(&mut e).await {
+        Err(anyhow!(
+          "No active device to play {} on, start Spotify somewhere first",
+          uri
+        ))
+      } else {
+        Err(anyhow!("Failed to play {}: {}", uri, e))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_open_target() {
+    let target = OpenTarget::new("album", "Low", "spotify:album:1DFixLWuPkv3KT3TnV35m3");
+    assert_eq!(target.label, "Album: Low");
+    assert_eq!(target.uri, "spotify:album:1DFixLWuPkv3KT3TnV35m3");
+    assert_eq!(
+      OpenTarget::new("track", "Heroes", "4uLU6hMCjMI75M1A2tKUQC").uri,
+      "spotify:track:4uLU6hMCjMI75M1A2tKUQC"
+    );
+    assert_eq!(
+      web_url(&target.uri),
+      Some("https://open.spotify.com/album/1DFixLWuPkv3KT3TnV35m3".to_string())
+    );
+    assert_eq!(web_url("https://open.spotify.com/album/1"), None);
+  }
+
+  #[test]
+  fn test_link_uri() {
+    let album = Some("spotify:album:1DFixLWuPkv3KT3TnV35m3".to_string());
+    assert_eq!(link_uri(" spotify:album:1DFixLWuPkv3KT3TnV35m3\n"), album);
+    assert_eq!(
+      link_uri("https://open.spotify.com/intl-de/album/1DFixLWuPkv3KT3TnV35m3?si=abc#x"),
+      album
+    );
+    assert_eq!(
+      link_uri("open.spotify.com/album/1DFixLWuPkv3KT3TnV35m3/"),
+      album
+    );
+    assert_eq!(
+      link_uri("spotify:user:someone:playlist:37i9dQZF1DX4sWSpwq3LiO"),
+      Some("spotify:playlist:37i9dQZF1DX4sWSpwq3LiO".to_string())
+    );
+    assert_eq!(
+      link_uri("https://example.com/album/1DFixLWuPkv3KT3TnV35m3"),
+      None
+    );
+    assert_eq!(link_uri("https://open.spotify.com/user/someone"), None);
+    assert_eq!(link_uri("spotify:album:"), None);
+    assert_eq!(link_uri("david bowie low"), None);
+  }
+}
@@ -0,0 +1,205 @@
+use crate::paths::expand_home;
+use crate::user_config::Theme;
+use anyhow::{anyhow, Result};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, ops::Range, path::Path, path::PathBuf};
+
+const PYWAL_COLORS_PATH: &str = ".cache/wal/colors.json";
+
+/// Where an external color scheme comes from
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteKind {
+  Pywal,
+  Base16,
+}
+
+/// A terminal color scheme: background, foreground and the 16 ANSI colors
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+  pub background: Color,
+  pub foreground: Color,
+  pub colors: [Color; 16],
+}
+
+#[derive(Deserialize)]
+struct PywalSpecial {
+  background: String,
+  foreground: String,
+}
+
+#[derive(Deserialize)]
+struct PywalColors {
+  special: PywalSpecial,
+  colors: HashMap<String, String>,
+}
+
+/// Parse a `theme.source` value: `pywal`, `pywal:<path>` or `base16:<path>`
+pub fn parse_source(source: &str) -> Result<(PaletteKind, PathBuf)> {
+  let (kind, path) = match source.split_once(':') {
+    Some((kind, path)) => (kind, Some(path.trim())),
+    None => (source, None),
+  };
+
+  match (kind.trim().to_lowercase().as_str(), path) {
+    ("pywal", Some(path)) => Ok((PaletteKind::Pywal, expand_home(path))),
+    ("pywal", None) => match dirs::home_dir() {
+      Some(home) => Ok((PaletteKind::Pywal, home.join(PYWAL_COLORS_PATH))),
+      None => Err(anyhow!("No $HOME directory found for pywal colors")),
+    },
+    ("base16", Some(path)) => Ok((PaletteKind::Base16, expand_home(path))),
+    ("base16", None) => Err(anyhow!(
+      "A base16 theme source needs a path, e.g. \"base16:~/schemes/nord.yaml\""
+    )),
+    _ => Err(anyhow!(
+      "Unknown theme source \"{}\", expected pywal or base16:<path>",
+      source
+    )),
+  }
+}
+
+pub fn load(kind: &PaletteKind, path: &Path) -> Result<Palette> {
+  let contents = fs::read_to_string(path)
+    .map_err(|e| anyhow!("Could not read theme source {}: {}", path.display(), e))?;
+  match kind {
+    PaletteKind::Pywal => parse_pywal(&contents),
+    PaletteKind::Base16 => parse_base16(&contents),
+  }
+}
+
+fn parse_pywal(contents: &str) -> Result<Palette> {
+  let pywal: PywalColors = serde_json::from_str(contents)?;
+  let mut colors = [Color::Reset; 16];
+  for (i, color) in colors.iter_mut().enumerate() {
+    let key = format!("color{}", i);
+    match pywal.colors.get(&key) {
+      Some(hex) => *color = parse_hex(hex)?,
+      None => return Err(anyhow!("pywal colors are missing {}", key)),
+    }
+  }
+
+  Ok(Palette {
+    background: parse_hex(&pywal.special.background)?,
+    foreground: parse_hex(&pywal.special.foreground)?,
+    colors,
+  })
+}
+
+fn parse_base16(contents: &str) -> Result<Palette> {
+  let scheme: HashMap<String, String> = serde_yaml::from_str(contents)?;
+  let base = |slot: &str| -> Result<Color> {
+    scheme
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(slot))
+      .map(|(_, value)| parse_hex(value))
+      .unwrap_or_else(|| Err(anyhow!("base16 scheme is missing {}", slot)))
+  };
+
+  // Same slot to ANSI mapping as base16-shell
+  let colors = [
+    base("base00")?,
+    base("base08")?,
+    base("base0B")?,
+    base("base0A")?,
+    base("base0D")?,
+    base("base0E")?,
+    base("base0C")?,
+    base("base05")?,
+    base("base03")?,
+    base("base08")?,
+    base("base0B")?,
+    base("base0A")?,
+    base("base0D")?,
+    base("base0E")?,
+    base("base0C")?,
+    base("base07")?,
+  ];
+
+  Ok(Palette {
+    background: base("base00")?,
+    foreground: base("base05")?,
+    colors,
+  })
+}
+
+fn parse_hex(hex: &str) -> Result<Color> {
+  let hex = hex.trim().trim_start_matches('#');
+  if hex.len() != 6 || !hex.is_ascii() {
+    return Err(anyhow!("Expected a hex color like #rrggbb, got \"{}\"", hex));
+  }
+  let channel = |range: Range<usize>| u8::from_str_radix(&hex[range], 16);
+  Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+impl Palette {
+  /// Map the palette onto the theme slots, mirroring the named colors of the default theme
+  pub fn apply(&self, theme: &mut Theme) {
+    let c = &self.colors;
+    theme.analysis_bar = c[14];
+    theme.analysis_bar_text = self.foreground;
+    theme.active = c[6];
+    theme.banner = c[14];
+    theme.error_border = c[1];
+    theme.error_text = c[9];
+    theme.hint = c[3];
+    theme.hovered = c[5];
+    theme.inactive = c[8];
+    theme.playbar_background = self.background;
+    theme.playbar_progress = c[14];
+    theme.playbar_progress_text = c[6];
+    theme.playbar_text = self.foreground;
+    theme.selected = c[14];
+    theme.text = self.foreground;
+    theme.header = self.foreground;
+    theme.focus_letter = c[3];
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_hex() {
+    assert_eq!(parse_hex("#ff8000").unwrap(), Color::Rgb(255, 128, 0));
+    assert_eq!(parse_hex("2E3440").unwrap(), Color::Rgb(46, 52, 64));
+    assert!(parse_hex("#fff").is_err());
+  }
+
+  #[test]
+  fn test_parse_pywal() {
+    let colors = (0..16)
+      .map(|i| format!("\"color{}\": \"#0000{:02x}\"", i, i))
+      .collect::<Vec<String>>()
+      .join(",");
+    let json = format!(
+      "{{\"special\": {{\"background\": \"#101010\", \"foreground\": \"#eeeeee\", \"cursor\": \"#eeeeee\"}}, \"colors\": {{{}}}}}",
+      colors
+    );
+    let palette = parse_pywal(&json).unwrap();
+    assert_eq!(palette.background, Color::Rgb(16, 16, 16));
+    assert_eq!(palette.colors[15], Color::Rgb(0, 0, 15));
+  }
+
+  #[test]
+  fn test_parse_base16() {
+    let yaml = (0..16)
+      .map(|i| format!("base0{:X}: \"{:02x}0000\"", i, i))
+      .collect::<Vec<String>>()
+      .join("\n");
+    let palette = parse_base16(&format!("scheme: \"Test\"\n{}", yaml)).unwrap();
+    assert_eq!(palette.background, Color::Rgb(0, 0, 0));
+    assert_eq!(palette.foreground, Color::Rgb(5, 0, 0));
+    assert_eq!(palette.colors[1], Color::Rgb(8, 0, 0));
+  }
+
+  #[test]
+  fn test_parse_source() {
+    assert_eq!(
+      parse_source("base16:/tmp/nord.yaml").unwrap(),
+      (PaletteKind::Base16, PathBuf::from("/tmp/nord.yaml"))
+    );
+    assert!(parse_source("base16").is_err());
+    assert!(parse_source("solarized").is_err());
+  }
+}
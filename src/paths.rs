@@ -0,0 +1,11 @@
+//! Paths from the config and the command line, which may start with `~/` like in a shell
+
+use std::path::PathBuf;
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+  match (path.strip_prefix("~/"), dirs::home_dir()) {
+    (Some(rest), Some(home)) => home.join(rest),
+    _ => PathBuf::from(path),
+  }
+}
@@ -0,0 +1,112 @@
+/// How the playlists sidebar is ordered, the `playlist_sort` behavior option
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaylistSort {
+  /// The order Spotify returns them in, most recently created or followed first
+  Recent,
+  Name,
+  /// The user's own playlists first, then the ones they follow
+  Owner,
+}
+
+impl PlaylistSort {
+  pub fn from_config_str(value: &str) -> Option<PlaylistSort> {
+    match value.to_lowercase().as_str() {
+      "recent" => Some(PlaylistSort::Recent),
+      "name" => Some(PlaylistSort::Name),
+      "owner" => Some(PlaylistSort::Owner),
+      _ => None,
+    }
+  }
+
+  pub fn as_config_str(self) -> &'static str {
+    match self {
+      PlaylistSort::Recent => "recent",
+      PlaylistSort::Name => "name",
+      PlaylistSort::Owner => "owner",
+    }
+  }
+
+  /// The next sort when cycling through them at runtime
+  pub fn next(self) -> PlaylistSort {
+    match self {
+      PlaylistSort::Recent => PlaylistSort::Name,
+      PlaylistSort::Name => PlaylistSort::Owner,
+      PlaylistSort::Owner => PlaylistSort::Recent,
+    }
+  }
+}
+
+/// What a playlist is sorted by
+pub struct SortEntry<'a> {
+  pub id: &'a str,
+  pub name: &'a str,
+  pub own: bool,
+  /// Where Spotify listed it, `None` for playlists created since the list was fetched
+  pub spotify_position: Option<usize>,
+}
+
+/// Whether a `pinned_playlists` entry, a name or an ID, refers to the playlist
+pub fn is_pinned(entry: &SortEntry, pinned: &[String]) -> bool {
+  let bare_id = entry.id.rsplit(':').next().unwrap_or(entry.id);
+  pinned.iter().any(|pin| {
+    let pin_id = pin.rsplit(':').next().unwrap_or(pin);
+    pin.eq_ignore_ascii_case(entry.name) || pin_id == bare_id
+  })
+}
+
+/// Indices of `entries` in sidebar order: pinned playlists first, each group ordered by `sort`.
+/// New playlists count as the most recent, like in the Spotify clients.
+pub fn order(entries: &[SortEntry], sort: PlaylistSort, pinned: &[String]) -> Vec<usize> {
+  let mut indices = (0..entries.len()).collect::<Vec<usize>>();
+  // Without a position the playlist is newer than anything Spotify listed
+  let recency = |entry: &SortEntry| entry.spotify_position.map_or(0, |position| position + 1);
+  indices.sort_by(|&a, &b| {
+    let (a, b) = (&entries[a], &entries[b]);
+    let by_pin = is_pinned(b, pinned).cmp(&is_pinned(a, pinned));
+    let by_sort = match sort {
+      PlaylistSort::Recent => recency(a).cmp(&recency(b)),
+      PlaylistSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+      PlaylistSort::Owner => b.own.cmp(&a.own).then(recency(a).cmp(&recency(b))),
+    };
+    by_pin.then(by_sort)
+  });
+  indices
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entry<'a>(id: &'a str, name: &'a str, own: bool, position: usize) -> SortEntry<'a> {
+    SortEntry {
+      id,
+      name,
+      own,
+      spotify_position: Some(position),
+    }
+  }
+
+  #[test]
+  fn test_order() {
+    let entries = vec![
+      entry("a", "Road Trip", false, 0),
+      entry("b", "chill", true, 1),
+      entry("c", "Focus", true, 2),
+    ];
+    assert_eq!(order(&entries, PlaylistSort::Recent, &[]), vec![0, 1, 2]);
+    assert_eq!(order(&entries, PlaylistSort::Name, &[]), vec![1, 2, 0]);
+    assert_eq!(order(&entries, PlaylistSort::Owner, &[]), vec![1, 2, 0]);
+
+    let pinned = vec!["focus".to_string(), "spotify:playlist:a".to_string()];
+    assert_eq!(order(&entries, PlaylistSort::Name, &pinned), vec![2, 0, 1]);
+
+    let mut entries = entries;
+    entries.push(SortEntry {
+      id: "d",
+      name: "New",
+      own: true,
+      spotify_position: None,
+    });
+    assert_eq!(order(&entries, PlaylistSort::Recent, &[]), vec![3, 0, 1, 2]);
+  }
+}
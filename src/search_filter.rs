@@ -0,0 +1,119 @@
+use rspotify::model::enums::SearchType;
+
+const ALL_TYPES: [SearchType; 5] = [
+  SearchType::Track,
+  SearchType::Artist,
+  SearchType::Album,
+  SearchType::Playlist,
+  SearchType::Show,
+];
+
+/// What the search box asked for: the query sent to Spotify and the result types worth
+/// searching with it
+#[derive(Debug, PartialEq)]
+pub struct SearchQuery {
+  pub text: String,
+  pub types: Vec<SearchType>,
+}
+
+/// The result type of an `@album`-style prefix
+fn type_prefix(word: &str) -> Option<SearchType> {
+  match word.strip_prefix('@')?.to_lowercase().as_str() {
+    "track" | "tracks" | "song" | "songs" => Some(SearchType::Track),
+    "artist" | "artists" => Some(SearchType::Artist),
+    "album" | "albums" => Some(SearchType::Album),
+    "playlist" | "playlists" => Some(SearchType::Playlist),
+    "show" | "shows" | "podcast" | "podcasts" => Some(SearchType::Show),
+    _ => None,
+  }
+}
+
+/// The result types Spotify applies a field filter such as `genre:` to. Playlists and shows
+/// ignore them all, so a filtered search leaves them out rather than listing unfiltered ones.
+fn filter_types(field: &str) -> Option<&'static [SearchType]> {
+  match field {
+    "artist" | "year" => Some(&[SearchType::Album, SearchType::Artist, SearchType::Track]),
+    "album" => Some(&[SearchType::Album, SearchType::Track]),
+    "track" | "isrc" => Some(&[SearchType::Track]),
+    "genre" => Some(&[SearchType::Artist, SearchType::Track]),
+    "upc" | "tag" => Some(&[SearchType::Album]),
+    _ => None,
+  }
+}
+
+/// Split `@type` prefixes out of a search and narrow it to the types its field filters
+/// (`artist:`, `album:`, `year:1990-2000`, `genre:` ...) apply to. Field names are lowercased
+/// and `artist: beatles` is joined up, as Spotify only understands `artist:beatles`.
+pub fn parse(input: &str) -> SearchQuery {
+  let mut types = ALL_TYPES.to_vec();
+  let mut prefixed = vec![];
+  let mut words: Vec<String> = vec![];
+  let mut value_follows = false;
+
+  for word in input.split_whitespace() {
+    if value_follows {
+      value_follows = false;
+      if let Some(filter) = words.last_mut() {
+        filter.push_str(word);
+        continue;
+      }
+    }
+    if let Some(search_type) = type_prefix(word) {
+      prefixed.push(search_type);
+      continue;
+    }
+
+    let filter = word.split_once(':').and_then(|(field, value)| {
+      let field = field.to_lowercase();
+      filter_types(&field).map(|applies_to| (field, value, applies_to))
+    });
+    match filter {
+      Some((field, value, applies_to)) => {
+        types.retain(|search_type| applies_to.contains(search_type));
+        value_follows = value.is_empty();
+        words.push(format!("{}:{}", field, value));
+      }
+      None => words.push(word.to_string()),
+    }
+  }
+
+  if !prefixed.is_empty() {
+    types.retain(|search_type| prefixed.contains(search_type));
+  }
+  SearchQuery {
+    text: words.join(" "),
+    types,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(
+      parse("the beatles"),
+      SearchQuery {
+        text: "the beatles".to_string(),
+        types: ALL_TYPES.to_vec(),
+      }
+    );
+    assert_eq!(
+      parse("@album Artist: beatles year:1965-1970"),
+      SearchQuery {
+        text: "artist:beatles year:1965-1970".to_string(),
+        types: vec![SearchType::Album],
+      }
+    );
+    assert_eq!(
+      parse("genre:shoegaze https://example.com @artists @tracks"),
+      SearchQuery {
+        text: "genre:shoegaze https://example.com".to_string(),
+        types: vec![SearchType::Track, SearchType::Artist],
+      }
+    );
+    assert_eq!(parse("@playlist genre:jazz").types, vec![]);
+    assert_eq!(parse("@someone else").text, "@someone else");
+  }
+}
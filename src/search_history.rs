@@ -0,0 +1,130 @@
+use anyhow::{anyhow, Result};
+use std::{fs, path::PathBuf};
+
+const APP_CACHE_DIR: &str = "spotify-tui";
+const FILE_NAME: &str = "search_history.json";
+
+// Older searches are dropped once there are this many
+const MAX_ENTRIES: usize = 200;
+
+/// Recent searches, oldest first, kept in the cache directory so Up/Down and Ctrl-R in the
+/// search box can recall them after a restart.
+#[derive(Default)]
+pub struct SearchHistory {
+  path: Option<PathBuf>,
+  entries: Vec<String>,
+  dirty: bool,
+}
+
+impl SearchHistory {
+  /// Load saved searches, starting empty if the file is missing or unreadable
+  pub fn load() -> SearchHistory {
+    let path = dirs::cache_dir().map(|cache| cache.join(APP_CACHE_DIR).join(FILE_NAME));
+    let entries = path
+      .as_ref()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|contents| serde_json::from_str(&contents).ok())
+      .unwrap_or_default();
+
+    SearchHistory {
+      path,
+      entries,
+      dirty: false,
+    }
+  }
+
+  pub fn entries(&self) -> &[String] {
+    &self.entries
+  }
+
+  /// Remember a search as the newest entry, moving it there if it was searched before
+  pub fn record(&mut self, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+      return;
+    }
+    self.entries.retain(|entry| entry != query);
+    self.entries.push(query.to_string());
+    if self.entries.len() > MAX_ENTRIES {
+      self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+    }
+    self.dirty = true;
+  }
+
+  /// The index of the newest entry before `before` that contains `query`, ignoring case
+  pub fn find(&self, query: &str, before: usize) -> Option<usize> {
+    let query = query.to_lowercase();
+    self.entries[..before.min(self.entries.len())]
+      .iter()
+      .rposition(|entry| entry.to_lowercase().contains(&query))
+  }
+
+  pub fn save(&mut self) -> Result<()> {
+    if !self.dirty {
+      return Ok(());
+    }
+    let path = match &self.path {
+      Some(path) => path,
+      None => return Err(anyhow!("No cache directory found for the search history")),
+    };
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&self.entries)?)?;
+    self.dirty = false;
+    Ok(())
+  }
+}
+
+/// Where Up/Down are in the history, and the input from before they were first pressed
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryRecall {
+  pub index: usize,
+  pub draft: String,
+}
+
+/// A Ctrl-R search through the history, showing its match in the search box as it is typed
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReverseSearch {
+  pub query: String,
+  pub matched: Option<usize>,
+  // The input to go back to when the search is cancelled
+  pub draft: String,
+}
+
+impl ReverseSearch {
+  pub fn title(&self) -> String {
+    if self.matched.is_none() && !self.query.is_empty() {
+      format!("History search (no match): {}", self.query)
+    } else {
+      format!("History search: {}", self.query)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_record_and_find() {
+    let mut history = SearchHistory::default();
+    history.record("bowie");
+    history.record("  ");
+    history.record("Brian Eno");
+    history.record("bowie low");
+    history.record("Brian Eno ");
+    assert_eq!(history.entries(), ["bowie", "bowie low", "Brian Eno"]);
+
+    assert_eq!(history.find("BOWIE", 3), Some(1));
+    assert_eq!(history.find("bowie", 1), Some(0));
+    assert_eq!(history.find("bowie", 0), None);
+    assert_eq!(history.find("kraftwerk", 3), None);
+
+    for i in 0..MAX_ENTRIES {
+      history.record(&i.to_string());
+    }
+    assert_eq!(history.entries().len(), MAX_ENTRIES);
+    assert_eq!(history.entries()[0], "0");
+  }
+}
@@ -0,0 +1,106 @@
+//! Where the app was left: the view on top, the library option and playlist selected in the
+//! sidebar and the selected track, saved on quitting so the next start opens in the same place.
+
+use crate::app::{App, LIBRARY_OPTIONS};
+use crate::location::{Location, PendingSelection};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+const APP_STATE_DIR: &str = "spotify-tui";
+const FILE_NAME: &str = "session.json";
+
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct Session {
+  // The view on top, if it is one that can be opened again
+  pub view: Option<Location>,
+  pub library_index: usize,
+  // The playlist selected in the sidebar
+  pub playlist_id: Option<String>,
+  pub track_index: usize,
+}
+
+impl Session {
+  /// The last session, or an empty one if there was none or it can't be read
+  pub fn load() -> Session {
+    path()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|contents| serde_json::from_str(&contents).ok())
+      .unwrap_or_default()
+  }
+
+  pub fn save(&self) -> Result<()> {
+    let path = path().ok_or_else(|| anyhow!("No state directory found for the session"))?;
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(self)?)?;
+    Ok(())
+  }
+
+  pub fn capture(app: &App) -> Session {
+    let playlist_id = app
+      .selected_playlist_index
+      .and_then(|index| app.playlists.as_ref()?.items.get(index))
+      .map(|playlist| playlist.id.to_string());
+    Session {
+      view: Location::current(app),
+      library_index: app.library.selected_index,
+      playlist_id,
+      track_index: app.track_table.selected_index,
+    }
+  }
+
+  /// Reopen the saved view, with the selections that depend on what it loads made once it has
+  pub fn restore(self, app: &mut App) {
+    app.library.selected_index = self.library_index.min(LIBRARY_OPTIONS.len() - 1);
+    app
+      .pending_selection
+      .get_or_insert_with(PendingSelection::default)
+      .playlist_id = self.playlist_id;
+    if let Some(view) = self.view {
+      view.open(app, self.track_index);
+    }
+  }
+}
+
+// In the state directory, which only Linux has, or else the cache directory
+fn path() -> Option<PathBuf> {
+  dirs::state_dir()
+    .or_else(dirs::cache_dir)
+    .map(|dir| dir.join(APP_STATE_DIR).join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::{test_track as track, ActiveBlock, LibraryOption, RouteId, TrackTableContext};
+  use crate::location;
+
+  #[test]
+  fn reopens_liked_songs_at_the_selected_track() {
+    let liked_songs = LibraryOption::LikedSongs as usize;
+    let mut app = App::default();
+    app.library.selected_index = liked_songs;
+    app.track_table.context = Some(TrackTableContext::SavedTracks);
+    app.track_table.tracks = vec![track("1111"), track("2222"), track("3333")];
+    app.track_table.selected_index = 2;
+    app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    let session = Session::capture(&app);
+    assert_eq!(session.view, Some(Location::Library(liked_songs)));
+
+    let mut app = App::default();
+    session.restore(&mut app);
+    assert_eq!(app.library.selected_index, liked_songs);
+    assert_eq!(app.get_current_route().id, RouteId::TrackTable);
+
+    // Nothing to select until the tracks are in
+    location::apply_loaded(&mut app);
+    assert!(app.pending_selection.is_some());
+    app.track_table.tracks = vec![track("1111"), track("2222")];
+    location::apply_loaded(&mut app);
+    assert_eq!(app.track_table.selected_index, 1);
+    assert!(app.pending_selection.is_none());
+  }
+}
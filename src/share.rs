@@ -0,0 +1,126 @@
+use crate::open_in;
+use rspotify::model::{artist::SimplifiedArtist, track::FullTrack};
+
+/// A track, album, playlist or episode to share
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareItem {
+  pub name: String,
+  // The artists, playlist owner or show, when known
+  pub creator: Option<String>,
+  pub uri: String,
+}
+
+impl ShareItem {
+  /// `kind` is the URI kind such as "track", `id` a bare ID or already a URI
+  pub fn new(kind: &str, name: &str, creator: Option<String>, id: &str) -> ShareItem {
+    ShareItem {
+      name: name.to_string(),
+      creator,
+      uri: open_in::uri(kind, id),
+    }
+  }
+}
+
+/// Artist names as shared, e.g. "Simon & Garfunkel, Paul Simon"
+pub fn artist_names(artists: &[SimplifiedArtist]) -> String {
+  artists
+    .iter()
+    .map(|artist| artist.name.as_str())
+    .collect::<Vec<&str>>()
+    .join(", ")
+}
+
+pub fn track_item(track: &FullTrack) -> Option<ShareItem> {
+  let id = track.id.as_ref()?;
+  Some(ShareItem::new(
+    "track",
+    &track.name,
+    Some(artist_names(&track.artists)),
+    &id.to_string(),
+  ))
+}
+
+/// The ways the share menu copies an item
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShareFormat {
+  Url,
+  Uri,
+  ArtistTitle,
+  Markdown,
+}
+
+impl ShareFormat {
+  pub const ALL: [ShareFormat; 4] = [
+    ShareFormat::Url,
+    ShareFormat::Uri,
+    ShareFormat::ArtistTitle,
+    ShareFormat::Markdown,
+  ];
+
+  pub fn label(self) -> &'static str {
+    match self {
+      ShareFormat::Url => "URL",
+      ShareFormat::Uri => "URI",
+      ShareFormat::ArtistTitle => "Artist – Title",
+      ShareFormat::Markdown => "Markdown link",
+    }
+  }
+
+  pub fn format(self, item: &ShareItem) -> String {
+    let url = open_in::web_url(&item.uri).unwrap_or_default();
+    let title = match &item.creator {
+      Some(creator) => format!("{} – {}", creator, item.name),
+      None => item.name.clone(),
+    };
+    match self {
+      ShareFormat::Url => url,
+      ShareFormat::Uri => item.uri.clone(),
+      ShareFormat::ArtistTitle => title,
+      ShareFormat::Markdown => format!("[{}]({})", title.replace(']', "\\]"), url),
+    }
+  }
+}
+
+/// The share menu: the item being shared and the highlighted format
+#[derive(Clone)]
+pub struct ShareMenu {
+  pub item: ShareItem,
+  pub selected_index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_format() {
+    let item = ShareItem::new(
+      "track",
+      "Heroes",
+      Some("David Bowie".to_string()),
+      "spotify:track:7Jh1bpe76CNTCgdgAdBw4Z",
+    );
+    assert_eq!(
+      ShareFormat::Url.format(&item),
+      "https://open.spotify.com/track/7Jh1bpe76CNTCgdgAdBw4Z"
+    );
+    assert_eq!(
+      ShareFormat::Uri.format(&item),
+      "spotify:track:7Jh1bpe76CNTCgdgAdBw4Z"
+    );
+    assert_eq!(
+      ShareFormat::ArtistTitle.format(&item),
+      "David Bowie – Heroes"
+    );
+    assert_eq!(
+      ShareFormat::Markdown.format(&item),
+      "[David Bowie – Heroes](https://open.spotify.com/track/7Jh1bpe76CNTCgdgAdBw4Z)"
+    );
+
+    let playlist = ShareItem::new("playlist", "Mix [2024]", None, "37i9dQZF1DX4sWSpwq3LiO");
+    assert_eq!(
+      ShareFormat::Markdown.format(&playlist),
+      "[Mix [2024\\]](https://open.spotify.com/playlist/37i9dQZF1DX4sWSpwq3LiO)"
+    );
+  }
+}
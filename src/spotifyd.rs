@@ -0,0 +1,151 @@
+//! A spotifyd process started and watched by spotui, so there is a device to play on from the
+//! first run. Its output goes to the log stream.
+
+use crate::app::{App, LogLevel};
+use crate::paths::expand_home;
+use anyhow::{anyhow, Result};
+use std::{
+  io::{BufRead, BufReader, Read},
+  process::{Child, Command, Stdio},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+  },
+  thread,
+  time::Duration,
+};
+use tokio::sync::Mutex as AppMutex;
+
+// How often the watcher checks whether spotifyd is still running
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct Spotifyd {
+  child: Arc<Mutex<Child>>,
+  // Set before stopping it on purpose, so the watcher doesn't report the exit as an error
+  stopping: Arc<AtomicBool>,
+}
+
+/// Arguments for running spotifyd in the foreground under the given device name, with its own
+/// config file when there is one
+pub fn command_args(config_path: &str, device_name: &str) -> Vec<String> {
+  let mut args = vec![
+    "--no-daemon".to_string(),
+    "--device-name".to_string(),
+    device_name.to_string(),
+  ];
+  if !config_path.is_empty() {
+    args.push("--config-path".to_string());
+    args.push(config_path.to_string());
+  }
+  args
+}
+
+impl Spotifyd {
+  pub fn spawn(
+    path: &str,
+    config_path: &str,
+    device_name: &str,
+    app: &Arc<AppMutex<App>>,
+  ) -> Result<Self> {
+    let config_path = match config_path {
+      "" => String::new(),
+      config_path => expand_home(config_path).display().to_string(),
+    };
+    let mut child = Command::new(expand_home(path))
+      .args(command_args(&config_path, device_name))
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .map_err(|e| anyhow!("Couldn't start spotifyd at {}: {}", path, e))?;
+
+    if let Some(stdout) = child.stdout.take() {
+      forward_output(stdout, LogLevel::Debug, app);
+    }
+    // spotifyd logs to stderr, including why it fails to log in or play
+    if let Some(stderr) = child.stderr.take() {
+      forward_output(stderr, LogLevel::Info, app);
+    }
+
+    let child = Arc::new(Mutex::new(child));
+    let stopping = Arc::new(AtomicBool::new(false));
+    let watched = Arc::clone(&child);
+    let watched_stopping = Arc::clone(&stopping);
+    let app = Arc::clone(app);
+    thread::spawn(move || loop {
+      thread::sleep(EXIT_CHECK_INTERVAL);
+      let status = match watched.lock() {
+        Ok(mut child) => child.try_wait(),
+        Err(_) => return,
+      };
+      match status {
+        Ok(None) => {}
+        Ok(Some(_)) if watched_stopping.load(Ordering::SeqCst) => return,
+        Ok(Some(status)) => {
+          let mut app = app.blocking_lock();
+          app.awaited_device = None;
+          app.handle_error(anyhow!("spotifyd stopped ({})", status));
+          return;
+        }
+        Err(e) => {
+          app.blocking_lock().log(
+            LogLevel::Warn,
+            format!("spotifyd: couldn't check on it: {}", e),
+          );
+          return;
+        }
+      }
+    });
+
+    Ok(Self { child, stopping })
+  }
+
+  /// Stop spotifyd along with spotui
+  pub fn stop(&self) {
+    self.stopping.store(true, Ordering::SeqCst);
+    if let Ok(mut child) = self.child.lock() {
+      let _ = child.kill();
+      let _ = child.wait();
+    }
+  }
+}
+
+fn forward_output(output: impl Read + Send + 'static, level: LogLevel, app: &Arc<AppMutex<App>>) {
+  let app = Arc::clone(app);
+  thread::spawn(move || {
+    for line in BufReader::new(output).lines() {
+      match line {
+        Ok(line) if !line.trim().is_empty() => {
+          app
+            .blocking_lock()
+            .log(level, format!("spotifyd: {}", line.trim_end()));
+        }
+        Ok(_) => {}
+        Err(_) => return,
+      }
+    }
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn command_args_test() {
+    assert_eq!(
+      command_args("", "spotifyd"),
+      ["--no-daemon", "--device-name", "spotifyd"]
+    );
+    assert_eq!(
+      command_args("~/.config/spotifyd/spotifyd.conf", "den"),
+      [
+        "--no-daemon",
+        "--device-name",
+        "den",
+        "--config-path",
+        "~/.config/spotifyd/spotifyd.conf"
+      ]
+    );
+  }
+}
@@ -0,0 +1,105 @@
+//! `spt module`: what is playing, formatted for a waybar or polybar module. The running app is
+//! asked over its socket, so nothing authenticates and the module stays empty while it is closed.
+
+use crate::{ipc, user_config::BehaviorConfig};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::{io::Write, thread, time::Duration};
+
+pub const BARS: [&str; 2] = ["waybar", "polybar"];
+
+/// One line for the bar: JSON with text, tooltip and a playing/paused/stopped class for
+/// waybar, plain text for polybar
+pub fn render(bar: &str, status: &Value, behavior: &BehaviorConfig) -> String {
+  let state = status["state"].as_str().unwrap_or("stopped");
+  let title = status["title"].as_str().unwrap_or_default();
+  let artist = status["artist"].as_str().unwrap_or_default();
+  let text = match (state, title) {
+    ("stopped", _) | (_, "") => String::new(),
+    (state, title) => {
+      let icon = if state == "playing" {
+        &behavior.playing_icon
+      } else {
+        &behavior.paused_icon
+      };
+      if artist.is_empty() {
+        format!("{} {}", icon, title)
+      } else {
+        format!("{} {} – {}", icon, artist, title)
+      }
+    }
+  };
+
+  if bar != "waybar" {
+    return text;
+  }
+  let tooltip = [title, artist, status["album"].as_str().unwrap_or_default()]
+    .iter()
+    .filter(|line| !line.is_empty())
+    .cloned()
+    .collect::<Vec<_>>()
+    .join("\n");
+  json!({
+    "text": text,
+    "tooltip": tooltip,
+    "class": state,
+    "alt": state,
+  })
+  .to_string()
+}
+
+/// Print the module once, or with `follow` every time it changes, checking every `interval`
+pub fn run(bar: &str, follow: bool, interval: Duration, behavior: &BehaviorConfig) -> Result<()> {
+  if !BARS.contains(&bar) {
+    return Err(anyhow!(
+      "Unknown bar \"{}\", expected waybar or polybar",
+      bar
+    ));
+  }
+  let mut last_line = None;
+  loop {
+    let status = ipc::send("status")
+      .and_then(|reply| serde_json::from_str(&reply).ok())
+      .unwrap_or_else(|| json!({ "state": "stopped" }));
+    let line = render(bar, &status, behavior);
+    if last_line.as_ref() != Some(&line) {
+      let mut stdout = std::io::stdout();
+      writeln!(stdout, "{}", line)?;
+      stdout.flush()?;
+      last_line = Some(line);
+    }
+    if !follow {
+      return Ok(());
+    }
+    thread::sleep(interval);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::user_config::UserConfig;
+
+  #[test]
+  fn render_test() {
+    let behavior = UserConfig::new().behavior;
+    let status = json!({
+      "state": "playing",
+      "title": "Teardrop",
+      "artist": "Massive Attack",
+      "album": "Mezzanine",
+    });
+    let playing = format!("{} Massive Attack – Teardrop", behavior.playing_icon);
+    assert_eq!(render("polybar", &status, &behavior), playing);
+
+    let waybar: Value = serde_json::from_str(&render("waybar", &status, &behavior)).unwrap();
+    assert_eq!(waybar["text"], playing.as_str());
+    assert_eq!(waybar["tooltip"], "Teardrop\nMassive Attack\nMezzanine");
+    assert_eq!(waybar["class"], "playing");
+
+    let stopped: Value =
+      serde_json::from_str(&render("waybar", &json!({ "state": "stopped" }), &behavior)).unwrap();
+    assert_eq!(stopped["text"], "");
+    assert_eq!(stopped["class"], "stopped");
+  }
+}
@@ -0,0 +1,86 @@
+//! spotui's own Spotify Connect device, built with the `streaming` feature, so nothing else has
+//! to be running to play music on a headless box.
+
+use anyhow::{anyhow, Result};
+use librespot_connect::{config::ConnectConfig, spirc::Spirc};
+use librespot_core::{
+  authentication::Credentials,
+  cache::Cache,
+  config::{DeviceType, SessionConfig},
+  session::Session,
+};
+use librespot_playback::{
+  audio_backend,
+  config::{AudioFormat, PlayerConfig},
+  mixer::{self, MixerConfig},
+  player::Player,
+};
+use std::path::PathBuf;
+
+pub struct StreamingDevice {
+  name: String,
+  spirc: Spirc,
+}
+
+impl StreamingDevice {
+  /// Register the device with Spotify Connect and serve it in the background. Credentials
+  /// librespot saved on an earlier start are tried before the Web API's access token.
+  /// An empty `backend` picks librespot's default audio backend.
+  pub async fn start(
+    name: &str,
+    backend: &str,
+    access_token: &str,
+    cache_dir: Option<PathBuf>,
+  ) -> Result<Self> {
+    // Only credentials and volume are kept, an audio cache would grow without bound
+    let cache = match &cache_dir {
+      Some(dir) => Some(Cache::new(Some(dir), Some(dir), None, None)?),
+      None => None,
+    };
+    let credentials = cache
+      .as_ref()
+      .and_then(|cache| cache.credentials())
+      .unwrap_or_else(|| Credentials::with_access_token(access_token));
+
+    let backend_name = if backend.is_empty() {
+      None
+    } else {
+      Some(backend.to_string())
+    };
+    let sink_builder = audio_backend::find(backend_name)
+      .ok_or_else(|| anyhow!("Unknown audio backend \"{}\"", backend))?;
+    let mixer_builder = mixer::find(None).ok_or_else(|| anyhow!("No audio mixer available"))?;
+    let mixer = mixer_builder(MixerConfig::default());
+
+    let session = Session::new(SessionConfig::default(), cache);
+    let player = Player::new(
+      PlayerConfig::default(),
+      session.clone(),
+      mixer.get_soft_volume(),
+      move || sink_builder(None, AudioFormat::default()),
+    );
+    let connect_config = ConnectConfig {
+      name: name.to_string(),
+      device_type: DeviceType::Computer,
+      ..Default::default()
+    };
+    let (spirc, spirc_task) = Spirc::new(connect_config, session, credentials, player, mixer)
+      .await
+      .map_err(|e| anyhow!("{}", e))?;
+    tokio::spawn(spirc_task);
+
+    Ok(Self {
+      name: name.to_string(),
+      spirc,
+    })
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Take the device off Spotify Connect, stopping whatever it plays
+  pub fn stop(self) -> Result<()> {
+    self.spirc.shutdown().map_err(|e| anyhow!("{}", e))
+  }
+}
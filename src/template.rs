@@ -0,0 +1,79 @@
+/// The `{name}` placeholders used in a template, in order
+pub fn placeholders(template: &str) -> Vec<&str> {
+  let mut names = vec![];
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    rest = &rest[start + 1..];
+    if let Some(end) = rest.find('}') {
+      let name = &rest[..end];
+      if is_placeholder_name(name) {
+        names.push(name);
+        rest = &rest[end + 1..];
+      }
+    }
+  }
+  names
+}
+
+/// Replace the `{name}` placeholders of a template with their values in one pass, so values
+/// that look like placeholders are left alone. Unknown placeholders stay as they are.
+pub fn fill(template: &str, values: &[(&str, String)]) -> String {
+  let mut filled = String::new();
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    filled.push_str(&rest[..start]);
+    let after = &rest[start + 1..];
+    let value = after.find('}').and_then(|end| {
+      let name = &after[..end];
+      values
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, value)| (end, value))
+    });
+    match value {
+      Some((end, value)) => {
+        filled.push_str(value);
+        rest = &after[end + 1..];
+      }
+      None => {
+        filled.push('{');
+        rest = after;
+      }
+    }
+  }
+  filled.push_str(rest);
+  filled
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+  !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_placeholders() {
+    assert_eq!(
+      placeholders("{track} by {artist} {not a name} {url}"),
+      vec!["track", "artist", "url"]
+    );
+    assert!(placeholders("no placeholders {").is_empty());
+  }
+
+  #[test]
+  fn test_fill() {
+    let values = vec![
+      ("track", "Heroes".to_string()),
+      ("artist", "{album}".to_string()),
+    ];
+    assert_eq!(
+      fill("♫ {track} - {artist} {album} {", &values),
+      "♫ Heroes - {album} {album} {"
+    );
+  }
+}
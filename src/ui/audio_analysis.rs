@@ -1,84 +1,88 @@
 use super::util;
 use crate::app::App;
 use ratatui::{
-  backend::Backend,
-  layout::{Constraint, Direction, Layout},
-  style::Style,
+  layout::{Constraint, Direction, Layout, Rect},
+  style::{Color, Modifier, Style},
   text::{Line, Span},
-  widgets::{BarChart, Block, Borders, BorderType, Paragraph},
+  widgets::{Block, BorderType, Borders, Paragraph},
   Frame,
 };
-const PITCHES: [&str; 12] = [
+use rspotify::model::{audio::AudioAnalysis, Modality};
+
+pub const PITCHES: [&str; 12] = [
   "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
 ];
 
+// Segments the heatmap keeps in view behind the playing one, the rest of it shows what's coming
+const HEATMAP_LEAD: usize = 8;
+
 pub fn draw(f: &mut Frame, app: &App) {
   let margin = util::get_main_layout_margin(app);
 
   let chunks = Layout::default()
     .direction(Direction::Vertical)
-    .constraints([Constraint::Min(5), Constraint::Length(95)].as_ref())
+    .constraints(
+      [
+        Constraint::Length(5),
+        Constraint::Length(3),
+        Constraint::Length(5),
+        Constraint::Min(15),
+      ]
+      .as_ref(),
+    )
     .margin(margin)
     .split(f.area());
 
-  let analysis_block = Block::default()
-    .title(Span::styled(
-      "Analysis",
-      Style::default().fg(app.user_config.theme.inactive),
-    ))
-    .borders(Borders::ALL)
-    .border_type(BorderType::Rounded)
-    .border_style(Style::default().fg(app.user_config.theme.inactive));
+  let analysis = match &app.audio_analysis {
+    Some(analysis) => analysis,
+    None => {
+      let empty = Paragraph::new("No analysis available")
+        .block(analysis_block(app, "Analysis"))
+        .style(Style::default().fg(app.user_config.theme.text));
+      f.render_widget(empty, chunks[0]);
+      return;
+    }
+  };
 
-  let white = Style::default().fg(app.user_config.theme.text);
-  let gray = Style::default().fg(app.user_config.theme.inactive);
-  let width = (chunks[1].width) as f32 / (1 + PITCHES.len()) as f32;
-  let tick_rate = app.user_config.behavior.tick_rate_milliseconds;
-  let bar_chart_title = &format!("Pitches | Tick Rate {} {}FPS", tick_rate, 1000 / tick_rate);
+  let position = (app.song_progress_ms as f32) / 1000.0;
+  draw_summary(f, app, analysis, position, chunks[0]);
+  draw_beat_grid(f, app, analysis, position, chunks[1]);
+  draw_sections(f, app, analysis, position, chunks[2]);
+  draw_heatmap(f, app, analysis, position, chunks[3]);
+}
 
-  let bar_chart_block = Block::default()
+fn analysis_block<'a>(app: &App, title: &'a str) -> Block<'a> {
+  let gray = Style::default().fg(app.user_config.theme.inactive);
+  Block::default()
+    .title(Span::styled(title, gray))
     .borders(Borders::ALL)
     .border_type(BorderType::Rounded)
-    .style(white)
-    .title(Span::styled(bar_chart_title, gray))
-    .border_style(gray);
-
-  let empty_analysis_block = || {
-    Paragraph::new("No analysis available")
-      .block(analysis_block.clone())
-      .style(Style::default().fg(app.user_config.theme.text))
-  };
-  let empty_pitches_block = || {
-    Paragraph::new("No pitch information available")
-      .block(bar_chart_block.clone())
-      .style(Style::default().fg(app.user_config.theme.text))
-  };
-
-  if let Some(analysis) = &app.audio_analysis {
-    let progress_seconds = (app.song_progress_ms as f32) / 1000.0;
-
-    let beat = analysis
-      .beats
-      .iter()
-      .find(|beat| beat.start >= progress_seconds);
+    .border_style(gray)
+}
 
-    let beat_offset = beat
-      .map(|beat| beat.start - progress_seconds)
-      .unwrap_or(0.0);
-    // Note: AudioAnalysis segment/section start field may have changed in newer API
-    let segment = analysis.segments.first();
-    let section = analysis.sections.first();
+/// Tempo, key and time signature of the playing section
+fn draw_summary(f: &mut Frame, app: &App, analysis: &AudioAnalysis, position: f32, area: Rect) {
+  let section_starts = analysis
+    .sections
+    .iter()
+    .map(|section| section.time_interval.start);
+  let section = interval_at(section_starts, position)
+    .and_then(|index| Some((index, analysis.sections.get(index)?)));
 
-    if let (Some(segment), Some(section)) = (segment, section) {
-      let texts = vec![
+  let texts = match section {
+    Some((index, section)) => {
+      let key = util::key_name(section.key, section.mode == Modality::Major);
+      vec![
         Line::from(format!(
-          "Tempo: {} (confidence {:.0}%)",
+          "Section {} of {} | Tempo: {:.0} BPM (confidence {:.0}%)",
+          index + 1,
+          analysis.sections.len(),
           section.tempo,
           section.tempo_confidence * 100.0
         )),
         Line::from(format!(
           "Key: {} (confidence {:.0}%)",
-          PITCHES.get(section.key as usize).unwrap_or(&PITCHES[0]),
+          key.unwrap_or_else(|| "Unknown".to_string()),
           section.key_confidence * 100.0
         )),
         Line::from(format!(
@@ -86,45 +90,303 @@ pub fn draw(f: &mut Frame, app: &App) {
           section.time_signature,
           section.time_signature_confidence * 100.0
         )),
-      ];
-      let p = Paragraph::new(texts)
-        .block(analysis_block)
-        .style(Style::default().fg(app.user_config.theme.text));
-      f.render_widget(p, chunks[0]);
-
-      let data: Vec<(&str, u64)> = segment
-        .clone()
-        .pitches
-        .iter()
-        .enumerate()
-        .map(|(index, pitch)| {
-          let display_pitch = *PITCHES.get(index).unwrap_or(&PITCHES[0]);
-          let bar_value = ((pitch * 1000.0) as u64)
-            // Add a beat offset to make the bar animate between beats
-            .checked_add((beat_offset * 3000.0) as u64)
-            .unwrap_or(0);
-
-          (display_pitch, bar_value)
-        })
-        .collect();
-
-      let analysis_bar = BarChart::default()
-        .block(bar_chart_block)
-        .data(&data)
-        .bar_width(width as u16)
-        .bar_style(Style::default().fg(app.user_config.theme.analysis_bar))
-        .value_style(
-          Style::default()
-            .fg(app.user_config.theme.analysis_bar_text)
-            .bg(app.user_config.theme.analysis_bar),
-        );
-      f.render_widget(analysis_bar, chunks[1]);
+      ]
+    }
+    None => vec![Line::from("No section has started yet")],
+  };
+  let p = Paragraph::new(texts)
+    .block(analysis_block(app, "Analysis"))
+    .style(Style::default().fg(app.user_config.theme.text));
+  f.render_widget(p, area);
+}
+
+/// A cell of the beat grid
+#[derive(Debug, PartialEq)]
+enum GridCell {
+  BarLine,
+  Played,
+  Playing,
+  Upcoming,
+}
+
+/// Up to `cells` cells of beats from the start of the playing bar on, with a bar line before
+/// every bar's first beat. `bars` and `beats` are start times in seconds.
+fn beat_grid(bars: &[f32], beats: &[f32], position: f32, cells: usize) -> Vec<GridCell> {
+  let bar_index = interval_at(bars.iter().copied(), position).unwrap_or(0);
+  let playing_beat = interval_at(beats.iter().copied(), position);
+  let first_start = bars.get(bar_index).copied().unwrap_or(0.0);
+
+  let mut next_bar = bar_index;
+  let mut grid = vec![];
+  for (index, start) in beats.iter().enumerate() {
+    if *start < first_start {
+      continue;
+    }
+    let mut starts_bar = false;
+    while bars
+      .get(next_bar)
+      .is_some_and(|bar_start| start >= bar_start)
+    {
+      next_bar += 1;
+      starts_bar = true;
+    }
+    if starts_bar {
+      grid.push(GridCell::BarLine);
+    }
+    grid.push(match playing_beat {
+      Some(playing) if index == playing => GridCell::Playing,
+      Some(playing) if index < playing => GridCell::Played,
+      _ => GridCell::Upcoming,
+    });
+    if grid.len() >= cells {
+      break;
+    }
+  }
+  grid.truncate(cells);
+  grid
+}
+
+fn draw_beat_grid(f: &mut Frame, app: &App, analysis: &AudioAnalysis, position: f32, area: Rect) {
+  let theme = &app.user_config.theme;
+  let bars: Vec<f32> = analysis.bars.iter().map(|bar| bar.start).collect();
+  let beats: Vec<f32> = analysis.beats.iter().map(|beat| beat.start).collect();
+  // Every cell is followed by a space
+  let cells = usize::from(area.width.saturating_sub(2)) / 2;
+
+  let spans: Vec<Span> = beat_grid(&bars, &beats, position, cells)
+    .into_iter()
+    .map(|cell| match cell {
+      GridCell::BarLine => Span::styled("│ ", Style::default().fg(theme.inactive)),
+      GridCell::Played => Span::styled("● ", Style::default().fg(theme.text)),
+      GridCell::Playing => Span::styled(
+        "● ",
+        Style::default()
+          .fg(theme.active)
+          .add_modifier(Modifier::BOLD),
+      ),
+      GridCell::Upcoming => Span::styled("○ ", Style::default().fg(theme.inactive)),
+    })
+    .collect();
+
+  let bar = interval_at(bars.iter().copied(), position).map_or(0, |index| index + 1);
+  let title = format!("Beats | Bar {} of {}", bar, bars.len());
+  let grid = Paragraph::new(Line::from(spans)).block(analysis_block(app, &title));
+  f.render_widget(grid, area);
+}
+
+/// The section each of `width` columns falls in, the columns spreading the track's `duration`
+/// evenly. `None` before the first section starts.
+fn section_columns(section_starts: &[f32], duration: f32, width: usize) -> Vec<Option<usize>> {
+  (0..width)
+    .map(|column| {
+      let time = duration * column as f32 / width as f32;
+      interval_at(section_starts.iter().copied(), time)
+    })
+    .collect()
+}
+
+/// The whole track as a timeline of its sections, numbered, with where playback is
+fn draw_sections(f: &mut Frame, app: &App, analysis: &AudioAnalysis, position: f32, area: Rect) {
+  let theme = &app.user_config.theme;
+  let width = usize::from(area.width.saturating_sub(2));
+  let duration = analysis.track.duration;
+  let section_starts: Vec<f32> = analysis
+    .sections
+    .iter()
+    .map(|section| section.time_interval.start)
+    .collect();
+  let playing_section = interval_at(section_starts.iter().copied(), position);
+  let columns = section_columns(&section_starts, duration, width);
+
+  let timeline: Vec<Span> = columns
+    .iter()
+    .map(|section| {
+      let color = match section {
+        Some(_) if *section == playing_section => theme.active,
+        Some(index) if index % 2 == 0 => theme.analysis_bar,
+        _ => theme.inactive,
+      };
+      Span::styled("█", Style::default().fg(color))
+    })
+    .collect();
+
+  // Each section's number where it begins, if there is room before the next one
+  let mut labels = String::new();
+  for (column, section) in columns.iter().enumerate() {
+    let starts_here = column == 0 || columns[column - 1] != *section;
+    match section {
+      Some(index) if starts_here && labels.len() <= column => {
+        labels.push_str(&" ".repeat(column - labels.len()));
+        labels.push_str(&(index + 1).to_string());
+      }
+      _ => {}
+    }
+  }
+
+  let progress_column = if duration > 0.0 {
+    ((position / duration) * width as f32) as usize
+  } else {
+    0
+  };
+  let cursor = format!(
+    "{}▲",
+    " ".repeat(progress_column.min(width.saturating_sub(1)))
+  );
+
+  let lines = vec![
+    Line::from(timeline),
+    Line::from(Span::styled(labels, Style::default().fg(theme.text))),
+    Line::from(Span::styled(cursor, Style::default().fg(theme.active))),
+  ];
+  let sections = Paragraph::new(lines).block(analysis_block(app, "Sections"));
+  f.render_widget(sections, area);
+}
+
+/// Dark for 0, through green to pale yellow for 1
+fn heat_color(level: f32) -> Color {
+  const STOPS: [(f32, f32, f32); 3] = [
+    (20.0, 20.0, 30.0),
+    (29.0, 185.0, 84.0),
+    (240.0, 230.0, 140.0),
+  ];
+  let level = level.clamp(0.0, 1.0) * 2.0;
+  let (from, to, t) = if level < 1.0 {
+    (STOPS[0], STOPS[1], level)
+  } else {
+    (STOPS[1], STOPS[2], level - 1.0)
+  };
+  let mix = |a: f32, b: f32| (a + (b - a) * t).round() as u8;
+  Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Scale `values` into 0 to 1 around 0.5. Timbre coefficients are unbounded, so they are only
+/// comparable to the others in view.
+fn normalize_timbre(values: &[f32]) -> Vec<f32> {
+  let max = values
+    .iter()
+    .fold(0.0_f32, |max, value| max.max(value.abs()));
+  if max == 0.0 {
+    return vec![0.5; values.len()];
+  }
+  values
+    .iter()
+    .map(|value| 0.5 + value / (2.0 * max))
+    .collect()
+}
+
+/// The pitch classes (or timbre coefficients) of the segments around the playing one, a column
+/// per segment, scrolling as the track plays
+fn draw_heatmap(f: &mut Frame, app: &App, analysis: &AudioAnalysis, position: f32, area: Rect) {
+  let theme = &app.user_config.theme;
+  let segment_starts = analysis
+    .segments
+    .iter()
+    .map(|segment| segment.time_interval.start);
+  let playing = interval_at(segment_starts, position).unwrap_or(0);
+  let first = playing.saturating_sub(HEATMAP_LEAD);
+  // Row labels take 4 columns
+  let width = usize::from(area.width.saturating_sub(2 + 4));
+  let segments: Vec<_> = analysis.segments.iter().skip(first).take(width).collect();
+
+  let marker = format!("    {}▼", " ".repeat(playing - first));
+  let mut lines = vec![Line::from(Span::styled(
+    marker,
+    Style::default().fg(theme.active),
+  ))];
+  for (row, pitch) in PITCHES.iter().enumerate() {
+    let values: Vec<f32> = segments
+      .iter()
+      .map(|segment| {
+        let coefficients = if app.analysis_shows_timbre {
+          &segment.timbre
+        } else {
+          &segment.pitches
+        };
+        coefficients.get(row).copied().unwrap_or(0.0)
+      })
+      .collect();
+    let (label, levels) = if app.analysis_shows_timbre {
+      (format!("{:<4}", row + 1), normalize_timbre(&values))
     } else {
-      f.render_widget(empty_analysis_block(), chunks[0]);
-      f.render_widget(empty_pitches_block(), chunks[1]);
+      (format!("{:<4}", pitch), values)
     };
+
+    let mut spans = vec![Span::styled(label, Style::default().fg(theme.text))];
+    spans.extend(
+      levels
+        .into_iter()
+        .map(|level| Span::styled("█", Style::default().fg(heat_color(level)))),
+    );
+    lines.push(Line::from(spans));
+  }
+
+  let tick_rate = app.user_config.behavior.tick_rate_milliseconds;
+  let (showing, other) = if app.analysis_shows_timbre {
+    ("Timbre", "pitches")
   } else {
-    f.render_widget(empty_analysis_block(), chunks[0]);
-    f.render_widget(empty_pitches_block(), chunks[1]);
+    ("Pitches", "timbre")
+  };
+  let title = format!(
+    "{} | t to show {} | Tick Rate {} {}FPS",
+    showing,
+    other,
+    tick_rate,
+    1000 / tick_rate
+  );
+  let heatmap = Paragraph::new(lines).block(analysis_block(app, &title));
+  f.render_widget(heatmap, area);
+}
+
+/// The index of the interval playing at `position`, given the intervals' start times in order:
+/// the last one to start at or before it
+fn interval_at(starts: impl Iterator<Item = f32>, position: f32) -> Option<usize> {
+  starts
+    .take_while(|start| *start <= position)
+    .count()
+    .checked_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_interval_at() {
+    let starts = [0.0, 1.5, 3.0];
+    assert_eq!(interval_at(starts.iter().copied(), 0.0), Some(0));
+    assert_eq!(interval_at(starts.iter().copied(), 2.9), Some(1));
+    assert_eq!(interval_at(starts.iter().copied(), 10.0), Some(2));
+    assert_eq!(interval_at([1.0].iter().copied(), 0.5), None);
+  }
+
+  #[test]
+  fn test_beat_grid() {
+    use GridCell::*;
+
+    let bars = [0.0, 2.0, 4.0];
+    let beats = [0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0];
+    assert_eq!(
+      beat_grid(&bars, &beats, 2.6, 8),
+      vec![BarLine, Played, Playing, Upcoming, Upcoming, BarLine, Upcoming]
+    );
+    assert_eq!(
+      beat_grid(&bars, &beats, 0.0, 3),
+      vec![BarLine, Playing, Upcoming]
+    );
+  }
+
+  #[test]
+  fn test_section_columns() {
+    assert_eq!(
+      section_columns(&[0.0, 5.0], 10.0, 4),
+      vec![Some(0), Some(0), Some(1), Some(1)]
+    );
+    assert_eq!(section_columns(&[5.0], 10.0, 2), vec![None, Some(0)]);
+  }
+
+  #[test]
+  fn test_normalize_timbre() {
+    assert_eq!(normalize_timbre(&[-10.0, 0.0, 5.0]), vec![0.0, 0.5, 0.75]);
+    assert_eq!(normalize_timbre(&[0.0, 0.0]), vec![0.5, 0.5]);
   }
 }
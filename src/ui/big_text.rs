@@ -0,0 +1,195 @@
+//! Large type for the basic view, drawn in half blocks so each cell holds two pixels, one above the
+//! other. Letters are three cells tall at their own size and scale up by whole pixels to fill
+//! wider terminals.
+
+// Rows of cells a glyph takes up at its own size, and the pixel rows in them
+const GLYPH_HEIGHT: usize = 3;
+const PIXEL_HEIGHT: usize = GLYPH_HEIGHT * 2;
+// Blank pixel columns between letters and between words
+const LETTER_GAP: usize = 1;
+const WORD_GAP: usize = 3;
+
+// One row per pixel row, one flag per pixel column
+type Pixels = Vec<Vec<bool>>;
+
+/// `text` as large as fits in `width` by `height` cells, up to `max_scale` times the glyphs' own
+/// size and wrapped between words, with a blank row between lines. `None` when it doesn't fit even
+/// at its own size or has characters the font lacks.
+pub fn fit(text: &str, width: usize, height: usize, max_scale: usize) -> Option<Vec<String>> {
+  let words = text
+    .split_whitespace()
+    .map(word_pixels)
+    .collect::<Option<Vec<Pixels>>>()?;
+  if words.is_empty() {
+    return None;
+  }
+
+  (1..=max_scale).rev().find_map(|scale| {
+    let lines = wrap(&words, width / scale)?;
+    let rows = lines.len() * GLYPH_HEIGHT * scale + lines.len() - 1;
+    if rows > height {
+      return None;
+    }
+    let mut cells = Vec::with_capacity(rows);
+    for (index, line) in lines.iter().enumerate() {
+      if index > 0 {
+        cells.push(String::new());
+      }
+      cells.extend(to_cells(line, scale));
+    }
+    Some(cells)
+  })
+}
+
+// Words laid out in lines at most `max_columns` pixels wide, `None` if a word is wider than that
+fn wrap(words: &[Pixels], max_columns: usize) -> Option<Vec<Pixels>> {
+  let mut lines: Vec<Pixels> = Vec::new();
+  for word in words {
+    let word_width = word[0].len();
+    if word_width > max_columns {
+      return None;
+    }
+    match lines.last_mut() {
+      Some(line) if line[0].len() + WORD_GAP + word_width <= max_columns => {
+        for (row, word_row) in line.iter_mut().zip(word) {
+          row.extend([false; WORD_GAP]);
+          row.extend(word_row);
+        }
+      }
+      _ => lines.push(word.clone()),
+    }
+  }
+  Some(lines)
+}
+
+fn word_pixels(word: &str) -> Option<Pixels> {
+  let mut pixels = vec![Vec::new(); PIXEL_HEIGHT];
+  for (index, c) in word.chars().flat_map(char::to_uppercase).enumerate() {
+    let glyph = glyph(fold_accent(c))?;
+    for (cell_row, cells) in glyph.iter().enumerate() {
+      let (top, bottom) = pixels.split_at_mut(cell_row * 2 + 1);
+      let (top, bottom) = (&mut top[cell_row * 2], &mut bottom[0]);
+      if index > 0 {
+        top.extend([false; LETTER_GAP]);
+        bottom.extend([false; LETTER_GAP]);
+      }
+      for cell in cells.chars() {
+        top.push(matches!(cell, '█' | '▀'));
+        bottom.push(matches!(cell, '█' | '▄'));
+      }
+    }
+  }
+  Some(pixels)
+}
+
+// Each pixel `scale` pixels wide and tall, two pixel rows to a row of cells
+fn to_cells(pixels: &Pixels, scale: usize) -> Vec<String> {
+  let columns = pixels[0].len() * scale;
+  (0..GLYPH_HEIGHT * scale)
+    .map(|cell_row| {
+      let top = &pixels[cell_row * 2 / scale];
+      let bottom = &pixels[(cell_row * 2 + 1) / scale];
+      (0..columns)
+        .map(|column| {
+          let pixel = column / scale;
+          match (top[pixel], bottom[pixel]) {
+            (true, true) => '█',
+            (true, false) => '▀',
+            (false, true) => '▄',
+            (false, false) => ' ',
+          }
+        })
+        .collect()
+    })
+    .collect()
+}
+
+// The font only has unaccented capitals
+fn fold_accent(c: char) -> char {
+  match c {
+    'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => 'A',
+    'Ç' => 'C',
+    'È' | 'É' | 'Ê' | 'Ë' => 'E',
+    'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+    'Ñ' => 'N',
+    'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => 'O',
+    'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+    'Ý' | 'Ÿ' => 'Y',
+    _ => c,
+  }
+}
+
+fn glyph(c: char) -> Option<[&'static str; GLYPH_HEIGHT]> {
+  let glyph = match c {
+    'A' => ["▄▀▄", "█▀█", "▀ ▀"],
+    'B' => ["█▀▄", "█▀▄", "▀▀ "],
+    'C' => ["▄▀▀", "█  ", " ▀▀"],
+    'D' => ["█▀▄", "█ █", "▀▀ "],
+    'E' => ["█▀▀", "█▀▀", "▀▀▀"],
+    'F' => ["█▀▀", "█▀ ", "▀  "],
+    'G' => ["▄▀▀ ", "█ ▀█", " ▀▀ "],
+    'H' => ["█ █", "█▀█", "▀ ▀"],
+    'I' => ["█", "█", "▀"],
+    'J' => ["  █", "▄ █", " ▀ "],
+    'K' => ["█ ▄▀", "█▀▄ ", "▀  ▀"],
+    'L' => ["█  ", "█  ", "▀▀▀"],
+    'M' => ["█▄ ▄█", "█ ▀ █", "▀   ▀"],
+    'N' => ["█▄ █", "█ ▀█", "▀  ▀"],
+    'O' => ["▄▀▄", "█ █", " ▀ "],
+    'P' => ["█▀▄", "█▀ ", "▀  "],
+    'Q' => ["▄▀▄", "█ █", " ▀█"],
+    'R' => ["█▀▄", "█▀▄", "▀ ▀"],
+    'S' => ["▄▀▀", " ▀▄", "▀▀ "],
+    'T' => ["▀█▀", " █ ", " ▀ "],
+    'U' => ["█ █", "█ █", "▀▀▀"],
+    'V' => ["█ █", "▀▄▀", " ▀ "],
+    'W' => ["█   █", "█ █ █", " ▀ ▀ "],
+    'X' => ["█ █", "▄▀▄", "▀ ▀"],
+    'Y' => ["█ █", "▀█▀", " ▀ "],
+    'Z' => ["▀▀█", "▄▀ ", "▀▀▀"],
+    '0' => ["█▀█", "█ █", "▀▀▀"],
+    '1' => ["▄█", " █", " ▀"],
+    '2' => ["▀▀█", "█▀▀", "▀▀▀"],
+    '3' => ["▀▀█", " ▀█", "▀▀▀"],
+    '4' => ["█ █", "▀▀█", "  ▀"],
+    '5' => ["█▀▀", "▀▀█", "▀▀▀"],
+    '6' => ["█▀▀", "█▀█", "▀▀▀"],
+    '7' => ["▀▀█", "  █", "  ▀"],
+    '8' => ["█▀█", "█▀█", "▀▀▀"],
+    '9' => ["█▀█", "▀▀█", "▀▀▀"],
+    '.' => [" ", " ", "▀"],
+    ',' => [" ", " ", "█"],
+    '\'' | '’' => ["█", " ", " "],
+    '"' => ["█ █", "   ", "   "],
+    '!' => ["█", "▀", "▀"],
+    '?' => ["▀▀█", " ▄▀", " ▀ "],
+    ':' => [" ", "▀", "▀"],
+    '-' | '–' | '—' => ["   ", "▀▀▀", "   "],
+    '+' => ["   ", "▄█▄", " ▀ "],
+    '&' => ["▄▀▄ ", "▄▀▄▀", "▀▀ ▀"],
+    '/' => ["  █", " █ ", "▀  "],
+    '(' | '[' => ["▄▀", "█ ", " ▀"],
+    ')' | ']' => ["▀▄", " █", "▀ "],
+    _ => return None,
+  };
+  Some(glyph)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scales_to_fit_and_wraps_between_words() {
+    assert_eq!(fit("hi", 20, 3, 1).unwrap(), ["█ █ █", "█▀█ █", "▀ ▀ ▀"]);
+    // Twice the size fills two pixel rows per row of cells
+    assert_eq!(
+      fit("I", 20, 6, 2).unwrap(),
+      ["██", "██", "██", "██", "██", "  "]
+    );
+    // Too narrow for both words on a line, so a blank row goes between them
+    assert_eq!(fit("I I", 4, 20, 1).unwrap().len(), 7);
+    assert_eq!(fit("WWW", 4, 20, 3), None);
+    assert_eq!(fit("日本", 80, 20, 3), None);
+  }
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use crossterm::terminal;
+use std::io::{self, Write};
+
+// Cells at least this many times as tall as they are wide get two columns per art pixel
+const DOUBLE_WIDTH_ASPECT: f32 = 1.5;
+
+/// How many columns album art draws per pixel so pixels come out roughly square, the
+/// `art_aspect_ratio` behavior option
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArtAspect {
+  // Two, for the usual cells about twice as tall as they are wide
+  Double,
+  // One, for cells close to square
+  Single,
+  // Measured from the terminal when the UI starts
+  Auto,
+}
+
+impl ArtAspect {
+  pub fn from_config_str(value: &str) -> Option<ArtAspect> {
+    match value {
+      "double" => Some(ArtAspect::Double),
+      "single" => Some(ArtAspect::Single),
+      "auto" => Some(ArtAspect::Auto),
+      _ => None,
+    }
+  }
+
+  /// `Auto` measured from the terminal, `Double` when it doesn't report its pixel size
+  pub fn resolve(self) -> ArtAspect {
+    match self {
+      ArtAspect::Auto => detect().map_or(ArtAspect::Double, from_cell_aspect),
+      aspect => aspect,
+    }
+  }
+}
+
+fn from_cell_aspect(aspect: f32) -> ArtAspect {
+  if aspect >= DOUBLE_WIDTH_ASPECT {
+    ArtAspect::Double
+  } else {
+    ArtAspect::Single
+  }
+}
+
+/// Measure the height of a cell divided by its width from the pixel size the terminal reports,
+/// if it reports one
+pub fn detect() -> Option<f32> {
+  let size = terminal::window_size().ok()?;
+  from_window_size(size.columns, size.rows, size.width, size.height)
+}
+
+fn from_window_size(columns: u16, rows: u16, width: u16, height: u16) -> Option<f32> {
+  if columns == 0 || rows == 0 || width == 0 || height == 0 {
+    return None;
+  }
+  let cell_width = f32::from(width) / f32::from(columns);
+  let cell_height = f32::from(height) / f32::from(rows);
+  Some(cell_height / cell_width)
+}
+
+/// Columns to draw per album art pixel, two until `Auto` has been resolved
+pub fn art_cell_width(aspect: ArtAspect) -> u16 {
+  match aspect {
+    ArtAspect::Single => 1,
+    ArtAspect::Double | ArtAspect::Auto => 2,
+  }
+}
+
+/// The filled and empty strings making up one art pixel
+pub fn art_pixel(cell_width: u16) -> (&'static str, &'static str) {
+  if cell_width >= 2 {
+    ("██", "  ")
+  } else {
+    ("█", " ")
+  }
+}
+
+/// `spt calibrate-art`: report the detected ratio, then let the user confirm by eye which test
+/// square actually looks square, and print the matching config line
+pub fn calibrate() -> Result<()> {
+  let mut stdout = io::stdout();
+  match detect() {
+    Some(aspect) => writeln!(
+      stdout,
+      "Your terminal reports cells {:.2} times as tall as they are wide.\n",
+      aspect
+    )?,
+    None => writeln!(
+      stdout,
+      "Your terminal doesn't report its pixel size, so the ratio can't be measured.\n"
+    )?,
+  }
+
+  writeln!(stdout, "  1           2")?;
+  for _ in 0..4 {
+    writeln!(stdout, "  {}    {}", "█".repeat(8), "█".repeat(4))?;
+  }
+  write!(stdout, "\nWhich block looks square? [1/2] ")?;
+  stdout.flush()?;
+
+  let mut answer = String::new();
+  io::stdin().read_line(&mut answer)?;
+  let aspect = match answer.trim() {
+    "1" => "double",
+    "2" => "single",
+    _ => {
+      writeln!(stdout, "No change, art keeps using the detected ratio.")?;
+      return Ok(());
+    }
+  };
+
+  writeln!(
+    stdout,
+    "\nAdd this under `behavior` in your config.yml:\n\n  art_aspect_ratio: {}",
+    aspect
+  )?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from_window_size() {
+    assert_eq!(from_window_size(100, 50, 800, 800), Some(2.0));
+    assert_eq!(from_window_size(100, 50, 800, 400), Some(1.0));
+    assert_eq!(from_window_size(100, 50, 0, 0), None);
+  }
+
+  #[test]
+  fn test_art_aspect() {
+    assert_eq!(from_cell_aspect(2.0), ArtAspect::Double);
+    assert_eq!(from_cell_aspect(1.1), ArtAspect::Single);
+    assert_eq!(ArtAspect::Single.resolve(), ArtAspect::Single);
+    assert_eq!(ArtAspect::from_config_str("auto"), Some(ArtAspect::Auto));
+    assert_eq!(ArtAspect::from_config_str("2.0"), None);
+    assert_eq!(art_cell_width(ArtAspect::Single), 1);
+    assert_eq!(art_cell_width(ArtAspect::Auto), 2);
+  }
+}
@@ -0,0 +1,200 @@
+use ratatui::{buffer::Buffer, style::Color};
+use std::env;
+
+/// How many colors the terminal can display. Anything below truecolor gets its RGB values
+/// mapped onto the nearest palette entry right before the frame is flushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSupport {
+  TrueColor,
+  Ansi256,
+  Ansi16,
+}
+
+// xterm's default values for the 16 base colors
+const ANSI16_PALETTE: [(Color, (u8, u8, u8)); 16] = [
+  (Color::Black, (0, 0, 0)),
+  (Color::Red, (205, 0, 0)),
+  (Color::Green, (0, 205, 0)),
+  (Color::Yellow, (205, 205, 0)),
+  (Color::Blue, (0, 0, 238)),
+  (Color::Magenta, (205, 0, 205)),
+  (Color::Cyan, (0, 205, 205)),
+  (Color::Gray, (229, 229, 229)),
+  (Color::DarkGray, (127, 127, 127)),
+  (Color::LightRed, (255, 0, 0)),
+  (Color::LightGreen, (0, 255, 0)),
+  (Color::LightYellow, (255, 255, 0)),
+  (Color::LightBlue, (92, 92, 255)),
+  (Color::LightMagenta, (255, 0, 255)),
+  (Color::LightCyan, (0, 255, 255)),
+  (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorSupport {
+  /// Guess color support from `COLORTERM` and `TERM`, the same hints most terminal
+  /// applications rely on.
+  pub fn detect() -> ColorSupport {
+    Self::from_env(
+      env::var("COLORTERM").ok().as_deref(),
+      env::var("TERM").ok().as_deref(),
+    )
+  }
+
+  fn from_env(colorterm: Option<&str>, term: Option<&str>) -> ColorSupport {
+    if let Some(colorterm) = colorterm {
+      let colorterm = colorterm.to_lowercase();
+      if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorSupport::TrueColor;
+      }
+    }
+
+    match term {
+      Some(term) if term.contains("truecolor") || term.contains("direct") => {
+        ColorSupport::TrueColor
+      }
+      Some(term) if term.contains("256color") => ColorSupport::Ansi256,
+      Some("linux") | Some("xterm") | Some("screen") | Some("vt100") => ColorSupport::Ansi16,
+      // Most modern terminals handle at least 256 colors even when TERM is vague
+      _ => ColorSupport::Ansi256,
+    }
+  }
+
+  /// Parse the `color_support` behavior option. `auto` defers to `detect`.
+  pub fn from_config_str(value: &str) -> Option<ColorSupport> {
+    match value.to_lowercase().as_str() {
+      "auto" => Some(Self::detect()),
+      "truecolor" | "24bit" => Some(ColorSupport::TrueColor),
+      "256" => Some(ColorSupport::Ansi256),
+      "16" => Some(ColorSupport::Ansi16),
+      _ => None,
+    }
+  }
+
+  pub fn quantize(self, color: Color) -> Color {
+    match (self, color) {
+      (ColorSupport::TrueColor, _) => color,
+      (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+      (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+      (ColorSupport::Ansi16, Color::Indexed(i)) if i >= 16 => {
+        let (r, g, b) = indexed_to_rgb(i);
+        nearest_16(r, g, b)
+      }
+      _ => color,
+    }
+  }
+}
+
+/// Rewrite every cell of a rendered frame so it only uses colors the terminal supports.
+pub fn downsample_buffer(buffer: &mut Buffer, support: ColorSupport) {
+  if support == ColorSupport::TrueColor {
+    return;
+  }
+  for cell in buffer.content.iter_mut() {
+    cell.fg = support.quantize(cell.fg);
+    cell.bg = support.quantize(cell.bg);
+  }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+  let dr = i32::from(a.0) - i32::from(b.0);
+  let dg = i32::from(a.1) - i32::from(b.1);
+  let db = i32::from(a.2) - i32::from(b.2);
+  (dr * dr + dg * dg + db * db) as u32
+}
+
+fn nearest_cube_level(value: u8) -> usize {
+  CUBE_LEVELS
+    .iter()
+    .enumerate()
+    .min_by_key(|(_, level)| (i32::from(**level) - i32::from(value)).abs())
+    .map(|(i, _)| i)
+    .unwrap_or(0)
+}
+
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+  let (ri, gi, bi) = (nearest_cube_level(r), nearest_cube_level(g), nearest_cube_level(b));
+  let cube_color = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+  let cube_index = (16 + 36 * ri + 6 * gi + bi) as u8;
+
+  // The grayscale ramp (232-255) is often a closer match for desaturated colors
+  let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+  let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+  let gray_value = 8 + gray_step * 10;
+  let gray_index = 232 + gray_step;
+
+  if distance((r, g, b), (gray_value, gray_value, gray_value)) < distance((r, g, b), cube_color) {
+    gray_index
+  } else {
+    cube_index
+  }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+  ANSI16_PALETTE
+    .iter()
+    .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+    .map(|(color, _)| *color)
+    .unwrap_or(Color::Reset)
+}
+
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+  match index {
+    0..=15 => ANSI16_PALETTE[index as usize].1,
+    16..=231 => {
+      let i = (index - 16) as usize;
+      (CUBE_LEVELS[i / 36], CUBE_LEVELS[(i / 6) % 6], CUBE_LEVELS[i % 6])
+    }
+    _ => {
+      let value = 8 + (index - 232) * 10;
+      (value, value, value)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_detect_from_env() {
+    assert_eq!(
+      ColorSupport::from_env(Some("truecolor"), Some("xterm-256color")),
+      ColorSupport::TrueColor
+    );
+    assert_eq!(
+      ColorSupport::from_env(None, Some("xterm-256color")),
+      ColorSupport::Ansi256
+    );
+    assert_eq!(
+      ColorSupport::from_env(None, Some("linux")),
+      ColorSupport::Ansi16
+    );
+  }
+
+  #[test]
+  fn test_quantize() {
+    assert_eq!(
+      ColorSupport::Ansi256.quantize(Color::Rgb(255, 0, 0)),
+      Color::Indexed(196)
+    );
+    assert_eq!(
+      ColorSupport::Ansi256.quantize(Color::Rgb(128, 128, 128)),
+      Color::Indexed(244)
+    );
+    assert_eq!(
+      ColorSupport::Ansi16.quantize(Color::Rgb(250, 10, 10)),
+      Color::LightRed
+    );
+    assert_eq!(
+      ColorSupport::Ansi16.quantize(Color::Indexed(21)),
+      Color::Blue
+    );
+    assert_eq!(ColorSupport::Ansi16.quantize(Color::Reset), Color::Reset);
+    assert_eq!(
+      ColorSupport::TrueColor.quantize(Color::Rgb(1, 2, 3)),
+      Color::Rgb(1, 2, 3)
+    );
+  }
+}
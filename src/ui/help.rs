@@ -0,0 +1,348 @@
+//! The help overlay: every key grouped by what it is for, built from the keys as configured so
+//! remapped ones show as they are bound
+
+use super::util::{display_width, help_rect};
+use crate::app::App;
+use crate::event::Key;
+use crate::user_config::UserConfig;
+use ratatui::{
+  style::{Modifier, Style},
+  text::{Line, Span},
+  widgets::{Block, BorderType, Borders, Clear, Paragraph},
+  Frame,
+};
+
+pub struct Section {
+  pub title: &'static str,
+  // The keys, and what they do
+  pub entries: Vec<(String, String)>,
+}
+
+// Entries without keys are left out, for chords that aren't configured
+fn section(title: &'static str, entries: Vec<(String, &str)>) -> Section {
+  Section {
+    title,
+    entries: entries
+      .into_iter()
+      .filter(|(keys, _)| !keys.is_empty())
+      .map(|(keys, action)| (keys, action.to_string()))
+      .collect(),
+  }
+}
+
+// The keys of the first chord for `action`, or nothing when there isn't one
+fn chord_keys(config: &UserConfig, action: &str) -> String {
+  config
+    .chords
+    .iter()
+    .find(|chord| chord.action == action)
+    .map(|chord| chord_string(&chord.keys))
+    .unwrap_or_default()
+}
+
+fn chord_string(keys: &[Key]) -> String {
+  keys
+    .iter()
+    .map(Key::to_string)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+// Several keys for one action
+fn either(keys: &[Key]) -> String {
+  keys
+    .iter()
+    .map(Key::to_string)
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// The global keys, then the ones particular to a block, then any chords
+pub fn sections(config: &UserConfig) -> Vec<Section> {
+  let keys = &config.keys;
+  let key = |key: Key| key.to_string();
+  // Blocks only delete through the chord, as `D` on its own opens the devices
+  let delete = chord_keys(config, "delete");
+  let mut sections = vec![
+    section(
+      "General",
+      vec![
+        (key(keys.help), "Show or hide this help"),
+        (key(keys.back), "Go back"),
+        (key(Key::Esc), "Leave the focused block or close a dialog"),
+        (key(keys.command_line), "Command line"),
+        (key(keys.search), "Search"),
+        (either(&[Key::Char('l'), Key::Char('L')]), "Library"),
+        (either(&[Key::Char('p'), Key::Char('P')]), "Playlists"),
+        (either(&[Key::Char('s'), Key::Char('S')]), "Search box"),
+        (either(&[keys.log_stream, Key::Ctrl('l')]), "Log stream"),
+        (
+          key(keys.basic_view),
+          "Basic view, with what is playing in large type",
+        ),
+        (key(keys.toggle_idle_mode), "Fullscreen album art"),
+        (key(keys.switch_idle_animation), "Next fullscreen animation"),
+        (key(keys.debug_overlay), "Debug overlay"),
+        (key(Key::Ctrl('z')), "Suspend to the shell"),
+        (key(Key::Ctrl('c')), "Quit"),
+      ],
+    ),
+    section(
+      "Moving around",
+      vec![
+        (
+          either(&[Key::Char('j'), Key::Char('k'), Key::Down, Key::Up]),
+          "Down and up",
+        ),
+        (
+          either(&[Key::Char('h'), Key::Left, Key::Right]),
+          "Between blocks",
+        ),
+        ("0-9".to_string(), "Repeat the next move that many times"),
+        (key(keys.submit), "Open or play the selected item"),
+        (
+          either(&[keys.next_page, keys.previous_page]),
+          "Next and previous page",
+        ),
+        (
+          either(&[keys.jump_back, keys.jump_forward]),
+          "Back and forward through the views gone to",
+        ),
+        (
+          key(keys.breadcrumb),
+          "Go back to a breadcrumb level by its number",
+        ),
+        (key(keys.split_view), "Split view"),
+        (key(keys.switch_pane), "Other side of the split view"),
+        (
+          format!("{}-{}", Key::Alt('1'), Key::Alt('9')),
+          "Switch workspace",
+        ),
+        (key(keys.jump_to_album), "The playing track's album"),
+        (key(keys.jump_to_artist_album), "The playing track's artist"),
+        (key(keys.jump_to_context), "The album or playlist playing"),
+        (either(&[keys.manage_devices, Key::Char('D')]), "Devices"),
+      ],
+    ),
+    section(
+      "Playback",
+      vec![
+        (key(keys.toggle_playback), "Play or pause"),
+        (
+          either(&[keys.next_track, keys.previous_track]),
+          "Next and previous track",
+        ),
+        (
+          either(&[keys.seek_backwards, keys.seek_forwards]),
+          "Seek back and forward",
+        ),
+        (key(keys.shuffle), "Shuffle"),
+        (key(keys.repeat), "Repeat"),
+        (
+          either(&[keys.decrease_volume, keys.increase_volume]),
+          "Volume down and up",
+        ),
+        (key(keys.mute), "Mute"),
+        (
+          either(&[keys.loop_start, keys.loop_end]),
+          "Start and end of an A-B loop",
+        ),
+        (key(keys.sleep_timer), "Sleep timer"),
+      ],
+    ),
+    section(
+      "What is playing",
+      vec![
+        (key(keys.like_current_track), "Like or unlike it"),
+        (
+          key(keys.follow_current_artist),
+          "Follow or unfollow its artist",
+        ),
+        (key(keys.copy_now_playing), "Copy it"),
+        (key(keys.share), "Share the selected item"),
+        (key(keys.open_in), "Open in Spotify or the browser"),
+        (key(keys.audio_analysis), "Audio analysis"),
+        (key(keys.audio_features), "Audio features"),
+      ],
+    ),
+    section(
+      "Tracks",
+      vec![
+        (key(keys.add_item_to_queue), "Add to the queue"),
+        (key(keys.play_next), "Play next"),
+        (key(keys.add_to_playlist), "Add to a playlist"),
+        (key(keys.remove_from_playlist), "Remove from the playlist"),
+        (key(keys.save_track), "Like or unlike"),
+        (key(Key::Char('r')), "Tracks like this one"),
+        (key(keys.visual_mode), "Mark a range of tracks"),
+        (
+          either(&[keys.jump_to_start, keys.jump_to_end]),
+          "First and last track",
+        ),
+      ],
+    ),
+    section(
+      "Playlists",
+      vec![
+        (key(Key::Char('f')), "Find one by name"),
+        (key(keys.sort_playlists), "Sort by recency, name or owner"),
+        (key(keys.pin_playlist), "Pin to the top"),
+        (key(Key::Char('N')), "New playlist"),
+        (key(Key::Char('R')), "Rename"),
+        (delete.clone(), "Delete"),
+      ],
+    ),
+    section(
+      "Albums, artists and search results",
+      vec![
+        (key(Key::Char('w')), "Save the album or follow the artist"),
+        (delete.clone(), "Remove the album or unfollow the artist"),
+        (key(Key::Char('e')), "Play the artist, album or playlist"),
+        (key(Key::Char('r')), "Tracks like the selected one"),
+      ],
+    ),
+    section("Podcasts", vec![(delete, "Unfollow")]),
+    section(
+      "Top tracks and artists",
+      vec![(
+        either(&[Key::Char('1'), Key::Char('2'), Key::Char('3')]),
+        "Last 4 weeks, 6 months or all time",
+      )],
+    ),
+    section(
+      "Devices",
+      vec![
+        (
+          either(&[keys.decrease_volume, keys.increase_volume]),
+          "Volume of the highlighted device",
+        ),
+        (key(keys.toggle_streaming), "Start or stop spotui's own device"),
+      ],
+    ),
+    section(
+      "Log stream",
+      vec![
+        (key(Key::Char('f')), "Level shown"),
+        (key(keys.search), "Search"),
+        (key(keys.copy_now_playing), "Copy the selected line"),
+      ],
+    ),
+    section(
+      "Audio analysis",
+      vec![(key(Key::Char('t')), "Pitch or timbre")],
+    ),
+  ];
+
+  sections.retain(|section| !section.entries.is_empty());
+
+  if !config.chords.is_empty() {
+    sections.push(Section {
+      title: "Chords",
+      entries: config
+        .chords
+        .iter()
+        .map(|chord| (chord_string(&chord.keys), chord.action.replace('_', " ")))
+        .collect(),
+    });
+  }
+  sections
+}
+
+// A title for each section and a row for each key, with a blank row between sections
+fn lines(app: &App) -> Vec<Line<'static>> {
+  let theme = app.user_config.theme;
+  let sections = sections(&app.user_config);
+  let key_width = sections
+    .iter()
+    .flat_map(|section| &section.entries)
+    .map(|(keys, _)| display_width(keys))
+    .max()
+    .unwrap_or_default();
+
+  let mut lines = Vec::new();
+  for section in sections {
+    if !lines.is_empty() {
+      lines.push(Line::default());
+    }
+    lines.push(Line::styled(
+      section.title,
+      Style::default()
+        .fg(theme.header)
+        .add_modifier(Modifier::BOLD),
+    ));
+    for (keys, action) in section.entries {
+      let padding = " ".repeat(usize::from(key_width - display_width(&keys)) + 2);
+      lines.push(Line::from(vec![
+        Span::styled(keys + &padding, Style::default().fg(theme.hint)),
+        Span::styled(action, Style::default().fg(theme.text)),
+      ]));
+    }
+  }
+  lines
+}
+
+/// How far the help can scroll before its last row is at the bottom of the overlay
+pub fn max_scroll(app: &App) -> u16 {
+  let visible = help_rect(app.size).height.saturating_sub(2);
+  u16::try_from(lines(app).len())
+    .unwrap_or(u16::MAX)
+    .saturating_sub(visible)
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+  let scroll = match app.help_scroll {
+    Some(scroll) => scroll,
+    None => return,
+  };
+  let rect = help_rect(f.area());
+  if rect.height < 3 {
+    return;
+  }
+
+  let keys = &app.user_config.keys;
+  let hint = format!(
+    " {}/{} scroll, {} close ",
+    Key::Char('j'),
+    Key::Char('k'),
+    keys.help
+  );
+  f.render_widget(Clear, rect);
+  let help = Paragraph::new(lines(app)).scroll((scroll, 0)).block(
+    Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .border_style(Style::default().fg(app.user_config.theme.inactive))
+      .title("Help")
+      .title_bottom(hint),
+  );
+  f.render_widget(help, rect);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn shows_keys_as_they_are_bound() {
+    let mut config = UserConfig::new();
+    config.keys.toggle_playback = Key::Char('x');
+    let sections = sections(&config);
+    let playback = sections
+      .iter()
+      .find(|section| section.title == "Playback")
+      .unwrap();
+    assert_eq!(
+      playback.entries[0],
+      ("x".to_string(), "Play or pause".to_string())
+    );
+    let chords = sections.last().unwrap();
+    assert_eq!(chords.title, "Chords");
+    assert_eq!(
+      chords.entries,
+      [
+        ("g g".to_string(), "top".to_string()),
+        ("\\ d".to_string(), "delete".to_string())
+      ]
+    );
+  }
+}
@@ -1,19 +1,26 @@
 pub mod audio_analysis;
+pub mod big_text;
+pub mod cell_aspect;
+pub mod color;
+pub mod help;
 pub mod util;
 use super::{
   app::{
-    ActiveBlock, AlbumTableContext, App, ArtistBlock, EpisodeTableContext, RecommendationsContext,
-    RouteId, SearchResultBlock, LIBRARY_OPTIONS,
+    AbLoop, ActiveBlock, AlbumTableContext, App, ArtistBlock, Dialog, DialogContext,
+    EpisodeTableContext, LogLevel, RecommendationsContext, Route, RouteId, SearchResultBlock,
+    SleepAction, LIBRARY_OPTIONS,
   },
   banner::BANNER,
+  focus_manager::ComponentId,
+  share::ShareFormat,
   user_config::Theme,
 };
-use rspotify::model::show::ResumePoint;
+use rspotify::model::{playlist::SimplifiedPlaylist, show::ResumePoint};
 use crate::network::{PlayingItem, RepeatState};
 use rspotify::model::{RepeatState as SpotifyRepeatState, PlayableItem};
 use ratatui::{
   backend::{Backend, CrosstermBackend},
-  layout::{Alignment, Constraint, Direction, Layout, Rect},
+  layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
   style::{Color, Modifier, Style},
   symbols::border,
   text::{Line, Span, Text},
@@ -21,9 +28,13 @@ use ratatui::{
   Frame,
 };
 use util::{
-  create_artist_string, get_artist_highlight_state, get_color,
+  audio_features_rect, breadcrumb_level_number, create_artist_label, create_artist_string,
+  device_label, device_picker_label, display_track_progress, get_artist_highlight_state, get_color,
   get_percentage_width, get_search_results_highlight_state, get_track_progress_percentage,
-  millis_to_minutes, BASIC_VIEW_HEIGHT, SMALL_TERMINAL_WIDTH,
+  is_ultra_small_layout, is_wide_layout, key_name, millis_to_minutes, pattern_bar, playbar_height,
+  playlist_picker_rect, search_page_title, search_popup_rect, shows_sidebar, track_column_widths,
+  track_table_summary, track_table_title, up_next_label, PlaybarPosition, ProgressBarStyle,
+  TrackColumn, BASIC_VIEW_HEIGHT, BREADCRUMB_SEPARATOR,
 };
 
 pub enum TableId {
@@ -34,6 +45,10 @@ pub enum TableId {
   Song,
   RecentlyPlayed,
   PodcastEpisodes,
+  Duplicates,
+  Playlists,
+  NewReleases,
+  Categories,
 }
 
 #[derive(PartialEq)]
@@ -114,7 +129,7 @@ pub fn draw_input_and_help_box<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
   let chunks = Layout::default()
     .direction(Direction::Horizontal)
     .constraints(
-      if app.size.width >= SMALL_TERMINAL_WIDTH && !app.user_config.behavior.enforce_wide_search_bar
+      if is_wide_layout(app)
       {
         [Constraint::Percentage(65), Constraint::Percentage(35)].as_ref()
       } else {
@@ -123,16 +138,16 @@ pub fn draw_input_and_help_box<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     )
     .split(layout_chunk);
 
-  let current_route = app.get_current_route();
 
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::Input,
-    current_route.hovered_block == ActiveBlock::Input,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::Input);
 
   let input_string: String = app.input.iter().collect();
   let lines = Text::from((&input_string).as_str());
-  let search_title_spans = create_focus_title("Search", &app.user_config.theme, highlight_state);
+  let title = match &app.reverse_search {
+    Some(search) => search.title(),
+    None => app.input_purpose.title().to_string(),
+  };
+  let search_title_spans = create_focus_title(&title, &app.user_config.theme, highlight_state);
   let input = Paragraph::new(lines).block(
     Block::default()
       .borders(Borders::ALL)
@@ -143,20 +158,20 @@ pub fn draw_input_and_help_box<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
   f.render_widget(input, chunks[0]);
 
   let (device_text, text_color) = if let Some(context) = &app.current_playback_context {
-    (context.device.name.clone(), app.user_config.theme.active)
+    (device_label(&context.device), app.user_config.theme.active)
   } else if let Some(devices) = &app.devices {
     if devices.devices.is_empty() {
       ("NO DEVICES".to_string(), Color::Red)
     } else if let Some(idx) = app.selected_device_index {
       if let Some(device) = devices.devices.get(idx) {
-        (device.name.clone(), app.user_config.theme.inactive)
+        (device_label(device), app.user_config.theme.inactive)
       } else if let Some(first_device) = devices.devices.first() {
-        (first_device.name.clone(), app.user_config.theme.inactive)
+        (device_label(first_device), app.user_config.theme.inactive)
       } else {
         ("NO DEVICES".to_string(), Color::Red)
       }
     } else if let Some(first_device) = devices.devices.first() {
-      (first_device.name.clone(), app.user_config.theme.inactive)
+      (device_label(first_device), app.user_config.theme.inactive)
     } else {
       ("NO DEVICES".to_string(), Color::Red)
     }
@@ -164,10 +179,7 @@ pub fn draw_input_and_help_box<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     ("NO DEVICES".to_string(), Color::Red)
   };
 
-  let device_highlight_state = (
-    current_route.active_block == ActiveBlock::SelectDevice,
-    current_route.hovered_block == ActiveBlock::SelectDevice,
-  );
+  let device_highlight_state = app.block_highlight_state(ActiveBlock::SelectDevice);
   
   let device_title_spans = create_focus_title("Device", &app.user_config.theme, device_highlight_state);
   let block = Block::default()
@@ -185,136 +197,363 @@ pub fn draw_input_and_help_box<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
 pub fn draw_main_layout(f: &mut Frame, app: &App) {
   let margin = util::get_main_layout_margin(app);
-  // Responsive layout: new one kicks in at width 150 or higher
-  // Calculate playbar height dynamically based on terminal height
-  let playbar_height = (f.area().height / 5).max(6).min(14);
-  
-  if app.size.width >= SMALL_TERMINAL_WIDTH && !app.user_config.behavior.enforce_wide_search_bar {
-    let parent_layout = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints([Constraint::Min(1), Constraint::Length(playbar_height)].as_ref())
-      .margin(margin)
-      .split(f.area());
+  // Along the bottom unless the playbar_position behavior option puts it at the top
+  let playbar_height = playbar_height(f.area());
+  let playbar_on_top = app.user_config.behavior.playbar_position == PlaybarPosition::Top;
+  let constraints = if playbar_on_top {
+    [Constraint::Length(playbar_height), Constraint::Min(1)]
+  } else {
+    [Constraint::Min(1), Constraint::Length(playbar_height)]
+  };
+  let parent_layout = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints(constraints.as_ref())
+    .margin(margin)
+    .split(f.area());
+  let (main_area, playbar_area) = if playbar_on_top {
+    (parent_layout[1], parent_layout[0])
+  } else {
+    (parent_layout[0], parent_layout[1])
+  };
 
-    // Nested main block with potential routes
-    draw_routes::<CrosstermBackend<std::io::Stdout>>(f, app, parent_layout[0]);
+  if is_ultra_small_layout(app) {
+    // No room for a sidebar, so it only takes over the main area while focused
+    if sidebar_focused(app) {
+      draw_user_block(f, app, main_area);
+    } else {
+      draw_route_content(f, app, main_area);
+    }
 
-    // Currently playing (now taller)
-    draw_playbar::<CrosstermBackend<std::io::Stdout>>(f, app, parent_layout[1]);
+    if app.is_component_focused(&ComponentId::SearchInput) {
+      draw_search_popup(f, app);
+    }
+  } else if is_wide_layout(app) {
+    // Nested main block with potential routes
+    draw_routes::<CrosstermBackend<std::io::Stdout>>(f, app, main_area);
   } else {
-    let parent_layout = Layout::default()
+    let chunks = Layout::default()
       .direction(Direction::Vertical)
-      .constraints(
-        [
-          Constraint::Length(3),
-          Constraint::Min(1),
-          Constraint::Length(playbar_height),
-        ]
-        .as_ref(),
-      )
-      .margin(margin)
-      .split(f.area());
+      .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+      .split(main_area);
 
     // Search input and help
-    draw_input_and_help_box::<CrosstermBackend<std::io::Stdout>>(f, app, parent_layout[0]);
+    draw_input_and_help_box::<CrosstermBackend<std::io::Stdout>>(f, app, chunks[0]);
 
     // Nested main block with potential routes
-    draw_routes::<CrosstermBackend<std::io::Stdout>>(f, app, parent_layout[1]);
-
-    // Currently playing (now taller)
-    draw_playbar::<CrosstermBackend<std::io::Stdout>>(f, app, parent_layout[2]);
+    draw_routes::<CrosstermBackend<std::io::Stdout>>(f, app, chunks[1]);
   }
 
+  // Currently playing
+  draw_playbar::<CrosstermBackend<std::io::Stdout>>(f, app, playbar_area);
+
+  draw_audio_features(f, app);
+
   // Possibly draw confirm dialog
   draw_dialog::<CrosstermBackend<std::io::Stdout>>(f, app);
+
+  draw_toast(f, app);
+}
+
+/// The playing track's audio features, toggled with the audio_features key
+fn draw_audio_features(f: &mut Frame, app: &App) {
+  use rspotify::model::Modality;
+
+  if !app.show_audio_features {
+    return;
+  }
+  let label_style = Style::default().fg(app.user_config.theme.hint);
+  let text_style = Style::default().fg(app.user_config.theme.text);
+  let detail = |label: &'static str, value: String| {
+    Line::from(vec![
+      Span::styled(format!("{:<13}", label), label_style),
+      Span::styled(value, text_style),
+    ])
+  };
+  // The 0 to 1 features, as a bar
+  let level = |label: &'static str, value: f32| {
+    let (filled, empty) = pattern_bar(10, f64::from(value));
+    detail(label, format!("{}{} {:.2}", filled, empty, value))
+  };
+
+  let message = |text: &'static str| vec![Line::from(Span::styled(text, text_style))];
+  let lines = match app.playing_track_id() {
+    None => message("Not playing a track"),
+    Some(track_id) => match app.audio_features.get(&track_id) {
+      None => message("Loading..."),
+      Some(None) => message("Not available for this track"),
+      Some(Some(features)) => {
+        let key = key_name(features.key, features.mode == Modality::Major);
+        vec![
+          detail("Tempo", format!("{:.0} BPM", features.tempo)),
+          detail("Key", key.unwrap_or_else(|| "Unknown".to_string())),
+          level("Energy", features.energy),
+          level("Danceability", features.danceability),
+          level("Valence", features.valence),
+          detail("Loudness", format!("{:.1} dB", features.loudness)),
+        ]
+      }
+    },
+  };
+
+  let rect = audio_features_rect(f.area());
+  f.render_widget(Clear, rect);
+  let panel = Paragraph::new(lines).block(
+    Block::default()
+      .title(Span::styled(
+        "Audio Features",
+        Style::default().fg(app.user_config.theme.header),
+      ))
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .border_style(Style::default().fg(app.user_config.theme.inactive)),
+  );
+  f.render_widget(panel, rect);
+}
+
+/// The search input of the ultra small layout, drawn over the main area while typing
+fn draw_search_popup(f: &mut Frame, app: &App) {
+  let rect = search_popup_rect(f.area());
+  let input_string: String = app.input.iter().collect();
+  let search_title_spans =
+    create_focus_title(app.input_purpose.title(), &app.user_config.theme, (true, true));
+  let input = Paragraph::new(input_string).block(
+    Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .title(Line::from(search_title_spans))
+      .border_style(get_color((true, true), app.user_config.theme)),
+  );
+  f.render_widget(Clear, rect);
+  f.render_widget(input, rect);
 }
 
 pub fn draw_breadcrumb_box(f: &mut Frame, app: &App, layout_chunk: Rect) {
-  let breadcrumb_text = app.get_navigation_breadcrumb();
-  
-  let block = Block::default()
+  app.breadcrumb_area.set(Some(layout_chunk));
+
+  // Numbered while the breadcrumb key waits for a level to go back to
+  let mut spans = Vec::new();
+  for (level, part) in app.get_navigation_breadcrumb().into_iter().enumerate() {
+    if level > 0 {
+      spans.push(Span::raw(BREADCRUMB_SEPARATOR));
+    }
+    if let Some(number) = breadcrumb_level_number(app, level) {
+      spans.push(Span::styled(
+        number,
+        Style::default().fg(app.user_config.theme.hint),
+      ));
+    }
+    spans.push(Span::raw(part));
+  }
+
+  let mut block = Block::default()
     .borders(Borders::ALL)
     .border_type(BorderType::Rounded)
     .border_style(Style::default().fg(app.user_config.theme.inactive));
 
-  let lines = Text::from(breadcrumb_text.as_str());
-  let breadcrumb = Paragraph::new(lines)
+  // When the current view's data was last fetched
+  let route = app.get_current_route();
+  let status = if route.loading {
+    Some("Loading...".to_string())
+  } else {
+    route
+      .updated_at
+      .map(|updated_at| format!("Updated {}", updated_at.format("%H:%M")))
+  };
+  if let Some(status) = status {
+    block = block.title_top(Line::from(status).right_aligned());
+  }
+
+  // The workspaces opened, once there is more than one, with the one shown highlighted
+  let workspaces = app.workspaces.opened();
+  if workspaces.len() > 1 {
+    let theme = app.user_config.theme;
+    let tabs: Vec<Span> = workspaces
+      .into_iter()
+      .map(|index| {
+        let style = if index == app.workspaces.active() {
+          Style::default()
+            .fg(theme.active)
+            .add_modifier(Modifier::BOLD)
+        } else {
+          Style::default().fg(theme.inactive)
+        };
+        Span::styled(format!(" {} ", index + 1), style)
+      })
+      .collect();
+    block = block.title_top(Line::from(tabs));
+  }
+
+  let breadcrumb = Paragraph::new(Line::from(spans))
     .block(block)
     .style(Style::default().fg(app.user_config.theme.text));
   
   f.render_widget(breadcrumb, layout_chunk);
 }
 
+/// Whether the library or playlists have focus, for layouts that only show them while they do
+fn sidebar_focused(app: &App) -> bool {
+  let is_sidebar = |component: Option<&ComponentId>| {
+    matches!(
+      component,
+      Some(ComponentId::Library) | Some(ComponentId::MyPlaylists)
+    )
+  };
+  match app.get_focused_component() {
+    None => is_sidebar(app.get_hovered_component()),
+    focused => is_sidebar(focused),
+  }
+}
+
 pub fn draw_routes<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
-  let chunks = Layout::default()
-    .direction(Direction::Horizontal)
-    .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
-    .split(layout_chunk);
-
-  draw_user_block(f, app, chunks[0]);
+  // Without the sidebar beside it, the main view gives way to it while it is focused
+  let right_chunk = if shows_sidebar(app) {
+    let chunks = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
+      .split(layout_chunk);
+    draw_user_block(f, app, chunks[0]);
+    chunks[1]
+  } else if sidebar_focused(app) {
+    draw_user_block(f, app, layout_chunk);
+    return;
+  } else {
+    layout_chunk
+  };
 
   // Split the right side into breadcrumb (top) and main content (bottom)
-  let right_chunks = Layout::default()
-    .direction(Direction::Vertical)
-    .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
-    .split(chunks[1]);
+  let content_chunk = if app.user_config.behavior.show_breadcrumb {
+    let right_chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+      .split(right_chunk);
+    draw_breadcrumb_box(f, app, right_chunks[0]);
+    right_chunks[1]
+  } else {
+    right_chunk
+  };
+
+  // Side by side when the split view has two routes to show, each pane as wide as the other
+  match app.split_view_routes() {
+    Some((left, right)) => {
+      let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(content_chunk);
+      draw_route(f, app, left, panes[0]);
+      draw_route(f, app, right, panes[1]);
+    }
+    None => draw_route_content(f, app, content_chunk),
+  }
+}
 
-  // Draw breadcrumb box at the top of the right side
-  draw_breadcrumb_box(f, app, right_chunks[0]);
+/// Draw the view for the current route, without the sidebar or breadcrumb around it
+fn draw_route_content(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  draw_route(f, app, app.get_current_route(), layout_chunk);
+}
 
-  let current_route = app.get_current_route();
+/// Draw `route`, which is the one on top unless it is the split view's unfocused pane
+fn draw_route(f: &mut Frame, app: &App, route: &Route, layout_chunk: Rect) {
+  if route.loading {
+    draw_loading_skeleton(f, app, layout_chunk);
+    return;
+  }
 
-  match current_route.id {
+  match route.id {
     RouteId::Search => {
-      draw_search_results::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_search_results::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::TrackTable => {
-      draw_song_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_song_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::AlbumTracks => {
-      draw_album_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_album_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::RecentlyPlayed => {
-      draw_recently_played_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_recently_played_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::Artist => {
-      draw_artist_albums::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_artist_albums::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::AlbumList => {
-      draw_album_list::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_album_list::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::PodcastEpisodes => {
-      draw_show_episodes::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_show_episodes::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::Home => {
-      draw_home::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_home::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::Artists => {
-      draw_artist_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_artist_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
     }
     RouteId::Podcasts => {
-      draw_podcast_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_podcast_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
+    }
+    RouteId::MadeForYou => {
+      draw_made_for_you_table(f, app, layout_chunk);
+    }
+    RouteId::NewReleases => {
+      draw_new_releases_table(f, app, layout_chunk);
+    }
+    RouteId::FeaturedPlaylists => {
+      draw_featured_playlists_table(f, app, layout_chunk);
+    }
+    RouteId::Categories => {
+      draw_categories_table(f, app, layout_chunk);
+    }
+    RouteId::CategoryPlaylists => {
+      draw_category_playlists_table(f, app, layout_chunk);
+    }
+    RouteId::Profile => {
+      draw_profile(f, app, layout_chunk);
     }
     RouteId::Recommendations => {
-      draw_recommendations_table::<CrosstermBackend<std::io::Stdout>>(f, app, right_chunks[1]);
+      draw_recommendations_table::<CrosstermBackend<std::io::Stdout>>(f, app, layout_chunk);
+    }
+    RouteId::Duplicates => {
+      draw_duplicates_table(f, app, layout_chunk);
     }
     RouteId::SelectedDevice => {} // This is handled as a "full screen" route in main.rs
     RouteId::Analysis => {} // This is handled as a "full screen" route in main.rs
     RouteId::BasicView => {} // This is handled as a "full screen" route in main.rs
     RouteId::LogStream => {} // This is handled as a "full screen" route in main.rs
+    RouteId::ColorStory => {} // This is handled as a "full screen" route in main.rs
     RouteId::Error => {} // Error screen no longer exists, errors are handled via log stream
     RouteId::Dialog => {} // This is handled in the draw_dialog function in mod.rs
   };
 }
 
+/// Placeholder rows for a view whose data is still on its way
+fn draw_loading_skeleton(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  const ROW_WIDTHS: [f32; 5] = [0.7, 0.45, 0.85, 0.6, 0.75];
+
+  let inner_width = layout_chunk.width.saturating_sub(4) as f32;
+  let rows = (0..layout_chunk.height.saturating_sub(3))
+    .map(|row| {
+      let width = (inner_width * ROW_WIDTHS[row as usize % ROW_WIDTHS.len()]) as usize;
+      Line::from(Span::styled(
+        "━".repeat(width),
+        Style::default().fg(app.user_config.theme.inactive),
+      ))
+    })
+    .collect::<Vec<Line>>();
+
+  let skeleton = Paragraph::new(rows).block(
+    Block::default()
+      .borders(Borders::ALL)
+      .title(Span::styled(
+        "Loading...",
+        Style::default().fg(app.user_config.theme.inactive),
+      ))
+      .border_style(Style::default().fg(app.user_config.theme.inactive)),
+  );
+  f.render_widget(skeleton, layout_chunk);
+}
+
 pub fn draw_library_block<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::Library,
-    current_route.hovered_block == ActiveBlock::Library,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::Library);
   draw_selectable_list::<&str>(
     f,
     app,
@@ -328,32 +567,43 @@ pub fn draw_library_block<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
 pub fn draw_playlist_block<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
-  let playlist_items = match &app.playlists {
-    Some(p) => p.items.iter().map(|item| item.name.to_owned()).collect(),
-    None => vec![],
+  let highlight_state = app.block_highlight_state(ActiveBlock::MyPlaylists);
+
+  // While filtering, only the matches are listed, best first
+  let (playlist_items, selected_index, title) = match (&app.playlists, &app.playlist_filter) {
+    (Some(p), Some(filter)) => {
+      let matches = app.playlist_filter_matches();
+      let selected_index = app
+        .selected_playlist_index
+        .and_then(|selected| matches.iter().position(|&index| index == selected));
+      let items = matches
+        .iter()
+        .map(|&index| p.items[index].name.to_owned())
+        .collect();
+      (items, selected_index, format!("Playlists /{}", filter))
+    }
+    (Some(p), None) => (
+      p.items.iter().map(|item| item.name.to_owned()).collect(),
+      app.selected_playlist_index,
+      "Playlists".to_string(),
+    ),
+    (None, _) => (vec![], app.selected_playlist_index, "Playlists".to_string()),
   };
 
-  let current_route = app.get_current_route();
-
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::MyPlaylists,
-    current_route.hovered_block == ActiveBlock::MyPlaylists,
-  );
-
   draw_selectable_list::<String>(
     f,
     app,
     layout_chunk,
-    "Playlists",
+    &title,
     &playlist_items,
     highlight_state,
-    app.selected_playlist_index,
+    selected_index,
   );
 }
 
 pub fn draw_user_block(f: &mut Frame, app: &App, layout_chunk: Rect) {
   // Check for width to make a responsive layout
-  if app.size.width >= SMALL_TERMINAL_WIDTH && !app.user_config.behavior.enforce_wide_search_bar {
+  if is_wide_layout(app) {
     let chunks = Layout::default()
       .direction(Direction::Vertical)
       .constraints(
@@ -439,7 +689,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       f,
       app,
       song_artist_block[0],
-      "Songs",
+      &search_page_title("Songs", app.search_results.tracks.as_ref()),
       &songs,
       get_search_results_highlight_state(app, SearchResultBlock::SongSearch),
       app.search_results.selected_tracks_index,
@@ -449,14 +699,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       Some(artists) => artists
         .items
         .iter()
-        .map(|item| {
-          let mut artist = String::new();
-          if app.followed_artist_ids_set.contains(&item.id.to_string()) {
-            artist.push_str(&app.user_config.padded_liked_icon());
-          }
-          artist.push_str(&item.name.to_owned());
-          artist
-        })
+        .map(|item| create_artist_label(app, &item.id.to_string(), &item.name))
         .collect(),
       None => vec![],
     };
@@ -465,7 +708,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       f,
       app,
       song_artist_block[1],
-      "Artists",
+      &search_page_title("Artists", app.search_results.artists.as_ref()),
       &artists,
       get_search_results_highlight_state(app, SearchResultBlock::ArtistSearch),
       app.search_results.selected_artists_index,
@@ -505,7 +748,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       f,
       app,
       albums_playlist_block[0],
-      "Albums",
+      &search_page_title("Albums", app.search_results.albums.as_ref()),
       &albums,
       get_search_results_highlight_state(app, SearchResultBlock::AlbumSearch),
       app.search_results.selected_album_index,
@@ -523,7 +766,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       f,
       app,
       albums_playlist_block[1],
-      "Playlists",
+      &search_page_title("Playlists", app.search_results.playlists.as_ref()),
       &playlists,
       get_search_results_highlight_state(app, SearchResultBlock::PlaylistSearch),
       app.search_results.selected_playlists_index,
@@ -555,7 +798,7 @@ pub fn draw_search_results<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       f,
       app,
       podcasts_block[0],
-      "Podcasts",
+      &search_page_title("Podcasts", app.search_results.shows.as_ref()),
       &podcasts,
       get_search_results_highlight_state(app, SearchResultBlock::ShowSearch),
       app.search_results.selected_shows_index,
@@ -580,25 +823,27 @@ pub fn draw_artist_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     }],
   };
 
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::Artists,
-    current_route.hovered_block == ActiveBlock::Artists,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::Artists);
   let items = app
     .artists
     .iter()
     .map(|item| TableItem {
       id: item.id.to_string(),
-      format: vec![item.name.to_owned()],
+      format: vec![create_artist_label(app, &item.id.to_string(), &item.name)],
     })
     .collect::<Vec<TableItem>>();
 
+  let title = if app.showing_top_artists {
+    format!("Top Artists ({})", app.top_time_range.label())
+  } else {
+    String::new()
+  };
+
   draw_table::<CrosstermBackend<std::io::Stdout>>(
     f,
     app,
     layout_chunk,
-    ("", &header),
+    (&title, &header),
     &items,
     app.artists_list_index,
     highlight_state,
@@ -623,12 +868,8 @@ pub fn draw_podcast_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     ],
   };
 
-  let current_route = app.get_current_route();
 
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::Podcasts,
-    current_route.hovered_block == ActiveBlock::Podcasts,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::Podcasts);
 
   if let Some(saved_shows) = app.library.saved_shows.get_results(None) {
     let items = saved_shows
@@ -655,6 +896,275 @@ pub fn draw_podcast_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
   };
 }
 
+pub fn draw_made_for_you_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  let playlists = &app.library.made_for_you_playlists;
+  let title = if playlists.is_empty() {
+    "No Made For You playlists found"
+  } else {
+    "Made For You"
+  };
+  draw_playlist_table(
+    f,
+    app,
+    layout_chunk,
+    title,
+    playlists,
+    app.made_for_you_index,
+    ActiveBlock::MadeForYou,
+  );
+}
+
+pub fn draw_featured_playlists_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  // Spotify's editorial message, e.g. "Monday morning music, coming right up"
+  let featured = app.featured_playlists.as_ref();
+  let title = featured
+    .map(|featured| featured.message.as_str())
+    .filter(|message| !message.is_empty())
+    .unwrap_or("Featured Playlists");
+  let playlists = featured
+    .map(|featured| featured.playlists.items.as_slice())
+    .unwrap_or_default();
+  draw_playlist_table(
+    f,
+    app,
+    layout_chunk,
+    title,
+    playlists,
+    app.featured_playlists_index,
+    ActiveBlock::FeaturedPlaylists,
+  );
+}
+
+pub fn draw_profile(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  use rspotify::model::SubscriptionLevel;
+
+  let highlight_state = app.block_highlight_state(ActiveBlock::Profile);
+  let block = Block::default()
+    .title(Span::styled(
+      "Profile",
+      get_color(highlight_state, app.user_config.theme),
+    ))
+    .borders(Borders::ALL)
+    .border_type(BorderType::Rounded)
+    .border_style(get_color(highlight_state, app.user_config.theme));
+  let inner_area = block.inner(layout_chunk);
+  f.render_widget(block, layout_chunk);
+
+  let user = match &app.user {
+    Some(user) => user,
+    None => return,
+  };
+
+  let details_area = match &app.profile_image {
+    Some(image) => {
+      let cell_width = cell_aspect::art_cell_width(app.user_config.behavior.art_aspect_ratio);
+      let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+          Constraint::Length(image.width as u16 * cell_width + 4),
+          Constraint::Min(0),
+        ])
+        .split(inner_area);
+      draw_pixelated_art(f, app, image, chunks[0]);
+      chunks[1]
+    }
+    None => inner_area,
+  };
+
+  let name = user
+    .display_name
+    .clone()
+    .unwrap_or_else(|| user.id.to_string());
+  // Spotify leaves these out without the user-read-private scope
+  let plan = match user.product {
+    Some(SubscriptionLevel::Premium) => "Premium",
+    Some(SubscriptionLevel::Free) => "Free",
+    None => "Unknown",
+  };
+  let followers = user
+    .followers
+    .as_ref()
+    .map(|followers| followers.total.to_string())
+    .unwrap_or_else(|| "Unknown".to_string());
+  let country = user.country.map(<&str>::from).unwrap_or("Unknown");
+
+  let label_style = Style::default().fg(app.user_config.theme.hint);
+  let text_style = Style::default().fg(app.user_config.theme.text);
+  let detail = |label: &'static str, value: String| {
+    Line::from(vec![
+      Span::styled(format!("{:<11}", label), label_style),
+      Span::styled(value, text_style),
+    ])
+  };
+  let lines = vec![
+    Line::from(Span::styled(
+      name,
+      Style::default()
+        .fg(app.user_config.theme.active)
+        .add_modifier(Modifier::BOLD),
+    )),
+    Line::from(""),
+    detail("Plan", plan.to_string()),
+    detail("Followers", followers),
+    detail("Country", country.to_string()),
+  ];
+
+  let details = Paragraph::new(lines)
+    .block(Block::default().borders(Borders::NONE))
+    .wrap(Wrap { trim: true });
+  f.render_widget(details, details_area);
+}
+
+pub fn draw_categories_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  let header = TableHeader {
+    id: TableId::Categories,
+    items: vec![TableHeaderItem {
+      id: ColumnId::Title,
+      text: "Name",
+      width: get_percentage_width(layout_chunk.width, 1.0),
+    }],
+  };
+
+  let items = app
+    .categories
+    .iter()
+    .map(|category| TableItem {
+      id: category.id.clone(),
+      format: vec![category.name.clone()],
+    })
+    .collect::<Vec<TableItem>>();
+
+  draw_table::<CrosstermBackend<std::io::Stdout>>(
+    f,
+    app,
+    layout_chunk,
+    ("Categories", &header),
+    &items,
+    app.categories_index,
+    app.block_highlight_state(ActiveBlock::Categories),
+  )
+}
+
+pub fn draw_category_playlists_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  let title = app
+    .selected_category()
+    .map(|category| category.name.as_str())
+    .unwrap_or("Category");
+  draw_playlist_table(
+    f,
+    app,
+    layout_chunk,
+    title,
+    &app.category_playlists,
+    app.category_playlists_index,
+    ActiveBlock::CategoryPlaylists,
+  );
+}
+
+/// Playlists that aren't the user's, with how many tracks each has
+fn draw_playlist_table(
+  f: &mut Frame,
+  app: &App,
+  layout_chunk: Rect,
+  title: &str,
+  playlists: &[SimplifiedPlaylist],
+  selected_index: usize,
+  block: ActiveBlock,
+) {
+  let header = TableHeader {
+    id: TableId::Playlists,
+    items: vec![
+      TableHeaderItem {
+        id: ColumnId::Title,
+        text: "Name",
+        width: get_percentage_width(layout_chunk.width, 4.0 / 5.0),
+      },
+      TableHeaderItem {
+        text: "Tracks",
+        width: get_percentage_width(layout_chunk.width, 1.0 / 5.0),
+        ..Default::default()
+      },
+    ],
+  };
+
+  let items = playlists
+    .iter()
+    .map(|playlist| TableItem {
+      id: playlist.id.to_string(),
+      format: vec![playlist.name.clone(), playlist.tracks.total.to_string()],
+    })
+    .collect::<Vec<TableItem>>();
+
+  draw_table::<CrosstermBackend<std::io::Stdout>>(
+    f,
+    app,
+    layout_chunk,
+    (title, &header),
+    &items,
+    selected_index,
+    app.block_highlight_state(block),
+  )
+}
+
+pub fn draw_new_releases_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  let header = TableHeader {
+    id: TableId::NewReleases,
+    items: vec![
+      TableHeaderItem {
+        id: ColumnId::Title,
+        text: "Name",
+        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0),
+      },
+      TableHeaderItem {
+        text: "Artists",
+        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0),
+        ..Default::default()
+      },
+      TableHeaderItem {
+        text: "Release Date",
+        width: get_percentage_width(layout_chunk.width, 1.0 / 5.0),
+        ..Default::default()
+      },
+    ],
+  };
+
+  let page = app.new_releases.get_results(None);
+  let items = page
+    .iter()
+    .flat_map(|page| page.items.iter())
+    .map(|album| {
+      let id = album
+        .id
+        .as_ref()
+        .map(|id| id.to_string())
+        .unwrap_or_default();
+      let mut name = String::new();
+      if app.saved_album_ids_set.contains(&id) {
+        name.push_str(&app.user_config.padded_liked_icon());
+      }
+      name.push_str(&album.name);
+      TableItem {
+        id,
+        format: vec![
+          name,
+          create_artist_string(&album.artists),
+          album.release_date.clone().unwrap_or_default(),
+        ],
+      }
+    })
+    .collect::<Vec<TableItem>>();
+
+  draw_table::<CrosstermBackend<std::io::Stdout>>(
+    f,
+    app,
+    layout_chunk,
+    (&search_page_title("New Releases", page), &header),
+    &items,
+    app.new_releases_index,
+    app.block_highlight_state(ActiveBlock::NewReleases),
+  )
+}
+
 pub fn draw_album_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
   let header = TableHeader {
@@ -688,11 +1198,7 @@ pub fn draw_album_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     ],
   };
 
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::AlbumTracks,
-    current_route.hovered_block == ActiveBlock::AlbumTracks,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::AlbumTracks);
 
   let album_ui = match &app.album_table_context {
     AlbumTableContext::Simplified => {
@@ -798,11 +1304,7 @@ pub fn draw_recommendations_table<B>(f: &mut Frame, app: &App, layout_chunk: Rec
     ],
   };
 
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::TrackTable,
-    current_route.hovered_block == ActiveBlock::TrackTable,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::TrackTable);
 
   let items = app
     .track_table
@@ -844,88 +1346,252 @@ pub fn draw_recommendations_table<B>(f: &mut Frame, app: &App, layout_chunk: Rec
 
 pub fn draw_song_table<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
+  let columns = &app.user_config.behavior.track_table_columns;
+  let widths = track_column_widths(columns, layout_chunk.width);
   let header = TableHeader {
     id: TableId::Song,
-    items: vec![
-      TableHeaderItem {
-        id: ColumnId::Liked,
-        text: "",
-        width: 2,
-      },
-      TableHeaderItem {
-        id: ColumnId::Title,
-        text: "Title",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-      },
-      TableHeaderItem {
-        text: "Artist",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Album",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Length",
-        width: get_percentage_width(layout_chunk.width, 0.1),
-        ..Default::default()
-      },
-    ],
+    items: columns
+      .iter()
+      .zip(widths)
+      .map(|(column, width)| TableHeaderItem {
+        id: match column {
+          TrackColumn::Liked => ColumnId::Liked,
+          TrackColumn::Title => ColumnId::Title,
+          _ => ColumnId::None,
+        },
+        text: column.header(),
+        width,
+      })
+      .collect(),
   };
 
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::TrackTable,
-    current_route.hovered_block == ActiveBlock::TrackTable,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::TrackTable);
 
   let items = app
     .track_table
     .tracks
     .iter()
-    .map(|item| TableItem {
-      id: item.id.as_ref().map(|id| id.to_string()).unwrap_or_else(|| "".to_string()),
-      format: vec![
-        "".to_string(),
-        item.name.to_owned(),
-        create_artist_string(&item.artists),
-        item.album.name.to_owned(),
-        millis_to_minutes(item.duration.num_milliseconds() as u128),
-      ],
+    .map(|item| {
+      let id = item
+        .id
+        .as_ref()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "".to_string());
+      let format = columns
+        .iter()
+        .map(|column| match column {
+          // Filled in by draw_table
+          TrackColumn::Liked => "".to_string(),
+          TrackColumn::Title => item.name.to_owned(),
+          TrackColumn::Artist => create_artist_string(&item.artists),
+          TrackColumn::Album => item.album.name.to_owned(),
+          TrackColumn::Added => app
+            .track_table
+            .added_at
+            .get(&id)
+            .map(|added_at| added_at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+          TrackColumn::Popularity => item.popularity.to_string(),
+          TrackColumn::Explicit => if item.explicit { "E" } else { "" }.to_string(),
+          TrackColumn::Length => millis_to_minutes(item.duration.num_milliseconds() as u128),
+        })
+        .collect();
+      TableItem { id, format }
     })
     .collect::<Vec<TableItem>>();
 
+  let chunks = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+    .split(layout_chunk);
+
+  let title = track_table_title(app);
+
   draw_table::<CrosstermBackend<std::io::Stdout>>(
     f,
     app,
-    layout_chunk,
-    ("", &header),
+    chunks[0],
+    (&title, &header),
     &items,
     app.track_table.selected_index,
     highlight_state,
-  )
+  );
+
+  // Summary of the loaded tracks
+  let total_millis = app
+    .track_table
+    .tracks
+    .iter()
+    .map(|track| track.duration.num_milliseconds().max(0) as u128)
+    .sum();
+  let liked_count = items
+    .iter()
+    .filter(|item| app.liked_song_ids_set.contains(item.id.as_str()))
+    .count();
+  let summary = Paragraph::new(track_table_summary(items.len(), total_millis, liked_count))
+    .style(Style::default().fg(app.user_config.theme.inactive))
+    .alignment(Alignment::Right);
+  f.render_widget(summary, chunks[1]);
 }
 
-pub fn draw_basic_view(f: &mut Frame, app: &App) {
-  // If space is negative, do nothing because the widget would not fit
-  if let Some(s) = app.size.height.checked_sub(BASIC_VIEW_HEIGHT) {
-    let space = s / 2;
-    let chunks = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints(
-        [
-          Constraint::Length(space),
-          Constraint::Length(BASIC_VIEW_HEIGHT),
-          Constraint::Length(space),
-        ]
-        .as_ref(),
-      )
-      .split(f.area());
+pub fn draw_basic_view(f: &mut Frame, app: &App) {
+  // If space is negative, do nothing because the widget would not fit
+  let space = match app.size.height.checked_sub(BASIC_VIEW_HEIGHT) {
+    Some(space) => space,
+    None => return,
+  };
+  // Leave a row between the large type and the playbar and one at the top, and keep it off the
+  // edges
+  let width = usize::from(app.size.width.saturating_sub(2));
+  let now_playing = big_now_playing(app, width, usize::from(space.saturating_sub(2)));
+  let mut constraints = vec![Constraint::Length(BASIC_VIEW_HEIGHT)];
+  if !now_playing.is_empty() {
+    constraints.splice(
+      0..0,
+      [
+        Constraint::Length(now_playing.len() as u16),
+        Constraint::Length(1),
+      ],
+    );
+  }
+  let chunks = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints(constraints)
+    .flex(Flex::Center)
+    .split(f.area());
+
+  if let Some((playbar_area, _)) = chunks.split_last() {
+    if !now_playing.is_empty() {
+      let text = Paragraph::new(now_playing).alignment(Alignment::Center);
+      f.render_widget(text, chunks[0]);
+    }
+    draw_playbar::<CrosstermBackend<std::io::Stdout>>(f, app, *playbar_area);
+  }
+}
+
+// The title and artists of what is playing in large type for the basic view, the artists at the
+// font's own size and the title as large as fits in the rest of `height`. Either is plain text
+// when the font can't show it, and nothing is shown when that doesn't fit either.
+fn big_now_playing(app: &App, width: usize, height: usize) -> Vec<Line<'static>> {
+  let item = match app
+    .current_playback_context
+    .as_ref()
+    .and_then(|context| context.item.as_ref())
+  {
+    Some(item) => item,
+    None => return Vec::new(),
+  };
+  let (name, artists) = match item {
+    PlayableItem::Track(track) => (&track.name, create_artist_string(&track.artists)),
+    PlayableItem::Episode(episode) => (&episode.name, String::new()),
+  };
+  let (vibrant_color, _) = match &app.current_album_art {
+    Some(art) => get_album_art_colors(art),
+    None => (Color::Cyan, Color::DarkGray),
+  };
+
+  let artist_rows = if artists.is_empty() {
+    Vec::new()
+  } else {
+    big_text::fit(&artists, width, height / 3, 1).unwrap_or_else(|| vec![artists])
+  };
+  let gap = usize::from(!artist_rows.is_empty());
+  let title_height = height.saturating_sub(artist_rows.len() + gap);
+  let title_rows =
+    big_text::fit(name, width, title_height, 3).unwrap_or_else(|| vec![name.clone()]);
+  if title_rows.len() + gap + artist_rows.len() > height {
+    return Vec::new();
+  }
+
+  let title_style = Style::default()
+    .fg(vibrant_color)
+    .add_modifier(Modifier::BOLD);
+  let artist_style = Style::default().fg(app.user_config.theme.text);
+  let mut lines: Vec<Line> = title_rows
+    .into_iter()
+    .map(|row| Line::styled(row, title_style))
+    .collect();
+  if gap > 0 {
+    lines.push(Line::default());
+  }
+  lines.extend(
+    artist_rows
+      .into_iter()
+      .map(|row| Line::styled(row, artist_style)),
+  );
+  lines
+}
 
-    draw_playbar::<CrosstermBackend<std::io::Stdout>>(f, app, chunks[1]);
+/// What is playing, its progress and the playback keys, for terminals too small for the full
+/// layout
+pub fn draw_mini_layout(f: &mut Frame, app: &App) {
+  let rows = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([Constraint::Length(1); 3].as_ref())
+    .flex(Flex::Center)
+    .horizontal_margin(1)
+    .split(f.area());
+  let theme = app.user_config.theme;
+
+  let playing = app
+    .current_playback_context
+    .as_ref()
+    .and_then(|context| Some((context, context.item.as_ref()?)));
+  match playing {
+    Some((context, item)) => {
+      let (name, artists, duration) = match item {
+        PlayableItem::Track(track) => (
+          &track.name,
+          create_artist_string(&track.artists),
+          track.duration,
+        ),
+        PlayableItem::Episode(episode) => (&episode.name, String::new(), episode.duration),
+      };
+      let icon = if context.is_playing {
+        &app.user_config.behavior.playing_icon
+      } else {
+        &app.user_config.behavior.paused_icon
+      };
+      let mut now_playing = vec![
+        Span::styled(format!("{} ", icon), Style::default().fg(theme.active)),
+        Span::styled(
+          name.as_str(),
+          Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+      ];
+      if !artists.is_empty() {
+        now_playing.push(Span::styled(
+          format!(" · {}", artists),
+          Style::default().fg(theme.inactive),
+        ));
+      }
+      f.render_widget(Paragraph::new(Line::from(now_playing)), rows[0]);
+
+      let duration_ms = duration.num_milliseconds() as u32;
+      let progress_ms = app.seek_ms.unwrap_or(app.song_progress_ms);
+      let ratio = f64::from(get_track_progress_percentage(progress_ms, duration_ms)) / 100.0;
+      let colors = match &app.current_album_art {
+        Some(art) => get_album_art_colors(art),
+        None => (Color::Cyan, Color::DarkGray),
+      };
+      let label = display_track_progress(progress_ms, duration_ms);
+      draw_progress_bar(f, app, rows[1], ratio, &label, colors);
+    }
+    None => {
+      let idle = Paragraph::new("Nothing playing").style(Style::default().fg(theme.inactive));
+      f.render_widget(idle, rows[0]);
+    }
   }
+
+  let keys = &app.user_config.keys;
+  let hint = format!(
+    "{} play/pause  {} next  {} previous  : commands",
+    keys.toggle_playback, keys.next_track, keys.previous_track
+  );
+  let hint = Paragraph::new(hint).style(Style::default().fg(theme.inactive));
+  f.render_widget(hint, rows[2]);
+
+  draw_toast(f, app);
 }
 
 pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
@@ -1002,14 +1668,71 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
         SpotifyRepeatState::Context => "All",
       };
 
-      let title = format!(
-        "{:-7} ({} | Shuffle: {:-3} | Repeat: {:-5} | Volume: {:-2}%)",
+      let volume = current_playback_context.device.volume_percent.unwrap_or(0);
+      let volume_text = if app.muted_volume.is_some() {
+        "Muted".to_string()
+      } else {
+        format!("{:-2}%", volume)
+      };
+      let mut title = format!(
+        "{:-7} ({} | Shuffle: {:-3} | Repeat: {:-5} | Volume: {})",
         play_title,
-        current_playback_context.device.name,
+        device_label(&current_playback_context.device),
         shuffle_text,
         repeat_text,
-        current_playback_context.device.volume_percent.unwrap_or(0)
+        volume_text
       );
+      if let Some(up_next) = &app.up_next {
+        title.push_str(&format!(" • Up next: {}", up_next_label(up_next)));
+      }
+      if let Some(timer) = &app.sleep_timer {
+        let label = match timer.action {
+          SleepAction::Pause => "Sleep",
+          SleepAction::Quit => "Quit",
+        };
+        title.push_str(&format!(
+          " • {} in {}",
+          label,
+          millis_to_minutes(timer.remaining().as_millis())
+        ));
+      }
+      if let Some(AbLoop {
+        start_ms,
+        end_ms: Some(end_ms),
+        ..
+      }) = &app.ab_loop
+      {
+        title.push_str(&format!(
+          " • Loop {}–{}",
+          millis_to_minutes(u128::from(*start_ms)),
+          millis_to_minutes(u128::from(*end_ms))
+        ));
+      }
+      // Free accounts can't control playback, so the buttons below are shown disabled
+      let controls_disabled = app.is_free_tier();
+      if controls_disabled {
+        title.push_str(" • Playback control needs Premium");
+      }
+      let button_color = |color| {
+        if controls_disabled {
+          app.user_config.theme.inactive
+        } else {
+          color
+        }
+      };
+
+      // The border lights up on the beat when the playing track's analysis is loaded
+      let position = app.song_progress_ms as f32 / 1000.0;
+      let border_color = match &app.audio_analysis {
+        Some(analysis)
+          if app.user_config.behavior.beat_accents
+            && current_playback_context.is_playing
+            && util::on_beat(&analysis.beats, position) =>
+        {
+          app.user_config.theme.active
+        }
+        _ => app.user_config.theme.inactive,
+      };
 
       let title_block = Block::default()
         .borders(Borders::ALL)
@@ -1018,7 +1741,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
           &title,
           Style::default().fg(app.user_config.theme.inactive),
         ))
-        .border_style(Style::default().fg(app.user_config.theme.inactive));
+        .border_style(Style::default().fg(border_color));
 
       f.render_widget(title_block, layout_chunk);
 
@@ -1078,7 +1801,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
       // Previous button
       let prev_button = Paragraph::new("⏮")
-        .style(Style::default().fg(app.user_config.theme.playbar_text))
+        .style(Style::default().fg(button_color(app.user_config.theme.playbar_text)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1099,7 +1822,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
         app.user_config.theme.playbar_text
       };
       let play_pause_button = Paragraph::new(play_pause_icon)
-        .style(Style::default().fg(play_pause_color))
+        .style(Style::default().fg(button_color(play_pause_color)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1115,7 +1838,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
       // Next button
       let next_button = Paragraph::new("⏭")
-        .style(Style::default().fg(app.user_config.theme.playbar_text))
+        .style(Style::default().fg(button_color(app.user_config.theme.playbar_text)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1141,7 +1864,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
         app.user_config.theme.inactive
       };
       let shuffle_button = Paragraph::new("🔀")
-        .style(Style::default().fg(shuffle_color))
+        .style(Style::default().fg(button_color(shuffle_color)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1173,7 +1896,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
         app.user_config.theme.inactive
       };
       let repeat_button = Paragraph::new(repeat_icon)
-        .style(Style::default().fg(repeat_color))
+        .style(Style::default().fg(button_color(repeat_color)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1189,7 +1912,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
       // Seek backward button
       let seek_back_button = Paragraph::new("◀◀")
-        .style(Style::default().fg(app.user_config.theme.playbar_text))
+        .style(Style::default().fg(button_color(app.user_config.theme.playbar_text)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1204,7 +1927,7 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 
       // Seek forward button
       let seek_forward_button = Paragraph::new("▶▶")
-        .style(Style::default().fg(app.user_config.theme.playbar_text))
+        .style(Style::default().fg(button_color(app.user_config.theme.playbar_text)))
         .alignment(Alignment::Center)
         .block(
           Block::default()
@@ -1233,19 +1956,6 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       // Calculate progress ratio for the gauge
       let progress_ratio = f64::from(perc) / 100.0;
       
-      // Calculate text color with good contrast against the progress bar
-      let text_color = calculate_text_color_for_progress(vibrant_color, dark_color);
-      
-      let song_progress = Gauge::default()
-        .block(Block::default().borders(Borders::NONE))
-        .gauge_style(Style::default()
-          .fg(vibrant_color)
-          .bg(dark_color))
-        .ratio(progress_ratio)
-        .label(Span::styled(
-          progress_label,
-          Style::default().fg(text_color).add_modifier(Modifier::BOLD),
-        ));
       // Add horizontal margin to the progress bar
       let progress_area = Layout::default()
         .direction(Direction::Horizontal)
@@ -1255,12 +1965,31 @@ pub fn draw_playbar<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
         ].as_ref())
         .split(chunks[1]);
       
-      f.render_widget(song_progress, progress_area[0]);
+      // The bar shows the volume for a moment after it changes
+      if app.is_volume_changing() {
+        draw_progress_bar(
+          f,
+          app,
+          progress_area[0],
+          f64::from(volume.min(100)) / 100.0,
+          &format!("Volume: {}", volume_text),
+          (vibrant_color, dark_color),
+        );
+      } else {
+        draw_progress_bar(
+          f,
+          app,
+          progress_area[0],
+          progress_ratio,
+          &progress_label,
+          (vibrant_color, dark_color),
+        );
+      }
     } else {
       // Clear the playbar area when no track is playing
       let device_text = format!(
         "Connected to: {} - No track playing",
-        current_playback_context.device.name
+        device_label(&current_playback_context.device)
       );
       let empty_block = Block::default()
         .borders(Borders::ALL)
@@ -1294,11 +2023,7 @@ fn draw_home<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     .margin(2)
     .split(layout_chunk);
 
-  let current_route = app.get_current_route();
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::Home,
-    current_route.hovered_block == ActiveBlock::Home,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::Home);
 
   let welcome = Block::default()
     .title(Span::styled(
@@ -1381,11 +2106,16 @@ fn draw_artist_albums<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       })
       .collect::<Vec<String>>();
 
+    let top_tracks_title = format!(
+      "{} - Top Tracks",
+      create_artist_label(app, &artist.artist_id, &artist.artist_name)
+    );
+
     draw_selectable_list(
       f,
       app,
       chunks[0],
-      "Top Tracks",
+      &top_tracks_title,
       &top_tracks,
       get_artist_highlight_state(app, ArtistBlock::TopTracks),
       Some(artist.selected_top_track_index),
@@ -1425,14 +2155,7 @@ fn draw_artist_albums<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     let related_artists = artist
       .related_artists
       .iter()
-      .map(|item| {
-        let mut artist = String::new();
-        if app.followed_artist_ids_set.contains(&item.id.to_string()) {
-          artist.push_str(&app.user_config.padded_liked_icon());
-        }
-        artist.push_str(&item.name.to_owned());
-        artist
-      })
+      .map(|item| create_artist_label(app, &item.id.to_string(), &item.name))
       .collect::<Vec<String>>();
 
     draw_selectable_list(
@@ -1458,7 +2181,9 @@ pub fn draw_device_list(f: &mut Frame, app: &App) {
         "To play tracks, please select a device. ",
         "Use `j/k` or up/down arrow keys to move up and down and <Enter> to select. ",
         "Your choice here will be cached so you can jump straight back in when you next open `spotify-tui`. ",
-        "You can change the playback device at any time by pressing `d`.",
+        "You can change the playback device at any time by pressing `d`. ",
+        "Press `-` or `+` to change the volume of the highlighted device; restricted devices can't be controlled. ",
+        "Press `r` to start or stop spotui's own device, in builds with the streaming feature.",
     ].into_iter().map(|instruction| Line::from(Span::raw(instruction))).collect();
 
   let instructions = Paragraph::new(device_instructions)
@@ -1484,7 +2209,7 @@ pub fn draw_device_list(f: &mut Frame, app: &App) {
         items
           .devices
           .iter()
-          .map(|device| ListItem::new(Span::raw(&device.name)))
+          .map(|device| ListItem::new(Span::raw(device_picker_label(device))))
           .collect()
       }
     }
@@ -1507,6 +2232,14 @@ pub fn draw_device_list(f: &mut Frame, app: &App) {
             "evices",
             Style::default().fg(app.user_config.theme.active),
           ),
+          Span::styled(
+            app
+              .streaming_device
+              .as_ref()
+              .map(|name| format!(" • {} is running here", name))
+              .unwrap_or_default(),
+            Style::default().fg(app.user_config.theme.inactive),
+          ),
         ]))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -1544,12 +2277,8 @@ pub fn draw_album_list<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     ],
   };
 
-  let current_route = app.get_current_route();
 
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::AlbumList,
-    current_route.hovered_block == ActiveBlock::AlbumList,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::AlbumList);
 
   let selected_song_index = app.album_list_index;
 
@@ -1612,12 +2341,8 @@ pub fn draw_show_episodes<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
     ],
   };
 
-  let current_route = app.get_current_route();
 
-  let highlight_state = (
-    current_route.active_block == ActiveBlock::EpisodeTable,
-    current_route.hovered_block == ActiveBlock::EpisodeTable,
-  );
+  let highlight_state = app.block_highlight_state(ActiveBlock::EpisodeTable);
 
   if let Some(episodes) = app.library.show_episodes.get_results(None) {
     let items = episodes
@@ -1722,12 +2447,8 @@ pub fn draw_recently_played_table<B>(f: &mut Frame, app: &App, layout_chunk: Rec
   };
 
   if let Some(recently_played) = &app.recently_played.result {
-    let current_route = app.get_current_route();
 
-    let highlight_state = (
-      current_route.active_block == ActiveBlock::RecentlyPlayed,
-      current_route.hovered_block == ActiveBlock::RecentlyPlayed,
-    );
+    let highlight_state = app.block_highlight_state(ActiveBlock::RecentlyPlayed);
 
     let selected_song_index = app.recently_played.index;
 
@@ -1757,81 +2478,435 @@ pub fn draw_recently_played_table<B>(f: &mut Frame, app: &App, layout_chunk: Rec
   };
 }
 
-fn draw_selectable_list<S>(
-  f: &mut Frame,
-  app: &App,
-  layout_chunk: Rect,
-  title: &str,
-  items: &[S],
-  highlight_state: (bool, bool),
-  selected_index: Option<usize>,
-) where
-  S: std::convert::AsRef<str>,
-{
-  let mut state = ListState::default();
-  state.select(selected_index);
+/// The `:dedupe` review: each later copy of a track next to the position of its first copy
+pub fn draw_duplicates_table(f: &mut Frame, app: &App, layout_chunk: Rect) {
+  let header = TableHeader {
+    id: TableId::Duplicates,
+    items: vec![
+      TableHeaderItem {
+        text: "#",
+        width: 6,
+        ..Default::default()
+      },
+      TableHeaderItem {
+        id: ColumnId::Title,
+        text: "Title",
+        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0) - 6,
+      },
+      TableHeaderItem {
+        text: "Artist",
+        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0),
+        ..Default::default()
+      },
+      TableHeaderItem {
+        text: "First copy",
+        width: get_percentage_width(layout_chunk.width, 1.0 / 5.0),
+        ..Default::default()
+      },
+    ],
+  };
+
+  let review = match &app.playlist_duplicates {
+    Some(review) => review,
+    None => return,
+  };
+  let duplicates = review.duplicates.as_deref().unwrap_or_default();
+  let title = match &review.duplicates {
+    None => format!("Looking for duplicates in {}...", review.playlist_name),
+    Some(duplicates) if duplicates.is_empty() => {
+      format!("No duplicates in {}", review.playlist_name)
+    }
+    Some(duplicates) => format!(
+      "{} duplicates in {} ({} to remove them)",
+      duplicates.len(),
+      review.playlist_name,
+      app.user_config.keys.remove_from_playlist
+    ),
+  };
+
+  // Positions are shown 1-based like in the Spotify apps
+  let items = duplicates
+    .iter()
+    .map(|duplicate| TableItem {
+      id: duplicate.id.clone(),
+      format: vec![
+        (duplicate.position + 1).to_string(),
+        duplicate.name.clone(),
+        duplicate.artists.clone(),
+        format!("#{}", duplicate.original_position + 1),
+      ],
+    })
+    .collect::<Vec<TableItem>>();
+
+  draw_table::<CrosstermBackend<std::io::Stdout>>(
+    f,
+    app,
+    layout_chunk,
+    (&title, &header),
+    &items,
+    review.selected_index,
+    app.block_highlight_state(ActiveBlock::Duplicates),
+  )
+}
+
+fn draw_selectable_list<S>(
+  f: &mut Frame,
+  app: &App,
+  layout_chunk: Rect,
+  title: &str,
+  items: &[S],
+  highlight_state: (bool, bool),
+  selected_index: Option<usize>,
+) where
+  S: std::convert::AsRef<str>,
+{
+  let mut state = ListState::default();
+  state.select(selected_index);
+
+  let lst_items: Vec<ListItem> = items
+    .iter()
+    .map(|i| ListItem::new(Span::raw(i.as_ref())))
+    .collect();
+
+  let title_spans = create_focus_title(title, &app.user_config.theme, highlight_state);
+  let list = List::new(lst_items)
+    .block(
+      Block::default()
+        .title(Line::from(title_spans))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(get_color(highlight_state, app.user_config.theme))
+    )
+    .style(Style::default().fg(app.user_config.theme.text))
+    .highlight_style(
+      get_color(highlight_state, app.user_config.theme).add_modifier(Modifier::BOLD),
+    );
+  f.render_stateful_widget(list, layout_chunk, &mut state);
+}
+
+// Special version for search results without focus letters
+fn draw_search_result_list<S>(
+  f: &mut Frame,
+  app: &App,
+  layout_chunk: Rect,
+  title: &str,
+  items: &[S],
+  highlight_state: (bool, bool),
+  selected_index: Option<usize>,
+) where
+  S: std::convert::AsRef<str>,
+{
+  let mut state = ListState::default();
+  state.select(selected_index);
+
+  let lst_items: Vec<ListItem> = items
+    .iter()
+    .map(|i| ListItem::new(Span::raw(i.as_ref())))
+    .collect();
+
+  // Use plain title without focus letters for search results
+  let list = List::new(lst_items)
+    .block(
+      Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(get_color(highlight_state, app.user_config.theme))
+    )
+    .style(Style::default().fg(app.user_config.theme.text))
+    .highlight_style(
+      get_color(highlight_state, app.user_config.theme).add_modifier(Modifier::BOLD),
+    );
+  f.render_stateful_widget(list, layout_chunk, &mut state);
+}
+
+/// Draw the `:` command line over the bottom row, with any completion candidates above it
+pub fn draw_command_line(f: &mut Frame, app: &App) {
+  if let Some(command_line) = &app.command_line {
+    let bounds = f.area();
+    if bounds.height < 2 {
+      return;
+    }
+    let rect = Rect::new(bounds.x, bounds.y + bounds.height - 1, bounds.width, 1);
+    f.render_widget(Clear, rect);
+    f.render_widget(
+      Paragraph::new(format!(":{}", command_line.input))
+        .style(Style::default().fg(app.user_config.theme.text)),
+      rect,
+    );
+
+    if command_line.completions.len() > 1 {
+      let candidates = command_line
+        .completions
+        .iter()
+        .enumerate()
+        .flat_map(|(i, candidate)| {
+          let style = if Some(i) == command_line.completion_index {
+            Style::default()
+              .fg(app.user_config.theme.selected)
+              .add_modifier(Modifier::BOLD)
+          } else {
+            Style::default().fg(app.user_config.theme.hint)
+          };
+          vec![Span::styled(candidate.clone(), style), Span::raw("  ")]
+        })
+        .collect::<Vec<Span>>();
+      let rect = Rect::new(bounds.x, bounds.y + bounds.height - 2, bounds.width, 1);
+      f.render_widget(Clear, rect);
+      f.render_widget(Paragraph::new(Line::from(candidates)), rect);
+    }
+  }
+}
+
+/// The latest error along the bottom row, with the key that opens the log for the rest
+pub fn draw_error_banner(f: &mut Frame, app: &App) {
+  let summary = match &app.error_banner {
+    Some((summary, _)) => summary,
+    None => return,
+  };
+  if app.get_current_route().active_block == ActiveBlock::LogStream {
+    return;
+  }
+  let bounds = f.area();
+  if bounds.width < 20 || bounds.height < 5 {
+    return;
+  }
+  let row = bounds.y + bounds.height - 1;
+  let rect = Rect::new(bounds.x + 1, row, bounds.width - 2, 1);
+  let hint = format!(" {} for the log ", app.user_config.keys.log_stream);
+  let room = (rect.width as usize).saturating_sub(hint.chars().count() + 2);
+  let summary = if summary.chars().count() > room {
+    let shortened = summary
+      .chars()
+      .take(room.saturating_sub(1))
+      .collect::<String>();
+    format!("{}…", shortened)
+  } else {
+    summary.clone()
+  };
+
+  f.render_widget(Clear, rect);
+  let banner = Paragraph::new(Line::from(vec![
+    Span::styled(
+      format!(" {} ", summary),
+      Style::default()
+        .fg(app.user_config.theme.error_text)
+        .add_modifier(Modifier::BOLD),
+    ),
+    Span::styled(hint, Style::default().fg(app.user_config.theme.hint)),
+  ]));
+  f.render_widget(banner, rect);
+}
+
+/// Render and network numbers in the top right corner, below where toasts show
+pub fn draw_debug_overlay(f: &mut Frame, app: &App) {
+  const WIDTH: u16 = 36;
+  let bounds = f.area();
+  if bounds.width < WIDTH + 2 || bounds.height < 12 {
+    return;
+  }
+  let stats = &app.network_stats;
+  let request = match stats.running() {
+    Some((event, elapsed)) => format!("{} {:.1}s", event, elapsed.as_secs_f64()),
+    None => "none".to_string(),
+  };
+  let poll = match stats.last_poll() {
+    Some(latency) => format!("{}ms", latency.as_millis()),
+    None => "-".to_string(),
+  };
+  let (images, image_bytes) = app.image_memory_usage();
+  let rows = [
+    (
+      "Frame",
+      format!(
+        "{:.1}ms, {} fps",
+        app.frame_stats.frame_time().as_secs_f64() * 1000.0,
+        app.frame_stats.fps()
+      ),
+    ),
+    ("Queue", format!("{} waiting", stats.queue_depth())),
+    ("Request", request),
+    ("Poll", poll),
+    (
+      "Images",
+      format!("{} in {:.1} MB", images, image_bytes as f64 / 1_048_576.0),
+    ),
+  ];
+  let lines = rows
+    .into_iter()
+    .map(|(label, value)| {
+      Line::from(vec![
+        Span::styled(
+          format!("{:<8}", label),
+          Style::default().fg(app.user_config.theme.hint),
+        ),
+        Span::styled(value, Style::default().fg(app.user_config.theme.text)),
+      ])
+    })
+    .collect::<Vec<Line>>();
+
+  let rect = Rect::new(bounds.width - WIDTH - 1, 4, WIDTH, lines.len() as u16 + 2);
+  f.render_widget(Clear, rect);
+  let overlay = Paragraph::new(lines).block(
+    Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .border_style(Style::default().fg(app.user_config.theme.inactive))
+      .title("Debug"),
+  );
+  f.render_widget(overlay, rect);
+}
+
+fn draw_toast(f: &mut Frame, app: &App) {
+  if let Some((message, _)) = &app.toast {
+    let bounds = f.area();
+    if bounds.width < 10 || bounds.height < 5 {
+      return;
+    }
+    // Top right corner, clear of the playbar and any dialog
+    let width = std::cmp::min(message.chars().count() as u16 + 4, bounds.width - 2);
+    let rect = Rect::new(bounds.width - width - 1, 1, width, 3);
+
+    f.render_widget(Clear, rect);
+    let toast = Paragraph::new(message.as_str())
+      .style(Style::default().fg(app.user_config.theme.text))
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(BorderType::Rounded)
+          .border_style(Style::default().fg(app.user_config.theme.hint)),
+      );
+    f.render_widget(toast, rect);
+  }
+}
+
+fn draw_playlist_picker(f: &mut Frame, app: &App) {
+  let picker = match app.playlist_picker.as_ref() {
+    Some(picker) => picker,
+    None => return,
+  };
+  let rect = playlist_picker_rect(f.area());
+  f.render_widget(Clear, rect);
+
+  let chunks = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+    .split(rect);
+
+  let title = match picker.track_uris.len() {
+    1 => "Add track to playlist".to_string(),
+    count => format!("Add {} tracks to playlist", count),
+  };
+  let title_spans = create_focus_title(&title, &app.user_config.theme, (true, true));
+  let filter = Paragraph::new(picker.filter.as_str()).block(
+    Block::default()
+      .borders(Borders::ALL)
+      .border_type(BorderType::Rounded)
+      .title(Line::from(title_spans))
+      .border_style(get_color((true, true), app.user_config.theme)),
+  );
+  f.render_widget(filter, chunks[0]);
+
+  let names = app
+    .playlist_picker_matches()
+    .iter()
+    .map(|playlist| playlist.name.clone())
+    .collect::<Vec<String>>();
+  let selected_index = if names.is_empty() {
+    None
+  } else {
+    Some(picker.selected_index)
+  };
+  draw_selectable_list(
+    f,
+    app,
+    chunks[1],
+    "Playlists",
+    &names,
+    (true, false),
+    selected_index,
+  );
+}
+
+fn draw_open_in_menu(f: &mut Frame, app: &App) {
+  let menu = match app.open_in_menu.as_ref() {
+    Some(menu) => menu,
+    None => return,
+  };
+  let bounds = f.area();
+  let width = bounds.width.saturating_sub(4).min(50);
+  let height = (menu.targets.len() as u16 + 2).min(bounds.height);
+  let rect = Rect::new(
+    bounds.x + (bounds.width - width) / 2,
+    bounds.y + bounds.height / 4,
+    width,
+    height,
+  );
+  f.render_widget(Clear, rect);
 
-  let lst_items: Vec<ListItem> = items
+  let title = if menu.in_app {
+    "Open in Spotify (Tab: browser)"
+  } else {
+    "Open in browser (Tab: Spotify)"
+  };
+  let labels = menu
+    .targets
     .iter()
-    .map(|i| ListItem::new(Span::raw(i.as_ref())))
-    .collect();
-
-  let title_spans = create_focus_title(title, &app.user_config.theme, highlight_state);
-  let list = List::new(lst_items)
-    .block(
-      Block::default()
-        .title(Line::from(title_spans))
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(get_color(highlight_state, app.user_config.theme))
-    )
-    .style(Style::default().fg(app.user_config.theme.text))
-    .highlight_style(
-      get_color(highlight_state, app.user_config.theme).add_modifier(Modifier::BOLD),
-    );
-  f.render_stateful_widget(list, layout_chunk, &mut state);
+    .map(|target| target.label.as_str())
+    .collect::<Vec<&str>>();
+  draw_selectable_list(
+    f,
+    app,
+    rect,
+    title,
+    &labels,
+    (true, true),
+    Some(menu.selected_index),
+  );
 }
 
-// Special version for search results without focus letters
-fn draw_search_result_list<S>(
-  f: &mut Frame,
-  app: &App,
-  layout_chunk: Rect,
-  title: &str,
-  items: &[S],
-  highlight_state: (bool, bool),
-  selected_index: Option<usize>,
-) where
-  S: std::convert::AsRef<str>,
-{
-  let mut state = ListState::default();
-  state.select(selected_index);
+fn draw_share_menu(f: &mut Frame, app: &App) {
+  let menu = match app.share_menu.as_ref() {
+    Some(menu) => menu,
+    None => return,
+  };
+  let bounds = f.area();
+  let width = bounds.width.saturating_sub(4).min(70);
+  let height = (ShareFormat::ALL.len() as u16 + 2).min(bounds.height);
+  let rect = Rect::new(
+    bounds.x + (bounds.width - width) / 2,
+    bounds.y + bounds.height / 4,
+    width,
+    height,
+  );
+  f.render_widget(Clear, rect);
 
-  let lst_items: Vec<ListItem> = items
+  // Preview what each format copies
+  let labels = ShareFormat::ALL
     .iter()
-    .map(|i| ListItem::new(Span::raw(i.as_ref())))
-    .collect();
-
-  // Use plain title without focus letters for search results
-  let list = List::new(lst_items)
-    .block(
-      Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(get_color(highlight_state, app.user_config.theme))
-    )
-    .style(Style::default().fg(app.user_config.theme.text))
-    .highlight_style(
-      get_color(highlight_state, app.user_config.theme).add_modifier(Modifier::BOLD),
-    );
-  f.render_stateful_widget(list, layout_chunk, &mut state);
+    .map(|format| format!("{}: {}", format.label(), format.format(&menu.item)))
+    .collect::<Vec<String>>();
+  draw_selectable_list(
+    f,
+    app,
+    rect,
+    &format!("Share {}", menu.item.name),
+    &labels,
+    (true, true),
+    Some(menu.selected_index),
+  );
 }
 
 fn draw_dialog<B>(f: &mut Frame, app: &App)
 {
-  if let ActiveBlock::Dialog(_) = app.get_current_route().active_block {
-    if let Some(playlist) = app.dialog.as_ref() {
+  if let ActiveBlock::Dialog(context) = app.get_current_route().active_block {
+    if context == DialogContext::AddToPlaylist {
+      draw_playlist_picker(f, app);
+    } else if context == DialogContext::OpenIn {
+      draw_open_in_menu(f, app);
+    } else if context == DialogContext::Share {
+      draw_share_menu(f, app);
+    } else if let Some(dialog) = app.dialog.as_ref() {
       let bounds = f.area();
       // maybe do this better
       let width = std::cmp::min(bounds.width - 2, 45);
@@ -1856,15 +2931,52 @@ fn draw_dialog<B>(f: &mut Frame, app: &App)
         .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
         .split(rect);
 
-      // suggestion: possibly put this as part of
-      // app.dialog, but would have to introduce lifetime
+      let (prompt, suffix, ok_label, cancel_label) = match dialog {
+        Dialog::ResumeEpisode { position_ms, .. } => (
+          "Resume where you left off in: ".to_string(),
+          format!("at {}?", millis_to_minutes(u128::from(*position_ms))),
+          "Resume",
+          "Start over",
+        ),
+        Dialog::UnfollowPlaylist { .. } => (
+          "Are you sure you want to delete the playlist: ".to_string(),
+          "?".to_string(),
+          "Ok",
+          "Cancel",
+        ),
+        Dialog::UnfollowArtist { .. } => (
+          "Stop following: ".to_string(),
+          "?".to_string(),
+          "Unfollow",
+          "Cancel",
+        ),
+        Dialog::RemoveSavedAlbum { .. } => (
+          "Remove from your library: ".to_string(),
+          "?".to_string(),
+          "Remove",
+          "Cancel",
+        ),
+        Dialog::RemoveFromPlaylist { .. } => (
+          "Remove from this playlist: ".to_string(),
+          "?".to_string(),
+          "Remove",
+          "Cancel",
+        ),
+        Dialog::RemoveDuplicates { .. } => (
+          "Remove ".to_string(),
+          "keeping the first copy of each track?".to_string(),
+          "Remove",
+          "Cancel",
+        ),
+      };
+
       let text = vec![
-        Line::from(Span::raw("Are you sure you want to delete the playlist: ")),
+        Line::from(Span::raw(prompt)),
         Line::from(Span::styled(
-          playlist.as_str(),
+          dialog.subject(),
           Style::default().add_modifier(Modifier::BOLD),
         )),
-        Line::from(Span::raw("?")),
+        Line::from(Span::raw(suffix)),
       ];
 
       let text = Paragraph::new(text)
@@ -1879,7 +2991,7 @@ fn draw_dialog<B>(f: &mut Frame, app: &App)
         .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
         .split(vchunks[1]);
 
-      let ok_text = Span::raw("Ok");
+      let ok_text = Span::raw(ok_label);
       let ok = Paragraph::new(ok_text)
         .style(Style::default().fg(if app.confirm {
           app.user_config.theme.hovered
@@ -1890,7 +3002,7 @@ fn draw_dialog<B>(f: &mut Frame, app: &App)
 
       f.render_widget(ok, hchunks[0]);
 
-      let cancel_text = Span::raw("Cancel");
+      let cancel_text = Span::raw(cancel_label);
       let cancel = Paragraph::new(cancel_text)
         .style(Style::default().fg(if app.confirm {
           app.user_config.theme.inactive
@@ -1939,6 +3051,12 @@ fn draw_table<B>(
     .and_then(|height| selected_index.checked_sub(height as usize))
     .unwrap_or(0);
 
+  // Rows marked in the track table's visual mode
+  let marked_range = match header.id {
+    TableId::Song => app.track_table.visual_range(),
+    _ => None,
+  };
+
   let rows = items.iter().skip(offset).enumerate().map(|(i, item)| {
     let mut formatted_row = item.format.clone();
     let mut style = Style::default().fg(app.user_config.theme.text); // default styling
@@ -1984,9 +3102,22 @@ fn draw_table<B>(
       _ => {}
     }
 
+    if marked_range
+      .as_ref()
+      .map(|range| range.contains(&(i + offset)))
+      .unwrap_or(false)
+    {
+      style = Style::default()
+        .fg(app.user_config.theme.selected)
+        .add_modifier(Modifier::REVERSED);
+    }
+
     // Next check if the item is under selection.
     if Some(i) == selected_index.checked_sub(offset) {
       style = selected_style;
+      if marked_range.is_some() {
+        style = style.add_modifier(Modifier::REVERSED);
+      }
     }
 
     // Return row styled data
@@ -2021,18 +3152,24 @@ fn draw_table<B>(
 
 pub fn draw_log_stream<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
 {
-  let is_active = app.get_current_route().active_block == ActiveBlock::LogStream;
-  
-  let log_items = if app.log_messages.is_empty() {
+  let is_active = app.is_component_focused(&ComponentId::LogStream);
+  let shown_messages = app.shown_log_messages();
+
+  let log_items = if shown_messages.is_empty() {
+    let placeholder = if app.log_messages.is_empty() {
+      "No log messages yet"
+    } else {
+      "No log messages match"
+    };
     vec![ListItem::new(Span::styled(
-      "No log messages yet",
+      placeholder,
       Style::default().fg(app.user_config.theme.inactive),
     ))]
   } else {
     // Calculate visible range based on scroll offset and chunk height
     let visible_height = layout_chunk.height.saturating_sub(2) as usize; // Account for borders
-    let total_messages = app.log_messages.len();
-    
+    let total_messages = shown_messages.len();
+
     // When not active, show last messages (original behavior)
     // When active, use scroll offset for navigation
     let (start_index, end_index) = if is_active {
@@ -2048,38 +3185,30 @@ pub fn draw_log_stream<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
       };
       (start, total_messages)
     };
-    
-    app.log_messages[start_index..end_index]
+
+    shown_messages[start_index..end_index]
       .iter()
       .enumerate()
       .flat_map(|(i, message)| {
         let actual_index = start_index + i;
-        // Check if this is an error message and style accordingly
-        let is_error = message.contains("] ERROR:");
-        
-        let style = if is_active && actual_index == app.log_stream_selected_index {
-          if is_error {
-            Style::default()
-              .bg(app.user_config.theme.hovered)
-              .fg(Color::Red)
-              .add_modifier(Modifier::BOLD)
-          } else {
-            Style::default()
-              .bg(app.user_config.theme.hovered)
-              .fg(app.user_config.theme.text)
-          }
-        } else if is_error {
-          Style::default()
-            .fg(Color::Red)
-            .add_modifier(Modifier::BOLD)
-        } else {
-          Style::default().fg(app.user_config.theme.text)
+        let mut style = match message.level {
+          LogLevel::Error => Style::default()
+            .fg(app.user_config.theme.error_text)
+            .add_modifier(Modifier::BOLD),
+          LogLevel::Warn => Style::default().fg(app.user_config.theme.hint),
+          LogLevel::Info => Style::default().fg(app.user_config.theme.text),
+          LogLevel::Debug => Style::default().fg(app.user_config.theme.inactive),
         };
-        
+        if is_active && actual_index == app.log_stream_selected_index {
+          style = style.bg(app.user_config.theme.hovered);
+        }
+
         // Split the message by newlines and create a ListItem for each line
-        message.lines().map(move |line| {
-          ListItem::new(Span::styled(line.to_string(), style))
-        }).collect::<Vec<_>>()
+        message
+          .text
+          .lines()
+          .map(move |line| ListItem::new(Span::styled(line.to_string(), style)))
+          .collect::<Vec<_>>()
       })
       .collect()
   };
@@ -2099,7 +3228,13 @@ pub fn draw_log_stream<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
           .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
       ),
       Span::styled(
-        format!("og Stream [{}/{}]", app.log_stream_selected_index + 1, app.log_messages.len()),
+        format!(
+          "og Stream [{}/{}] {}+{}",
+          app.log_stream_selected_index + 1,
+          shown_messages.len(),
+          app.log_stream_level.label(),
+          log_stream_search_title(app)
+        ),
         Style::default().fg(app.user_config.theme.header),
       ),
     ])
@@ -2131,17 +3266,32 @@ pub fn draw_log_stream<B>(f: &mut Frame, app: &App, layout_chunk: Rect)
   f.render_widget(log_list, layout_chunk);
 }
 
+// The search in the log stream's title, with a cursor while it is typed
+fn log_stream_search_title(app: &App) -> String {
+  if app.log_stream_search_input {
+    format!(" /{}▏", app.log_stream_search)
+  } else if !app.log_stream_search.is_empty() {
+    format!(" /{}", app.log_stream_search)
+  } else {
+    String::new()
+  }
+}
+
 pub fn draw_log_stream_full_screen(f: &mut Frame, app: &App) {
   let chunks = Layout::default()
     .direction(Direction::Vertical)
-    .constraints([Constraint::Length(3), Constraint::Min(10)].as_ref())
+    .constraints([Constraint::Length(5), Constraint::Min(10)].as_ref())
     .margin(2)
     .split(f.area());
 
   let instructions: Vec<Line> = vec![
     "Use j/k or ↑/↓ to navigate, Page Up/Down for faster scrolling",
     "Press 'g' for top, 'G' for bottom, Esc to go back",
-  ].into_iter().map(|instruction| Line::from(Span::raw(instruction))).collect();
+    "Press '/' to search, 'f' to change the level shown, 'y' to copy the selected line",
+  ]
+  .into_iter()
+  .map(|instruction| Line::from(Span::raw(instruction)))
+  .collect();
 
   let help_text = Paragraph::new(instructions)
     .style(Style::default().fg(app.user_config.theme.inactive))
@@ -2237,6 +3387,13 @@ fn blend_colors(color1: Color, color2: Color, factor: f32) -> Color {
 
 /// Extract vibrant and dark colors from album art
 fn get_album_art_colors(art: &crate::album_art::PixelatedAlbumArt) -> (Color, Color) {
+  let (vibrant_color, darkest_color) = pick_album_art_colors(art);
+  // Ensure good contrast between foreground and background colors
+  ensure_color_contrast(vibrant_color, darkest_color)
+}
+
+/// The most vibrant and the darkest color of the art, before any contrast adjustment
+fn pick_album_art_colors(art: &crate::album_art::PixelatedAlbumArt) -> (Color, Color) {
   let mut darkest_color = art.pixels[0][0].to_ratatui_color();
   let mut min_brightness = u32::MAX;
   let mut vibrant_color = art.pixels[0][0].to_ratatui_color();
@@ -2274,13 +3431,116 @@ fn get_album_art_colors(art: &crate::album_art::PixelatedAlbumArt) -> (Color, Co
       }
     }
   }
-  
-  // Ensure good contrast between foreground and background colors
-  let (vibrant_color, darkest_color) = ensure_color_contrast(vibrant_color, darkest_color);
-  
+
   (vibrant_color, darkest_color)
 }
 
+/// WCAG contrast ratio of two colors, from 1 (none) to 21 (black on white)
+fn contrast_ratio(a: Color, b: Color) -> f32 {
+  let luminance = |color| match color {
+    Color::Rgb(r, g, b) => (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.0,
+    _ => 0.5,
+  };
+  let (a, b) = (luminance(a), luminance(b));
+  (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+fn color_hex(color: Color) -> String {
+  match color {
+    Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+    color => format!("{:?}", color),
+  }
+}
+
+/// The `:colors` debug view: the colors picked from the album art next to the art itself, what
+/// the contrast check turned them into and how the terminal ends up showing them
+pub fn draw_color_story(f: &mut Frame, app: &App) {
+  let art = match &app.current_album_art {
+    Some(art) => art,
+    None => {
+      let message = Paragraph::new("No album art loaded. Play something and try again.")
+        .style(Style::default().fg(app.user_config.theme.inactive))
+        .block(
+          Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Color Story"),
+        );
+      f.render_widget(message, f.area());
+      return;
+    }
+  };
+
+  let (vibrant, dark) = pick_album_art_colors(art);
+  let (played, unplayed) = ensure_color_contrast(vibrant, dark);
+  let support = app.user_config.behavior.color_support;
+
+  let chunks = Layout::default()
+    .direction(Direction::Horizontal)
+    .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+    .margin(1)
+    .split(f.area());
+  draw_album_art_dynamic(f, app, chunks[0]);
+
+  let swatch = |name: &str, color: Color| {
+    let shown = support.quantize(color);
+    Line::from(vec![
+      Span::styled(
+        format!("{:<12}", name),
+        Style::default().fg(app.user_config.theme.text),
+      ),
+      Span::styled("██████", Style::default().fg(color)),
+      Span::raw(format!("  {}", color_hex(color))),
+      Span::styled(
+        format!("  shown as {}", color_hex(shown)),
+        Style::default().fg(app.user_config.theme.inactive),
+      ),
+    ])
+  };
+  let lines = vec![
+    Line::from(Span::styled(
+      "Picked from the art",
+      Style::default()
+        .fg(app.user_config.theme.header)
+        .add_modifier(Modifier::BOLD),
+    )),
+    swatch("Vibrant", vibrant),
+    swatch("Dark", dark),
+    Line::from(format!("Contrast {:.2}:1", contrast_ratio(vibrant, dark))),
+    Line::from(""),
+    Line::from(Span::styled(
+      "After the contrast check (3:1 minimum)",
+      Style::default()
+        .fg(app.user_config.theme.header)
+        .add_modifier(Modifier::BOLD),
+    )),
+    swatch("Played", played),
+    swatch("Unplayed", unplayed),
+    Line::from(format!(
+      "Contrast {:.2}:1",
+      contrast_ratio(played, unplayed)
+    )),
+    Line::from(""),
+    Line::from(format!("Color support: {:?}", support)),
+  ];
+
+  let block = Block::default()
+    .borders(Borders::ALL)
+    .border_type(BorderType::Rounded)
+    .title("Color Story")
+    .border_style(Style::default().fg(app.user_config.theme.inactive));
+  let inner = block.inner(chunks[1]);
+  f.render_widget(block, chunks[1]);
+
+  let rows = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+    .margin(1)
+    .split(inner);
+  f.render_widget(Paragraph::new(lines), rows[0]);
+  draw_progress_bar(f, app, rows[1], 0.4, "Preview", (played, unplayed));
+}
+
 /// Ensure sufficient contrast between two colors for progress bar visibility
 fn ensure_color_contrast(fg: Color, bg: Color) -> (Color, Color) {
   // Get RGB values for both colors
@@ -2329,6 +3589,73 @@ fn ensure_color_contrast(fg: Color, bg: Color) -> (Color, Color) {
 }
 
 /// Calculate optimal text color for progress bar that contrasts with both filled and unfilled portions
+/// A progress bar in the configured `progress_bar_style` with `label` in the middle. `colors` are
+/// the played and unplayed colors of the solid style.
+fn draw_progress_bar(
+  f: &mut Frame,
+  app: &App,
+  area: Rect,
+  ratio: f64,
+  label: &str,
+  (played_color, unplayed_color): (Color, Color),
+) {
+  match app.user_config.behavior.progress_bar_style {
+    ProgressBarStyle::Solid => {
+      // Calculate text color with good contrast against the progress bar
+      let text_color = calculate_text_color_for_progress(played_color, unplayed_color);
+      let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::NONE))
+        .gauge_style(Style::default().fg(played_color).bg(unplayed_color))
+        .ratio(ratio)
+        .label(Span::styled(
+          label,
+          Style::default().fg(text_color).add_modifier(Modifier::BOLD),
+        ));
+      f.render_widget(gauge, area);
+    }
+    ProgressBarStyle::Pattern => {
+      let width = area.width as usize;
+      let (played, unplayed) = pattern_bar(width, ratio);
+      let played_style = Style::default().fg(played_color);
+      let unplayed_style = Style::default().fg(app.user_config.theme.inactive);
+      let cells = played
+        .chars()
+        .chain(unplayed.chars())
+        .collect::<Vec<char>>();
+      let played_cells = played.chars().count();
+      let bar = |from: usize, to: usize| {
+        let split = played_cells.clamp(from, to);
+        vec![
+          Span::styled(cells[from..split].iter().collect::<String>(), played_style),
+          Span::styled(cells[split..to].iter().collect::<String>(), unplayed_style),
+        ]
+      };
+
+      // The label goes on the middle row on its own, never on top of the pattern
+      let label = label.chars().take(width).collect::<String>();
+      let label_width = label.chars().count();
+      let label_start = (width - label_width) / 2;
+      let rows = (0..area.height)
+        .map(|row| {
+          if row != area.height / 2 {
+            return Line::from(bar(0, width));
+          }
+          let mut spans = bar(0, label_start);
+          spans.push(Span::styled(
+            label.clone(),
+            Style::default()
+              .fg(app.user_config.theme.text)
+              .add_modifier(Modifier::BOLD),
+          ));
+          spans.extend(bar(label_start + label_width, width));
+          Line::from(spans)
+        })
+        .collect::<Vec<Line>>();
+      f.render_widget(Paragraph::new(rows), area);
+    }
+  }
+}
+
 fn calculate_text_color_for_progress(fg_color: Color, bg_color: Color) -> Color {
   // Get luminance of both colors
   let fg_lum = match fg_color {
@@ -2366,38 +3693,48 @@ fn draw_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) {
     
     let inner_area = block.inner(layout_chunk);
     f.render_widget(block, layout_chunk);
-    
-    // Convert pixelated art to colored text
-    let lines = crate::album_art::render_pixelated_art(art);
-    
-    // Calculate centering offsets
-    let y_offset = inner_area.height.saturating_sub(lines.len() as u16) / 2;
-    let x_offset = inner_area.width.saturating_sub(art.width as u16) / 2;
-    
-    // Render each line of pixels
-    for (y, line) in lines.iter().enumerate() {
-      let y_pos = inner_area.y + y_offset + y as u16;
-      if y_pos >= inner_area.y + inner_area.height {
+    draw_pixelated_art(f, app, art, inner_area);
+  }
+}
+
+/// Pixelated art as colored blocks, centered in `area`
+fn draw_pixelated_art(
+  f: &mut Frame,
+  app: &App,
+  art: &crate::album_art::PixelatedAlbumArt,
+  area: Rect,
+) {
+  // Convert pixelated art to colored text
+  let lines = crate::album_art::render_pixelated_art(art);
+  let cell_width = cell_aspect::art_cell_width(app.user_config.behavior.art_aspect_ratio);
+
+  // Calculate centering offsets
+  let y_offset = area.height.saturating_sub(lines.len() as u16) / 2;
+  let x_offset = area.width.saturating_sub(art.width as u16 * cell_width) / 2;
+
+  // Render each line of pixels
+  for (y, line) in lines.iter().enumerate() {
+    let y_pos = area.y + y_offset + y as u16;
+    if y_pos >= area.y + area.height {
+      break;
+    }
+
+    for (x, (ch, color)) in line.iter().enumerate() {
+      let x_pos = area.x + x_offset + x as u16 * cell_width;
+      if x_pos + cell_width > area.x + area.width {
         break;
       }
-      
-      for (x, (ch, color)) in line.iter().enumerate() {
-        let x_pos = inner_area.x + x_offset + x as u16;
-        if x_pos >= inner_area.x + inner_area.width {
-          break;
-        }
-        
-        // Render each pixel as a colored block
-        let pixel = Span::styled(ch, Style::default().fg(*color));
-        let paragraph = Paragraph::new(pixel);
-        let pixel_area = Rect {
-          x: x_pos,
-          y: y_pos,
-          width: 1,
-          height: 1,
-        };
-        f.render_widget(paragraph, pixel_area);
-      }
+
+      // Render each pixel as a colored block
+      let pixel = Span::styled(ch.repeat(cell_width as usize), Style::default().fg(*color));
+      let paragraph = Paragraph::new(pixel);
+      let pixel_area = Rect {
+        x: x_pos,
+        y: y_pos,
+        width: cell_width,
+        height: 1,
+      };
+      f.render_widget(paragraph, pixel_area);
     }
   }
 }
@@ -2416,14 +3753,16 @@ fn draw_album_art_dynamic(f: &mut Frame, app: &App, layout_chunk: Rect) {
     
     // Calculate the maximum size that maintains square aspect ratio
     // For the playbar, we want to use all available height
+    let cell_width = cell_aspect::art_cell_width(app.user_config.behavior.art_aspect_ratio);
+    let (filled_pixel, _) = cell_aspect::art_pixel(cell_width);
     let available_height = inner_area.height;
-    let available_width = inner_area.width / 2; // Divide by 2 for double-width chars
+    let available_width = inner_area.width / cell_width;
     
     // Use the full height available, constrained by width for square aspect
     let display_size = available_height.min(available_width);
     
     // Center horizontally only, align to top to fill vertical space
-    let x_offset = (inner_area.width.saturating_sub(display_size * 2)) / 2;
+    let x_offset = (inner_area.width.saturating_sub(display_size * cell_width)) / 2;
     let y_offset = 0; // No vertical offset - fill from top to bottom
     
     // Scale factor from source to display
@@ -2444,8 +3783,8 @@ fn draw_album_art_dynamic(f: &mut Frame, app: &App, layout_chunk: Rect) {
       }
       
       for dx in 0..display_size {
-        let x_pos = inner_area.x + x_offset + (dx * 2); // Double width for square appearance
-        if x_pos + 1 >= inner_area.x + inner_area.width {
+        let x_pos = inner_area.x + x_offset + (dx * cell_width);
+        if x_pos + cell_width > inner_area.x + inner_area.width {
           break;
         }
         
@@ -2458,13 +3797,12 @@ fn draw_album_art_dynamic(f: &mut Frame, app: &App, layout_chunk: Rect) {
         let pixel = &art.pixels[src_y][src_x];
         let color = pixel.to_ratatui_color();
         
-        // Use double-width block for square appearance
-        let pixel_span = Span::styled("██", Style::default().fg(color));
+        let pixel_span = Span::styled(filled_pixel, Style::default().fg(color));
         let paragraph = Paragraph::new(pixel_span);
         let pixel_area = Rect {
           x: x_pos,
           y: y_pos,
-          width: 2,
+          width: cell_width,
           height: 1,
         };
         f.render_widget(paragraph, pixel_area);
@@ -2492,6 +3830,7 @@ pub fn draw_idle_mode(f: &mut Frame, app: &App) {
     match app.idle_animation {
       crate::app::IdleAnimation::SpinningRecord => draw_fullscreen_album_art(f, app, chunks[0]),
       crate::app::IdleAnimation::CoinFlip => draw_coin_flip_album_art(f, app, chunks[0]),
+      crate::app::IdleAnimation::Split => draw_split_idle(f, app, chunks[0]),
     }
   } else {
     (Color::Cyan, Color::DarkGray)
@@ -2532,25 +3871,123 @@ pub fn draw_idle_mode(f: &mut Frame, app: &App) {
       // Use a single 3-line tall progress bar that spans the full width
       let progress_area = chunks[1];
       
-      // Calculate text color with good contrast against the progress bar
-      // We need to consider both filled and unfilled portions
-      let text_color = calculate_text_color_for_progress(vibrant_color, dark_color);
-      
-      let progress_bar = Gauge::default()
-        .block(Block::default().borders(Borders::NONE))
-        .gauge_style(Style::default()
-          .fg(vibrant_color)
-          .bg(lighten_color(dark_color, 1.3)))
-        .ratio(progress_ratio)
-        .label(Span::styled(
-          track_info,
-          Style::default().fg(text_color).add_modifier(Modifier::BOLD),
-        ));
-      f.render_widget(progress_bar, progress_area);
+      draw_progress_bar(
+        f,
+        app,
+        progress_area,
+        progress_ratio,
+        &track_info,
+        (vibrant_color, lighten_color(dark_color, 1.3)),
+      );
     }
   }
 }
 
+/// Album art on the left half, the playing track in large type on the right. The text scrolls
+/// with the song's progress when it doesn't fit.
+fn draw_split_idle(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Color, Color) {
+  let halves = Layout::default()
+    .direction(Direction::Horizontal)
+    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+    .split(layout_chunk);
+
+  let (vibrant_color, dark_color) = draw_fullscreen_album_art(f, app, halves[0]);
+  let background = darken_color(dark_color, 0.5);
+  f.render_widget(
+    Block::default().style(Style::default().bg(background)),
+    halves[1],
+  );
+
+  let (lines, progress_ratio) = match app
+    .current_playback_context
+    .as_ref()
+    .and_then(|context| context.item.as_ref().map(|item| (context, item)))
+  {
+    Some((context, item)) => {
+      let (title, subtitle, duration_ms) = match item {
+        PlayableItem::Track(track) => (
+          track.name.clone(),
+          format!(
+            "{}\n{}",
+            create_artist_string(&track.artists),
+            track.album.name
+          ),
+          track.duration.num_milliseconds(),
+        ),
+        PlayableItem::Episode(episode) => (
+          episode.name.clone(),
+          episode.release_date.clone(),
+          episode.duration.num_milliseconds(),
+        ),
+      };
+      let progress_ms = context
+        .progress
+        .map(|progress| progress.num_milliseconds())
+        .unwrap_or(0);
+      let ratio = if duration_ms > 0 {
+        (progress_ms as f64 / duration_ms as f64).clamp(0.0, 1.0)
+      } else {
+        0.0
+      };
+
+      // Spaced out capitals stand in for a larger font
+      let large_title = title
+        .to_uppercase()
+        .chars()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>()
+        .join(" ");
+      let mut lines = vec![Line::from(Span::styled(
+        large_title,
+        Style::default()
+          .fg(vibrant_color)
+          .add_modifier(Modifier::BOLD),
+      ))];
+      lines.push(Line::from(""));
+      lines.extend(subtitle.lines().map(|line| {
+        Line::from(Span::styled(
+          line.to_string(),
+          Style::default().fg(lighten_color(dark_color, 2.5)),
+        ))
+      }));
+      (lines, ratio)
+    }
+    None => (vec![Line::from("Nothing playing")], 0.0),
+  };
+
+  let text_area = Rect {
+    x: halves[1].x + 2,
+    y: halves[1].y + 1,
+    width: halves[1].width.saturating_sub(4),
+    height: halves[1].height.saturating_sub(2),
+  };
+  // Rough wrapped height, enough to decide how far to scroll
+  let text_height: u16 = lines
+    .iter()
+    .map(|line| (line.width() as u16 / text_area.width.max(1)) + 1)
+    .sum();
+  let overflow = text_height.saturating_sub(text_area.height);
+  let (scroll, top_padding) = if overflow > 0 {
+    ((f64::from(overflow) * progress_ratio) as u16, 0)
+  } else {
+    (0, (text_area.height - text_height) / 2)
+  };
+  let text = Paragraph::new(lines)
+    .wrap(Wrap { trim: true })
+    .alignment(Alignment::Center)
+    .scroll((scroll, 0));
+  f.render_widget(
+    text,
+    Rect {
+      y: text_area.y + top_padding,
+      height: text_area.height - top_padding,
+      ..text_area
+    },
+  );
+
+  (vibrant_color, dark_color)
+}
+
 /// Draw fullscreen album art that fills the available space
 fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Color, Color) {
   if let Some(art) = &app.current_album_art {
@@ -2566,10 +4003,12 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
     f.render_widget(background, layout_chunk);
     
     // Calculate the maximum size we can display
-    // Account for double-width characters (2:1 aspect ratio)
+    // Each art pixel takes `cell_width` columns so it comes out square for the font
     // Reduce size to make room for shadow and spacing from playbar
+    let cell_width = cell_aspect::art_cell_width(app.user_config.behavior.art_aspect_ratio);
+    let (filled_pixel, empty_pixel) = cell_aspect::art_pixel(cell_width);
     let shadow_space = 4; // Space needed for shadow (2 pixels) + gap from playbar (2 pixels)
-    let available_width = (layout_chunk.width / 2).saturating_sub(shadow_space);  // Divide by 2 for double-width chars
+    let available_width = (layout_chunk.width / cell_width).saturating_sub(shadow_space);
     let available_height = layout_chunk.height.saturating_sub(shadow_space);
     
     // Determine the size to use (maintain square aspect ratio)
@@ -2581,7 +4020,7 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
     let scale_factor = display_size as f32 / art.width as f32;
     
     // Center the art in the available space (accounting for shadow)
-    let total_width = (display_size as u16 + 2) * 2; // +2 for shadow offset
+    let total_width = (display_size as u16 + 2) * cell_width; // +2 for shadow offset
     let total_height = display_size as u16 + 2; // +2 for shadow offset
     let x_offset = (layout_chunk.width.saturating_sub(total_width)) / 2;
     let y_offset = (layout_chunk.height.saturating_sub(total_height)) / 2;
@@ -2665,10 +4104,10 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
             color = Color::Red; // Red mark for visibility
           }
           
-          line_spans.push(Span::styled("██", Style::default().fg(color)));
+          line_spans.push(Span::styled(filled_pixel, Style::default().fg(color)));
         } else {
           // Outside the circle - transparent
-          line_spans.push(Span::raw("  "));
+          line_spans.push(Span::raw(empty_pixel));
         }
       }
       
@@ -2685,9 +4124,9 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
         let distance = (dx * dx + dy * dy).sqrt();
         
         if distance <= radius {
-          shadow_line.push_str("██");
+          shadow_line.push_str(filled_pixel);
         } else {
-          shadow_line.push_str("  ");
+          shadow_line.push_str(empty_pixel);
         }
       }
       shadow_lines.push(Line::from(Span::styled(shadow_line, Style::default().fg(shadow_color))));
@@ -2698,7 +4137,7 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
     let shadow_area = Rect {
       x: layout_chunk.x + inset_x_offset + 2,
       y: layout_chunk.y + inset_y_offset + 2,
-      width: display_size as u16 * 2,
+      width: display_size as u16 * cell_width,
       height: display_size as u16,
     };
     f.render_widget(shadow_paragraph, shadow_area);
@@ -2713,7 +4152,7 @@ fn draw_fullscreen_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (C
     let album_area = Rect {
       x: layout_chunk.x + inset_x_offset,
       y: layout_chunk.y + inset_y_offset,
-      width: display_size as u16 * 2,
+      width: display_size as u16 * cell_width,
       height: display_size as u16,
     };
     f.render_widget(album_paragraph, album_area);
@@ -2741,9 +4180,11 @@ fn draw_coin_flip_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Co
     f.render_widget(background, layout_chunk);
     
     // Calculate the maximum size we can display
-    // Account for double-width characters (2:1 aspect ratio)
+    // Each art pixel takes `cell_width` columns so it comes out square for the font
     // No shadow space needed for coin flip
-    let available_width = layout_chunk.width / 2;  // Divide by 2 for double-width chars
+    let cell_width = cell_aspect::art_cell_width(app.user_config.behavior.art_aspect_ratio);
+    let (filled_pixel, empty_pixel) = cell_aspect::art_pixel(cell_width);
+    let available_width = layout_chunk.width / cell_width;
     let available_height = layout_chunk.height;
     
     // Determine the size to use (maintain square aspect ratio)
@@ -2755,7 +4196,7 @@ fn draw_coin_flip_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Co
     let scale_factor = display_size as f32 / art.width as f32;
     
     // Center the art in the available space (no shadow offset)
-    let total_width = display_size as u16 * 2; // No shadow offset
+    let total_width = display_size as u16 * cell_width; // No shadow offset
     let total_height = display_size as u16; // No shadow offset
     let x_offset = (layout_chunk.width.saturating_sub(total_width)) / 2;
     let y_offset = (layout_chunk.height.saturating_sub(total_height)) / 2;
@@ -2802,7 +4243,7 @@ fn draw_coin_flip_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Co
         // Check if this screen position maps to a point on the disc
         if distance_from_center > radius || compression_factor.abs() < 0.01 {
           // Outside the disc or edge-on
-          line_spans.push(Span::raw("  "));
+          line_spans.push(Span::raw(empty_pixel));
           continue;
         }
         
@@ -2989,7 +4430,7 @@ fn draw_coin_flip_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Co
           color = darker_background;
         }
         
-        line_spans.push(Span::styled("██", Style::default().fg(color)));
+        line_spans.push(Span::styled(filled_pixel, Style::default().fg(color)));
       }
       
       lines.push(line_spans);
@@ -3005,7 +4446,7 @@ fn draw_coin_flip_album_art(f: &mut Frame, app: &App, layout_chunk: Rect) -> (Co
     let coin_area = Rect {
       x: layout_chunk.x + inset_x_offset,
       y: layout_chunk.y + inset_y_offset,
-      width: display_size as u16 * 2,
+      width: display_size as u16 * cell_width,
       height: display_size as u16,
     };
     f.render_widget(coin_paragraph, coin_area);
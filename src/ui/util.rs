@@ -1,29 +1,194 @@
-use super::super::app::{ActiveBlock, App, ArtistBlock, SearchResultBlock};
+use super::super::app::{ActiveBlock, App, ArtistBlock, SearchResultBlock, TrackTableContext};
+use super::audio_analysis::PITCHES;
 use crate::user_config::Theme;
-use rspotify::model::artist::SimplifiedArtist;
-use ratatui::style::Style;
+use rspotify::model::{
+  artist::SimplifiedArtist, audio::TimeInterval, device::Device, enums::DeviceType, page::Page,
+  PlayableItem,
+};
+use ratatui::{layout::Rect, style::Style};
+use serde::de::DeserializeOwned;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 pub const BASIC_VIEW_HEIGHT: u16 = 6;
+// Drawn between the breadcrumb's levels
+pub const BREADCRUMB_SEPARATOR: &str = " > ";
+// Default layout breakpoints, configurable under `behavior`
 pub const SMALL_TERMINAL_WIDTH: u16 = 150;
 pub const SMALL_TERMINAL_HEIGHT: u16 = 45;
+pub const ULTRA_SMALL_TERMINAL_WIDTH: u16 = 100;
+pub const ULTRA_SMALL_TERMINAL_HEIGHT: u16 = 30;
+pub const MINI_TERMINAL_WIDTH: u16 = 60;
+pub const MINI_TERMINAL_HEIGHT: u16 = 12;
+
+/// How many terminal cells `text` takes up, measured a grapheme at a time the way ratatui draws
+/// it, so an emoji built from several code points counts once
+pub fn display_width(text: &str) -> u16 {
+  let width: usize = text.graphemes(true).map(UnicodeWidthStr::width).sum();
+  width.try_into().unwrap_or(u16::MAX)
+}
+
+/// Whether the search bar fits above the sidebar instead of spanning the whole width
+pub fn is_wide_layout(app: &App) -> bool {
+  app.size.width >= app.user_config.behavior.small_terminal_width
+    && !app.user_config.behavior.enforce_wide_search_bar
+    && shows_sidebar(app)
+}
+
+/// Terminals too small for any layout with blocks in, which only show what is playing
+pub fn is_mini_layout(app: &App) -> bool {
+  app.size.width < app.user_config.behavior.mini_terminal_width
+    || app.size.height < app.user_config.behavior.mini_terminal_height
+}
+
+/// Whether the sidebar is drawn beside the main view rather than only over it while focused
+pub fn shows_sidebar(app: &App) -> bool {
+  let behavior = &app.user_config.behavior;
+  behavior.show_sidebar && app.size.width >= behavior.sidebar_min_width
+}
+
+/// Rows the playbar takes up, more on taller terminals
+pub fn playbar_height(area: Rect) -> u16 {
+  (area.height / 5).clamp(6, 14)
+}
+
+pub fn is_tall_layout(app: &App) -> bool {
+  app.size.height > app.user_config.behavior.small_terminal_height
+}
+
+/// Terminals around 80x24 drop the sidebar and show search as a popup
+pub fn is_ultra_small_layout(app: &App) -> bool {
+  app.size.width < app.user_config.behavior.ultra_small_terminal_width
+    || app.size.height < app.user_config.behavior.ultra_small_terminal_height
+}
+
+/// The number shown before a breadcrumb level while one is being picked, for the first nine
+pub fn breadcrumb_level_number(app: &App, level: usize) -> Option<String> {
+  if app.picking_breadcrumb && level < 9 {
+    Some(format!("{} ", level + 1))
+  } else {
+    None
+  }
+}
+
+/// Where the search input popup of the ultra small layout is drawn
+pub fn search_popup_rect(area: Rect) -> Rect {
+  let width = area.width.saturating_sub(4).min(60);
+  Rect::new(
+    area.x + (area.width - width) / 2,
+    area.y + area.height / 4,
+    width,
+    3.min(area.height),
+  )
+}
+
+/// Where the add-to-playlist picker is drawn: a filter input with the matching playlists below
+pub fn playlist_picker_rect(area: Rect) -> Rect {
+  let width = area.width.saturating_sub(4).min(50);
+  let height = area.height.saturating_sub(4).min(18);
+  Rect::new(
+    area.x + (area.width - width) / 2,
+    area.y + (area.height - height) / 2,
+    width,
+    height,
+  )
+}
+
+/// Where the help overlay is drawn: centered, and as tall as there is room for
+pub fn help_rect(area: Rect) -> Rect {
+  let width = area.width.saturating_sub(4).min(70);
+  let height = area.height.saturating_sub(4);
+  Rect::new(
+    area.x + (area.width - width) / 2,
+    area.y + (area.height - height) / 2,
+    width,
+    height,
+  )
+}
+
+/// Where the audio features panel is drawn: the top right corner, below the search bar
+pub fn audio_features_rect(area: Rect) -> Rect {
+  let width = area.width.saturating_sub(2).min(34);
+  let height = area.height.saturating_sub(4).min(8);
+  Rect::new(area.x + area.width - width - 1, area.y + 4, width, height)
+}
+
+/// A key as Spotify numbers them in pitch class notation, e.g. 1 in major is "C# major". Spotify
+/// uses -1 when it couldn't tell.
+pub fn key_name(key: i32, major: bool) -> Option<String> {
+  let pitch = PITCHES.get(usize::try_from(key).ok()?)?;
+  let mode = if major { "major" } else { "minor" };
+  Some(format!("{} {}", pitch, mode))
+}
+
+/// Whether `position`, in seconds, falls in the first half of a beat, so accents can pulse with
+/// the music
+pub fn on_beat(beats: &[TimeInterval], position: f32) -> bool {
+  beats
+    .iter()
+    .take_while(|beat| beat.start <= position)
+    .last()
+    .is_some_and(|beat| position - beat.start < beat.duration / 2.0)
+}
+
+/// Prefix an artist name with the liked icon when the user follows them, so the follow state
+/// looks the same everywhere an artist is listed
+pub fn create_artist_label(app: &App, artist_id: &str, name: &str) -> String {
+  if app.followed_artist_ids_set.contains(artist_id) {
+    format!("{}{}", app.user_config.padded_liked_icon(), name)
+  } else {
+    name.to_owned()
+  }
+}
+
+/// An icon for the kind of device, to tell apart devices that share a name. Spotify doesn't
+/// report battery levels, so the type is all there is to go on.
+pub fn device_icon(device_type: &DeviceType) -> &'static str {
+  match device_type {
+    DeviceType::Computer => "💻",
+    DeviceType::Smartphone | DeviceType::Tablet => "📱",
+    DeviceType::Speaker | DeviceType::Avr | DeviceType::AudioDongle | DeviceType::CastAudio => "🔊",
+    DeviceType::Tv | DeviceType::Stb | DeviceType::CastVideo => "📺",
+    DeviceType::GameConsole => "🎮",
+    DeviceType::Automobile => "🚗",
+    _ => "🎵",
+  }
+}
+
+/// A device name prefixed with its type icon
+pub fn device_label(device: &Device) -> String {
+  format!("{} {}", device_icon(&device._type), device.name)
+}
+
+/// A row of the device picker: the label, marked when the device is the active one, with its
+/// volume and whether Spotify lets it be controlled
+pub fn device_picker_label(device: &Device) -> String {
+  let marker = if device.is_active { "▶" } else { " " };
+  let mut label = format!("{} {}", marker, device_label(device));
+  if let Some(volume) = device.volume_percent {
+    label.push_str(&format!(" • {}%", volume));
+  }
+  if device.is_restricted {
+    label.push_str(" • restricted");
+  }
+  label
+}
 
 pub fn get_search_results_highlight_state(
   app: &App,
   block_to_match: SearchResultBlock,
 ) -> (bool, bool) {
-  let current_route = app.get_current_route();
   (
     app.search_results.selected_block == block_to_match,
-    current_route.hovered_block == ActiveBlock::SearchResultBlock
+    app.block_highlight_state(ActiveBlock::SearchResultBlock).1
       && app.search_results.hovered_block == block_to_match,
   )
 }
 
 pub fn get_artist_highlight_state(app: &App, block_to_match: ArtistBlock) -> (bool, bool) {
-  let current_route = app.get_current_route();
   if let Some(artist) = &app.artist {
     let is_hovered = artist.artist_selected_block == block_to_match;
-    let is_selected = current_route.hovered_block == ActiveBlock::ArtistBlock
+    let is_selected = app.block_highlight_state(ActiveBlock::ArtistBlock).1
       && artist.artist_hovered_block == block_to_match;
     (is_hovered, is_selected)
   } else {
@@ -88,17 +253,353 @@ pub fn get_track_progress_percentage(song_progress_ms: u128, track_duration_ms:
 
 // Make better use of space on small terminals
 pub fn get_main_layout_margin(app: &App) -> u16 {
-  if app.size.height > SMALL_TERMINAL_HEIGHT {
+  if is_tall_layout(app) {
     1
   } else {
     0
   }
 }
 
+/// Long durations such as a playlist's total, e.g. "3 h 42 min"
+pub fn millis_to_hours_and_minutes(millis: u128) -> String {
+  let total_minutes = millis / 60_000;
+  let hours = total_minutes / 60;
+  let minutes = total_minutes % 60;
+  if hours > 0 {
+    format!("{} h {} min", hours, minutes)
+  } else {
+    format!("{} min", minutes)
+  }
+}
+
+/// The line under a track table, e.g. "24 tracks • total 1 h 32 min • 12 liked"
+pub fn track_table_summary(track_count: usize, total_millis: u128, liked_count: usize) -> String {
+  format!(
+    "{} {} • total {} • {} liked",
+    track_count,
+    if track_count == 1 { "track" } else { "tracks" },
+    millis_to_hours_and_minutes(total_millis),
+    liked_count
+  )
+}
+
+/// The track table title: a badge for what Enter and paging act on, e.g. "[Playlist] Road Trip"
+pub fn track_table_title(app: &App) -> String {
+  let context = match &app.track_table.context {
+    Some(context) => context,
+    None => return String::new(),
+  };
+  let detail = match context {
+    TrackTableContext::MyPlaylists
+    | TrackTableContext::MadeForYou
+    | TrackTableContext::FeaturedPlaylists
+    | TrackTableContext::CategoryPlaylists => {
+      app.viewed_playlist().map(|playlist| playlist.name.as_str())
+    }
+    TrackTableContext::TopTracks => Some(app.top_time_range.label()),
+    _ => None,
+  };
+  match detail {
+    Some(detail) => format!("[{}] {}", context.badge(), detail),
+    None => format!("[{}]", context.badge()),
+  }
+}
+
+/// The queued item in the playbar title, e.g. "Heroes - David Bowie"
+pub fn up_next_label(item: &PlayableItem) -> String {
+  match item {
+    PlayableItem::Track(track) => {
+      format!("{} - {}", track.name, create_artist_string(&track.artists))
+    }
+    PlayableItem::Episode(episode) => episode.name.clone(),
+  }
+}
+
+/// A search results block title with the results its page shows, e.g. "Songs (5-8 of 120)"
+pub fn search_page_title<T: DeserializeOwned>(title: &str, page: Option<&Page<T>>) -> String {
+  match page {
+    Some(page) if !page.items.is_empty() => format!(
+      "{} ({}-{} of {})",
+      title,
+      page.offset + 1,
+      page.offset as usize + page.items.len(),
+      page.total
+    ),
+    _ => title.to_string(),
+  }
+}
+
+/// How progress bars show the played part
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProgressBarStyle {
+  // Filled with the album art colors
+  Solid,
+  // ▓ for played, ░ for the rest, so progress reads the same whatever the colors
+  Pattern,
+}
+
+impl ProgressBarStyle {
+  pub fn from_config_str(value: &str) -> Option<ProgressBarStyle> {
+    match value {
+      "solid" => Some(ProgressBarStyle::Solid),
+      "pattern" => Some(ProgressBarStyle::Pattern),
+      _ => None,
+    }
+  }
+}
+
+/// Which edge of the screen the playbar is drawn along, the `playbar_position` behavior option
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaybarPosition {
+  Top,
+  Bottom,
+}
+
+impl PlaybarPosition {
+  pub fn from_config_str(value: &str) -> Option<PlaybarPosition> {
+    match value {
+      "top" => Some(PlaybarPosition::Top),
+      "bottom" => Some(PlaybarPosition::Bottom),
+      _ => None,
+    }
+  }
+}
+
+/// A column of the track table, the `track_table_columns` behavior option
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackColumn {
+  Liked,
+  Title,
+  Artist,
+  Album,
+  // When the track was added to the playlist or liked
+  Added,
+  Popularity,
+  Explicit,
+  Length,
+}
+
+impl TrackColumn {
+  pub const ALL: [TrackColumn; 8] = [
+    TrackColumn::Liked,
+    TrackColumn::Title,
+    TrackColumn::Artist,
+    TrackColumn::Album,
+    TrackColumn::Added,
+    TrackColumn::Popularity,
+    TrackColumn::Explicit,
+    TrackColumn::Length,
+  ];
+
+  pub fn from_config_str(value: &str) -> Option<TrackColumn> {
+    TrackColumn::ALL
+      .iter()
+      .copied()
+      .find(|column| column.as_config_str() == value.to_lowercase())
+  }
+
+  pub fn as_config_str(self) -> &'static str {
+    match self {
+      TrackColumn::Liked => "liked",
+      TrackColumn::Title => "title",
+      TrackColumn::Artist => "artist",
+      TrackColumn::Album => "album",
+      TrackColumn::Added => "added",
+      TrackColumn::Popularity => "popularity",
+      TrackColumn::Explicit => "explicit",
+      TrackColumn::Length => "length",
+    }
+  }
+
+  pub fn header(self) -> &'static str {
+    match self {
+      TrackColumn::Liked | TrackColumn::Explicit => "",
+      TrackColumn::Title => "Title",
+      TrackColumn::Artist => "Artist",
+      TrackColumn::Album => "Album",
+      TrackColumn::Added => "Added",
+      TrackColumn::Popularity => "Popularity",
+      TrackColumn::Length => "Length",
+    }
+  }
+
+  /// The width of columns with short values; the text columns share what is left
+  fn fixed_width(self) -> Option<u16> {
+    match self {
+      TrackColumn::Liked => Some(2),
+      TrackColumn::Explicit => Some(3),
+      TrackColumn::Added => Some(10),
+      TrackColumn::Popularity => Some(10),
+      TrackColumn::Length => Some(8),
+      TrackColumn::Title | TrackColumn::Artist | TrackColumn::Album => None,
+    }
+  }
+}
+
+/// The width of each of `columns` in a table `width` cells wide
+pub fn track_column_widths(columns: &[TrackColumn], width: u16) -> Vec<u16> {
+  let fixed = columns
+    .iter()
+    .filter_map(|column| column.fixed_width())
+    .sum::<u16>();
+  // Leave room for the spacing ratatui puts between columns
+  let spacing = columns.len().saturating_sub(1) as u16;
+  let flexible_count = columns
+    .iter()
+    .filter(|column| column.fixed_width().is_none())
+    .count() as u16;
+  let flexible_width = width.saturating_sub(fixed + spacing) / flexible_count.max(1);
+  columns
+    .iter()
+    .map(|column| column.fixed_width().unwrap_or(flexible_width))
+    .collect()
+}
+
+/// The played and unplayed parts of a `width` wide pattern bar at `ratio`. A cell that is only
+/// partly played is drawn with ▒ and counts as played.
+pub fn pattern_bar(width: usize, ratio: f64) -> (String, String) {
+  let filled = width as f64 * ratio.clamp(0.0, 1.0);
+  let full_cells = filled.floor() as usize;
+  let mut played = "▓".repeat(full_cells);
+  if filled > full_cells as f64 && full_cells < width {
+    played.push('▒');
+  }
+  let unplayed = "░".repeat(width - played.chars().count());
+  (played, unplayed)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn layout_breakpoints_test() {
+    let mut app = App::default();
+    app.size = Rect::new(0, 0, 80, 24);
+    assert!(is_ultra_small_layout(&app));
+    assert!(!is_wide_layout(&app));
+    assert!(!is_mini_layout(&app));
+
+    app.size = Rect::new(0, 0, 80, 10);
+    assert!(is_mini_layout(&app));
+
+    app.size = Rect::new(0, 0, 160, 50);
+    assert!(!is_ultra_small_layout(&app));
+    assert!(is_wide_layout(&app));
+    assert!(is_tall_layout(&app));
+
+    app.user_config.behavior.small_terminal_width = 200;
+    assert!(!is_wide_layout(&app));
+  }
+
+  #[test]
+  fn search_page_title_test() {
+    let page = |offset, items: Vec<u32>| Page {
+      href: String::new(),
+      limit: 4,
+      next: None,
+      offset,
+      previous: None,
+      total: 10,
+      items,
+    };
+    assert_eq!(search_page_title::<u32>("Songs", None), "Songs");
+    assert_eq!(search_page_title("Songs", Some(&page(0, vec![]))), "Songs");
+    assert_eq!(
+      search_page_title("Songs", Some(&page(8, vec![1, 2]))),
+      "Songs (9-10 of 10)"
+    );
+  }
+
+  #[test]
+  fn key_name_test() {
+    assert_eq!(key_name(0, true), Some("C major".to_string()));
+    assert_eq!(key_name(1, false), Some("C# minor".to_string()));
+    assert_eq!(key_name(11, true), Some("B major".to_string()));
+    assert_eq!(key_name(-1, true), None);
+    assert_eq!(key_name(12, true), None);
+  }
+
+  #[test]
+  fn on_beat_test() {
+    let beat = |start| TimeInterval {
+      start,
+      duration: 0.5,
+      confidence: 1.0,
+    };
+    let beats = [beat(1.0), beat(1.5)];
+    assert!(!on_beat(&beats, 0.5));
+    assert!(on_beat(&beats, 1.1));
+    assert!(!on_beat(&beats, 1.3));
+    assert!(on_beat(&beats, 1.6));
+    assert!(!on_beat(&beats, 1.8));
+  }
+
+  #[test]
+  fn pattern_bar_test() {
+    assert_eq!(pattern_bar(4, 0.0), (String::new(), "░░░░".to_string()));
+    assert_eq!(pattern_bar(4, 0.5), ("▓▓".to_string(), "░░".to_string()));
+    assert_eq!(pattern_bar(4, 0.6), ("▓▓▒".to_string(), "░".to_string()));
+    assert_eq!(pattern_bar(4, 1.5), ("▓▓▓▓".to_string(), String::new()));
+  }
+
+  #[test]
+  fn track_table_title_test() {
+    let mut app = App::default();
+    assert_eq!(track_table_title(&app), "");
+    app.track_table.context = Some(TrackTableContext::SavedTracks);
+    assert_eq!(track_table_title(&app), "[Liked]");
+    app.track_table.context = Some(TrackTableContext::TopTracks);
+    assert_eq!(track_table_title(&app), "[Top Tracks] last 6 months");
+  }
+
+  #[test]
+  fn track_column_widths_test() {
+    use TrackColumn::*;
+    assert_eq!(
+      track_column_widths(&[Liked, Title, Artist, Album, Length], 100),
+      vec![2, 28, 28, 28, 8]
+    );
+    assert_eq!(
+      track_column_widths(&[Explicit, Title, Added, Popularity], 60),
+      vec![3, 34, 10, 10]
+    );
+    assert_eq!(track_column_widths(&[Title, Length], 5), vec![0, 8]);
+    assert_eq!(TrackColumn::from_config_str("Added"), Some(Added));
+    assert_eq!(TrackColumn::from_config_str("rating"), None);
+  }
+
+  #[test]
+  fn device_icon_test() {
+    assert_eq!(device_icon(&DeviceType::Computer), "💻");
+    assert_eq!(device_icon(&DeviceType::Smartphone), "📱");
+    assert_eq!(device_icon(&DeviceType::CastAudio), "🔊");
+    assert_eq!(device_icon(&DeviceType::Unknown), "🎵");
+  }
+
+  #[test]
+  fn device_picker_label_test() {
+    let device: Device = serde_json::from_value(serde_json::json!({
+      "id": "a",
+      "is_active": true,
+      "is_private_session": false,
+      "is_restricted": false,
+      "name": "Kitchen",
+      "type": "Speaker",
+      "volume_percent": 40
+    }))
+    .unwrap();
+    assert_eq!(device_picker_label(&device), "▶ 🔊 Kitchen • 40%");
+
+    let device = Device {
+      is_active: false,
+      is_restricted: true,
+      volume_percent: None,
+      ..device
+    };
+    assert_eq!(device_picker_label(&device), "  🔊 Kitchen • restricted");
+  }
+
   #[test]
   fn millis_to_minutes_test() {
     assert_eq!(millis_to_minutes(0), "0:00");
@@ -109,6 +610,19 @@ mod tests {
     assert_eq!(millis_to_minutes(60 * 1500), "1:30");
   }
 
+  #[test]
+  fn track_table_summary_test() {
+    assert_eq!(millis_to_hours_and_minutes(42 * 60 * 1000), "42 min");
+    assert_eq!(
+      track_table_summary(50, (3 * 60 + 42) * 60 * 1000, 12),
+      "50 tracks • total 3 h 42 min • 12 liked"
+    );
+    assert_eq!(
+      track_table_summary(1, 3 * 60 * 1000, 0),
+      "1 track • total 3 min • 0 liked"
+    );
+  }
+
   #[test]
   fn display_track_progress_test() {
     assert_eq!(
@@ -1,9 +1,16 @@
-use crate::event::Key;
+use crate::app::{IdleAnimation, NOW_PLAYING_FIELDS};
+use crate::event::{Chord, Key};
+use crate::palette::{self, PaletteKind};
+use crate::playlist_sort::PlaylistSort;
+use crate::template;
+use crate::ui::{cell_aspect, color::ColorSupport, util};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::{
+  collections::HashMap,
   fs,
   path::{Path, PathBuf},
+  time::SystemTime,
 };
 use ratatui::style::Color;
 
@@ -28,6 +35,19 @@ pub struct UserTheme {
   pub text: Option<String>,
   pub header: Option<String>,
   pub focus_letter: Option<String>,
+  pub source: Option<String>,
+  pub hot_reload: Option<bool>,
+}
+
+/// An external palette (pywal or base16) the theme is built from. Colors set explicitly in
+/// `theme` are kept as overrides so they can be reapplied after a reload.
+#[derive(Clone, Debug)]
+pub struct ThemeSource {
+  pub kind: PaletteKind,
+  pub path: PathBuf,
+  pub hot_reload: bool,
+  pub last_modified: Option<SystemTime>,
+  overrides: UserTheme,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -76,15 +96,15 @@ impl Default for Theme {
 }
 
 fn parse_key(key: String) -> Result<Key> {
-  fn get_single_char(string: &str) -> char {
-    match string.chars().next() {
-      Some(c) => c,
-      None => panic!(),
+  fn get_single_char(key: &str, string: Option<&&str>) -> Result<char> {
+    match string.and_then(|s| s.chars().next()) {
+      Some(c) => Ok(c),
+      None => Err(anyhow!("The shortcut \"{}\" is missing a key", key)),
     }
   }
 
-  match key.len() {
-    1 => Ok(Key::Char(get_single_char(key.as_str()))),
+  match key.chars().count() {
+    1 => Ok(Key::Char(get_single_char(&key, Some(&key.as_str()))?)),
     _ => {
       let sections: Vec<&str> = key.split('-').collect();
 
@@ -97,8 +117,8 @@ fn parse_key(key: String) -> Result<Key> {
       }
 
       match sections[0].to_lowercase().as_str() {
-        "ctrl" => Ok(Key::Ctrl(get_single_char(sections[1]))),
-        "alt" => Ok(Key::Alt(get_single_char(sections[1]))),
+        "ctrl" => Ok(Key::Ctrl(get_single_char(&key, sections.get(1))?)),
+        "alt" => Ok(Key::Alt(get_single_char(&key, sections.get(1))?)),
         "left" => Ok(Key::Left),
         "right" => Ok(Key::Right),
         "up" => Ok(Key::Up),
@@ -109,7 +129,24 @@ fn parse_key(key: String) -> Result<Key> {
         "pageup" => Ok(Key::PageUp),
         "pagedown" => Ok(Key::PageDown),
         "space" => Ok(Key::Char(' ')),
-        _ => Err(anyhow!("The key \"{}\" is unknown.", sections[0])),
+        "tab" => Ok(Key::Tab),
+        "shift" => match sections.get(1).map(|arrow| arrow.to_lowercase()).as_deref() {
+          Some("left") => Ok(Key::ShiftLeft),
+          Some("right") => Ok(Key::ShiftRight),
+          Some("up") => Ok(Key::ShiftUp),
+          Some("down") => Ok(Key::ShiftDown),
+          _ => Err(anyhow!(
+            "Only the arrow keys can be bound with shift, not \"{}\"",
+            key
+          )),
+        },
+        function_key => match function_key
+          .strip_prefix('f')
+          .and_then(|number| number.parse::<u8>().ok())
+        {
+          Some(number) if (1..=12).contains(&number) => Ok(Key::from_f(number)),
+          _ => Err(anyhow!("The key \"{}\" is unknown.", sections[0])),
+        },
       }
     }
   }
@@ -143,6 +180,163 @@ fn check_reserved_keys(key: Key) -> Result<()> {
   Ok(())
 }
 
+/// Find the 1-based line of `name:` inside the top level `section:` of a config file
+fn find_config_line(config: &str, section: &str, name: &str) -> Option<usize> {
+  let mut in_section = false;
+  for (index, line) in config.lines().enumerate() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+      continue;
+    }
+    if !line.starts_with(char::is_whitespace) {
+      in_section = trimmed.strip_suffix(':') == Some(section);
+      continue;
+    }
+    let key = trimmed.split(':').next().unwrap_or("").trim_matches(|c| c == '"' || c == '\'');
+    if in_section && key == name {
+      return Some(index + 1);
+    }
+  }
+  None
+}
+
+fn parse_track_table_columns(names: &[String]) -> Result<Vec<util::TrackColumn>> {
+  if names.is_empty() {
+    return Err(anyhow!("Track table columns must list at least one column"));
+  }
+  names
+    .iter()
+    .map(|name| {
+      util::TrackColumn::from_config_str(name).ok_or_else(|| {
+        let valid = util::TrackColumn::ALL
+          .iter()
+          .map(|column| column.as_config_str())
+          .collect::<Vec<&str>>();
+        anyhow!(
+          "Track table columns must be some of {}, found {}",
+          valid.join(", "),
+          name
+        )
+      })
+    })
+    .collect()
+}
+
+/// Set `name: value` inside the top level `section:` of a config file, replacing the old value
+/// along with any nested lines it had, and adding the section if there is none
+fn set_config_value(config: &str, section: &str, name: &str, value: &str) -> String {
+  let mut lines = config.lines().map(str::to_string).collect::<Vec<String>>();
+  let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+  if let Some(line_number) = find_config_line(config, section, name) {
+    let index = line_number - 1;
+    let indent = indent_of(&lines[index]);
+    lines[index] = format!("{}{}: {}", " ".repeat(indent), name, value);
+    // Drop the items of a block list or mapping the old value spread over
+    let nested = lines[index + 1..]
+      .iter()
+      .take_while(|line| {
+        let trimmed = line.trim_start();
+        !trimmed.is_empty()
+          && (indent_of(line) > indent || (indent_of(line) == indent && trimmed.starts_with('-')))
+      })
+      .count();
+    lines.drain(index + 1..index + 1 + nested);
+  } else if let Some(line_number) = find_top_level_line(config, section) {
+    lines.insert(line_number, format!("  {}: {}", name, value));
+  } else {
+    lines.push(format!("{}:", section));
+    lines.push(format!("  {}: {}", name, value));
+  }
+  lines.join("\n") + "\n"
+}
+
+/// Find the 1-based line of a top level `key:` in a config file
+pub fn find_top_level_line(config: &str, key: &str) -> Option<usize> {
+  let position = config.lines().position(|line| {
+    let name = line.split(':').next().unwrap_or("");
+    !line.starts_with(char::is_whitespace) && name.trim_matches(|c| c == '"' || c == '\'') == key
+  })?;
+  Some(position + 1)
+}
+
+/// Append the config line of the keybinding named in an "Invalid keybinding \"name\"" error
+fn with_line_context(config: &str, message: &str) -> String {
+  let name = message.split('"').nth(1).unwrap_or("");
+  match find_config_line(config, "keybindings", name) {
+    Some(line) => format!("{} (line {})", message, line),
+    None => message.to_string(),
+  }
+}
+
+/// A warning naming the keybindings the config sets that aren't actions, if there are any. They
+/// are ignored rather than refused, so a config from a newer version still loads.
+fn check_keybinding_actions(config: &str) -> Option<String> {
+  let value: serde_yaml::Value = serde_yaml::from_str(config).ok()?;
+  let keybindings = value.get("keybindings")?.as_mapping()?;
+
+  let unknown: Vec<String> = keybindings
+    .keys()
+    .filter_map(|key| key.as_str())
+    .filter(|name| !KEYBINDING_ACTIONS.contains(name) && !RETIRED_KEYBINDING_ACTIONS.contains(name))
+    // The leader is a setting for chords rather than an action
+    .filter(|name| *name != "leader")
+    .map(|name| match find_config_line(config, "keybindings", name) {
+      Some(line) => format!("\"{}\" (line {})", name, line),
+      None => format!("\"{}\"", name),
+    })
+    .collect();
+
+  if unknown.is_empty() {
+    None
+  } else {
+    Some(format!(
+      "Ignoring unknown keybinding action {}. Valid actions are: {}",
+      unknown.join(", "),
+      KEYBINDING_ACTIONS.join(", ")
+    ))
+  }
+}
+
+fn check_keybinding_conflicts(keys: &KeyBindings, config: &str) -> Result<()> {
+  let bindings = keys.bindings();
+  let mut conflicts = vec![];
+  let describe = |name: &str| match find_config_line(config, "keybindings", name) {
+    Some(line) => format!("\"{}\" (line {})", name, line),
+    None => format!("\"{}\" (default)", name),
+  };
+
+  for (i, (name, key)) in bindings.iter().enumerate() {
+    for (other_name, other_key) in bindings.iter().skip(i + 1) {
+      let shared = SHARED_KEY_ACTIONS
+        .iter()
+        .any(|&(a, b)| (a, b) == (*name, *other_name) || (b, a) == (*name, *other_name));
+      if key == other_key && !shared {
+        conflicts.push(format!(
+          "{} and {} are both bound to {}",
+          describe(name),
+          describe(other_name),
+          key
+        ));
+      }
+    }
+    if let Some((_, builtin)) = BUILTIN_GLOBAL_KEYS.iter().find(|(k, _)| k == key) {
+      conflicts.push(format!(
+        "{} is bound to {}, which is already used to {}",
+        describe(name),
+        key,
+        builtin
+      ));
+    }
+  }
+
+  if conflicts.is_empty() {
+    Ok(())
+  } else {
+    Err(anyhow!("Keybinding conflicts:\n  {}", conflicts.join("\n  ")))
+  }
+}
+
 #[derive(Clone)]
 pub struct UserConfigPaths {
   pub config_file_path: PathBuf,
@@ -159,6 +353,7 @@ pub struct KeyBindingsString {
   jump_to_artist_album: Option<String>,
   jump_to_context: Option<String>,
   manage_devices: Option<String>,
+  toggle_streaming: Option<String>,
   decrease_volume: Option<String>,
   increase_volume: Option<String>,
   toggle_playback: Option<String>,
@@ -170,11 +365,39 @@ pub struct KeyBindingsString {
   repeat: Option<String>,
   search: Option<String>,
   submit: Option<String>,
-  copy_song_url: Option<String>,
-  copy_album_url: Option<String>,
+  #[serde(alias = "copy_song_url")]
+  share: Option<String>,
   audio_analysis: Option<String>,
+  audio_features: Option<String>,
+  sleep_timer: Option<String>,
+  mute: Option<String>,
+  loop_start: Option<String>,
+  loop_end: Option<String>,
   basic_view: Option<String>,
   add_item_to_queue: Option<String>,
+  play_next: Option<String>,
+  save_track: Option<String>,
+  visual_mode: Option<String>,
+  add_to_playlist: Option<String>,
+  remove_from_playlist: Option<String>,
+  command_line: Option<String>,
+  log_stream: Option<String>,
+  debug_overlay: Option<String>,
+  help: Option<String>,
+  jump_back: Option<String>,
+  jump_forward: Option<String>,
+  breadcrumb: Option<String>,
+  split_view: Option<String>,
+  switch_pane: Option<String>,
+  toggle_idle_mode: Option<String>,
+  switch_idle_animation: Option<String>,
+  sort_playlists: Option<String>,
+  pin_playlist: Option<String>,
+  like_current_track: Option<String>,
+  follow_current_artist: Option<String>,
+  open_in: Option<String>,
+  copy_now_playing: Option<String>,
+  leader: Option<String>,
 }
 
 #[derive(Clone)]
@@ -188,6 +411,7 @@ pub struct KeyBindings {
   pub jump_to_artist_album: Key,
   pub jump_to_context: Key,
   pub manage_devices: Key,
+  pub toggle_streaming: Key,
   pub decrease_volume: Key,
   pub increase_volume: Key,
   pub toggle_playback: Key,
@@ -199,11 +423,187 @@ pub struct KeyBindings {
   pub repeat: Key,
   pub search: Key,
   pub submit: Key,
-  pub copy_song_url: Key,
-  pub copy_album_url: Key,
+  pub share: Key,
   pub audio_analysis: Key,
+  pub audio_features: Key,
+  pub sleep_timer: Key,
+  pub mute: Key,
+  pub loop_start: Key,
+  pub loop_end: Key,
   pub basic_view: Key,
   pub add_item_to_queue: Key,
+  pub play_next: Key,
+  pub save_track: Key,
+  pub visual_mode: Key,
+  pub add_to_playlist: Key,
+  pub remove_from_playlist: Key,
+  pub command_line: Key,
+  pub log_stream: Key,
+  pub debug_overlay: Key,
+  pub help: Key,
+  pub jump_back: Key,
+  pub jump_forward: Key,
+  pub breadcrumb: Key,
+  pub split_view: Key,
+  pub switch_pane: Key,
+  pub toggle_idle_mode: Key,
+  pub switch_idle_animation: Key,
+  pub sort_playlists: Key,
+  pub pin_playlist: Key,
+  pub like_current_track: Key,
+  pub follow_current_artist: Key,
+  pub open_in: Key,
+  pub copy_now_playing: Key,
+  pub leader: Key,
+}
+
+/// Every action that can be bound under `keybindings` in the config file. The `leader` setting
+/// there is the key chords start with, not an action, so it isn't one of them
+pub const KEYBINDING_ACTIONS: [&str; 52] = [
+  "back",
+  "next_page",
+  "previous_page",
+  "jump_to_start",
+  "jump_to_end",
+  "jump_to_album",
+  "jump_to_artist_album",
+  "jump_to_context",
+  "manage_devices",
+  "toggle_streaming",
+  "decrease_volume",
+  "increase_volume",
+  "toggle_playback",
+  "seek_backwards",
+  "seek_forwards",
+  "next_track",
+  "previous_track",
+  "shuffle",
+  "repeat",
+  "search",
+  "submit",
+  "share",
+  "audio_analysis",
+  "audio_features",
+  "sleep_timer",
+  "mute",
+  "loop_start",
+  "loop_end",
+  "basic_view",
+  "add_item_to_queue",
+  "play_next",
+  "save_track",
+  "visual_mode",
+  "add_to_playlist",
+  "remove_from_playlist",
+  "command_line",
+  "log_stream",
+  "debug_overlay",
+  "help",
+  "jump_back",
+  "jump_forward",
+  "breadcrumb",
+  "split_view",
+  "switch_pane",
+  "toggle_idle_mode",
+  "switch_idle_animation",
+  "sort_playlists",
+  "pin_playlist",
+  "like_current_track",
+  "follow_current_artist",
+  "open_in",
+  "copy_now_playing",
+];
+
+/// Actions merged into another one, still accepted so older configs keep working
+const RETIRED_KEYBINDING_ACTIONS: [&str; 2] = ["copy_song_url", "copy_album_url"];
+
+/// Actions that only make sense as chords and act on the focused block directly
+pub const CHORD_ACTIONS: [&str; 2] = ["top", "delete"];
+
+/// Pairs of actions that may share a key because they never fire in the same place: the idle
+/// animation only switches in fullscreen idle mode, where no track table is shown
+const SHARED_KEY_ACTIONS: [(&str, &str); 1] = [("visual_mode", "switch_idle_animation")];
+
+/// Keys handled globally before any configurable binding, so binding an action to one of
+/// these would never fire
+const BUILTIN_GLOBAL_KEYS: [(Key, &str); 17] = [
+  (Key::Char('L'), "focus library"),
+  (Key::Char('l'), "focus library"),
+  (Key::Char('P'), "focus playlists"),
+  (Key::Char('p'), "focus playlists"),
+  (Key::Char('S'), "focus search"),
+  (Key::Char('s'), "focus search"),
+  (Key::Char('D'), "select device"),
+  (Key::Ctrl('l'), "log stream"),
+  (Key::Alt('1'), "switch workspace"),
+  (Key::Alt('2'), "switch workspace"),
+  (Key::Alt('3'), "switch workspace"),
+  (Key::Alt('4'), "switch workspace"),
+  (Key::Alt('5'), "switch workspace"),
+  (Key::Alt('6'), "switch workspace"),
+  (Key::Alt('7'), "switch workspace"),
+  (Key::Alt('8'), "switch workspace"),
+  (Key::Alt('9'), "switch workspace"),
+];
+
+impl KeyBindings {
+  /// All action bindings paired with their config name, in `KEYBINDING_ACTIONS` order
+  pub fn bindings(&self) -> Vec<(&'static str, Key)> {
+    vec![
+      ("back", self.back),
+      ("next_page", self.next_page),
+      ("previous_page", self.previous_page),
+      ("jump_to_start", self.jump_to_start),
+      ("jump_to_end", self.jump_to_end),
+      ("jump_to_album", self.jump_to_album),
+      ("jump_to_artist_album", self.jump_to_artist_album),
+      ("jump_to_context", self.jump_to_context),
+      ("manage_devices", self.manage_devices),
+      ("toggle_streaming", self.toggle_streaming),
+      ("decrease_volume", self.decrease_volume),
+      ("increase_volume", self.increase_volume),
+      ("toggle_playback", self.toggle_playback),
+      ("seek_backwards", self.seek_backwards),
+      ("seek_forwards", self.seek_forwards),
+      ("next_track", self.next_track),
+      ("previous_track", self.previous_track),
+      ("shuffle", self.shuffle),
+      ("repeat", self.repeat),
+      ("search", self.search),
+      ("submit", self.submit),
+      ("share", self.share),
+      ("audio_analysis", self.audio_analysis),
+      ("audio_features", self.audio_features),
+      ("sleep_timer", self.sleep_timer),
+      ("mute", self.mute),
+      ("loop_start", self.loop_start),
+      ("loop_end", self.loop_end),
+      ("basic_view", self.basic_view),
+      ("add_item_to_queue", self.add_item_to_queue),
+      ("play_next", self.play_next),
+      ("save_track", self.save_track),
+      ("visual_mode", self.visual_mode),
+      ("add_to_playlist", self.add_to_playlist),
+      ("remove_from_playlist", self.remove_from_playlist),
+      ("command_line", self.command_line),
+      ("log_stream", self.log_stream),
+      ("debug_overlay", self.debug_overlay),
+      ("help", self.help),
+      ("jump_back", self.jump_back),
+      ("jump_forward", self.jump_forward),
+      ("breadcrumb", self.breadcrumb),
+      ("split_view", self.split_view),
+      ("switch_pane", self.switch_pane),
+      ("toggle_idle_mode", self.toggle_idle_mode),
+      ("switch_idle_animation", self.switch_idle_animation),
+      ("sort_playlists", self.sort_playlists),
+      ("pin_playlist", self.pin_playlist),
+      ("like_current_track", self.like_current_track),
+      ("follow_current_artist", self.follow_current_artist),
+      ("open_in", self.open_in),
+      ("copy_now_playing", self.copy_now_playing),
+    ]
+  }
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -222,6 +622,47 @@ pub struct BehaviorConfigString {
   pub paused_icon: Option<String>,
   pub set_window_title: Option<bool>,
   pub idle_timeout_seconds: Option<u64>,
+  pub color_support: Option<String>,
+  pub chord_timeout_milliseconds: Option<u64>,
+  pub art_aspect_ratio: Option<String>,
+  pub small_terminal_width: Option<u16>,
+  pub small_terminal_height: Option<u16>,
+  pub ultra_small_terminal_width: Option<u16>,
+  pub ultra_small_terminal_height: Option<u16>,
+  pub mini_terminal_width: Option<u16>,
+  pub mini_terminal_height: Option<u16>,
+  pub show_sidebar: Option<bool>,
+  pub sidebar_min_width: Option<u16>,
+  pub playbar_position: Option<String>,
+  pub show_breadcrumb: Option<bool>,
+  pub network_timeout_seconds: Option<u64>,
+  pub idle_animation: Option<String>,
+  pub progress_bar_style: Option<String>,
+  pub playlist_sort: Option<String>,
+  pub pinned_playlists: Option<Vec<String>>,
+  pub track_table_columns: Option<Vec<String>>,
+  pub now_playing_template: Option<String>,
+  pub search_delay_milliseconds: Option<u64>,
+  pub audio_analysis_disk_cache: Option<bool>,
+  pub beat_accents: Option<bool>,
+  pub autoplay: Option<bool>,
+  pub streaming: Option<bool>,
+  pub streaming_device_name: Option<String>,
+  pub streaming_audio_backend: Option<String>,
+  pub spotifyd_path: Option<String>,
+  pub spotifyd_config_path: Option<String>,
+  pub spotifyd_device_name: Option<String>,
+  pub on_track_change: Option<String>,
+  pub on_pause: Option<String>,
+  pub on_resume: Option<String>,
+  pub now_playing_file: Option<String>,
+  pub now_playing_file_template: Option<String>,
+  pub now_playing_art_file: Option<String>,
+  pub log_file: Option<String>,
+  pub log_file_max_kb: Option<u64>,
+  pub pause_on_quit: Option<bool>,
+  pub finish_changes_on_quit: Option<bool>,
+  pub save_state_on_quit: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -240,11 +681,70 @@ pub struct BehaviorConfig {
   pub paused_icon: String,
   pub set_window_title: bool,
   pub idle_timeout_seconds: u64,
+  pub color_support: ColorSupport,
+  pub chord_timeout_milliseconds: u64,
+  pub art_aspect_ratio: cell_aspect::ArtAspect,
+  pub small_terminal_width: u16,
+  pub small_terminal_height: u16,
+  pub ultra_small_terminal_width: u16,
+  pub ultra_small_terminal_height: u16,
+  pub mini_terminal_width: u16,
+  pub mini_terminal_height: u16,
+  // The sidebar is hidden until focused when turned off or on terminals narrower than the
+  // minimum width, 0 for no minimum
+  pub show_sidebar: bool,
+  pub sidebar_min_width: u16,
+  pub playbar_position: util::PlaybarPosition,
+  pub show_breadcrumb: bool,
+  pub network_timeout_seconds: u64,
+  pub idle_animation: IdleAnimation,
+  pub progress_bar_style: util::ProgressBarStyle,
+  pub playlist_sort: PlaylistSort,
+  // Names or IDs of the playlists kept at the top of the sidebar
+  pub pinned_playlists: Vec<String>,
+  pub track_table_columns: Vec<util::TrackColumn>,
+  // What copy_now_playing puts on the clipboard
+  pub now_playing_template: String,
+  // Pause in typing after which the search box searches by itself, 0 to only search on Enter
+  pub search_delay_milliseconds: u64,
+  // Keep audio analyses and features in the cache directory as well as in memory
+  pub audio_analysis_disk_cache: bool,
+  // Pulse the playbar border on the playing track's beats
+  pub beat_accents: bool,
+  // Play recommendations when the playing album or playlist ends
+  pub autoplay: bool,
+  // Start spotui's own playback device on launch, in builds with the streaming feature
+  pub streaming: bool,
+  pub streaming_device_name: String,
+  // librespot's audio backend, empty for its default
+  pub streaming_audio_backend: String,
+  // A spotifyd binary to start with spotui, empty to leave it to the user
+  pub spotifyd_path: String,
+  pub spotifyd_config_path: String,
+  pub spotifyd_device_name: String,
+  // Shell commands run when the track changes or playback pauses or resumes, empty for none
+  pub on_track_change: String,
+  pub on_pause: String,
+  pub on_resume: String,
+  // Files kept up to date with what is playing for stream overlays, empty for none
+  pub now_playing_file: String,
+  pub now_playing_file_template: String,
+  pub now_playing_art_file: String,
+  // Where the log is written, empty for the state directory, and the size it is rotated at
+  pub log_file: String,
+  pub log_file_max_kb: u64,
+  // What quitting does besides closing and saving episode positions and search history: pause
+  // playback, finish saves, playlist edits and queue additions still waiting to be sent, and save
+  // the session
+  pub pause_on_quit: bool,
+  pub finish_changes_on_quit: bool,
+  pub save_state_on_quit: bool,
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UserConfigString {
   keybindings: Option<KeyBindingsString>,
+  chords: Option<HashMap<String, String>>,
   behavior: Option<BehaviorConfigString>,
   theme: Option<UserTheme>,
 }
@@ -255,6 +755,10 @@ pub struct UserConfig {
   pub theme: Theme,
   pub behavior: BehaviorConfig,
   pub path_to_config: Option<UserConfigPaths>,
+  pub theme_source: Option<ThemeSource>,
+  pub chords: Vec<Chord>,
+  /// Problems in the config file that didn't stop it loading, logged once logging has started
+  pub warnings: Vec<String>,
 }
 
 impl UserConfig {
@@ -271,6 +775,7 @@ impl UserConfig {
         jump_to_artist_album: Key::Char('A'),
         jump_to_context: Key::Char('o'),
         manage_devices: Key::Char('d'),
+        toggle_streaming: Key::Char('r'),
         decrease_volume: Key::Char('-'),
         increase_volume: Key::Char('+'),
         toggle_playback: Key::Char(' '),
@@ -282,12 +787,51 @@ impl UserConfig {
         repeat: Key::Ctrl('r'),
         search: Key::Char('/'),
         submit: Key::Enter,
-        copy_song_url: Key::Char('c'),
-        copy_album_url: Key::Char('C'),
+        share: Key::Char('c'),
         audio_analysis: Key::Char('v'),
+        audio_features: Key::Char('i'),
+        sleep_timer: Key::Char('T'),
+        mute: Key::Char('m'),
+        loop_start: Key::Char('['),
+        loop_end: Key::Char(']'),
         basic_view: Key::Char('B'),
         add_item_to_queue: Key::Char('z'),
+        play_next: Key::Char('Z'),
+        save_track: Key::Char('w'),
+        visual_mode: Key::Char('V'),
+        add_to_playlist: Key::Char('W'),
+        remove_from_playlist: Key::Char('X'),
+        command_line: Key::Char(':'),
+        log_stream: Key::Char('O'),
+        debug_overlay: Key::Char('`'),
+        help: Key::Char('?'),
+        jump_back: Key::Ctrl('o'),
+        // Terminals send Ctrl-I as Tab
+        jump_forward: Key::Tab,
+        breadcrumb: Key::Char('G'),
+        split_view: Key::Char('|'),
+        switch_pane: Key::Ctrl('w'),
+        toggle_idle_mode: Key::Char('F'),
+        switch_idle_animation: Key::Char('V'),
+        sort_playlists: Key::Char('t'),
+        pin_playlist: Key::Char('*'),
+        like_current_track: Key::Char('Y'),
+        follow_current_artist: Key::Char('U'),
+        open_in: Key::Char('E'),
+        copy_now_playing: Key::Char('y'),
+        leader: Key::Char('\\'),
       },
+      chords: vec![
+        Chord {
+          keys: vec![Key::Char('g'), Key::Char('g')],
+          action: "top".to_string(),
+        },
+        // `D` opens the device picker, so deleting starts with the leader instead
+        Chord {
+          keys: vec![Key::Char('\\'), Key::Char('d')],
+          action: "delete".to_string(),
+        },
+      ],
       behavior: BehaviorConfig {
         seek_milliseconds: 5 * 1000,
         volume_increment: 10,
@@ -303,8 +847,57 @@ impl UserConfig {
         paused_icon: "⏸".to_string(),
         set_window_title: true,
         idle_timeout_seconds: 30,
+        color_support: ColorSupport::detect(),
+        chord_timeout_milliseconds: 1000,
+        art_aspect_ratio: cell_aspect::ArtAspect::Auto,
+        small_terminal_width: util::SMALL_TERMINAL_WIDTH,
+        small_terminal_height: util::SMALL_TERMINAL_HEIGHT,
+        ultra_small_terminal_width: util::ULTRA_SMALL_TERMINAL_WIDTH,
+        ultra_small_terminal_height: util::ULTRA_SMALL_TERMINAL_HEIGHT,
+        mini_terminal_width: util::MINI_TERMINAL_WIDTH,
+        mini_terminal_height: util::MINI_TERMINAL_HEIGHT,
+        show_sidebar: true,
+        sidebar_min_width: 0,
+        playbar_position: util::PlaybarPosition::Bottom,
+        show_breadcrumb: true,
+        network_timeout_seconds: 30,
+        idle_animation: IdleAnimation::SpinningRecord,
+        progress_bar_style: util::ProgressBarStyle::Solid,
+        playlist_sort: PlaylistSort::Recent,
+        pinned_playlists: vec![],
+        track_table_columns: vec![
+          util::TrackColumn::Liked,
+          util::TrackColumn::Title,
+          util::TrackColumn::Artist,
+          util::TrackColumn::Album,
+          util::TrackColumn::Length,
+        ],
+        now_playing_template: "{track} - {artist} {url}".to_string(),
+        search_delay_milliseconds: 400,
+        audio_analysis_disk_cache: false,
+        beat_accents: false,
+        autoplay: false,
+        streaming: true,
+        streaming_device_name: "spotui".to_string(),
+        streaming_audio_backend: String::new(),
+        spotifyd_path: String::new(),
+        spotifyd_config_path: String::new(),
+        spotifyd_device_name: "spotifyd".to_string(),
+        on_track_change: String::new(),
+        on_pause: String::new(),
+        on_resume: String::new(),
+        now_playing_file: String::new(),
+        now_playing_file_template: "{track} - {artist}".to_string(),
+        now_playing_art_file: String::new(),
+        log_file: String::new(),
+        log_file_max_kb: 1024,
+        pause_on_quit: false,
+        finish_changes_on_quit: true,
+        save_state_on_quit: true,
       },
       path_to_config: None,
+      theme_source: None,
+      warnings: Vec::new(),
     }
   }
 
@@ -339,8 +932,9 @@ impl UserConfig {
     macro_rules! to_keys {
       ($name: ident) => {
         if let Some(key_string) = keybindings.$name {
-          self.keys.$name = parse_key(key_string)?;
-          check_reserved_keys(self.keys.$name)?;
+          self.keys.$name = parse_key(key_string)
+            .and_then(|key| check_reserved_keys(key).map(|_| key))
+            .map_err(|e| anyhow!("Invalid keybinding \"{}\": {}", stringify!($name), e))?;
         }
       };
     }
@@ -354,6 +948,7 @@ impl UserConfig {
     to_keys!(jump_to_artist_album);
     to_keys!(jump_to_context);
     to_keys!(manage_devices);
+    to_keys!(toggle_streaming);
     to_keys!(decrease_volume);
     to_keys!(increase_volume);
     to_keys!(toggle_playback);
@@ -365,20 +960,133 @@ impl UserConfig {
     to_keys!(repeat);
     to_keys!(search);
     to_keys!(submit);
-    to_keys!(copy_song_url);
-    to_keys!(copy_album_url);
+    to_keys!(share);
     to_keys!(audio_analysis);
+    to_keys!(audio_features);
+    to_keys!(sleep_timer);
+    to_keys!(mute);
+    to_keys!(loop_start);
+    to_keys!(loop_end);
     to_keys!(basic_view);
     to_keys!(add_item_to_queue);
+    to_keys!(play_next);
+    to_keys!(save_track);
+    to_keys!(visual_mode);
+    to_keys!(add_to_playlist);
+    to_keys!(remove_from_playlist);
+    to_keys!(command_line);
+    to_keys!(log_stream);
+    to_keys!(debug_overlay);
+    to_keys!(help);
+    to_keys!(jump_back);
+    to_keys!(jump_forward);
+    to_keys!(breadcrumb);
+    to_keys!(split_view);
+    to_keys!(switch_pane);
+    to_keys!(toggle_idle_mode);
+    to_keys!(switch_idle_animation);
+    to_keys!(sort_playlists);
+    to_keys!(pin_playlist);
+    to_keys!(like_current_track);
+    to_keys!(follow_current_artist);
+    to_keys!(open_in);
+    to_keys!(copy_now_playing);
+
+    // Default chords follow the leader when it is rebound
+    let previous_leader = self.keys.leader;
+    to_keys!(leader);
+    for chord in &mut self.chords {
+      if chord.keys.first() == Some(&previous_leader) {
+        chord.keys[0] = self.keys.leader;
+      }
+    }
 
     Ok(())
   }
 
+  /// Parse `chords` entries such as `"g g": top` or `"leader n": next_track`. Entries replace
+  /// any default chord with the same keys.
+  pub fn load_chords(&mut self, chords: HashMap<String, String>, config: &str) -> Result<()> {
+    for (sequence, action) in chords {
+      let context = |message: String| match find_config_line(config, "chords", &sequence) {
+        Some(line) => anyhow!("Invalid chord \"{}\" (line {}): {}", sequence, line, message),
+        None => anyhow!("Invalid chord \"{}\": {}", sequence, message),
+      };
+
+      if !KEYBINDING_ACTIONS.contains(&action.as_str()) && !CHORD_ACTIONS.contains(&action.as_str())
+      {
+        return Err(context(format!("unknown action \"{}\"", action)));
+      }
+
+      let keys = sequence
+        .split_whitespace()
+        .map(|token| match token {
+          "leader" => Ok(self.keys.leader),
+          _ => parse_key(token.to_string()),
+        })
+        .collect::<Result<Vec<Key>>>()
+        .map_err(|e| context(e.to_string()))?;
+
+      if keys.len() < 2 {
+        return Err(context(
+          "a chord needs at least two keys, bind single keys under keybindings".to_string(),
+        ));
+      }
+
+      self.chords.retain(|chord| chord.keys != keys);
+      self.chords.push(Chord { keys, action });
+    }
+    Ok(())
+  }
+
   pub fn load_theme(&mut self, theme: UserTheme) -> Result<()> {
+    if let Some(source) = &theme.source {
+      let (kind, path) = palette::parse_source(source)?;
+      self.theme_source = Some(ThemeSource {
+        kind,
+        path,
+        hot_reload: theme.hot_reload.unwrap_or(false),
+        last_modified: None,
+        overrides: theme.clone(),
+      });
+      self.reload_theme_source()?;
+    }
+
+    self.load_theme_items(&theme)
+  }
+
+  /// Rebuild the theme from the external palette if the file changed since it was last read.
+  /// Returns whether the theme was updated.
+  pub fn reload_theme_source(&mut self) -> Result<bool> {
+    let source = match &mut self.theme_source {
+      Some(source) => source,
+      None => return Ok(false),
+    };
+
+    let modified = fs::metadata(&source.path)
+      .and_then(|metadata| metadata.modified())
+      .ok();
+    if source.last_modified.is_some() && modified == source.last_modified {
+      return Ok(false);
+    }
+
+    let palette = palette::load(&source.kind, &source.path)?;
+    source.last_modified = modified;
+    let overrides = source.overrides.clone();
+
+    let mut theme = Theme::default();
+    palette.apply(&mut theme);
+    self.theme = theme;
+    self.load_theme_items(&overrides)?;
+    Ok(true)
+  }
+
+  fn load_theme_items(&mut self, theme: &UserTheme) -> Result<()> {
     macro_rules! to_theme_item {
       ($name: ident) => {
-        if let Some(theme_item) = theme.$name {
-          self.theme.$name = parse_theme_item(&theme_item)?;
+        if let Some(theme_item) = &theme.$name {
+          self.theme.$name = parse_theme_item(theme_item)
+            .map_err(|e| anyhow!("Invalid theme color \"{}\": {}", stringify!($name), e))?;
         }
       };
     }
@@ -472,6 +1180,261 @@ impl UserConfig {
       }
     }
 
+    if let Some(chord_timeout) = behavior_config.chord_timeout_milliseconds {
+      if chord_timeout == 0 {
+        return Err(anyhow!("Chord timeout must be greater than 0"));
+      } else {
+        self.behavior.chord_timeout_milliseconds = chord_timeout;
+      }
+    }
+
+    if let Some(color_support) = behavior_config.color_support {
+      match ColorSupport::from_config_str(&color_support) {
+        Some(support) => self.behavior.color_support = support,
+        None => {
+          return Err(anyhow!(
+            "Color support must be one of auto, truecolor, 256 or 16, is {}",
+            color_support
+          ))
+        }
+      }
+    }
+
+    if let Some(art_aspect_ratio) = behavior_config.art_aspect_ratio {
+      match cell_aspect::ArtAspect::from_config_str(&art_aspect_ratio) {
+        Some(aspect) => self.behavior.art_aspect_ratio = aspect,
+        None => {
+          return Err(anyhow!(
+            "Art aspect ratio must be one of double, single or auto, is {}",
+            art_aspect_ratio
+          ))
+        }
+      }
+    }
+
+    macro_rules! breakpoint {
+      ($name: ident) => {
+        if let Some(value) = behavior_config.$name {
+          if value == 0 {
+            return Err(anyhow!("{} must be greater than 0", stringify!($name)));
+          }
+          self.behavior.$name = value;
+        }
+      };
+    }
+    breakpoint!(small_terminal_width);
+    breakpoint!(small_terminal_height);
+    breakpoint!(ultra_small_terminal_width);
+    breakpoint!(ultra_small_terminal_height);
+    breakpoint!(mini_terminal_width);
+    breakpoint!(mini_terminal_height);
+
+    if let Some(show_sidebar) = behavior_config.show_sidebar {
+      self.behavior.show_sidebar = show_sidebar;
+    }
+
+    if let Some(sidebar_min_width) = behavior_config.sidebar_min_width {
+      self.behavior.sidebar_min_width = sidebar_min_width;
+    }
+
+    if let Some(playbar_position) = behavior_config.playbar_position {
+      match util::PlaybarPosition::from_config_str(&playbar_position) {
+        Some(position) => self.behavior.playbar_position = position,
+        None => {
+          return Err(anyhow!(
+            "Playbar position must be one of top or bottom, is {}",
+            playbar_position
+          ))
+        }
+      }
+    }
+
+    if let Some(show_breadcrumb) = behavior_config.show_breadcrumb {
+      self.behavior.show_breadcrumb = show_breadcrumb;
+    }
+
+    if let Some(network_timeout) = behavior_config.network_timeout_seconds {
+      if network_timeout == 0 {
+        return Err(anyhow!("Network timeout must be greater than 0"));
+      } else {
+        self.behavior.network_timeout_seconds = network_timeout;
+      }
+    }
+
+    if let Some(idle_animation) = behavior_config.idle_animation {
+      match IdleAnimation::from_config_str(&idle_animation) {
+        Some(animation) => self.behavior.idle_animation = animation,
+        None => {
+          return Err(anyhow!(
+            "Idle animation must be one of spinning_record, coin_flip or split, is {}",
+            idle_animation
+          ))
+        }
+      }
+    }
+
+    if let Some(progress_bar_style) = behavior_config.progress_bar_style {
+      match util::ProgressBarStyle::from_config_str(&progress_bar_style) {
+        Some(style) => self.behavior.progress_bar_style = style,
+        None => {
+          return Err(anyhow!(
+            "Progress bar style must be one of solid or pattern, is {}",
+            progress_bar_style
+          ))
+        }
+      }
+    }
+
+    if let Some(playlist_sort) = behavior_config.playlist_sort {
+      match PlaylistSort::from_config_str(&playlist_sort) {
+        Some(sort) => self.behavior.playlist_sort = sort,
+        None => {
+          return Err(anyhow!(
+            "Playlist sort must be one of recent, name or owner, is {}",
+            playlist_sort
+          ))
+        }
+      }
+    }
+
+    if let Some(pinned_playlists) = behavior_config.pinned_playlists {
+      self.behavior.pinned_playlists = pinned_playlists;
+    }
+
+    if let Some(track_table_columns) = behavior_config.track_table_columns {
+      self.behavior.track_table_columns = parse_track_table_columns(&track_table_columns)?;
+    }
+
+    if let Some(now_playing_template) = behavior_config.now_playing_template {
+      let unknown = template::placeholders(&now_playing_template)
+        .into_iter()
+        .find(|name| !NOW_PLAYING_FIELDS.contains(name));
+      if let Some(name) = unknown {
+        return Err(anyhow!(
+          "Now playing template placeholders must be some of {{{}}}, found {{{}}}",
+          NOW_PLAYING_FIELDS.join("}, {"),
+          name
+        ));
+      }
+      self.behavior.now_playing_template = now_playing_template;
+    }
+
+    if let Some(search_delay) = behavior_config.search_delay_milliseconds {
+      self.behavior.search_delay_milliseconds = search_delay;
+    }
+
+    if let Some(disk_cache) = behavior_config.audio_analysis_disk_cache {
+      self.behavior.audio_analysis_disk_cache = disk_cache;
+    }
+
+    if let Some(beat_accents) = behavior_config.beat_accents {
+      self.behavior.beat_accents = beat_accents;
+    }
+
+    if let Some(autoplay) = behavior_config.autoplay {
+      self.behavior.autoplay = autoplay;
+    }
+
+    if let Some(streaming) = behavior_config.streaming {
+      self.behavior.streaming = streaming;
+    }
+
+    if let Some(streaming_device_name) = behavior_config.streaming_device_name {
+      if streaming_device_name.trim().is_empty() {
+        return Err(anyhow!("Streaming device name can't be empty"));
+      }
+      self.behavior.streaming_device_name = streaming_device_name;
+    }
+
+    if let Some(streaming_audio_backend) = behavior_config.streaming_audio_backend {
+      self.behavior.streaming_audio_backend = streaming_audio_backend;
+    }
+
+    if let Some(spotifyd_path) = behavior_config.spotifyd_path {
+      self.behavior.spotifyd_path = spotifyd_path;
+    }
+
+    if let Some(spotifyd_config_path) = behavior_config.spotifyd_config_path {
+      self.behavior.spotifyd_config_path = spotifyd_config_path;
+    }
+
+    if let Some(spotifyd_device_name) = behavior_config.spotifyd_device_name {
+      if spotifyd_device_name.trim().is_empty() {
+        return Err(anyhow!("spotifyd device name can't be empty"));
+      }
+      self.behavior.spotifyd_device_name = spotifyd_device_name;
+    }
+
+    if let Some(on_track_change) = behavior_config.on_track_change {
+      self.behavior.on_track_change = on_track_change;
+    }
+
+    if let Some(on_pause) = behavior_config.on_pause {
+      self.behavior.on_pause = on_pause;
+    }
+
+    if let Some(on_resume) = behavior_config.on_resume {
+      self.behavior.on_resume = on_resume;
+    }
+
+    if let Some(now_playing_file) = behavior_config.now_playing_file {
+      self.behavior.now_playing_file = now_playing_file;
+    }
+
+    if let Some(now_playing_file_template) = behavior_config.now_playing_file_template {
+      let unknown = template::placeholders(&now_playing_file_template)
+        .into_iter()
+        .find(|name| !NOW_PLAYING_FIELDS.contains(name));
+      if let Some(name) = unknown {
+        return Err(anyhow!(
+          "Now playing file template placeholders must be some of {{{}}}, found {{{}}}",
+          NOW_PLAYING_FIELDS.join("}, {"),
+          name
+        ));
+      }
+      self.behavior.now_playing_file_template = now_playing_file_template;
+    }
+
+    if let Some(now_playing_art_file) = behavior_config.now_playing_art_file {
+      self.behavior.now_playing_art_file = now_playing_art_file;
+    }
+
+    if let Some(log_file) = behavior_config.log_file {
+      self.behavior.log_file = log_file;
+    }
+
+    if let Some(log_file_max_kb) = behavior_config.log_file_max_kb {
+      self.behavior.log_file_max_kb = log_file_max_kb;
+    }
+
+    if let Some(pause_on_quit) = behavior_config.pause_on_quit {
+      self.behavior.pause_on_quit = pause_on_quit;
+    }
+
+    if let Some(finish_changes_on_quit) = behavior_config.finish_changes_on_quit {
+      self.behavior.finish_changes_on_quit = finish_changes_on_quit;
+    }
+
+    if let Some(save_state_on_quit) = behavior_config.save_state_on_quit {
+      self.behavior.save_state_on_quit = save_state_on_quit;
+    }
+
+    Ok(())
+  }
+
+  /// Write a behavior option changed at runtime to config.yml so it sticks, leaving the rest of
+  /// the file as it is
+  pub fn save_behavior_value(&self, name: &str, value: &str) -> Result<()> {
+    let path = match &self.path_to_config {
+      Some(paths) => &paths.config_file_path,
+      None => return Err(anyhow!("No config file to save {} to", name)),
+    };
+    let config = if path.exists() {
+      fs::read_to_string(path)?
+    } else {
+      String::new()
+    };
+    fs::write(path, set_config_value(&config, "behavior", name, value))?;
     Ok(())
   }
 
@@ -490,10 +1453,19 @@ impl UserConfig {
         return Ok(());
       }
 
+      self
+        .warnings
+        .extend(check_keybinding_actions(&config_string));
       let config_yml: UserConfigString = serde_yaml::from_str(&config_string)?;
 
       if let Some(keybindings) = config_yml.keybindings.clone() {
-        self.load_keybindings(keybindings)?;
+        self.load_keybindings(keybindings)
+          .map_err(|e| anyhow!("{}", with_line_context(&config_string, &e.to_string())))?;
+        check_keybinding_conflicts(&self.keys, &config_string)?;
+      }
+
+      if let Some(chords) = config_yml.chords {
+        self.load_chords(chords, &config_string)?;
       }
 
       if let Some(behavior) = config_yml.behavior {
@@ -509,11 +1481,140 @@ impl UserConfig {
     }
   }
 
+  /// Every problem in a `config.yml`, for `spt check-config`. Unlike `load_config`, which stops
+  /// at the first error, this keeps going so all of them can be fixed in one pass.
+  pub fn check_config(config: &str) -> Vec<String> {
+    let value: serde_yaml::Value = match serde_yaml::from_str(config) {
+      Ok(value) => value,
+      Err(e) => {
+        return vec![match e.location() {
+          Some(location) => format!("line {}: {}", location.line(), e),
+          None => e.to_string(),
+        }]
+      }
+    };
+    let sections = match value.as_mapping() {
+      Some(sections) => sections,
+      // An empty file is fine, every setting keeps its default
+      None if value.is_null() => return vec![],
+      None => return vec!["Expected the top level to be a mapping of sections".to_string()],
+    };
+
+    let mut problems = vec![];
+    let at_line = |line: Option<usize>, message: String| match line {
+      Some(line) => format!("line {}: {}", line, message),
+      None => message,
+    };
+
+    for (section, entries) in sections {
+      let section = section.as_str().unwrap_or_default();
+      let entries = match (section, entries.as_mapping()) {
+        ("keybindings" | "chords" | "behavior" | "theme", Some(entries)) => entries,
+        ("keybindings" | "chords" | "behavior" | "theme", None) if entries.is_null() => continue,
+        ("keybindings" | "chords" | "behavior" | "theme", None) => {
+          problems.push(at_line(
+            find_top_level_line(config, section),
+            format!("\"{}\" should be a mapping", section),
+          ));
+          continue;
+        }
+        _ => {
+          problems.push(at_line(
+            find_top_level_line(config, section),
+            format!(
+              "unknown section \"{}\", expected keybindings, chords, behavior or theme",
+              section
+            ),
+          ));
+          continue;
+        }
+      };
+
+      let known_names = match section {
+        "keybindings" => config_field_names(&KeyBindingsString::default()),
+        "behavior" => config_field_names(&BehaviorConfigString::default()),
+        "theme" => serde_yaml::from_str::<UserTheme>("{}")
+          .map(|theme| config_field_names(&theme))
+          .unwrap_or_default(),
+        _ => vec![],
+      };
+
+      for (name, entry) in entries {
+        let name = match name.as_str() {
+          Some(name) => name,
+          None => continue,
+        };
+        let line = find_config_line(config, section, name);
+        if section != "chords" && !known_names.iter().any(|known| known == name) {
+          problems.push(at_line(
+            line,
+            format!("unknown {} setting \"{}\"", section, name),
+          ));
+          continue;
+        }
+
+        // Load each entry on its own so one mistake doesn't hide the next
+        let mut single = serde_yaml::Mapping::new();
+        single.insert(name.into(), entry.clone());
+        let single = serde_yaml::Value::Mapping(single);
+        let mut user_config = UserConfig::new();
+        let result = match section {
+          "keybindings" => serde_yaml::from_value::<KeyBindingsString>(single)
+            .map_err(anyhow::Error::from)
+            .and_then(|keybindings| user_config.load_keybindings(keybindings)),
+          "chords" => serde_yaml::from_value::<HashMap<String, String>>(single)
+            .map_err(anyhow::Error::from)
+            .and_then(|chords| user_config.load_chords(chords, config)),
+          "behavior" => serde_yaml::from_value::<BehaviorConfigString>(single)
+            .map_err(anyhow::Error::from)
+            .and_then(|behavior| user_config.load_behaviorconfig(behavior)),
+          _ => serde_yaml::from_value::<UserTheme>(single)
+            .map_err(anyhow::Error::from)
+            .and_then(|theme| user_config.load_theme(theme)),
+        };
+        if let Err(e) = result {
+          // Chord errors already name their line
+          let line = if section == "chords" { None } else { line };
+          problems.push(at_line(line, e.to_string()));
+        }
+      }
+    }
+
+    // Conflicts only show up once all the keybindings are in place
+    if let Ok(config_yml) = serde_yaml::from_str::<UserConfigString>(config) {
+      let mut user_config = UserConfig::new();
+      if let Some(keybindings) = config_yml.keybindings {
+        if user_config.load_keybindings(keybindings).is_ok() {
+          if let Err(e) = check_keybinding_conflicts(&user_config.keys, config) {
+            problems.push(e.to_string());
+          }
+        }
+      }
+    }
+
+    problems
+  }
+
   pub fn padded_liked_icon(&self) -> String {
     format!("{} ", &self.behavior.liked_icon)
   }
 }
 
+/// Names of the settings a config section accepts, read off its serialized form
+fn config_field_names<T: Serialize>(section: &T) -> Vec<String> {
+  serde_yaml::to_value(section)
+    .ok()
+    .and_then(|value| {
+      value.as_mapping().map(|mapping| {
+        mapping
+          .keys()
+          .filter_map(|key| key.as_str().map(|key| key.to_string()))
+          .collect()
+      })
+    })
+    .unwrap_or_default()
+}
+
 fn parse_theme_item(theme_item: &str) -> Result<Color> {
   let color = match theme_item {
     "Reset" => Color::Reset,
@@ -542,8 +1643,10 @@ fn parse_theme_item(theme_item: &str) -> Result<Color> {
           b.trim().parse::<u8>()?,
         )
       } else {
-        println!("Unexpected color {}", theme_item);
-        Color::Black
+        return Err(anyhow!(
+          "unknown color \"{}\", expected a name such as LightCyan or r, g, b",
+          theme_item
+        ));
       }
     }
   };
@@ -564,6 +1667,84 @@ mod tests {
     assert_eq!(parse_key(String::from("-")).unwrap(), Key::Char('-'));
     assert_eq!(parse_key(String::from("esc")).unwrap(), Key::Esc);
     assert_eq!(parse_key(String::from("del")).unwrap(), Key::Delete);
+    assert_eq!(parse_key(String::from("alt-x")).unwrap(), Key::Alt('x'));
+    assert_eq!(parse_key(String::from("f5")).unwrap(), Key::F5);
+    assert_eq!(parse_key(String::from("F12")).unwrap(), Key::F12);
+    assert_eq!(parse_key(String::from("shift-up")).unwrap(), Key::ShiftUp);
+    assert_eq!(parse_key(String::from("tab")).unwrap(), Key::Tab);
+    assert!(parse_key(String::from("ctrl")).is_err());
+    assert!(parse_key(String::from("hyper-x")).is_err());
+    assert!(parse_key(String::from("f13")).is_err());
+    assert!(parse_key(String::from("shift-a")).is_err());
+  }
+
+  #[test]
+  fn test_keybinding_actions_match_config_fields() {
+    use super::{KeyBindingsString, UserConfig, KEYBINDING_ACTIONS};
+
+    let value = serde_yaml::to_value(KeyBindingsString::default()).unwrap();
+    let fields: Vec<&str> = value
+      .as_mapping()
+      .unwrap()
+      .keys()
+      .filter_map(|key| key.as_str())
+      .collect();
+    // Followed by the leader, which chords use but isn't an action
+    assert_eq!(
+      fields,
+      [KEYBINDING_ACTIONS.as_slice(), &["leader"][..]].concat()
+    );
+
+    let names: Vec<&str> = UserConfig::new()
+      .keys
+      .bindings()
+      .iter()
+      .map(|(name, _)| *name)
+      .collect();
+    assert_eq!(names, KEYBINDING_ACTIONS.to_vec());
+  }
+
+  #[test]
+  fn test_keybinding_validation() {
+    use super::{check_keybinding_actions, check_keybinding_conflicts, find_config_line, UserConfig};
+    use crate::event::Key;
+
+    let config = "theme:\n  active: Cyan\nkeybindings:\n  next_track: \"n\"\n  toggle_idle: \"F\"\n";
+    assert_eq!(find_config_line(config, "keybindings", "next_track"), Some(4));
+    assert_eq!(find_config_line(config, "theme", "next_track"), None);
+
+    let warning = check_keybinding_actions(config).unwrap();
+    assert!(warning.contains("\"toggle_idle\" (line 5)"));
+
+    // The old copy keys now open the share menu
+    let retired = "keybindings:\n  copy_song_url: \"x\"\n  copy_album_url: \"X\"\n";
+    assert_eq!(check_keybinding_actions(retired), None);
+    let keybindings: super::KeyBindingsString =
+      serde_yaml::from_str("copy_song_url: \"x\"").unwrap();
+    let mut user_config = UserConfig::new();
+    user_config.load_keybindings(keybindings).unwrap();
+    assert_eq!(user_config.keys.share, Key::Char('x'));
+
+    let mut user_config = UserConfig::new();
+    assert!(check_keybinding_conflicts(&user_config.keys, "").is_ok());
+
+    user_config.keys.shuffle = Key::Char('n');
+    let error = check_keybinding_conflicts(&user_config.keys, config)
+      .unwrap_err()
+      .to_string();
+    assert!(error.contains("\"next_track\" (line 4) and \"shuffle\" (default)"));
+
+    // Visual mode and the idle animation switch share V by default, but nothing else may
+    user_config.keys.shuffle = Key::Char('V');
+    assert!(check_keybinding_conflicts(&user_config.keys, "").is_err());
+
+    user_config.keys.shuffle = Key::Char('s');
+    assert!(check_keybinding_conflicts(&user_config.keys, "").is_err());
+    user_config.keys.shuffle = Key::Alt('3');
+    let error = check_keybinding_conflicts(&user_config.keys, "")
+      .unwrap_err()
+      .to_string();
+    assert!(error.contains("already used to switch workspace"));
   }
 
   #[test]
@@ -594,6 +1775,8 @@ mod tests {
       parse_theme_item("23, 43, 45").unwrap(),
       Color::Rgb(23, 43, 45)
     );
+    assert!(parse_theme_item("Purple").is_err());
+    assert!(parse_theme_item("300, 0, 0").is_err());
   }
 
   #[test]
@@ -606,4 +1789,118 @@ mod tests {
       "Enter key should be reserved"
     );
   }
+
+  #[test]
+  fn test_load_chords() {
+    use super::UserConfig;
+    use crate::event::Key;
+    use std::collections::HashMap;
+
+    let mut user_config = UserConfig::new();
+    let mut chords = HashMap::new();
+    chords.insert("leader n".to_string(), "next_track".to_string());
+    user_config.load_chords(chords, "").unwrap();
+    assert!(user_config
+      .chords
+      .iter()
+      .any(|chord| chord.keys == vec![Key::Char('\\'), Key::Char('n')]
+        && chord.action == "next_track"));
+
+    let mut chords = HashMap::new();
+    chords.insert("g".to_string(), "top".to_string());
+    assert!(user_config.load_chords(chords, "").is_err());
+
+    let mut chords = HashMap::new();
+    chords.insert("g x".to_string(), "dance".to_string());
+    assert!(user_config.load_chords(chords, "").is_err());
+
+    // The leader is a key, not an action a chord can run
+    let mut chords = HashMap::new();
+    chords.insert("g x".to_string(), "leader".to_string());
+    assert!(user_config.load_chords(chords, "").is_err());
+
+    // The default delete chord moves with the leader
+    let mut user_config = UserConfig::new();
+    let keybindings: super::KeyBindingsString = serde_yaml::from_str("leader: \",\"").unwrap();
+    user_config.load_keybindings(keybindings).unwrap();
+    assert!(user_config
+      .chords
+      .iter()
+      .any(|chord| chord.keys == vec![Key::Char(','), Key::Char('d')] && chord.action == "delete"));
+  }
+  #[test]
+  fn test_check_config() {
+    use super::UserConfig;
+
+    let config = "keybindings:\n  next_track: n\n  dance: x\ntheme:\n  active: Purple\nbehavior:\n  volume_increment: 200\n  tick_rate_milliseconds: 250\nkeys:\n  back: q\n";
+    let problems = UserConfig::check_config(config);
+    assert_eq!(problems.len(), 4, "{:?}", problems);
+    assert!(problems[0].starts_with("line 3: unknown keybindings setting \"dance\""));
+    assert!(problems[1].starts_with("line 5: Invalid theme color \"active\""));
+    assert!(problems[2].starts_with("line 7: Volume increment"));
+    assert!(problems[3].starts_with("line 9: unknown section \"keys\""));
+
+    assert!(UserConfig::check_config("behavior:\n  seek_milliseconds: 1000\n").is_empty());
+    let template = "behavior:\n  now_playing_template: \"{track} on {station}\"\n";
+    assert!(UserConfig::check_config(template)[0].contains("found {station}"));
+    assert!(UserConfig::check_config("").is_empty());
+  }
+
+  #[test]
+  fn test_parse_track_table_columns() {
+    use super::{parse_track_table_columns, util::TrackColumn};
+    let names = vec!["title".to_string(), "Added".to_string()];
+    assert_eq!(
+      parse_track_table_columns(&names).unwrap(),
+      vec![TrackColumn::Title, TrackColumn::Added]
+    );
+    assert!(parse_track_table_columns(&[]).is_err());
+    assert!(parse_track_table_columns(&["rating".to_string()]).is_err());
+  }
+
+  #[test]
+  fn test_load_layout_options() {
+    use super::{util::PlaybarPosition, BehaviorConfigString, UserConfig};
+
+    let behavior: BehaviorConfigString =
+      serde_yaml::from_str("show_sidebar: false\nplaybar_position: top\nshow_breadcrumb: false")
+        .unwrap();
+    let mut user_config = UserConfig::new();
+    user_config.load_behaviorconfig(behavior).unwrap();
+    assert!(!user_config.behavior.show_sidebar);
+    assert_eq!(user_config.behavior.playbar_position, PlaybarPosition::Top);
+    assert!(!user_config.behavior.show_breadcrumb);
+
+    let behavior: BehaviorConfigString = serde_yaml::from_str("playbar_position: left").unwrap();
+    assert!(user_config.load_behaviorconfig(behavior).is_err());
+  }
+
+  #[test]
+  fn test_set_config_value() {
+    use super::set_config_value;
+
+    let config = "# mine\nbehavior:\n  pinned_playlists:\n    - Focus\n  playlist_sort: name\ntheme:\n  active: Cyan";
+    assert_eq!(
+      set_config_value(config, "behavior", "pinned_playlists", "[\"Chill\"]"),
+      "# mine\nbehavior:\n  pinned_playlists: [\"Chill\"]\n  playlist_sort: name\ntheme:\n  active: Cyan\n"
+    );
+    assert_eq!(
+      set_config_value(
+        "theme:\n  active: Cyan\n",
+        "behavior",
+        "playlist_sort",
+        "owner"
+      ),
+      "theme:\n  active: Cyan\nbehavior:\n  playlist_sort: owner\n"
+    );
+    assert_eq!(
+      set_config_value(
+        "behavior:\n  seek_milliseconds: 1000\n",
+        "behavior",
+        "playlist_sort",
+        "name"
+      ),
+      "behavior:\n  playlist_sort: name\n  seek_milliseconds: 1000\n"
+    );
+  }
 }
@@ -0,0 +1,227 @@
+//! Numbered workspaces, each with its own navigation stack and selections, so a playlist can stay
+//! open in one while another is used to search. Only the workspace being shown lives in `App`; the
+//! others wait here until they are switched back to.
+//!
+//! Events carry the workspace they were dispatched from, and the network holds the app through a
+//! `ResponseGuard` that shows that workspace while a response is written, so a view still loading
+//! when you switch away fills in where it was asked for.
+
+use crate::app::{
+  AlbumTableContext, App, Artist, EpisodeTableContext, RecommendationsContext, Route, SearchResult,
+  SelectedAlbum, SelectedFullAlbum, SelectedFullShow, SelectedShow, TrackTable,
+};
+use crate::jump_list::JumpList;
+use crate::location::PendingSelection;
+use rspotify::model::{artist::FullArtist, playlist::SimplifiedPlaylist, track::FullTrack};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use tokio::sync::MutexGuard;
+
+/// One for each of Alt+1 to Alt+9
+pub const WORKSPACE_COUNT: usize = 9;
+
+// Declares the state a workspace keeps in the `App` fields of the same names while it is shown,
+// with what a new workspace starts with
+macro_rules! workspace_state {
+  ($($field:ident: $type:ty = $initial:expr,)*) => {
+    pub struct Workspace {
+      // Empty until the workspace is first shown, when it starts at the default route
+      navigation_stack: Vec<Route>,
+      library_index: usize,
+      $($field: $type,)*
+    }
+
+    impl Workspace {
+      fn new() -> Workspace {
+        Workspace {
+          navigation_stack: Vec::new(),
+          library_index: 0,
+          $($field: $initial,)*
+        }
+      }
+
+      // Trade this workspace's state for the one `app` is showing
+      fn swap(&mut self, app: &mut App) {
+        mem::swap(&mut self.library_index, &mut app.library.selected_index);
+        $(mem::swap(&mut self.$field, &mut app.$field);)*
+        app.swap_navigation_stack(&mut self.navigation_stack);
+      }
+    }
+  };
+}
+
+workspace_state! {
+  selected_playlist_index: Option<usize> = None,
+  active_playlist_index: Option<usize> = None,
+  playlist_offset: u32 = 0,
+  track_table: TrackTable = TrackTable::default(),
+  pending_selection: Option<PendingSelection> = None,
+  jump_list: JumpList = JumpList::default(),
+  input: Vec<char> = Vec::new(),
+  input_idx: usize = 0,
+  input_cursor_position: u16 = 0,
+  search_results: SearchResult = SearchResult::default(),
+  artist: Option<Artist> = None,
+  artists: Vec<FullArtist> = Vec::new(),
+  showing_top_artists: bool = false,
+  artists_list_index: usize = 0,
+  selected_album_simplified: Option<SelectedAlbum> = None,
+  selected_album_full: Option<SelectedFullAlbum> = None,
+  album_table_context: AlbumTableContext = AlbumTableContext::Full,
+  saved_album_tracks_index: usize = 0,
+  album_list_index: usize = 0,
+  selected_show_simplified: Option<SelectedShow> = None,
+  selected_show_full: Option<SelectedFullShow> = None,
+  episode_table_context: EpisodeTableContext = EpisodeTableContext::Full,
+  episode_list_index: usize = 0,
+  shows_list_index: usize = 0,
+  recommended_tracks: Vec<FullTrack> = Vec::new(),
+  recommendations_seed: String = String::new(),
+  recommendations_context: Option<RecommendationsContext> = None,
+  category_playlists: Vec<SimplifiedPlaylist> = Vec::new(),
+  category_playlists_index: usize = 0,
+  categories_index: usize = 0,
+  made_for_you_index: usize = 0,
+  new_releases_index: usize = 0,
+  featured_playlists_index: usize = 0,
+  home_scroll: u16 = 0,
+  split_left_focused_at: Option<usize> = None,
+}
+
+pub struct Workspaces {
+  // The one being shown, from 0
+  active: usize,
+  // The rest, `None` until first switched to and for the one being shown
+  stashed: Vec<Option<Workspace>>,
+}
+
+impl Default for Workspaces {
+  fn default() -> Self {
+    Workspaces {
+      active: 0,
+      stashed: (0..WORKSPACE_COUNT).map(|_| None).collect(),
+    }
+  }
+}
+
+impl Workspaces {
+  pub fn active(&self) -> usize {
+    self.active
+  }
+
+  /// The workspaces that have been shown, in order
+  pub fn opened(&self) -> Vec<usize> {
+    (0..WORKSPACE_COUNT)
+      .filter(|&index| index == self.active || self.stashed[index].is_some())
+      .collect()
+  }
+}
+
+/// Show workspace `index`, keeping the one left as it was to come back to
+pub fn switch(app: &mut App, index: usize) {
+  if index == app.workspaces.active || index >= WORKSPACE_COUNT {
+    return;
+  }
+  swap_in(app, index);
+  app.picking_breadcrumb = false;
+}
+
+fn swap_in(app: &mut App, index: usize) {
+  let active = app.workspaces.active;
+  let mut workspace = app.workspaces.stashed[index]
+    .take()
+    .unwrap_or_else(Workspace::new);
+  workspace.swap(app);
+  app.workspaces.stashed[active] = Some(workspace);
+  app.workspaces.active = index;
+}
+
+/// The app locked for a network response, showing the workspace its event was dispatched from
+/// until it is let go, when the workspace being looked at comes back before the UI can draw
+pub struct ResponseGuard<'a> {
+  app: MutexGuard<'a, App>,
+  // The workspace to show again, when another was swapped in
+  showing: Option<usize>,
+}
+
+impl<'a> ResponseGuard<'a> {
+  pub fn new(mut app: MutexGuard<'a, App>, workspace: Option<usize>) -> ResponseGuard<'a> {
+    let showing = match workspace {
+      Some(index) if index != app.workspaces.active && index < WORKSPACE_COUNT => {
+        let showing = app.workspaces.active;
+        swap_in(&mut app, index);
+        Some(showing)
+      }
+      _ => None,
+    };
+    ResponseGuard { app, showing }
+  }
+}
+
+impl Deref for ResponseGuard<'_> {
+  type Target = App;
+
+  fn deref(&self) -> &App {
+    &self.app
+  }
+}
+
+impl DerefMut for ResponseGuard<'_> {
+  fn deref_mut(&mut self) -> &mut App {
+    &mut self.app
+  }
+}
+
+impl Drop for ResponseGuard<'_> {
+  fn drop(&mut self) {
+    if let Some(showing) = self.showing {
+      swap_in(&mut self.app, showing);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::app::{ActiveBlock, RouteId};
+
+  #[test]
+  fn each_workspace_keeps_its_own_view() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    app.album_list_index = 3;
+
+    switch(&mut app, 1);
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+    assert_eq!(app.album_list_index, 0);
+    app.push_navigation_stack(RouteId::Search, ActiveBlock::SearchResultBlock);
+
+    switch(&mut app, 0);
+    assert_eq!(app.get_current_route().id, RouteId::AlbumList);
+    assert_eq!(app.album_list_index, 3);
+    switch(&mut app, 1);
+    assert_eq!(app.get_current_route().id, RouteId::Search);
+    assert_eq!(app.workspaces.opened(), [0, 1]);
+  }
+
+  #[test]
+  fn responses_land_in_the_workspace_that_asked() {
+    let app = tokio::sync::Mutex::new(App::default());
+    let mut shown = app.try_lock().unwrap();
+    shown.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    switch(&mut shown, 1);
+    drop(shown);
+
+    // A load dispatched from the first workspace finishes after switching to the second
+    let mut response = ResponseGuard::new(app.try_lock().unwrap(), Some(0));
+    assert_eq!(response.get_current_route().id, RouteId::AlbumList);
+    response.album_list_index = 5;
+    drop(response);
+
+    let mut shown = app.try_lock().unwrap();
+    assert_eq!(shown.workspaces.active(), 1);
+    assert_eq!(shown.album_list_index, 0);
+    switch(&mut shown, 0);
+    assert_eq!(shown.album_list_index, 5);
+  }
+}